@@ -0,0 +1,566 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_ndarray::ops::*;
+use dendritic_metrics::activations::{sigmoid, relu};
+use std::cell::{RefCell, RefMut};
+use crate::node::{Node, Value};
+use crate::ops::elementwise;
+
+
+/// Softmax of each row in a rank 2 value
+pub fn softmax_rows(x: &NDArray<f64>) -> NDArray<f64> {
+
+    let cols = x.shape().dim(x.rank() - 1);
+    let mut results: Vec<f64> = Vec::new();
+    for row in x.values().chunks(cols) {
+        let max_x = row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let exp_values: Vec<f64> = row.iter().map(|v| (v - max_x).exp()).collect();
+        let sum_exp: f64 = exp_values.iter().sum();
+        results.extend(exp_values.iter().map(|v| v / sum_exp));
+    }
+
+    NDArray::array(x.shape().values(), results).unwrap()
+}
+
+
+/// Log softmax of each row in a rank 2 value, computed with log-sum-exp
+pub fn log_softmax_rows(x: &NDArray<f64>) -> NDArray<f64> {
+
+    let cols = x.shape().dim(x.rank() - 1);
+    let mut results: Vec<f64> = Vec::new();
+    for row in x.values().chunks(cols) {
+        let max_x = row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let sum_exp: f64 = row.iter().map(|v| (v - max_x).exp()).sum();
+        let log_sum_exp = max_x + sum_exp.ln();
+        results.extend(row.iter().map(|v| v - log_sum_exp));
+    }
+
+    NDArray::array(x.shape().values(), results).unwrap()
+}
+
+
+pub struct Sigmoid<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Sigmoid<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of sigmoid activation operation
+    pub fn new(input: V) -> Sigmoid<V> {
+
+        let op_result = input.value().apply(sigmoid).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Sigmoid {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of sigmoid activation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Sigmoid<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of sigmoid activation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().apply(sigmoid).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of sigmoid activation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let output = self.value();
+        let input_grad = elementwise(
+            &upstream_gradient, &output, 
+            |g, s| g * s * (1.0 - s)
+        ).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of sigmoid activation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of sigmoid activation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of sigmoid activation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of sigmoid activation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Tanh<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Tanh<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of tanh activation operation
+    pub fn new(input: V) -> Tanh<V> {
+
+        let op_result = input.value().apply(f64::tanh).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Tanh {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of tanh activation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Tanh<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of tanh activation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().apply(f64::tanh).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of tanh activation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let output = self.value();
+        let input_grad = elementwise(
+            &upstream_gradient, &output, 
+            |g, t| g * (1.0 - t * t)
+        ).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of tanh activation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of tanh activation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of tanh activation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of tanh activation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct ReLU<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> ReLU<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of relu activation operation
+    pub fn new(input: V) -> ReLU<V> {
+
+        let op_result = input.value().apply(relu).unwrap();
+        let op_value = Value::new(&op_result);
+
+        ReLU {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of relu activation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for ReLU<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of relu activation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().apply(relu).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of relu activation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let input = self.input().value();
+        let input_grad = elementwise(
+            &upstream_gradient, &input, 
+            |g, x| if x > 0.0 { g } else { 0.0 }
+        ).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of relu activation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of relu activation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of relu activation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of relu activation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct LeakyReLU<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub alpha: f64
+}
+
+
+impl<V> LeakyReLU<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of leaky relu activation operation
+    pub fn new(input: V, alpha: f64) -> LeakyReLU<V> {
+
+        let leaky: Vec<f64> = input.value().values().iter()
+            .map(|x| if *x > 0.0 { *x } else { alpha * x })
+            .collect();
+        let op_result = NDArray::array(input.value().shape().values(), leaky).unwrap();
+        let op_value = Value::new(&op_result);
+
+        LeakyReLU {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            alpha
+        }
+    }
+
+    /// Get input value of leaky relu activation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for LeakyReLU<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of leaky relu activation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let input = self.input().value();
+        let leaky: Vec<f64> = input.values().iter()
+            .map(|x| if *x > 0.0 { *x } else { self.alpha * x })
+            .collect();
+        let op_result = NDArray::array(input.shape().values(), leaky).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of leaky relu activation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let input = self.input().value();
+        let grads: Vec<f64> = upstream_gradient.values().iter()
+            .zip(input.values().iter())
+            .map(|(g, x)| if *x > 0.0 { *g } else { self.alpha * g })
+            .collect();
+        let input_grad = NDArray::array(input.shape().values(), grads).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of leaky relu activation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of leaky relu activation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of leaky relu activation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of leaky relu activation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Softmax<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Softmax<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of row wise softmax activation operation
+    pub fn new(input: V) -> Softmax<V> {
+
+        let op_result = softmax_rows(&input.value());
+        let op_value = Value::new(&op_result);
+
+        Softmax {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of row wise softmax activation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Softmax<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of row wise softmax activation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = softmax_rows(&self.input().value());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of row wise softmax activation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        /* dx = s * (g - sum(g * s)) for each row */
+        let output = self.value();
+        let cols = output.shape().dim(output.rank() - 1);
+        let mut grads: Vec<f64> = Vec::new();
+        for (s_row, g_row) in output.values().chunks(cols).zip(upstream_gradient.values().chunks(cols)) {
+            let dot: f64 = s_row.iter().zip(g_row.iter()).map(|(s, g)| s * g).sum();
+            grads.extend(s_row.iter().zip(g_row.iter()).map(|(s, g)| s * (g - dot)));
+        }
+        let input_grad = NDArray::array(output.shape().values(), grads).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of row wise softmax activation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of row wise softmax activation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of row wise softmax activation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of row wise softmax activation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct LogSoftmax<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> LogSoftmax<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of row wise log softmax activation operation
+    pub fn new(input: V) -> LogSoftmax<V> {
+
+        let op_result = log_softmax_rows(&input.value());
+        let op_value = Value::new(&op_result);
+
+        LogSoftmax {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of row wise log softmax activation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for LogSoftmax<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of row wise log softmax activation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = log_softmax_rows(&self.input().value());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of row wise log softmax activation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        /* dx = g - softmax(x) * sum(g) for each row */
+        let softmax = softmax_rows(&self.input().value());
+        let cols = softmax.shape().dim(softmax.rank() - 1);
+        let mut grads: Vec<f64> = Vec::new();
+        for (s_row, g_row) in softmax.values().chunks(cols).zip(upstream_gradient.values().chunks(cols)) {
+            let g_sum: f64 = g_row.iter().sum();
+            grads.extend(s_row.iter().zip(g_row.iter()).map(|(s, g)| g - s * g_sum));
+        }
+        let input_grad = NDArray::array(softmax.shape().values(), grads).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of row wise log softmax activation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of row wise log softmax activation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of row wise log softmax activation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of row wise log softmax activation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
@@ -0,0 +1,119 @@
+use dendritic_ndarray::ndarray::NDArray;
+use std::cell::Cell;
+use std::collections::HashSet;
+use crate::node::Node;
+
+
+thread_local! {
+    static ANOMALY_DETECTION: Cell<bool> = const { Cell::new(false) };
+}
+
+
+/// Enable or disable anomaly detection for graphs on the current thread
+pub fn set_anomaly_detection(enabled: bool) {
+    ANOMALY_DETECTION.with(|flag| flag.set(enabled));
+}
+
+
+/// Check if anomaly detection is enabled on the current thread
+pub fn anomaly_detection() -> bool {
+    ANOMALY_DETECTION.with(|flag| flag.get())
+}
+
+
+/// Check that every element of an array is finite
+pub fn is_finite(array: &NDArray<f64>) -> bool {
+    array.values().iter().all(|v| v.is_finite())
+}
+
+
+/// Find the first node producing a non-finite value in the forward pass.
+/// Inputs are checked before the node consuming them, so the reported node
+/// is the earliest one in data flow order with a NaN or infinite output
+pub fn check_forward(node: &dyn Node) -> Result<(), String> {
+
+    fn visit(node: &dyn Node, visited: &mut HashSet<usize>) -> Result<(), String> {
+
+        if !visited.insert(node.node_id()) {
+            return Ok(());
+        }
+
+        for input in node.inputs().iter() {
+            visit(&**input, visited)?;
+        }
+
+        if !is_finite(&node.value()) {
+            let msg = format!(
+                "Anomaly: Non-finite value produced in forward pass of {}",
+                node.op_name()
+            );
+            return Err(msg);
+        }
+
+        Ok(())
+    }
+
+    visit(node, &mut HashSet::new())
+}
+
+
+/// Find the first node producing a non-finite gradient in the last backward pass.
+/// Walks from the output towards the inputs and reports the operation whose
+/// backward pass turned a finite upstream gradient into a non-finite one
+pub fn check_backward(node: &dyn Node) -> Result<(), String> {
+
+    fn visit(node: &dyn Node, visited: &mut HashSet<usize>) -> Result<(), String> {
+
+        if !visited.insert(node.node_id()) {
+            return Ok(());
+        }
+
+        for (idx, input) in node.inputs().iter().enumerate() {
+            if !is_finite(&input.grad()) {
+                let msg = format!(
+                    "Anomaly: Non-finite gradient produced in backward pass of {} for input {} ({})",
+                    node.op_name(), idx, input.op_name()
+                );
+                return Err(msg);
+            }
+        }
+
+        for input in node.inputs().iter() {
+            visit(&**input, visited)?;
+        }
+
+        Ok(())
+    }
+
+    if !is_finite(&node.grad()) {
+        let msg = format!(
+            "Anomaly: Non-finite upstream gradient passed to backward pass of {}",
+            node.op_name()
+        );
+        return Err(msg);
+    }
+
+    visit(node, &mut HashSet::new())
+}
+
+
+/// Halt with the offending node if anomaly detection is enabled and the
+/// forward pass of the graph produced a non-finite value
+pub fn detect_forward_anomaly(node: &dyn Node) {
+    if anomaly_detection() {
+        if let Err(msg) = check_forward(node) {
+            panic!("{}", msg);
+        }
+    }
+}
+
+
+/// Halt with the offending node if anomaly detection is enabled and the
+/// backward pass of the graph produced a non-finite gradient
+pub fn detect_backward_anomaly(node: &dyn Node) {
+    if anomaly_detection() {
+        if let Err(msg) = check_backward(node) {
+            panic!("{}", msg);
+        }
+    }
+}
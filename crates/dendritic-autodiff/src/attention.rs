@@ -0,0 +1,365 @@
+use dendritic_ndarray::ndarray::NDArray;
+use std::cell::{RefCell, RefMut};
+use crate::node::{Node, Value};
+
+
+/// Sinusoidal positional encoding of shape (seq_len, d_model).
+/// Even columns hold sin(pos / 10000^(2i / d_model)), odd columns the matching cosine
+pub fn sinusoidal_encoding(seq_len: usize, d_model: usize) -> NDArray<f64> {
+
+    let mut values: Vec<f64> = Vec::with_capacity(seq_len * d_model);
+    for pos in 0..seq_len {
+        for col in 0..d_model {
+            let exponent = (2 * (col / 2)) as f64 / d_model as f64;
+            let angle = pos as f64 / 10000_f64.powf(exponent);
+            values.push(if col % 2 == 0 { angle.sin() } else { angle.cos() });
+        }
+    }
+
+    NDArray::array(vec![seq_len, d_model], values).unwrap()
+}
+
+
+/// Lower triangular (seq_len, seq_len) mask so each position only attends to itself and earlier positions
+pub fn causal_mask(seq_len: usize) -> NDArray<f64> {
+    let values: Vec<f64> = (0..seq_len * seq_len)
+        .map(|idx| if idx % seq_len <= idx / seq_len { 1.0 } else { 0.0 })
+        .collect();
+    NDArray::array(vec![seq_len, seq_len], values).unwrap()
+}
+
+
+/// Sizes of an attention problem derived from query, key and value shapes
+struct Dims {
+    batch: usize,
+    query_len: usize,
+    key_len: usize,
+    key_dim: usize,
+    value_dim: usize
+}
+
+
+/// Scaled dot product attention softmax(Q * K^T / sqrt(d) + mask) * V.
+/// Inputs are (seq, dim) or (batch, seq, dim). With more than one head the last axis of
+/// each input is split into equal sized heads, every head attends separately and the
+/// head outputs are concatenated back along the last axis.
+/// Mask is (query_len, key_len) or (batch, query_len, key_len), zeros block attention
+pub struct ScaledDotProductAttention<Q, K, V>
+where
+    Q: Node,
+    K: Node,
+    V: Node,
+{
+    pub query: RefCell<Q>,
+    pub key: RefCell<K>,
+    pub value: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub num_heads: usize,
+    mask: Option<NDArray<f64>>,
+    weights: Vec<f64>
+}
+
+
+impl<Q, K, V> ScaledDotProductAttention<Q, K, V>
+where
+    Q: Node,
+    K: Node,
+    V: Node,
+{
+
+    /// Create new instance of single head attention
+    pub fn new(query: Q, key: K, value: V) -> ScaledDotProductAttention<Q, K, V> {
+        ScaledDotProductAttention::with_heads(query, key, value, 1)
+    }
+
+    /// Create new instance of attention split into `num_heads` heads
+    pub fn with_heads(
+        query: Q,
+        key: K,
+        value: V,
+        num_heads: usize) -> ScaledDotProductAttention<Q, K, V> {
+
+        if num_heads == 0 {
+            panic!("Attention: Number of heads must be greater than 0");
+        }
+
+        let op_value = Value::new(&value.value());
+        let mut attention = ScaledDotProductAttention {
+            query: RefCell::new(query),
+            key: RefCell::new(key),
+            value: RefCell::new(value),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            num_heads,
+            mask: None,
+            weights: Vec::new()
+        };
+
+        attention.attend();
+        attention
+    }
+
+    /// Get query value of attention operation
+    pub fn query(&self) -> RefMut<'_, dyn Node> {
+        self.query.borrow_mut()
+    }
+
+    /// Get key value of attention operation
+    pub fn key(&self) -> RefMut<'_, dyn Node> {
+        self.key.borrow_mut()
+    }
+
+    /// Get value input of attention operation
+    pub fn value_input(&self) -> RefMut<'_, dyn Node> {
+        self.value.borrow_mut()
+    }
+
+    /// Set or remove attention mask and recompute output
+    pub fn set_mask(&mut self, mask: Option<NDArray<f64>>) {
+        self.mask = mask;
+        self.attend();
+    }
+
+    /// Attention weights of last forward pass with shape (batch, heads, query_len, key_len),
+    /// batch axis is left out for unbatched inputs
+    pub fn attention_weights(&self) -> NDArray<f64> {
+        let query = self.query().value();
+        let dims = self.dims();
+        let mut shape = vec![dims.batch, self.num_heads, dims.query_len, dims.key_len];
+        if query.rank() == 2 {
+            shape.remove(0);
+        }
+        NDArray::array(shape, self.weights.clone()).unwrap()
+    }
+
+    /// Validate input shapes and return attention sizes
+    fn dims(&self) -> Dims {
+
+        let query = self.query().value().shape().values();
+        let key = self.key().value().shape().values();
+        let value = self.value_input().value().shape().values();
+        let rank = query.len();
+
+        if !(rank == 2 || rank == 3) || key.len() != rank || value.len() != rank {
+            panic!(
+                "Attention: Inputs must all be of rank 2 or 3, got {:?}, {:?} and {:?}",
+                query, key, value
+            );
+        }
+
+        let batch = if rank == 3 { query[0] } else { 1 };
+        if rank == 3 && (key[0] != batch || value[0] != batch) {
+            panic!("Attention: Batch sizes of query, key and value do not match");
+        }
+
+        let (query_len, key_dim) = (query[rank - 2], query[rank - 1]);
+        let (key_len, value_dim) = (value[rank - 2], value[rank - 1]);
+        if key[rank - 1] != key_dim || key[rank - 2] != key_len {
+            panic!(
+                "Attention: Key shape {:?} does not match query {:?} and value {:?}",
+                key, query, value
+            );
+        }
+
+        if key_dim % self.num_heads != 0 || value_dim % self.num_heads != 0 {
+            panic!(
+                "Attention: Feature sizes {} and {} are not divisible by {} heads",
+                key_dim, value_dim, self.num_heads
+            );
+        }
+
+        Dims { batch, query_len, key_len, key_dim, value_dim }
+    }
+
+    /// Check if query position i may attend to key position j in sample b
+    fn allowed(&self, dims: &Dims, b: usize, i: usize, j: usize) -> bool {
+        match &self.mask {
+            Some(mask) => {
+                let plane = dims.query_len * dims.key_len;
+                let offset = if mask.rank() == 3 { b * plane } else { 0 };
+                mask.values()[offset + i * dims.key_len + j] != 0.0
+            },
+            None => true
+        }
+    }
+
+    /// Compute attention weights and output from current inputs
+    fn attend(&mut self) {
+
+        let dims = self.dims();
+        if let Some(mask) = &self.mask {
+            let plane = vec![dims.query_len, dims.key_len];
+            let batched = vec![dims.batch, dims.query_len, dims.key_len];
+            if mask.shape().values() != plane && mask.shape().values() != batched {
+                panic!(
+                    "Attention: Mask shape {:?} must be {:?} or {:?}",
+                    mask.shape().values(), plane, batched
+                );
+            }
+        }
+
+        let query = self.query().value();
+        let key = self.key().value();
+        let value = self.value_input().value();
+
+        let heads = self.num_heads;
+        let (head_dim, value_head_dim) = (dims.key_dim / heads, dims.value_dim / heads);
+        let scale = 1.0 / (head_dim as f64).sqrt();
+
+        self.weights = vec![0.0; dims.batch * heads * dims.query_len * dims.key_len];
+        let mut output = vec![0.0; dims.batch * dims.query_len * dims.value_dim];
+
+        for b in 0..dims.batch {
+            for h in 0..heads {
+                for i in 0..dims.query_len {
+                    let q_start = (b * dims.query_len + i) * dims.key_dim + h * head_dim;
+                    let q_row = &query.values()[q_start..q_start + head_dim];
+
+                    /* masked softmax over allowed keys, fully masked rows get zero weights */
+                    let mut scores: Vec<Option<f64>> = Vec::with_capacity(dims.key_len);
+                    for j in 0..dims.key_len {
+                        if !self.allowed(&dims, b, i, j) {
+                            scores.push(None);
+                            continue;
+                        }
+                        let k_start = (b * dims.key_len + j) * dims.key_dim + h * head_dim;
+                        let k_row = &key.values()[k_start..k_start + head_dim];
+                        let score: f64 = q_row.iter().zip(k_row.iter()).map(|(q, k)| q * k).sum();
+                        scores.push(Some(score * scale));
+                    }
+
+                    let max = scores.iter().flatten().cloned().fold(f64::NEG_INFINITY, f64::max);
+                    let exps: Vec<f64> = scores.iter()
+                        .map(|s| s.map_or(0.0, |s| (s - max).exp()))
+                        .collect();
+                    let total: f64 = exps.iter().sum();
+
+                    let w_start = ((b * heads + h) * dims.query_len + i) * dims.key_len;
+                    let o_start = (b * dims.query_len + i) * dims.value_dim + h * value_head_dim;
+                    for (j, e) in exps.iter().enumerate() {
+                        if total == 0.0 {
+                            continue;
+                        }
+                        let weight = e / total;
+                        self.weights[w_start + j] = weight;
+
+                        let v_start = (b * dims.key_len + j) * dims.value_dim + h * value_head_dim;
+                        for d in 0..value_head_dim {
+                            output[o_start + d] += weight * value.values()[v_start + d];
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut shape = value.shape().values();
+        let rank = shape.len();
+        shape[rank - 2] = dims.query_len;
+        let op_result = NDArray::array(shape, output).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+}
+
+
+impl<Q, K, V> Node for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Node,
+    K: Node,
+    V: Node,
+{
+
+    /// Perform forward pass of attention operation
+    fn forward(&mut self) {
+
+        self.query().forward();
+        self.key().forward();
+        self.value_input().forward();
+        self.attend();
+    }
+
+    /// Perform backward pass of attention operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let dims = self.dims();
+        let query = self.query().value();
+        let key = self.key().value();
+        let value = self.value_input().value();
+
+        let heads = self.num_heads;
+        let (head_dim, value_head_dim) = (dims.key_dim / heads, dims.value_dim / heads);
+        let scale = 1.0 / (head_dim as f64).sqrt();
+
+        let mut query_grad = vec![0.0; query.size()];
+        let mut key_grad = vec![0.0; key.size()];
+        let mut value_grad = vec![0.0; value.size()];
+
+        for b in 0..dims.batch {
+            for h in 0..heads {
+                for i in 0..dims.query_len {
+                    let w_start = ((b * heads + h) * dims.query_len + i) * dims.key_len;
+                    let weights = &self.weights[w_start..w_start + dims.key_len];
+                    let g_start = (b * dims.query_len + i) * dims.value_dim + h * value_head_dim;
+                    let g_row = &upstream_gradient.values()[g_start..g_start + value_head_dim];
+
+                    /* dA = dO * V^T and dV += A^T * dO */
+                    let mut weight_grads = vec![0.0; dims.key_len];
+                    for (j, weight) in weights.iter().enumerate() {
+                        let v_start = (b * dims.key_len + j) * dims.value_dim + h * value_head_dim;
+                        for d in 0..value_head_dim {
+                            weight_grads[j] += g_row[d] * value.values()[v_start + d];
+                            value_grad[v_start + d] += weight * g_row[d];
+                        }
+                    }
+
+                    /* softmax backward, dS = A * (dA - sum(dA * A)) */
+                    let dot: f64 = weights.iter().zip(weight_grads.iter()).map(|(w, g)| w * g).sum();
+                    let q_start = (b * dims.query_len + i) * dims.key_dim + h * head_dim;
+                    for (j, weight) in weights.iter().enumerate() {
+                        let score_grad = weight * (weight_grads[j] - dot) * scale;
+                        if score_grad == 0.0 {
+                            continue;
+                        }
+
+                        let k_start = (b * dims.key_len + j) * dims.key_dim + h * head_dim;
+                        for d in 0..head_dim {
+                            query_grad[q_start + d] += score_grad * key.values()[k_start + d];
+                            key_grad[k_start + d] += score_grad * query.values()[q_start + d];
+                        }
+                    }
+                }
+            }
+        }
+
+        let query_grad = NDArray::array(query.shape().values(), query_grad).unwrap();
+        let key_grad = NDArray::array(key.shape().values(), key_grad).unwrap();
+        let value_grad = NDArray::array(value.shape().values(), value_grad).unwrap();
+
+        self.query().backward(query_grad);
+        self.key().backward(key_grad);
+        self.value_input().backward(value_grad);
+    }
+
+    /// Get output value of attention operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of attention operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of attention operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of attention operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.query(), self.key(), self.value_input()]
+    }
+}
@@ -0,0 +1,120 @@
+use dendritic_ndarray::ndarray::NDArray;
+use crate::node::Value;
+use crate::optim::Optimizer;
+
+
+/// Clamp every gradient element of the parameters to [-clip_value, clip_value]
+pub fn clip_grad_value(parameters: &mut [Value<NDArray<f64>>], clip_value: f64) {
+
+    let clip_value = clip_value.abs();
+    for parameter in parameters.iter_mut() {
+        let grad = parameter.grad();
+        let values: Vec<f64> = grad.values().iter()
+            .map(|g| g.clamp(-clip_value, clip_value))
+            .collect();
+        parameter.set_grad(&NDArray::array(grad.shape().values(), values).unwrap());
+    }
+}
+
+
+/// Global L2 norm of the gradients of every parameter
+pub fn grad_norm(parameters: &[Value<NDArray<f64>>]) -> f64 {
+    parameters.iter()
+        .map(|p| p.grad().values().iter().map(|g| g * g).sum::<f64>())
+        .sum::<f64>()
+        .sqrt()
+}
+
+
+/// Rescale gradients so their global L2 norm is at most `max_norm`.
+/// The norm is taken over all parameters together so the update direction is kept.
+/// Returns the norm before clipping
+pub fn clip_grad_norm(parameters: &mut [Value<NDArray<f64>>], max_norm: f64) -> f64 {
+
+    let total_norm = grad_norm(parameters);
+    if !total_norm.is_finite() || total_norm <= max_norm || total_norm == 0.0 {
+        return total_norm;
+    }
+
+    let scale = max_norm / total_norm;
+    for parameter in parameters.iter_mut() {
+        let grad = parameter.grad();
+        let values: Vec<f64> = grad.values().iter().map(|g| g * scale).collect();
+        parameter.set_grad(&NDArray::array(grad.shape().values(), values).unwrap());
+    }
+
+    total_norm
+}
+
+
+/// Gradient clipping strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clip {
+    Value(f64),
+    Norm(f64)
+}
+
+
+/// Optimizer wrapper that clips gradients before every step of the inner optimizer
+#[derive(Debug, Clone)]
+pub struct Clipped<O: Optimizer> {
+    pub optimizer: O,
+    pub clip: Clip,
+    last_norm: f64
+}
+
+
+impl<O: Optimizer> Clipped<O> {
+
+    /// Create new instance of optimizer clipping each gradient element by value
+    pub fn by_value(optimizer: O, clip_value: f64) -> Clipped<O> {
+        Clipped {
+            optimizer,
+            clip: Clip::Value(clip_value),
+            last_norm: 0.0
+        }
+    }
+
+    /// Create new instance of optimizer clipping gradients by global norm
+    pub fn by_norm(optimizer: O, max_norm: f64) -> Clipped<O> {
+        Clipped {
+            optimizer,
+            clip: Clip::Norm(max_norm),
+            last_norm: 0.0
+        }
+    }
+
+    /// Global gradient norm before clipping on the last step
+    pub fn last_norm(&self) -> f64 {
+        self.last_norm
+    }
+
+}
+
+
+impl<O: Optimizer> Optimizer for Clipped<O> {
+
+    /// Clip gradients then update parameters with the inner optimizer
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+        self.last_norm = match self.clip {
+            Clip::Value(clip_value) => {
+                let norm = grad_norm(parameters);
+                clip_grad_value(parameters, clip_value);
+                norm
+            },
+            Clip::Norm(max_norm) => clip_grad_norm(parameters, max_norm)
+        };
+        self.optimizer.step(parameters);
+    }
+
+    /// Get learning rate of inner optimizer
+    fn learning_rate(&self) -> f64 {
+        self.optimizer.learning_rate()
+    }
+
+    /// Set learning rate of inner optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.optimizer.set_learning_rate(learning_rate);
+    }
+
+}
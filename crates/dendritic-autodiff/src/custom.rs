@@ -0,0 +1,201 @@
+use dendritic_ndarray::ndarray::NDArray;
+use std::cell::{RefCell, RefMut};
+use crate::node::{Node, Value};
+
+
+/// Differentiable function on ndarrays used by `CustomOp`.
+/// `backward` receives the input values, the forward output and the upstream
+/// gradient and returns one gradient per input with the shape of that input
+pub trait Function {
+    fn forward(&self, inputs: &[NDArray<f64>]) -> Result<NDArray<f64>, String>;
+    fn backward(
+        &self,
+        inputs: &[NDArray<f64>],
+        output: &NDArray<f64>,
+        upstream_gradient: &NDArray<f64>) -> Result<Vec<NDArray<f64>>, String>;
+
+    /// Name of function in graph exports and error messages
+    fn name(&self) -> String {
+        "CustomOp".to_string()
+    }
+}
+
+
+/// Function built from a pair of forward and backward closures
+#[derive(Clone)]
+pub struct Closure<F, B> {
+    pub name: String,
+    forward: F,
+    backward: B
+}
+
+
+/// Create function from forward and backward closures
+pub fn function<F, B>(name: &str, forward: F, backward: B) -> Closure<F, B>
+where
+    F: Fn(&[NDArray<f64>]) -> Result<NDArray<f64>, String>,
+    B: Fn(&[NDArray<f64>], &NDArray<f64>, &NDArray<f64>) -> Result<Vec<NDArray<f64>>, String>,
+{
+    Closure {
+        name: name.to_string(),
+        forward,
+        backward
+    }
+}
+
+
+impl<F, B> Function for Closure<F, B>
+where
+    F: Fn(&[NDArray<f64>]) -> Result<NDArray<f64>, String>,
+    B: Fn(&[NDArray<f64>], &NDArray<f64>, &NDArray<f64>) -> Result<Vec<NDArray<f64>>, String>,
+{
+
+    /// Call forward closure
+    fn forward(&self, inputs: &[NDArray<f64>]) -> Result<NDArray<f64>, String> {
+        (self.forward)(inputs)
+    }
+
+    /// Call backward closure
+    fn backward(
+        &self,
+        inputs: &[NDArray<f64>],
+        output: &NDArray<f64>,
+        upstream_gradient: &NDArray<f64>) -> Result<Vec<NDArray<f64>>, String> {
+        (self.backward)(inputs, output, upstream_gradient)
+    }
+
+    /// Name given to closure function
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+}
+
+
+/// Node applying a user defined function to any number of input nodes
+pub struct CustomOp<F>
+where
+    F: Function,
+{
+    pub function: F,
+    pub inputs: Vec<RefCell<Box<dyn Node>>>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<F> CustomOp<F>
+where
+    F: Function,
+{
+
+    /// Create new instance of custom operation, the forward pass runs immediately
+    pub fn new(function: F, inputs: Vec<Box<dyn Node>>) -> Result<CustomOp<F>, String> {
+
+        if inputs.is_empty() {
+            let msg = format!("CustomOp: {} requires at least one input", function.name());
+            return Err(msg);
+        }
+
+        let values: Vec<NDArray<f64>> = inputs.iter().map(|input| input.value()).collect();
+        let op_value = Value::new(&function.forward(&values)?);
+
+        Ok(CustomOp {
+            function,
+            inputs: inputs.into_iter().map(RefCell::new).collect(),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        })
+    }
+
+    /// Current values of every input
+    fn input_values(&self) -> Vec<NDArray<f64>> {
+        self.inputs.iter().map(|input| input.borrow().value()).collect()
+    }
+
+}
+
+
+impl<F> Node for CustomOp<F>
+where
+    F: Function,
+{
+
+    /// Perform forward pass of inputs and then the custom function
+    fn forward(&mut self) {
+
+        for input in self.inputs.iter() {
+            input.borrow_mut().forward();
+        }
+
+        let op_result = match self.function.forward(&self.input_values()) {
+            Ok(result) => result,
+            Err(err) => panic!("CustomOp: Forward pass of {} failed: {}", self.function.name(), err)
+        };
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of the custom function, gradients are checked
+    /// against the input shapes before being passed on
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let values = self.input_values();
+        let grads = match self.function.backward(&values, &self.value(), &upstream_gradient) {
+            Ok(grads) => grads,
+            Err(err) => panic!("CustomOp: Backward pass of {} failed: {}", self.function.name(), err)
+        };
+
+        if grads.len() != values.len() {
+            panic!(
+                "CustomOp: {} returned {} gradients for {} inputs",
+                self.function.name(), grads.len(), values.len()
+            );
+        }
+
+        for (idx, (grad, value)) in grads.iter().zip(values.iter()).enumerate() {
+            if grad.shape().values() != value.shape().values() {
+                panic!(
+                    "CustomOp: {} gradient shape {:?} does not match input {} shape {:?}",
+                    self.function.name(), grad.shape().values(), idx, value.shape().values()
+                );
+            }
+        }
+
+        for (input, grad) in self.inputs.iter().zip(grads) {
+            input.borrow_mut().backward(grad);
+        }
+    }
+
+    /// Get output value of custom operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of custom operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of custom operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of custom operation in the order given to `new`
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        self.inputs.iter()
+            .map(|input| RefMut::map(input.borrow_mut(), |node| {
+                let node: &mut dyn Node = node.as_mut();
+                node
+            }))
+            .collect()
+    }
+
+    /// Custom operations report the name of their function
+    fn op_name(&self) -> String {
+        self.function.name()
+    }
+
+}
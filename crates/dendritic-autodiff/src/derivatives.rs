@@ -0,0 +1,136 @@
+use dendritic_ndarray::ndarray::NDArray;
+use crate::node::{Node, Value};
+use crate::dual::{Dual, DualOps};
+
+
+/// Run one backward pass of the graph built by `f` at `x` seeded with `upstream`,
+/// returns the gradient with respect to x
+fn pullback<N, F>(f: &F, x: &NDArray<f64>, upstream: Option<NDArray<f64>>) -> Result<NDArray<f64>, String>
+where
+    N: Node,
+    F: Fn(Value<NDArray<f64>>) -> N,
+{
+    let input = Value::new(x);
+    let mut node = f(input.clone());
+
+    let output = node.value();
+    let upstream = match upstream {
+        Some(upstream) => upstream,
+        None => {
+            if output.size() != 1 {
+                let msg = format!(
+                    "Derivatives: Function must return a scalar, got shape {:?}",
+                    output.shape().values()
+                );
+                return Err(msg);
+            }
+            NDArray::fill(output.shape().values(), 1.0)?
+        }
+    };
+
+    node.backward(upstream);
+    let grad = input.grad();
+    if grad.shape().values() != x.shape().values() {
+        let msg = format!(
+            "Derivatives: Gradient shape {:?} does not match input shape {:?}",
+            grad.shape().values(), x.shape().values()
+        );
+        return Err(msg);
+    }
+
+    Ok(grad)
+}
+
+
+/// Gradient of scalar function `f` at `x` with one reverse mode pass.
+/// `f` builds the computation graph from a value node holding x
+pub fn gradient<N, F>(f: F, x: &NDArray<f64>) -> Result<NDArray<f64>, String>
+where
+    N: Node,
+    F: Fn(Value<NDArray<f64>>) -> N,
+{
+    pullback(&f, x, None)
+}
+
+
+/// Jacobian of `f` at `x` with shape (output size, input size).
+/// Row i is the reverse mode gradient of output element i, both sides flattened row major
+pub fn jacobian<N, F>(f: F, x: &NDArray<f64>) -> Result<NDArray<f64>, String>
+where
+    N: Node,
+    F: Fn(Value<NDArray<f64>>) -> N,
+{
+    let output_shape = f(Value::new(x)).value().shape().values();
+    let outputs: usize = output_shape.iter().product();
+
+    let mut values: Vec<f64> = Vec::with_capacity(outputs * x.size());
+    for idx in 0..outputs {
+        let mut seed = NDArray::new(output_shape.clone())?;
+        seed.set_idx(idx, 1.0)?;
+        values.extend(pullback(&f, x, Some(seed))?.values());
+    }
+
+    NDArray::array(vec![outputs, x.size()], values)
+}
+
+
+/// Exact Hessian vector product H(x) * v of scalar function `f` written with nested dual numbers.
+/// Each input enters as (x + e2 v) + e1 u, the e1 e2 part of f is then u^T H v with no truncation error.
+/// Entry i uses u = e_i, so it costs one forward pass per input element
+pub fn hvp<F>(f: F, x: &NDArray<f64>, v: &NDArray<f64>) -> Result<NDArray<f64>, String>
+where
+    F: Fn(&NDArray<Dual<Dual<f64>>>) -> Result<Dual<Dual<f64>>, String>,
+{
+    hessian_vector(&f, x, v)
+}
+
+
+/// Hessian vector product shared by `hvp` and `hessian`
+fn hessian_vector<F>(f: &F, x: &NDArray<f64>, v: &NDArray<f64>) -> Result<NDArray<f64>, String>
+where
+    F: Fn(&NDArray<Dual<Dual<f64>>>) -> Result<Dual<Dual<f64>>, String>,
+{
+    if v.shape().values() != x.shape().values() {
+        let msg = format!(
+            "Derivatives: Vector shape {:?} does not match input shape {:?}",
+            v.shape().values(), x.shape().values()
+        );
+        return Err(msg);
+    }
+
+    let inner = NDArray::<Dual<f64>>::from_parts(x, v)?;
+    let mut values: Vec<f64> = Vec::with_capacity(x.size());
+    for idx in 0..x.size() {
+        let mut unit = NDArray::new(x.shape().values())?;
+        unit.set_idx(idx, Dual::constant(1.0))?;
+        let input = NDArray::<Dual<Dual<f64>>>::from_parts(&inner, &unit)?;
+        values.push(f(&input)?.dual.dual);
+    }
+
+    NDArray::array(x.shape().values(), values)
+}
+
+
+/// Exact Hessian of scalar function `f` at `x` with shape (input size, input size).
+/// Column j is the `hvp` with unit vector e_j
+pub fn hessian<F>(f: F, x: &NDArray<f64>) -> Result<NDArray<f64>, String>
+where
+    F: Fn(&NDArray<Dual<Dual<f64>>>) -> Result<Dual<Dual<f64>>, String>,
+{
+    let n = x.size();
+    let mut columns: Vec<NDArray<f64>> = Vec::with_capacity(n);
+    for j in 0..n {
+        let mut unit = NDArray::new(x.shape().values())?;
+        unit.set_idx(j, 1.0)?;
+        columns.push(hessian_vector(&f, x, &unit)?);
+    }
+
+    let mut values = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            values[i * n + j] = columns[j].values()[i];
+        }
+    }
+
+    NDArray::array(vec![n, n], values)
+}
@@ -0,0 +1,379 @@
+use dendritic_ndarray::ndarray::NDArray;
+use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+
+/// Dual number a + b * e with e^2 = 0 for forward mode differentiation.
+/// `real` carries the value and `dual` the directional derivative (tangent),
+/// both are f64 or dual numbers again for second derivatives
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Dual<T> {
+    pub real: T,
+    pub dual: T
+}
+
+
+/// Scalars dual numbers are built over, f64 or dual numbers themselves.
+/// Nesting duals gives exact higher order derivatives
+pub trait Scalar:
+    Copy + Default + Debug + PartialEq
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self> + Neg<Output = Self>
+{
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(&self) -> f64;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, exponent: f64) -> Self;
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tanh(self) -> Self;
+}
+
+
+impl Scalar for f64 {
+
+    fn from_f64(value: f64) -> f64 { value }
+    fn to_f64(&self) -> f64 { *self }
+    fn exp(self) -> f64 { f64::exp(self) }
+    fn ln(self) -> f64 { f64::ln(self) }
+    fn powf(self, exponent: f64) -> f64 { f64::powf(self, exponent) }
+    fn sqrt(self) -> f64 { f64::sqrt(self) }
+    fn sin(self) -> f64 { f64::sin(self) }
+    fn cos(self) -> f64 { f64::cos(self) }
+    fn tanh(self) -> f64 { f64::tanh(self) }
+}
+
+
+impl<T: Scalar> Scalar for Dual<T> {
+
+    fn from_f64(value: f64) -> Dual<T> { Dual::constant(T::from_f64(value)) }
+    fn to_f64(&self) -> f64 { self.real.to_f64() }
+    fn exp(self) -> Dual<T> { Dual::exp(self) }
+    fn ln(self) -> Dual<T> { Dual::ln(self) }
+    fn powf(self, exponent: f64) -> Dual<T> { Dual::powf(self, exponent) }
+    fn sqrt(self) -> Dual<T> { Dual::sqrt(self) }
+    fn sin(self) -> Dual<T> { Dual::sin(self) }
+    fn cos(self) -> Dual<T> { Dual::cos(self) }
+    fn tanh(self) -> Dual<T> { Dual::tanh(self) }
+}
+
+
+impl<T: Scalar> Dual<T> {
+
+    /// Create new dual number from value and tangent
+    pub fn new(real: T, dual: T) -> Dual<T> {
+        Dual { real, dual }
+    }
+
+    /// Constant with zero tangent
+    pub fn constant(real: T) -> Dual<T> {
+        Dual { real, dual: T::from_f64(0.0) }
+    }
+
+    /// Variable being differentiated, tangent of one
+    pub fn variable(real: T) -> Dual<T> {
+        Dual { real, dual: T::from_f64(1.0) }
+    }
+
+    /// Apply function with known derivative, chain rule on the tangent
+    fn chain(self, value: T, derivative: T) -> Dual<T> {
+        Dual { real: value, dual: derivative * self.dual }
+    }
+
+    /// Exponential of dual number
+    pub fn exp(self) -> Dual<T> {
+        let value = self.real.exp();
+        self.chain(value, value)
+    }
+
+    /// Natural log of dual number
+    pub fn ln(self) -> Dual<T> {
+        self.chain(self.real.ln(), T::from_f64(1.0) / self.real)
+    }
+
+    /// Dual number raised to a real power
+    pub fn powf(self, exponent: f64) -> Dual<T> {
+        let slope = self.real.powf(exponent - 1.0) * T::from_f64(exponent);
+        self.chain(self.real.powf(exponent), slope)
+    }
+
+    /// Square root of dual number
+    pub fn sqrt(self) -> Dual<T> {
+        let value = self.real.sqrt();
+        self.chain(value, T::from_f64(0.5) / value)
+    }
+
+    /// Sine of dual number
+    pub fn sin(self) -> Dual<T> {
+        self.chain(self.real.sin(), self.real.cos())
+    }
+
+    /// Cosine of dual number
+    pub fn cos(self) -> Dual<T> {
+        self.chain(self.real.cos(), -self.real.sin())
+    }
+
+    /// Hyperbolic tangent of dual number
+    pub fn tanh(self) -> Dual<T> {
+        let value = self.real.tanh();
+        self.chain(value, T::from_f64(1.0) - value * value)
+    }
+
+    /// Logistic sigmoid of dual number
+    pub fn sigmoid(self) -> Dual<T> {
+        let one = T::from_f64(1.0);
+        let value = one / (one + (-self.real).exp());
+        self.chain(value, value * (one - value))
+    }
+
+    /// Rectified linear unit of dual number, derivative at zero is zero
+    pub fn relu(self) -> Dual<T> {
+        if self.real.to_f64() > 0.0 { self } else { Dual::constant(T::from_f64(0.0)) }
+    }
+
+    /// Absolute value of dual number, derivative at zero is zero
+    pub fn abs(self) -> Dual<T> {
+        let real = self.real.to_f64();
+        let slope = T::from_f64(if real == 0.0 { 0.0 } else { real.signum() });
+        self.chain(self.real * slope, slope)
+    }
+
+}
+
+
+impl<T: Scalar> Add for Dual<T> {
+    type Output = Dual<T>;
+
+    fn add(self, other: Dual<T>) -> Dual<T> {
+        Dual::new(self.real + other.real, self.dual + other.dual)
+    }
+}
+
+
+impl<T: Scalar> Sub for Dual<T> {
+    type Output = Dual<T>;
+
+    fn sub(self, other: Dual<T>) -> Dual<T> {
+        Dual::new(self.real - other.real, self.dual - other.dual)
+    }
+}
+
+
+impl<T: Scalar> Mul for Dual<T> {
+    type Output = Dual<T>;
+
+    fn mul(self, other: Dual<T>) -> Dual<T> {
+        Dual::new(
+            self.real * other.real,
+            self.real * other.dual + self.dual * other.real
+        )
+    }
+}
+
+
+impl<T: Scalar> Div for Dual<T> {
+    type Output = Dual<T>;
+
+    fn div(self, other: Dual<T>) -> Dual<T> {
+        Dual::new(
+            self.real / other.real,
+            (self.dual * other.real - self.real * other.dual) / (other.real * other.real)
+        )
+    }
+}
+
+
+impl<T: Scalar> Neg for Dual<T> {
+    type Output = Dual<T>;
+
+    fn neg(self) -> Dual<T> {
+        Dual::new(-self.real, -self.dual)
+    }
+}
+
+
+impl<T: Scalar> Add<f64> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn add(self, other: f64) -> Dual<T> {
+        self + Dual::constant(T::from_f64(other))
+    }
+}
+
+
+impl<T: Scalar> Sub<f64> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn sub(self, other: f64) -> Dual<T> {
+        self - Dual::constant(T::from_f64(other))
+    }
+}
+
+
+impl<T: Scalar> Mul<f64> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn mul(self, other: f64) -> Dual<T> {
+        let other = T::from_f64(other);
+        Dual::new(self.real * other, self.dual * other)
+    }
+}
+
+
+impl<T: Scalar> Div<f64> for Dual<T> {
+    type Output = Dual<T>;
+
+    fn div(self, other: f64) -> Dual<T> {
+        let other = T::from_f64(other);
+        Dual::new(self.real / other, self.dual / other)
+    }
+}
+
+
+/// Operations on ndarrays of dual numbers
+pub trait DualOps<T: Scalar> {
+    fn constant(values: &NDArray<f64>) -> NDArray<Dual<T>>;
+    fn from_parts(real: &NDArray<T>, tangent: &NDArray<T>) -> Result<NDArray<Dual<T>>, String>;
+    fn real(&self) -> NDArray<T>;
+    fn tangent(&self) -> NDArray<T>;
+    fn dot(&self, other: &NDArray<Dual<T>>) -> Result<NDArray<Dual<T>>, String>;
+    fn zip(&self, other: &NDArray<Dual<T>>, op: fn(Dual<T>, Dual<T>) -> Dual<T>) -> Result<NDArray<Dual<T>>, String>;
+    fn map(&self, op: fn(Dual<T>) -> Dual<T>) -> NDArray<Dual<T>>;
+    fn sum(&self) -> Dual<T>;
+}
+
+
+impl<T: Scalar> DualOps<T> for NDArray<Dual<T>> {
+
+    /// Lift array of constants into dual numbers with zero tangent
+    fn constant(values: &NDArray<f64>) -> NDArray<Dual<T>> {
+        let duals: Vec<Dual<T>> = values.values().iter().map(|v| Dual::constant(T::from_f64(*v))).collect();
+        NDArray::array(values.shape().values(), duals).unwrap()
+    }
+
+    /// Pair each value with its tangent, shapes must match
+    fn from_parts(
+        real: &NDArray<T>,
+        tangent: &NDArray<T>) -> Result<NDArray<Dual<T>>, String> {
+
+        if real.shape().values() != tangent.shape().values() {
+            let msg = format!(
+                "Dual: Tangent shape {:?} does not match value shape {:?}",
+                tangent.shape().values(), real.shape().values()
+            );
+            return Err(msg);
+        }
+
+        let duals: Vec<Dual<T>> = real.values().iter()
+            .zip(tangent.values().iter())
+            .map(|(r, t)| Dual::new(*r, *t))
+            .collect();
+        NDArray::array(real.shape().values(), duals)
+    }
+
+    /// Values of dual array
+    fn real(&self) -> NDArray<T> {
+        let values: Vec<T> = self.values().iter().map(|d| d.real).collect();
+        NDArray::array(self.shape().values(), values).unwrap()
+    }
+
+    /// Tangents of dual array
+    fn tangent(&self) -> NDArray<T> {
+        let values: Vec<T> = self.values().iter().map(|d| d.dual).collect();
+        NDArray::array(self.shape().values(), values).unwrap()
+    }
+
+    /// Matrix product of two rank 2 dual arrays
+    fn dot(&self, other: &NDArray<Dual<T>>) -> Result<NDArray<Dual<T>>, String> {
+
+        if self.rank() != 2 || other.rank() != 2 || self.shape().dim(1) != other.shape().dim(0) {
+            let msg = format!(
+                "Dual: Cannot multiply shapes {:?} and {:?}",
+                self.shape().values(), other.shape().values()
+            );
+            return Err(msg);
+        }
+
+        let (rows, inner, cols) = (self.shape().dim(0), self.shape().dim(1), other.shape().dim(1));
+        let mut values = vec![Dual::constant(T::from_f64(0.0)); rows * cols];
+        for i in 0..rows {
+            for k in 0..inner {
+                let a = self.values()[i * inner + k];
+                for j in 0..cols {
+                    values[i * cols + j] = values[i * cols + j] + a * other.values()[k * cols + j];
+                }
+            }
+        }
+
+        NDArray::array(vec![rows, cols], values)
+    }
+
+    /// Combine two dual arrays elementwise, rows of shape (1, n) broadcast over (m, n)
+    fn zip(
+        &self,
+        other: &NDArray<Dual<T>>,
+        op: fn(Dual<T>, Dual<T>) -> Dual<T>) -> Result<NDArray<Dual<T>>, String> {
+
+        let shape = self.shape().values();
+        if other.shape().values() == shape {
+            let values: Vec<Dual<T>> = self.values().iter()
+                .zip(other.values().iter())
+                .map(|(a, b)| op(*a, *b))
+                .collect();
+            return NDArray::array(shape, values);
+        }
+
+        let row = other.shape().values();
+        if self.rank() == 2 && row == vec![1, shape[1]] {
+            let values: Vec<Dual<T>> = self.values().iter()
+                .enumerate()
+                .map(|(idx, a)| op(*a, other.values()[idx % shape[1]]))
+                .collect();
+            return NDArray::array(shape, values);
+        }
+
+        let msg = format!(
+            "Dual: Shapes {:?} and {:?} are not compatible",
+            shape, row
+        );
+        Err(msg)
+    }
+
+    /// Apply function to each dual number
+    fn map(&self, op: fn(Dual<T>) -> Dual<T>) -> NDArray<Dual<T>> {
+        let values: Vec<Dual<T>> = self.values().iter().map(|d| op(*d)).collect();
+        NDArray::array(self.shape().values(), values).unwrap()
+    }
+
+    /// Sum of every dual number
+    fn sum(&self) -> Dual<T> {
+        self.values().iter().fold(Dual::constant(T::from_f64(0.0)), |acc, d| acc + *d)
+    }
+
+}
+
+
+/// Derivative of scalar function at x with a single forward pass
+pub fn derivative<F>(f: F, x: f64) -> f64
+where
+    F: Fn(Dual<f64>) -> Dual<f64>,
+{
+    f(Dual::variable(x)).dual
+}
+
+
+/// Jacobian vector product J(x) * v with one forward pass.
+/// Returns f(x) and the directional derivative of every output along v
+pub fn jvp<F>(
+    f: F,
+    x: &NDArray<f64>,
+    v: &NDArray<f64>) -> Result<(NDArray<f64>, NDArray<f64>), String>
+where
+    F: Fn(&NDArray<Dual<f64>>) -> Result<NDArray<Dual<f64>>, String>,
+{
+    let input = NDArray::<Dual<f64>>::from_parts(x, v)?;
+    let output = f(&input)?;
+    Ok((output.real(), output.tangent()))
+}
@@ -0,0 +1,145 @@
+use dendritic_ndarray::ndarray::NDArray;
+use crate::node::{Node, Value};
+
+
+/// Worst mismatch between analytic and numerical gradient for one input
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradMismatch {
+    pub index: usize,
+    pub analytic: f64,
+    pub numerical: f64,
+    pub error: f64
+}
+
+
+/// Result of comparing a node's backward pass against finite differences
+#[derive(Debug, Clone)]
+pub struct GradCheck {
+    pub mismatches: Vec<GradMismatch>,
+    pub tolerance: f64
+}
+
+
+impl GradCheck {
+
+    /// Check if every input is within tolerance
+    pub fn passed(&self) -> bool {
+        self.mismatches.iter().all(|m| m.error <= self.tolerance)
+    }
+
+    /// Largest error found across all inputs
+    pub fn max_error(&self) -> f64 {
+        self.mismatches.iter().fold(0.0, |acc, m| acc.max(m.error))
+    }
+
+}
+
+
+/// Weights used to project node output to a scalar.
+/// Non uniform so gradients that sum to zero along a row (softmax) are still exercised
+fn projection(shape: Vec<usize>) -> NDArray<f64> {
+    let size: usize = shape.iter().product();
+    let weights: Vec<f64> = (0..size)
+        .map(|i| 1.0 + (i % 7) as f64 * 0.25)
+        .collect();
+    NDArray::array(shape, weights).unwrap()
+}
+
+
+/// Scalar objective sum(weights * output) for node output
+fn objective(output: &NDArray<f64>, weights: &NDArray<f64>) -> f64 {
+    output.values().iter()
+        .zip(weights.values().iter())
+        .map(|(o, w)| o * w)
+        .sum()
+}
+
+
+/// Compare analytic gradients of a node against central finite differences.
+/// `node_builder` creates the graph from value nodes holding each input.
+/// Each input element is perturbed by `eps` and the worst relative error per input is reported
+pub fn gradcheck<N, F>(
+    node_builder: F,
+    inputs: &[NDArray<f64>],
+    eps: f64,
+    tol: f64) -> Result<GradCheck, String>
+where
+    N: Node,
+    F: Fn(&[Value<NDArray<f64>>]) -> N,
+{
+
+    if eps <= 0.0 {
+        return Err("Gradcheck: Epsilon must be greater than 0".to_string());
+    }
+
+    let mut values: Vec<Value<NDArray<f64>>> = inputs.iter()
+        .map(Value::new)
+        .collect();
+
+    let mut node = node_builder(&values);
+    node.forward();
+
+    /* analytic gradients */
+    let weights = projection(node.value().shape().values());
+    node.backward(weights.clone());
+    let analytic: Vec<NDArray<f64>> = values.iter()
+        .map(|v| v.grad())
+        .collect();
+
+    let mut mismatches: Vec<GradMismatch> = Vec::new();
+    for (input_idx, input) in inputs.iter().enumerate() {
+
+        let grad = &analytic[input_idx];
+        if grad.shape().values() != input.shape().values() {
+            let msg = format!(
+                "Gradcheck: Gradient shape {:?} does not match input {} shape {:?}",
+                grad.shape().values(), input_idx, input.shape().values()
+            );
+            return Err(msg);
+        }
+
+        let mut worst = GradMismatch {
+            index: 0,
+            analytic: 0.0,
+            numerical: 0.0,
+            error: 0.0
+        };
+
+        for idx in 0..input.size() {
+
+            let original = *input.idx(idx);
+            let mut perturbed = input.clone();
+
+            perturbed.set_idx(idx, original + eps)?;
+            values[input_idx].set_val(&perturbed);
+            node.forward();
+            let f_plus = objective(&node.value(), &weights);
+
+            perturbed.set_idx(idx, original - eps)?;
+            values[input_idx].set_val(&perturbed);
+            node.forward();
+            let f_minus = objective(&node.value(), &weights);
+
+            values[input_idx].set_val(input);
+
+            let numerical = (f_plus - f_minus) / (2.0 * eps);
+            let analytic_val = *grad.idx(idx);
+            let scale = 1.0_f64.max(numerical.abs()).max(analytic_val.abs());
+            let error = (analytic_val - numerical).abs() / scale;
+
+            if error > worst.error || idx == 0 {
+                worst = GradMismatch {
+                    index: idx,
+                    analytic: analytic_val,
+                    numerical,
+                    error
+                };
+            }
+        }
+
+        mismatches.push(worst);
+    }
+
+    node.forward();
+    Ok(GradCheck { mismatches, tolerance: tol })
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use crate::node::Node;
+
+
+/// Node declarations and edges collected while walking a graph
+struct DotBuilder {
+    include_grads: bool,
+    ids: HashMap<usize, usize>,
+    nodes: Vec<String>,
+    edges: Vec<String>
+}
+
+
+impl DotBuilder {
+
+    /// Visit inputs before the node itself so output follows data flow order.
+    /// Returns the dot identifier of the node
+    fn visit(&mut self, node: &dyn Node) -> usize {
+
+        if let Some(id) = self.ids.get(&node.node_id()) {
+            return *id;
+        }
+
+        let input_ids: Vec<usize> = node.inputs()
+            .iter()
+            .map(|input| self.visit(&**input))
+            .collect();
+
+        let id = self.ids.len();
+        self.ids.insert(node.node_id(), id);
+
+        let value = node.value();
+        let mut label = format!("{}\\nshape {:?}", node.op_name(), value.shape().values());
+        if self.include_grads {
+            let norm = node.grad().values().iter().map(|g| g * g).sum::<f64>().sqrt();
+            label.push_str(&format!("\\ngrad norm {:.6}", norm));
+        }
+
+        let shape = if input_ids.is_empty() { "ellipse" } else { "box" };
+        self.nodes.push(format!("    n{} [label=\"{}\", shape={}];", id, label, shape));
+        for input_id in input_ids {
+            self.edges.push(format!("    n{} -> n{};", input_id, id));
+        }
+
+        id
+    }
+
+}
+
+
+/// Export computation graph ending in `node` to Graphviz DOT.
+/// Each node is labeled with its op type and value shape, optionally with the
+/// L2 norm of its gradient from the last backward pass. Edges point from inputs
+/// to the operation consuming them and shared values appear once
+pub fn to_dot(node: &dyn Node, include_grads: bool) -> String {
+
+    let mut builder = DotBuilder {
+        include_grads,
+        ids: HashMap::new(),
+        nodes: Vec::new(),
+        edges: Vec::new()
+    };
+    builder.visit(node);
+
+    let mut lines: Vec<String> = vec![
+        "digraph computation_graph {".to_string(),
+        "    rankdir=LR;".to_string()
+    ];
+    lines.extend(builder.nodes);
+    lines.extend(builder.edges);
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+
+/// Graphviz export for any node of a computation graph
+pub trait ToDot {
+    fn to_dot(&self) -> String;
+    fn to_dot_with_grads(&self) -> String;
+    fn save_dot(&self, filepath: &str, include_grads: bool) -> std::io::Result<()>;
+}
+
+
+impl<N: Node> ToDot for N {
+
+    /// Export graph labeled with op types and shapes
+    fn to_dot(&self) -> String {
+        to_dot(self, false)
+    }
+
+    /// Export graph labeled with op types, shapes and gradient norms
+    fn to_dot_with_grads(&self) -> String {
+        to_dot(self, true)
+    }
+
+    /// Write graph to a `.dot` file
+    fn save_dot(&self, filepath: &str, include_grads: bool) -> std::io::Result<()> {
+        let mut file = File::create(filepath)?;
+        file.write_all(to_dot(self, include_grads).as_bytes())
+    }
+
+}
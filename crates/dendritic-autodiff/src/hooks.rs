@@ -0,0 +1,143 @@
+use dendritic_ndarray::ndarray::NDArray;
+use std::cell::RefMut;
+use crate::node::Node;
+use crate::anomaly::{anomaly_detection, is_finite};
+
+
+/// Hook called with the node name and upstream gradient before a backward pass
+pub type PreBackwardHook = Box<dyn FnMut(&str, &NDArray<f64>)>;
+
+/// Hook called with the node name and wrapped node after a backward pass,
+/// gradients of the node inputs are available through `inputs()`
+pub type PostBackwardHook = Box<dyn FnMut(&str, &dyn Node)>;
+
+
+/// Named wrapper around a node with pre and post backward hooks.
+/// With anomaly detection enabled the forward and backward passes halt
+/// with the node name as soon as a value or gradient is not finite
+pub struct Hooked<N>
+where
+    N: Node,
+{
+    pub name: String,
+    pub node: N,
+    pre_hooks: Vec<PreBackwardHook>,
+    post_hooks: Vec<PostBackwardHook>
+}
+
+
+impl<N> Hooked<N>
+where
+    N: Node,
+{
+
+    /// Create new instance of named node without hooks
+    pub fn new(name: &str, node: N) -> Hooked<N> {
+        Hooked {
+            name: name.to_string(),
+            node,
+            pre_hooks: Vec::new(),
+            post_hooks: Vec::new()
+        }
+    }
+
+    /// Register hook called before the backward pass of the wrapped node
+    pub fn register_pre_backward_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut(&str, &NDArray<f64>) + 'static,
+    {
+        self.pre_hooks.push(Box::new(hook));
+    }
+
+    /// Register hook called after the backward pass of the wrapped node
+    pub fn register_post_backward_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut(&str, &dyn Node) + 'static,
+    {
+        self.post_hooks.push(Box::new(hook));
+    }
+
+    /// Remove every registered hook
+    pub fn clear_hooks(&mut self) {
+        self.pre_hooks.clear();
+        self.post_hooks.clear();
+    }
+
+}
+
+
+impl<N> Node for Hooked<N>
+where
+    N: Node,
+{
+
+    /// Perform forward pass of wrapped node
+    fn forward(&mut self) {
+
+        self.node.forward();
+
+        if anomaly_detection() && !is_finite(&self.node.value()) {
+            panic!("Anomaly: Non-finite value produced in forward pass of {}", self.name);
+        }
+    }
+
+    /// Run pre hooks, backward pass of wrapped node and then post hooks
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        for hook in self.pre_hooks.iter_mut() {
+            hook(&self.name, &upstream_gradient);
+        }
+
+        if anomaly_detection() && !is_finite(&upstream_gradient) {
+            panic!("Anomaly: Non-finite upstream gradient passed to backward pass of {}", self.name);
+        }
+
+        self.node.backward(upstream_gradient);
+
+        if anomaly_detection() {
+            for (idx, input) in self.node.inputs().iter().enumerate() {
+                if !is_finite(&input.grad()) {
+                    panic!(
+                        "Anomaly: Non-finite gradient produced in backward pass of {} for input {} ({})",
+                        self.name, idx, input.op_name()
+                    );
+                }
+            }
+        }
+
+        for hook in self.post_hooks.iter_mut() {
+            hook(&self.name, &self.node);
+        }
+    }
+
+    /// Get output value of wrapped node
+    fn value(&self) -> NDArray<f64> {
+        self.node.value()
+    }
+
+    /// Get gradient of wrapped node
+    fn grad(&self) -> NDArray<f64> {
+        self.node.grad()
+    }
+
+    /// Set gradient of wrapped node
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.node.set_grad(upstream_gradient);
+    }
+
+    /// Get inputs of wrapped node in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        self.node.inputs()
+    }
+
+    /// Wrapped node keeps its identity in graph walks
+    fn node_id(&self) -> usize {
+        self.node.node_id()
+    }
+
+    /// Named nodes report their name in graph exports and anomaly messages
+    fn op_name(&self) -> String {
+        self.name.clone()
+    }
+
+}
@@ -0,0 +1,227 @@
+use dendritic_ndarray::ndarray::NDArray;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+
+/// Weight initialization scheme.
+/// Fan in and fan out follow the `(input_dim, output_dim)` weight layout used by
+/// models and layers, for higher ranks the last axis is fan out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Init {
+    Zeros,
+    Constant(f64),
+    Uniform(f64, f64),
+    Normal(f64, f64),
+    XavierUniform,
+    XavierNormal,
+    HeUniform,
+    HeNormal,
+    Orthogonal(f64)
+}
+
+
+/// Seeded weight initializer. Every call to `sample` advances the random
+/// stream, so initializing several parameters from one initializer is
+/// reproducible while each parameter still gets different values
+#[derive(Debug, Clone)]
+pub struct Initializer {
+    pub init: Init,
+    rng: StdRng
+}
+
+
+impl Initializer {
+
+    /// Create new instance of initializer with a seed
+    pub fn new(init: Init, seed: u64) -> Initializer {
+        Initializer {
+            init,
+            rng: StdRng::seed_from_u64(seed)
+        }
+    }
+
+    /// Create new instance of initializer seeded from the thread rng
+    pub fn random(init: Init) -> Initializer {
+        let seed: u64 = rand::thread_rng().gen();
+        Initializer::new(init, seed)
+    }
+
+    /// Initializer filling every value with zeros
+    pub fn zeros() -> Initializer {
+        Initializer::new(Init::Zeros, 0)
+    }
+
+    /// Sample array of the given shape
+    pub fn sample(&mut self, shape: Vec<usize>) -> Result<NDArray<f64>, String> {
+
+        let size: usize = shape.iter().product();
+        let (fan_in, fan_out) = fans(&shape)?;
+
+        let values: Vec<f64> = match self.init {
+            Init::Zeros => vec![0.0; size],
+            Init::Constant(value) => vec![value; size],
+            Init::Uniform(low, high) => {
+                if low >= high {
+                    let msg = format!(
+                        "Init: Uniform lower bound {} must be less than upper bound {}",
+                        low, high
+                    );
+                    return Err(msg);
+                }
+                self.uniform(size, low, high)
+            },
+            Init::Normal(mean, std) => {
+                if std < 0.0 {
+                    return Err("Init: Standard deviation must be non negative".to_string());
+                }
+                self.normal(size, mean, std)
+            },
+            Init::XavierUniform => {
+                let limit = (6.0 / (fan_in + fan_out) as f64).sqrt();
+                self.uniform(size, -limit, limit)
+            },
+            Init::XavierNormal => {
+                let std = (2.0 / (fan_in + fan_out) as f64).sqrt();
+                self.normal(size, 0.0, std)
+            },
+            Init::HeUniform => {
+                let limit = (6.0 / fan_in as f64).sqrt();
+                self.uniform(size, -limit, limit)
+            },
+            Init::HeNormal => {
+                let std = (2.0 / fan_in as f64).sqrt();
+                self.normal(size, 0.0, std)
+            },
+            Init::Orthogonal(gain) => {
+                if shape.len() < 2 {
+                    return Err("Init: Orthogonal initialization requires rank 2 or higher".to_string());
+                }
+                self.orthogonal(shape[0], size / shape[0], gain)
+            }
+        };
+
+        NDArray::array(shape, values)
+    }
+
+    /// Values drawn from U(low, high)
+    fn uniform(&mut self, size: usize, low: f64, high: f64) -> Vec<f64> {
+        (0..size).map(|_| self.rng.gen_range(low..high)).collect()
+    }
+
+    /// Values drawn from N(mean, std^2) with the Box-Muller transform
+    fn normal(&mut self, size: usize, mean: f64, std: f64) -> Vec<f64> {
+        (0..size).map(|_| {
+            let u1: f64 = 1.0 - self.rng.gen::<f64>();
+            let u2: f64 = self.rng.gen::<f64>();
+            let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+            mean + std * z
+        }).collect()
+    }
+
+    /// Row major (rows, cols) matrix with orthonormal columns, or orthonormal
+    /// rows when there are fewer rows than columns, scaled by gain
+    fn orthogonal(&mut self, rows: usize, cols: usize, gain: f64) -> Vec<f64> {
+
+        /* orthonormalize the smaller set of vectors with modified gram schmidt */
+        let (count, length) = if rows >= cols { (cols, rows) } else { (rows, cols) };
+        let mut vectors: Vec<Vec<f64>> = Vec::with_capacity(count);
+        while vectors.len() < count {
+            let mut v = self.normal(length, 0.0, 1.0);
+            for q in vectors.iter() {
+                let proj: f64 = v.iter().zip(q.iter()).map(|(a, b)| a * b).sum();
+                v.iter_mut().zip(q.iter()).for_each(|(a, b)| *a -= proj * b);
+            }
+
+            let norm = v.iter().map(|a| a * a).sum::<f64>().sqrt();
+            if norm > 1e-10 {
+                vectors.push(v.iter().map(|a| a / norm).collect());
+            }
+        }
+
+        let mut values = vec![0.0; rows * cols];
+        for (k, v) in vectors.iter().enumerate() {
+            for (i, a) in v.iter().enumerate() {
+                let idx = if rows >= cols { i * cols + k } else { k * cols + i };
+                values[idx] = gain * a;
+            }
+        }
+        values
+    }
+
+}
+
+
+/// Fan in and fan out of a weight shape
+fn fans(shape: &[usize]) -> Result<(usize, usize), String> {
+
+    let size: usize = shape.iter().product();
+    let (fan_in, fan_out) = match shape.len() {
+        0 => (1, 1),
+        1 => (shape[0], shape[0]),
+        _ => {
+            let last = shape[shape.len() - 1];
+            (size / last.max(1), last)
+        }
+    };
+
+    if fan_in == 0 || fan_out == 0 {
+        let msg = format!("Init: Shape {:?} has an empty dimension", shape);
+        return Err(msg);
+    }
+
+    Ok((fan_in, fan_out))
+}
+
+
+/// Array filled with zeros
+pub fn zeros(shape: Vec<usize>) -> Result<NDArray<f64>, String> {
+    Initializer::zeros().sample(shape)
+}
+
+
+/// Array filled with a constant
+pub fn constant(shape: Vec<usize>, value: f64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::Constant(value), 0).sample(shape)
+}
+
+
+/// Array drawn from U(low, high)
+pub fn uniform(shape: Vec<usize>, low: f64, high: f64, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::Uniform(low, high), seed).sample(shape)
+}
+
+
+/// Array drawn from N(mean, std^2)
+pub fn normal(shape: Vec<usize>, mean: f64, std: f64, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::Normal(mean, std), seed).sample(shape)
+}
+
+
+/// Xavier/Glorot uniform, U(-a, a) with a = sqrt(6 / (fan_in + fan_out))
+pub fn xavier_uniform(shape: Vec<usize>, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::XavierUniform, seed).sample(shape)
+}
+
+
+/// Xavier/Glorot normal, N(0, 2 / (fan_in + fan_out))
+pub fn xavier_normal(shape: Vec<usize>, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::XavierNormal, seed).sample(shape)
+}
+
+
+/// He/Kaiming uniform, U(-a, a) with a = sqrt(6 / fan_in)
+pub fn he_uniform(shape: Vec<usize>, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::HeUniform, seed).sample(shape)
+}
+
+
+/// He/Kaiming normal, N(0, 2 / fan_in)
+pub fn he_normal(shape: Vec<usize>, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::HeNormal, seed).sample(shape)
+}
+
+
+/// Orthogonal matrix scaled by gain, rank 2 or higher shapes are flattened to (dim 0, rest)
+pub fn orthogonal(shape: Vec<usize>, gain: f64, seed: u64) -> Result<NDArray<f64>, String> {
+    Initializer::new(Init::Orthogonal(gain), seed).sample(shape)
+}
@@ -7,8 +7,26 @@
 //!
 //! ## Features
 //! - **Node**: Node structure for holding shared methods across all values in a computation graph.
-//! - **Ops**: Operations with forward and backward pass implemented
+//! - **Ops**: Operations with forward and backward pass implemented (dot, matmul, elementwise arithmetic, reductions and reshape)
+//! - **Activations**: Sigmoid, tanh, relu, leaky relu, softmax and log softmax nodes for non linear layers
 //! - **Regularizers**: Operations specific to weight regualarization to prevent overfitting
+//! - **Loss**: Loss nodes (MSE, MAE, Huber, binary and categorical cross entropy) that reduce to a scalar and start backpropagation
+//! - **Gradcheck**: Numerical gradient checking of node backward passes with central finite differences
+//! - **Optim**: Optimizers (SGD with momentum, Adam, AdamW, RMSProp, Adagrad) that update parameter values from their gradients
+//! - **Scheduler**: Learning rate schedulers (step, exponential, cosine annealing with warm restarts, linear warmup, reduce on plateau)
+//! - **Normalization**: Dropout, batch normalization and layer normalization nodes with training and evaluation modes
+//! - **Attention**: Scaled dot product attention node with masking and multiple heads, sinusoidal positional encoding
+//! - **Graph**: Graphviz DOT export of computation graphs with op types, shapes and optional gradient norms
+//! - **Derivatives**: Gradient and jacobian of functions built from nodes, exact hessian and hessian vector products with nested dual numbers
+//! - **Dual**: Forward mode differentiation with dual numbers, ndarrays of duals and jacobian vector products
+//! - **Sync**: Thread safe values and data parallel gradient averaging over shards with rayon
+//! - **Clip**: Gradient clipping by value and by global norm, optimizer wrapper clipping before each step
+//! - **Anomaly**: Anomaly detection that halts with the offending node when a value or gradient is not finite
+//! - **Hooks**: Named node wrapper with pre and post backward hooks for logging and inspection
+//! - **Init**: Seedable weight initializers (zeros, constant, uniform, normal, Xavier, He and orthogonal)
+//! - **Penalty**: Composable weight penalties (L1, L2, elastic net, group lasso) attached to parameters
+//! - **State**: Named parameter registry with versioned state dict save and load
+//! - **Custom**: User defined ops from forward and backward closures through the `Function` trait
 //!
 //! ## Example Usage
 //! This is an example of creating the computation graph for a linear operation
@@ -51,4 +69,22 @@
 //! This is a project that is still very much a work in progress.
 pub mod node;
 pub mod ops;
-pub mod regularizers; 
+pub mod regularizers;
+pub mod activations;
+pub mod loss;
+pub mod gradcheck;
+pub mod optim;
+pub mod scheduler;
+pub mod normalization;
+pub mod attention;
+pub mod graph;
+pub mod derivatives;
+pub mod dual;
+pub mod sync;
+pub mod clip;
+pub mod anomaly;
+pub mod hooks;
+pub mod init;
+pub mod penalty;
+pub mod state;
+pub mod custom;
@@ -0,0 +1,836 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_ndarray::ops::*;
+use dendritic_metrics::activations::sigmoid;
+use dendritic_metrics::loss::*;
+use std::cell::{RefCell, RefMut};
+use crate::node::{Node, Value};
+use crate::ops::elementwise;
+use crate::activations::{softmax_rows, log_softmax_rows};
+
+/// Clamp for probabilities passed to log based losses
+const EPSILON: f64 = 1e-12;
+
+
+/// Methods shared by loss nodes, which reduce to a single scalar value
+pub trait Loss: Node {
+
+    /// Scalar loss value from the last forward pass
+    fn loss(&self) -> f64 {
+        self.value().values()[0]
+    }
+
+    /// Start backpropagation from the loss with a gradient of 1.0
+    fn backprop(&mut self) {
+        let seed = NDArray::fill(vec![1, 1], 1.0).unwrap();
+        self.backward(seed);
+    }
+}
+
+
+/// Wrap loss value in (1, 1) ndarray
+fn scalar(value: f64) -> NDArray<f64> {
+    NDArray::array(vec![1, 1], vec![value]).unwrap()
+}
+
+
+/// Number of elements a loss is averaged over
+fn samples(y_pred: &NDArray<f64>) -> f64 {
+    y_pred.size() as f64
+}
+
+
+/// Number of rows a categorical loss is averaged over
+fn rows(y_pred: &NDArray<f64>) -> f64 {
+    y_pred.shape().dim(0) as f64
+}
+
+
+/// Clamp probabilities before a log based loss
+fn clamped(y_pred: &NDArray<f64>) -> NDArray<f64> {
+    y_pred.apply(|p| p.clamp(EPSILON, 1.0 - EPSILON)).unwrap()
+}
+
+
+/// Categorical losses from the metrics crate average over every element,
+/// the nodes average over rows
+fn per_row(loss: f64, y_pred: &NDArray<f64>) -> f64 {
+    loss * samples(y_pred) / rows(y_pred)
+}
+
+
+/// Mean squared error loss node
+pub struct MSE<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> MSE<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of mean squared error loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS) -> MSE<RHS, LHS> {
+
+        let op_result = scalar(mse(&lhs.value(), &rhs.value()).unwrap());
+        let op_value = Value::new(&op_result);
+
+        MSE {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get predictions of mean squared error loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of mean squared error loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for MSE<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of mean squared error loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(mse(&y_true, &y_pred).unwrap());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of mean squared error loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let scale = upstream_gradient.values()[0] / samples(&y_pred);
+
+        /* d/dp = 2(p - t)/n, d/dt = -2(p - t)/n */
+        let pred_grad = elementwise(&y_pred, &y_true, |p, t| 2.0 * (p - t)).unwrap()
+            .scalar_mult(scale).unwrap();
+        let true_grad = pred_grad.scalar_mult(-1.0).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of mean squared error loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of mean squared error loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of mean squared error loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of mean squared error loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for MSE<RHS, LHS> {}
+
+
+/// Mean absolute error loss node
+pub struct MAE<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> MAE<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of mean absolute error loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS) -> MAE<RHS, LHS> {
+
+        let op_result = scalar(mae(&lhs.value(), &rhs.value()).unwrap());
+        let op_value = Value::new(&op_result);
+
+        MAE {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get predictions of mean absolute error loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of mean absolute error loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for MAE<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of mean absolute error loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(mae(&y_true, &y_pred).unwrap());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of mean absolute error loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let scale = upstream_gradient.values()[0] / samples(&y_pred);
+
+        let pred_grad = y_pred.subtract(y_true).unwrap()
+            .signum().unwrap()
+            .scalar_mult(scale).unwrap();
+        let true_grad = pred_grad.scalar_mult(-1.0).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of mean absolute error loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of mean absolute error loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of mean absolute error loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of mean absolute error loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for MAE<RHS, LHS> {}
+
+
+/// Huber loss node, robust to outliers past `delta`
+pub struct Huber<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub delta: f64
+}
+
+
+impl<RHS, LHS> Huber<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of huber loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS, delta: f64) -> Huber<RHS, LHS> {
+
+        let op_result = scalar(huber(&lhs.value(), &rhs.value(), delta).unwrap());
+        let op_value = Value::new(&op_result);
+
+        Huber {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            delta
+        }
+    }
+
+    /// Get predictions of huber loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of huber loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for Huber<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of huber loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(huber(&y_true, &y_pred, self.delta).unwrap());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of huber loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let scale = upstream_gradient.values()[0] / samples(&y_pred);
+
+        let delta = self.delta;
+        let residuals = y_pred.subtract(y_true).unwrap();
+        let clipped: Vec<f64> = residuals.values().iter()
+            .map(|r| r.clamp(-delta, delta) * scale)
+            .collect();
+        let pred_grad = NDArray::array(residuals.shape().values(), clipped).unwrap();
+        let true_grad = pred_grad.scalar_mult(-1.0).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of huber loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of huber loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of huber loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of huber loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for Huber<RHS, LHS> {}
+
+
+/// Binary cross entropy loss node over predicted probabilities
+pub struct BinaryCrossEntropy<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> BinaryCrossEntropy<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of binary cross entropy loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS) -> BinaryCrossEntropy<RHS, LHS> {
+
+        let op_result = scalar(binary_cross_entropy(&clamped(&rhs.value()), &lhs.value()).unwrap());
+        let op_value = Value::new(&op_result);
+
+        BinaryCrossEntropy {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get predictions of binary cross entropy loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of binary cross entropy loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for BinaryCrossEntropy<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of binary cross entropy loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(binary_cross_entropy(&clamped(&y_pred), &y_true).unwrap());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of binary cross entropy loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let scale = upstream_gradient.values()[0] / samples(&y_pred);
+
+        /* d/dp = (p - t) / (p(1 - p)), d/dt = ln(1 - p) - ln(p) */
+        let pred_grad = elementwise(&y_pred, &y_true, |p, t| {
+            let p = p.clamp(EPSILON, 1.0 - EPSILON);
+            (p - t) / (p * (1.0 - p))
+        }).unwrap().scalar_mult(scale).unwrap();
+
+        let true_grad = y_pred.apply(|p| {
+            let p = p.clamp(EPSILON, 1.0 - EPSILON);
+            (1.0 - p).ln() - p.ln()
+        }).unwrap().scalar_mult(scale).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of binary cross entropy loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of binary cross entropy loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of binary cross entropy loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of binary cross entropy loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for BinaryCrossEntropy<RHS, LHS> {}
+
+
+/// Binary cross entropy loss node over raw logits, fusing the sigmoid
+pub struct BinaryCrossEntropyWithLogits<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> BinaryCrossEntropyWithLogits<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of binary cross entropy with logits loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS) -> BinaryCrossEntropyWithLogits<RHS, LHS> {
+
+        let op_result = scalar(binary_cross_entropy_logits(&rhs.value(), &lhs.value()).unwrap());
+        let op_value = Value::new(&op_result);
+
+        BinaryCrossEntropyWithLogits {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get predictions of binary cross entropy with logits loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of binary cross entropy with logits loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for BinaryCrossEntropyWithLogits<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of binary cross entropy with logits loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(binary_cross_entropy_logits(&y_pred, &y_true).unwrap());
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of binary cross entropy with logits loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let scale = upstream_gradient.values()[0] / samples(&y_pred);
+
+        /* d/dz = sigmoid(z) - t, d/dt = -z */
+        let pred_grad = elementwise(&y_pred, &y_true, |z, t| sigmoid(z) - t).unwrap()
+            .scalar_mult(scale).unwrap();
+        let true_grad = y_pred.scalar_mult(-scale).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of binary cross entropy with logits loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of binary cross entropy with logits loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of binary cross entropy with logits loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of binary cross entropy with logits loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for BinaryCrossEntropyWithLogits<RHS, LHS> {}
+
+
+/// Categorical cross entropy loss node over predicted class probabilities
+pub struct CategoricalCrossEntropy<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> CategoricalCrossEntropy<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of categorical cross entropy loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS) -> CategoricalCrossEntropy<RHS, LHS> {
+
+        let op_result = scalar(per_row(
+            categorical_cross_entropy(&clamped(&rhs.value()), &lhs.value()).unwrap(),
+            &rhs.value()
+        ));
+        let op_value = Value::new(&op_result);
+
+        CategoricalCrossEntropy {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get predictions of categorical cross entropy loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of categorical cross entropy loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for CategoricalCrossEntropy<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of categorical cross entropy loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(per_row(
+            categorical_cross_entropy(&clamped(&y_pred), &y_true).unwrap(),
+            &y_pred
+        ));
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of categorical cross entropy loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let row_scale = upstream_gradient.values()[0] / rows(&y_pred);
+
+        let pred_grad = elementwise(&y_pred, &y_true, |p, t| -t / p.max(EPSILON)).unwrap()
+            .scalar_mult(row_scale).unwrap();
+        let true_grad = y_pred.apply(|p| -p.max(EPSILON).ln()).unwrap()
+            .scalar_mult(row_scale).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of categorical cross entropy loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of categorical cross entropy loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of categorical cross entropy loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of categorical cross entropy loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for CategoricalCrossEntropy<RHS, LHS> {}
+
+
+/// Categorical cross entropy loss node over raw logits, fusing the softmax
+pub struct CategoricalCrossEntropyWithLogits<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> CategoricalCrossEntropyWithLogits<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of categorical cross entropy with logits loss with predictions (rhs) and targets (lhs)
+    pub fn new(rhs: RHS, lhs: LHS) -> CategoricalCrossEntropyWithLogits<RHS, LHS> {
+
+        let op_result = scalar(per_row(
+            categorical_cross_entropy_logits(&rhs.value(), &lhs.value()).unwrap(),
+            &rhs.value()
+        ));
+        let op_value = Value::new(&op_result);
+
+        CategoricalCrossEntropyWithLogits {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get predictions of categorical cross entropy with logits loss
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get targets of categorical cross entropy with logits loss
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for CategoricalCrossEntropyWithLogits<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of categorical cross entropy with logits loss
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let op_result = scalar(per_row(
+            categorical_cross_entropy_logits(&y_pred, &y_true).unwrap(),
+            &y_pred
+        ));
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of categorical cross entropy with logits loss
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let y_pred = self.rhs().value();
+        let y_true = self.lhs().value();
+        let row_scale = upstream_gradient.values()[0] / rows(&y_pred);
+
+        /* d/dz = softmax(z) * sum(t) - t for each row, d/dt = -log_softmax(z) */
+        let softmax = softmax_rows(&y_pred);
+        let cols = y_pred.shape().dim(y_pred.rank() - 1);
+        let mut grads: Vec<f64> = Vec::new();
+        for (s_row, t_row) in softmax.values().chunks(cols).zip(y_true.values().chunks(cols)) {
+            let t_sum: f64 = t_row.iter().sum();
+            grads.extend(s_row.iter().zip(t_row.iter()).map(|(s, t)| (s * t_sum - t) * row_scale));
+        }
+        let pred_grad = NDArray::array(y_pred.shape().values(), grads).unwrap();
+        let true_grad = log_softmax_rows(&y_pred).scalar_mult(-row_scale).unwrap();
+
+        self.rhs().backward(pred_grad);
+        self.lhs().backward(true_grad);
+    }
+
+    /// Get output value of categorical cross entropy with logits loss
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of categorical cross entropy with logits loss
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of categorical cross entropy with logits loss
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of categorical cross entropy with logits loss in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+impl<RHS: Node, LHS: Node> Loss for CategoricalCrossEntropyWithLogits<RHS, LHS> {}
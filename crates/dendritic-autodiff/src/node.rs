@@ -1,6 +1,6 @@
 use dendritic_ndarray::ndarray::NDArray;
 use std::rc::Rc; 
-use std::cell::{RefCell}; 
+use std::cell::{Cell, RefCell, RefMut}; 
 
 
 /// Methods for each value in computation graph
@@ -10,14 +10,43 @@ pub trait Node {
     fn value(&self) -> NDArray<f64>;
     fn grad(&self) -> NDArray<f64>;
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>);
+
+    /// Get inputs of node in data flow order, values have no inputs
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        Vec::new()
+    }
+
+    /// Name of operation without module path or type parameters
+    fn op_name(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
+
+    /// Identifier of node in a graph, clones of a shared value have the same id
+    fn node_id(&self) -> usize {
+        self as *const Self as *const u8 as usize
+    }
+
+    /// Reset gradients of every value reachable from node to zero.
+    /// Values sum the gradients of every backward pass, call this between passes
+    fn zero_grad(&mut self) {
+        for mut input in self.inputs() {
+            input.zero_grad();
+        }
+    }
 }
 
 
-/// Value node for computation graph
+/// Value node for computation graph.
+/// The first backward pass after `new` replaces the gradient and every later pass adds to it,
+/// so a value used by several nodes gets the sum of their gradients.
+/// Call `zero_grad` between backward passes to start from a zero gradient
 #[derive(Debug, Clone, Default)]
 pub struct Value<T> {
     pub value: Rc<RefCell<T>>,
     pub gradient: Rc<RefCell<T>>,
+    accumulate: Rc<Cell<bool>>
 }
 
 impl<T: Clone> Value<T> {
@@ -27,7 +56,8 @@ impl<T: Clone> Value<T> {
         
         Value {
             value: Rc::new(RefCell::new(value.clone())),
-            gradient: Rc::new(RefCell::new(value.clone()))
+            gradient: Rc::new(RefCell::new(value.clone())),
+            accumulate: Rc::new(Cell::new(false))
         }
     }
 
@@ -46,7 +76,7 @@ impl<T: Clone> Value<T> {
         self.value.replace(val.clone());
     }
 
-    /// Set gradient of value in computation graph
+    /// Set gradient of value in computation graph, overwriting the stored gradient
     pub fn set_grad(&mut self, value: &T) {
         self.gradient.replace(value.clone());
     }
@@ -54,6 +84,17 @@ impl<T: Clone> Value<T> {
 }
 
 
+impl Value<NDArray<f64>> {
+
+    /// Reset gradient of value to zero
+    pub fn zero_grad(&mut self) {
+        let zeros = zeros_like(&self.val());
+        Value::set_grad(self, &zeros);
+    }
+
+}
+
+
 impl Node for Value<NDArray<f64>> {
 
     /// Forward operation for a value
@@ -61,12 +102,17 @@ impl Node for Value<NDArray<f64>> {
 
     /// Set gradient from upstream for value
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
-        self.gradient.replace(upstream_gradient);
+        Value::set_grad(self, &upstream_gradient);
     } 
 
-    /// Set gradient from upstream in backward pass
+    /// Add gradient from upstream in backward pass
     fn backward(&mut self, upstream_gradient: NDArray<f64>) {
-        self.gradient.replace(upstream_gradient);        
+        let total = match self.accumulate.get() {
+            true => accumulate(&self.grad(), upstream_gradient),
+            false => upstream_gradient
+        };
+        Value::set_grad(self, &total);
+        self.accumulate.set(true);
     } 
 
     /// Retrieve value from node in computation graph
@@ -79,4 +125,39 @@ impl Node for Value<NDArray<f64>> {
         self.gradient.borrow().clone()
     }
 
+    /// Clones of a value share the same storage and therefore the same id
+    fn node_id(&self) -> usize {
+        Rc::as_ptr(&self.value) as usize
+    }
+
+    /// Reset gradient of value to zero
+    fn zero_grad(&mut self) {
+        Value::zero_grad(self);
+    }
+
+}
+
+
+/// Array of zeros with the shape of `value`
+pub(crate) fn zeros_like(value: &NDArray<f64>) -> NDArray<f64> {
+    NDArray::array(value.shape().values(), vec![0.0; value.size()]).unwrap()
+}
+
+
+/// Add an upstream gradient to a stored gradient, panics when the shapes differ
+/// since that points to a broadcasting or reduction bug or a missing `zero_grad`
+pub(crate) fn accumulate(gradient: &NDArray<f64>, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+    if gradient.shape().values() != upstream_gradient.shape().values() {
+        panic!(
+            "Accumulate: Gradient shape {:?} does not match upstream gradient shape {:?}",
+            gradient.shape().values(),
+            upstream_gradient.shape().values()
+        );
+    }
+    let values = gradient.values()
+        .iter()
+        .zip(upstream_gradient.values().iter())
+        .map(|(a, b)| a + b)
+        .collect();
+    NDArray::array(upstream_gradient.shape().values(), values).unwrap()
 }
@@ -0,0 +1,578 @@
+use dendritic_ndarray::ndarray::NDArray;
+use std::cell::{RefCell, RefMut};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use crate::node::{Node, Value};
+
+
+/// Number of channels and elements per channel and sample for a (N, C, ...) shape
+fn channel_layout(shape: &[usize]) -> (usize, usize) {
+    let channels = shape[1];
+    let inner: usize = shape[2..].iter().product();
+    (channels, inner)
+}
+
+
+/// Inverted dropout, zeroes elements with probability `probability` during training
+/// and scales the rest by 1 / (1 - probability). Identity in evaluation mode.
+/// A new mask is drawn from the seeded generator on every forward pass.
+pub struct Dropout<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub probability: f64,
+    training: bool,
+    mask: Vec<f64>,
+    rng: StdRng
+}
+
+
+impl<V> Dropout<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of dropout operation, starts in training mode
+    pub fn new(input: V, probability: f64, seed: u64) -> Dropout<V> {
+
+        if !(0.0..1.0).contains(&probability) {
+            panic!("Dropout: Probability {} must be in [0, 1)", probability);
+        }
+
+        let op_value = Value::new(&input.value());
+        let mut dropout = Dropout {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            probability,
+            training: true,
+            mask: Vec::new(),
+            rng: StdRng::seed_from_u64(seed)
+        };
+
+        dropout.apply();
+        dropout
+    }
+
+    /// Get input value of dropout operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+    /// Switch dropout to training mode
+    pub fn train(&mut self) {
+        self.training = true;
+    }
+
+    /// Switch dropout to evaluation mode
+    pub fn eval(&mut self) {
+        self.training = false;
+    }
+
+    /// Check if dropout is in training mode
+    pub fn is_training(&self) -> bool {
+        self.training
+    }
+
+    /// Scaling mask used in the last forward pass
+    pub fn mask(&self) -> NDArray<f64> {
+        let shape = self.input().value().shape().values();
+        NDArray::array(shape, self.mask.clone()).unwrap()
+    }
+
+    /// Draw mask and compute output from current input
+    fn apply(&mut self) {
+
+        let input = self.input().value();
+        let keep = 1.0 - self.probability;
+
+        self.mask = if self.training && self.probability > 0.0 {
+            (0..input.size())
+                .map(|_| if self.rng.gen::<f64>() < self.probability { 0.0 } else { 1.0 / keep })
+                .collect()
+        } else {
+            vec![1.0; input.size()]
+        };
+
+        let values: Vec<f64> = input.values().iter()
+            .zip(self.mask.iter())
+            .map(|(x, m)| x * m)
+            .collect();
+
+        let op_result = NDArray::array(input.shape().values(), values).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+}
+
+
+impl<V> Node for Dropout<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of dropout operation
+    fn forward(&mut self) {
+        self.input().forward();
+        self.apply();
+    }
+
+    /// Perform backward pass of dropout operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let values: Vec<f64> = upstream_gradient.values().iter()
+            .zip(self.mask.iter())
+            .map(|(g, m)| g * m)
+            .collect();
+
+        let input_grad = NDArray::array(upstream_gradient.shape().values(), values).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of dropout operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of dropout operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of dropout operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of dropout operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+/// Batch normalization over every axis except the channel axis (axis 1).
+/// Works with (N, C) inputs and (N, C, H, W) inputs, gamma and beta have shape (1, C).
+/// Training mode normalizes with batch statistics and updates the running statistics,
+/// evaluation mode normalizes with the running statistics.
+pub struct BatchNorm<X, G, B>
+where
+    X: Node,
+    G: Node,
+    B: Node,
+{
+    pub input: RefCell<X>,
+    pub gamma: RefCell<G>,
+    pub beta: RefCell<B>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub momentum: f64,
+    pub epsilon: f64,
+    training: bool,
+    running_mean: Vec<f64>,
+    running_var: Vec<f64>,
+    x_hat: Vec<f64>,
+    inv_std: Vec<f64>,
+    batch_stats: bool
+}
+
+
+impl<X, G, B> BatchNorm<X, G, B>
+where
+    X: Node,
+    G: Node,
+    B: Node,
+{
+
+    /// Create new instance of batch normalization with momentum 0.1 and epsilon 1e-5.
+    /// Starts in training mode with running mean of zeros and running variance of ones
+    pub fn new(input: X, gamma: G, beta: B) -> BatchNorm<X, G, B> {
+
+        let channels = gamma.value().size();
+        let op_value = Value::new(&input.value());
+        let mut batch_norm = BatchNorm {
+            input: RefCell::new(input),
+            gamma: RefCell::new(gamma),
+            beta: RefCell::new(beta),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            momentum: 0.1,
+            epsilon: 1e-5,
+            training: true,
+            running_mean: vec![0.0; channels],
+            running_var: vec![1.0; channels],
+            x_hat: Vec::new(),
+            inv_std: Vec::new(),
+            batch_stats: true
+        };
+
+        batch_norm.normalize(false);
+        batch_norm
+    }
+
+    /// Get input value of batch normalization
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+    /// Get scale parameter of batch normalization
+    pub fn gamma(&self) -> RefMut<'_, dyn Node> {
+        self.gamma.borrow_mut()
+    }
+
+    /// Get shift parameter of batch normalization
+    pub fn beta(&self) -> RefMut<'_, dyn Node> {
+        self.beta.borrow_mut()
+    }
+
+    /// Switch batch normalization to training mode
+    pub fn train(&mut self) {
+        self.training = true;
+    }
+
+    /// Switch batch normalization to evaluation mode
+    pub fn eval(&mut self) {
+        self.training = false;
+    }
+
+    /// Check if batch normalization is in training mode
+    pub fn is_training(&self) -> bool {
+        self.training
+    }
+
+    /// Running mean of each channel
+    pub fn running_mean(&self) -> NDArray<f64> {
+        NDArray::array(vec![1, self.running_mean.len()], self.running_mean.clone()).unwrap()
+    }
+
+    /// Running (unbiased) variance of each channel
+    pub fn running_var(&self) -> NDArray<f64> {
+        NDArray::array(vec![1, self.running_var.len()], self.running_var.clone()).unwrap()
+    }
+
+    /// Compute output from current input, optionally updating running statistics
+    fn normalize(&mut self, update_stats: bool) {
+
+        let input = self.input().value();
+        let gamma = self.gamma().value();
+        let beta = self.beta().value();
+        let shape = input.shape().values();
+
+        if input.rank() < 2 {
+            panic!("BatchNorm: Input must be at least rank 2, got {:?}", shape);
+        }
+
+        let (channels, inner) = channel_layout(&shape);
+        if gamma.size() != channels || beta.size() != channels {
+            panic!(
+                "BatchNorm: Input has {} channels, gamma and beta have {} and {}",
+                channels, gamma.size(), beta.size()
+            );
+        }
+
+        let count = (input.size() / channels) as f64;
+        let channel = |idx: usize| (idx / inner) % channels;
+
+        let (mean, var) = if self.training {
+            let mut mean = vec![0.0; channels];
+            let mut var = vec![0.0; channels];
+            for (idx, x) in input.values().iter().enumerate() {
+                mean[channel(idx)] += x / count;
+            }
+            for (idx, x) in input.values().iter().enumerate() {
+                let c = channel(idx);
+                var[c] += (x - mean[c]).powi(2) / count;
+            }
+
+            if update_stats {
+                let correction = if count > 1.0 { count / (count - 1.0) } else { 1.0 };
+                for c in 0..channels {
+                    self.running_mean[c] = (1.0 - self.momentum) * self.running_mean[c]
+                        + self.momentum * mean[c];
+                    self.running_var[c] = (1.0 - self.momentum) * self.running_var[c]
+                        + self.momentum * var[c] * correction;
+                }
+            }
+            (mean, var)
+        } else {
+            (self.running_mean.clone(), self.running_var.clone())
+        };
+
+        self.inv_std = var.iter().map(|v| 1.0 / (v + self.epsilon).sqrt()).collect();
+        self.x_hat = input.values().iter()
+            .enumerate()
+            .map(|(idx, x)| (x - mean[channel(idx)]) * self.inv_std[channel(idx)])
+            .collect();
+        self.batch_stats = self.training;
+
+        let values: Vec<f64> = self.x_hat.iter()
+            .enumerate()
+            .map(|(idx, x)| gamma.values()[channel(idx)] * x + beta.values()[channel(idx)])
+            .collect();
+
+        let op_result = NDArray::array(shape, values).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+}
+
+
+impl<X, G, B> Node for BatchNorm<X, G, B>
+where
+    X: Node,
+    G: Node,
+    B: Node,
+{
+
+    /// Perform forward pass of batch normalization
+    fn forward(&mut self) {
+
+        self.input().forward();
+        self.gamma().forward();
+        self.beta().forward();
+        self.normalize(true);
+    }
+
+    /// Perform backward pass of batch normalization
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let gamma = self.gamma().value();
+        let shape = upstream_gradient.shape().values();
+        let (channels, inner) = channel_layout(&shape);
+        let count = (upstream_gradient.size() / channels) as f64;
+        let channel = |idx: usize| (idx / inner) % channels;
+
+        let mut grad_sum = vec![0.0; channels];
+        let mut grad_x_hat_sum = vec![0.0; channels];
+        for (idx, g) in upstream_gradient.values().iter().enumerate() {
+            let c = channel(idx);
+            grad_sum[c] += g;
+            grad_x_hat_sum[c] += g * self.x_hat[idx];
+        }
+
+        let input_grad: Vec<f64> = upstream_gradient.values().iter()
+            .enumerate()
+            .map(|(idx, g)| {
+                let c = channel(idx);
+                let scale = gamma.values()[c] * self.inv_std[c];
+                if self.batch_stats {
+                    scale / count * (count * g - grad_sum[c] - self.x_hat[idx] * grad_x_hat_sum[c])
+                } else {
+                    scale * g
+                }
+            })
+            .collect();
+
+        let gamma_shape = gamma.shape().values();
+        let beta_shape = self.beta().value().shape().values();
+        self.gamma().backward(NDArray::array(gamma_shape, grad_x_hat_sum).unwrap());
+        self.beta().backward(NDArray::array(beta_shape, grad_sum).unwrap());
+        self.input().backward(NDArray::array(shape, input_grad).unwrap());
+    }
+
+    /// Get output value of batch normalization
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of batch normalization
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of batch normalization
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of batch normalization in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input(), self.gamma(), self.beta()]
+    }
+}
+
+
+/// Layer normalization over the last axis of each sample.
+/// Gamma and beta have shape (1, D) where D is the size of the last axis.
+/// Behaves the same in training and evaluation mode
+pub struct LayerNorm<X, G, B>
+where
+    X: Node,
+    G: Node,
+    B: Node,
+{
+    pub input: RefCell<X>,
+    pub gamma: RefCell<G>,
+    pub beta: RefCell<B>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub epsilon: f64,
+    x_hat: Vec<f64>,
+    inv_std: Vec<f64>
+}
+
+
+impl<X, G, B> LayerNorm<X, G, B>
+where
+    X: Node,
+    G: Node,
+    B: Node,
+{
+
+    /// Create new instance of layer normalization with epsilon 1e-5
+    pub fn new(input: X, gamma: G, beta: B) -> LayerNorm<X, G, B> {
+
+        let op_value = Value::new(&input.value());
+        let mut layer_norm = LayerNorm {
+            input: RefCell::new(input),
+            gamma: RefCell::new(gamma),
+            beta: RefCell::new(beta),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            epsilon: 1e-5,
+            x_hat: Vec::new(),
+            inv_std: Vec::new()
+        };
+
+        layer_norm.normalize();
+        layer_norm
+    }
+
+    /// Get input value of layer normalization
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+    /// Get scale parameter of layer normalization
+    pub fn gamma(&self) -> RefMut<'_, dyn Node> {
+        self.gamma.borrow_mut()
+    }
+
+    /// Get shift parameter of layer normalization
+    pub fn beta(&self) -> RefMut<'_, dyn Node> {
+        self.beta.borrow_mut()
+    }
+
+    /// Compute output from current input
+    fn normalize(&mut self) {
+
+        let input = self.input().value();
+        let gamma = self.gamma().value();
+        let beta = self.beta().value();
+        let shape = input.shape().values();
+        let dim = shape[shape.len() - 1];
+
+        if gamma.size() != dim || beta.size() != dim {
+            panic!(
+                "LayerNorm: Last axis has size {}, gamma and beta have {} and {}",
+                dim, gamma.size(), beta.size()
+            );
+        }
+
+        self.x_hat.clear();
+        self.inv_std.clear();
+        let mut values: Vec<f64> = Vec::new();
+        for row in input.values().chunks(dim) {
+            let mean = row.iter().sum::<f64>() / dim as f64;
+            let var = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / dim as f64;
+            let inv_std = 1.0 / (var + self.epsilon).sqrt();
+            self.inv_std.push(inv_std);
+
+            for (j, x) in row.iter().enumerate() {
+                let x_hat = (x - mean) * inv_std;
+                self.x_hat.push(x_hat);
+                values.push(gamma.values()[j] * x_hat + beta.values()[j]);
+            }
+        }
+
+        let op_result = NDArray::array(shape, values).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+}
+
+
+impl<X, G, B> Node for LayerNorm<X, G, B>
+where
+    X: Node,
+    G: Node,
+    B: Node,
+{
+
+    /// Perform forward pass of layer normalization
+    fn forward(&mut self) {
+
+        self.input().forward();
+        self.gamma().forward();
+        self.beta().forward();
+        self.normalize();
+    }
+
+    /// Perform backward pass of layer normalization
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let gamma = self.gamma().value();
+        let shape = upstream_gradient.shape().values();
+        let dim = shape[shape.len() - 1];
+        let n = dim as f64;
+
+        let mut gamma_grad = vec![0.0; dim];
+        let mut beta_grad = vec![0.0; dim];
+        let mut input_grad: Vec<f64> = Vec::new();
+
+        for (row_idx, row) in upstream_gradient.values().chunks(dim).enumerate() {
+            let offset = row_idx * dim;
+            let x_hat = &self.x_hat[offset..offset + dim];
+
+            let dx_hat: Vec<f64> = row.iter()
+                .enumerate()
+                .map(|(j, g)| g * gamma.values()[j])
+                .collect();
+            let dx_hat_sum: f64 = dx_hat.iter().sum();
+            let dx_hat_x_hat_sum: f64 = dx_hat.iter().zip(x_hat.iter()).map(|(d, x)| d * x).sum();
+
+            for j in 0..dim {
+                gamma_grad[j] += row[j] * x_hat[j];
+                beta_grad[j] += row[j];
+                let dx = self.inv_std[row_idx] / n
+                    * (n * dx_hat[j] - dx_hat_sum - x_hat[j] * dx_hat_x_hat_sum);
+                input_grad.push(dx);
+            }
+        }
+
+        let gamma_shape = gamma.shape().values();
+        let beta_shape = self.beta().value().shape().values();
+        self.gamma().backward(NDArray::array(gamma_shape, gamma_grad).unwrap());
+        self.beta().backward(NDArray::array(beta_shape, beta_grad).unwrap());
+        self.input().backward(NDArray::array(shape, input_grad).unwrap());
+    }
+
+    /// Get output value of layer normalization
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of layer normalization
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of layer normalization
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of layer normalization in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input(), self.gamma(), self.beta()]
+    }
+}
@@ -94,6 +94,11 @@ where
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
         self.gradient = Value::new(&upstream_gradient).into();
     } 
+
+    /// Get inputs of dot product operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
 }
 
 
@@ -183,6 +188,11 @@ where
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
         self.gradient = Value::new(&upstream_gradient).into();
     } 
+
+    /// Get inputs of elementwise add operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
 }
 
 
@@ -194,8 +204,7 @@ where
     pub rhs: RefCell<RHS>,
     pub lhs: RefCell<LHS>,
     pub output: RefCell<Value<NDArray<f64>>>,
-    pub gradient: RefCell<Value<NDArray<f64>>>,
-    pub learning_rate: f64
+    pub gradient: RefCell<Value<NDArray<f64>>>
 }
 
 
@@ -207,7 +216,7 @@ where
 {
 
     /// Create new instance of regularization operation
-    pub fn new(rhs: RHS, lhs: LHS, learning_rate: f64) -> Self {
+    pub fn new(rhs: RHS, lhs: LHS) -> Self {
 
         let weights = rhs.value();
         let w_square = weights.square().unwrap();
@@ -219,8 +228,7 @@ where
             rhs: RefCell::new(rhs),
             lhs: RefCell::new(lhs),
             output: RefCell::new(op_value.clone()),
-            gradient: RefCell::new(op_value),
-            learning_rate: learning_rate
+            gradient: RefCell::new(op_value)
         }
     }
 
@@ -256,12 +264,22 @@ where
         self.output = Value::new(&op_result).into(); 
     } 
 
-    /// Perform backward pass of regularization operation
+    /// Perform backward pass of regularization operation.
+    /// Weights receive upstream * 2 * lambda * w and lambda receives upstream * sum(w^2)
     fn backward(&mut self, upstream_gradient: NDArray<f64>) {
-        let lr = self.learning_rate / upstream_gradient.size() as f64;
-        let alpha = self.lhs().value().scalar_mult(2.0 * lr).unwrap();
-        let weight_update = self.rhs().value().scale_mult(alpha).unwrap();
-        self.gradient = Value::new(&weight_update).into();
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let upstream = upstream_gradient.values()[0];
+        let weights = self.rhs().value();
+        let lambda = self.lhs().value().values()[0];
+
+        let w_grad = weights.scalar_mult(2.0 * lambda * upstream).unwrap();
+        let w_square = weights.square().unwrap().sum().unwrap();
+        let lambda_grad = w_square.scalar_mult(upstream).unwrap();
+
+        self.rhs().backward(w_grad);
+        self.lhs().backward(lambda_grad);
     }
 
     /// Get output value of regularization operation
@@ -278,5 +296,1210 @@ where
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
         self.gradient = Value::new(&upstream_gradient).into();
     } 
+
+    /// Get inputs of regularization operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+
+/// Compute the shape two values broadcast to, dimensions must match or be 1
+pub fn broadcast_shape(
+    rhs: &NDArray<f64>, 
+    lhs: &NDArray<f64>) -> Result<Vec<usize>, String> {
+
+    if rhs.rank() != lhs.rank() {
+        return Err("Broadcast: Rank Mismatch".to_string());
+    }
+
+    let mut shape: Vec<usize> = Vec::new();
+    let rhs_shape = rhs.shape().values();
+    let lhs_shape = lhs.shape().values();
+    for (r, l) in rhs_shape.iter().zip(lhs_shape.iter()) {
+        if r == l || *l == 1 {
+            shape.push(*r);
+        } else if *r == 1 {
+            shape.push(*l);
+        } else {
+            return Err("Broadcast: Dimensions must match or be 1".to_string());
+        }
+    }
+
+    Ok(shape)
+}
+
+
+/// Broadcast value to a larger shape by repeating dimensions of size 1
+pub fn broadcast_to(
+    value: &NDArray<f64>, 
+    shape: Vec<usize>) -> Result<NDArray<f64>, String> {
+
+    if value.shape().values() == shape {
+        return Ok(value.clone());
+    }
+
+    if value.rank() != shape.len() {
+        return Err("Broadcast To: Rank Mismatch".to_string());
+    }
+
+    for (axis, dim) in shape.iter().enumerate() {
+        let value_dim = value.shape().dim(axis);
+        if value_dim != *dim && value_dim != 1 {
+            return Err("Broadcast To: Dimensions must match or be 1".to_string());
+        }
+    }
+
+    let mut result: NDArray<f64> = NDArray::new(shape).unwrap();
+    for idx in 0..result.size() {
+        let indices: Vec<usize> = result.indices(idx).unwrap()
+            .iter()
+            .enumerate()
+            .map(|(axis, i)| if value.shape().dim(axis) == 1 { 0 } else { *i })
+            .collect();
+        result.set_idx(idx, *value.get(indices)).unwrap();
+    }
+
+    Ok(result)
+}
+
+
+/// Sum gradient over broadcasted dimensions so it matches the operand shape
+pub fn reduce_to(grad: &NDArray<f64>, shape: Vec<usize>) -> NDArray<f64> {
+
+    if grad.shape().values() == shape {
+        return grad.clone();
+    }
+
+    let mut result: NDArray<f64> = NDArray::new(shape.clone()).unwrap();
+    for idx in 0..grad.size() {
+        let indices: Vec<usize> = grad.indices(idx).unwrap()
+            .iter()
+            .enumerate()
+            .map(|(axis, i)| if shape[axis] == 1 { 0 } else { *i })
+            .collect();
+        let index = result.index(indices).unwrap();
+        let sum = result.idx(index) + grad.idx(idx);
+        result.set_idx(index, sum).unwrap();
+    }
+
+    result
+}
+
+
+/// Apply binary function elementwise on two broadcastable values
+pub fn elementwise(
+    rhs: &NDArray<f64>, 
+    lhs: &NDArray<f64>,
+    op: fn(f64, f64) -> f64) -> Result<NDArray<f64>, String> {
+
+    let shape = broadcast_shape(rhs, lhs)?;
+    let rhs_b = broadcast_to(rhs, shape.clone())?;
+    let lhs_b = broadcast_to(lhs, shape.clone())?;
+
+    let values: Vec<f64> = rhs_b.values().iter()
+        .zip(lhs_b.values().iter())
+        .map(|(r, l)| op(*r, *l))
+        .collect();
+
+    NDArray::array(shape, values)
+}
+
+
+pub struct MatMul<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> MatMul<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of matrix multiplication operation
+    pub fn new(rhs: RHS, lhs: LHS) -> MatMul<RHS, LHS> {
+
+        let op_result = rhs.value().dot(lhs.value()).unwrap();
+        let op_value = Value::new(&op_result);
+
+        MatMul {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get right hand side value of matrix multiplication
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get left hand side value of matrix multiplication
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for MatMul<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of matrix multiplication
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+        let result = rhs.dot(lhs).unwrap();
+        self.output = Value::new(&result).into();
+    }
+
+    /// Perform backward pass of matrix multiplication.
+    /// Unlike `Dot`, each operand receives the gradient with respect to itself
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let rhs_t = self.rhs().value().transpose().unwrap();
+        let lhs_t = self.lhs().value().transpose().unwrap();
+
+        let rhs_grad = upstream_gradient.dot(lhs_t).unwrap();
+        let lhs_grad = rhs_t.dot(upstream_gradient).unwrap();
+
+        self.rhs().backward(rhs_grad);
+        self.lhs().backward(lhs_grad);
+    }
+
+    /// Get output value of matrix multiplication
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of matrix multiplication
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of matrix multiplication
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of matrix multiplication in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+pub struct Add<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> Add<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of elementwise add operation
+    pub fn new(rhs: RHS, lhs: LHS) -> Add<RHS, LHS> {
+
+        let op_result = elementwise(&rhs.value(), &lhs.value(), |a, b| a + b).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Add {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get right hand side value of elementwise add operation
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get left hand side value of elementwise add operation
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for Add<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of elementwise add operation
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+        let op_result = elementwise(&rhs, &lhs, |a, b| a + b).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of elementwise add operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let rhs_shape = self.rhs().value().shape().values();
+        let lhs_shape = self.lhs().value().shape().values();
+
+        self.rhs().backward(reduce_to(&upstream_gradient, rhs_shape));
+        self.lhs().backward(reduce_to(&upstream_gradient, lhs_shape));
+    }
+
+    /// Get output value of elementwise add operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of elementwise add operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of elementwise add operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of elementwise add operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+pub struct Sub<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> Sub<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of elementwise subtract operation
+    pub fn new(rhs: RHS, lhs: LHS) -> Sub<RHS, LHS> {
+
+        let op_result = elementwise(&rhs.value(), &lhs.value(), |a, b| a - b).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Sub {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get right hand side value of elementwise subtract operation
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get left hand side value of elementwise subtract operation
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
 }
 
+
+impl<RHS, LHS> Node for Sub<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of elementwise subtract operation
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+        let op_result = elementwise(&rhs, &lhs, |a, b| a - b).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of elementwise subtract operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let rhs_shape = self.rhs().value().shape().values();
+        let lhs_shape = self.lhs().value().shape().values();
+        let negated = upstream_gradient.scalar_mult(-1.0).unwrap();
+
+        self.rhs().backward(reduce_to(&upstream_gradient, rhs_shape));
+        self.lhs().backward(reduce_to(&negated, lhs_shape));
+    }
+
+    /// Get output value of elementwise subtract operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of elementwise subtract operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of elementwise subtract operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of elementwise subtract operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+pub struct Mul<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> Mul<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of elementwise multiply operation
+    pub fn new(rhs: RHS, lhs: LHS) -> Mul<RHS, LHS> {
+
+        let op_result = elementwise(&rhs.value(), &lhs.value(), |a, b| a * b).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Mul {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get right hand side value of elementwise multiply operation
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get left hand side value of elementwise multiply operation
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for Mul<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of elementwise multiply operation
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+        let op_result = elementwise(&rhs, &lhs, |a, b| a * b).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of elementwise multiply operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+
+        let rhs_grad = elementwise(&upstream_gradient, &lhs, |g, l| g * l).unwrap();
+        let lhs_grad = elementwise(&upstream_gradient, &rhs, |g, r| g * r).unwrap();
+
+        self.rhs().backward(reduce_to(&rhs_grad, rhs.shape().values()));
+        self.lhs().backward(reduce_to(&lhs_grad, lhs.shape().values()));
+    }
+
+    /// Get output value of elementwise multiply operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of elementwise multiply operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of elementwise multiply operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of elementwise multiply operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+pub struct Div<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+    pub rhs: RefCell<RHS>,
+    pub lhs: RefCell<LHS>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<RHS, LHS> Div<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Create new instance of elementwise divide operation
+    pub fn new(rhs: RHS, lhs: LHS) -> Div<RHS, LHS> {
+
+        let op_result = elementwise(&rhs.value(), &lhs.value(), |a, b| a / b).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Div {
+            rhs: RefCell::new(rhs),
+            lhs: RefCell::new(lhs),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get right hand side value of elementwise divide operation
+    pub fn rhs(&self) -> RefMut<'_, dyn Node> {
+        self.rhs.borrow_mut()
+    }
+
+    /// Get left hand side value of elementwise divide operation
+    pub fn lhs(&self) -> RefMut<'_, dyn Node> {
+        self.lhs.borrow_mut()
+    }
+
+}
+
+
+impl<RHS, LHS> Node for Div<RHS, LHS>
+where
+    RHS: Node,
+    LHS: Node,
+{
+
+    /// Perform forward pass of elementwise divide operation
+    fn forward(&mut self) {
+
+        self.rhs().forward();
+        self.lhs().forward();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+        let op_result = elementwise(&rhs, &lhs, |a, b| a / b).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of elementwise divide operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let rhs = self.rhs().value();
+        let lhs = self.lhs().value();
+
+        /* d(r/l)/dr = 1/l, d(r/l)/dl = -r/l^2 */
+        let rhs_grad = elementwise(&upstream_gradient, &lhs, |g, l| g / l).unwrap();
+        let ratio = elementwise(&rhs, &lhs, |r, l| -r / (l * l)).unwrap();
+        let lhs_grad = elementwise(&upstream_gradient, &ratio, |g, q| g * q).unwrap();
+
+        self.rhs().backward(reduce_to(&rhs_grad, rhs.shape().values()));
+        self.lhs().backward(reduce_to(&lhs_grad, lhs.shape().values()));
+    }
+
+    /// Get output value of elementwise divide operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of elementwise divide operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of elementwise divide operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of elementwise divide operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
+}
+
+
+pub struct Pow<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub exponent: f64
+}
+
+
+impl<V> Pow<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of elementwise power operation
+    pub fn new(input: V, exponent: f64) -> Pow<V> {
+
+        let powered: Vec<f64> = input.value().values().iter()
+            .map(|x| x.powf(exponent))
+            .collect();
+        let op_result = NDArray::array(input.value().shape().values(), powered).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Pow {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            exponent
+        }
+    }
+
+    /// Get input value of elementwise power operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Pow<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of elementwise power operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let input = self.input().value();
+        let powered: Vec<f64> = input.values().iter()
+            .map(|x| x.powf(self.exponent))
+            .collect();
+        let op_result = NDArray::array(input.shape().values(), powered).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of elementwise power operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let input = self.input().value();
+        let derivative: Vec<f64> = input.values().iter()
+            .map(|x| self.exponent * x.powf(self.exponent - 1.0))
+            .collect();
+        let local = NDArray::array(input.shape().values(), derivative).unwrap();
+        let input_grad = upstream_gradient.mult(local).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of elementwise power operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of elementwise power operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of elementwise power operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of elementwise power operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Exp<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Exp<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of exponential operation
+    pub fn new(input: V) -> Exp<V> {
+
+        let op_result = input.value().apply(f64::exp).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Exp {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of exponential operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Exp<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of exponential operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().apply(f64::exp).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of exponential operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let output = self.value();
+        let input_grad = upstream_gradient.mult(output).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of exponential operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of exponential operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of exponential operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of exponential operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Log<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Log<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of natural log operation
+    pub fn new(input: V) -> Log<V> {
+
+        let op_result = input.value().apply(f64::ln).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Log {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of natural log operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Log<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of natural log operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().apply(f64::ln).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of natural log operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let input = self.input().value();
+        let input_grad = elementwise(&upstream_gradient, &input, |g, x| g / x).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of natural log operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of natural log operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of natural log operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of natural log operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Neg<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Neg<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of negation operation
+    pub fn new(input: V) -> Neg<V> {
+
+        let op_result = input.value().scalar_mult(-1.0).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Neg {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of negation operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Neg<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of negation operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().scalar_mult(-1.0).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of negation operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let input_grad = upstream_gradient.scalar_mult(-1.0).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of negation operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of negation operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of negation operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of negation operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Sum<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Sum<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of sum reduction operation
+    pub fn new(input: V) -> Sum<V> {
+
+        let op_result = input.value().sum().unwrap();
+        let op_value = Value::new(&op_result);
+
+        Sum {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of sum reduction operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Sum<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of sum reduction operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let op_result = self.input().value().sum().unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of sum reduction operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let shape = self.input().value().shape().values();
+        let input_grad = NDArray::fill(shape, upstream_gradient.values()[0]).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of sum reduction operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of sum reduction operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of sum reduction operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of sum reduction operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Mean<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>
+}
+
+
+impl<V> Mean<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of mean reduction operation
+    pub fn new(input: V) -> Mean<V> {
+
+        let mean_value = input.value().avg();
+        let op_result = NDArray::array(vec![1, 1], vec![mean_value]).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Mean {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value)
+        }
+    }
+
+    /// Get input value of mean reduction operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Mean<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of mean reduction operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let mean_value = self.input().value().avg();
+        let op_result = NDArray::array(vec![1, 1], vec![mean_value]).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of mean reduction operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let input = self.input().value();
+        let scaled = upstream_gradient.values()[0] / input.size() as f64;
+        let input_grad = NDArray::fill(input.shape().values(), scaled).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of mean reduction operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of mean reduction operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of mean reduction operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of mean reduction operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
+
+
+pub struct Reshape<V>
+where
+    V: Node,
+{
+    pub input: RefCell<V>,
+    pub output: RefCell<Value<NDArray<f64>>>,
+    pub gradient: RefCell<Value<NDArray<f64>>>,
+    pub shape: Vec<usize>
+}
+
+
+impl<V> Reshape<V>
+where
+    V: Node,
+{
+
+    /// Create new instance of reshape operation, size of shape must match input size
+    pub fn new(input: V, shape: Vec<usize>) -> Reshape<V> {
+
+        let input_value = input.value();
+        if shape.iter().product::<usize>() != input_value.size() {
+            panic!(
+                "Reshape: Cannot reshape {:?} into {:?}",
+                input_value.shape().values(), shape
+            );
+        }
+
+        let op_result = NDArray::array(shape.clone(), input_value.values().to_vec()).unwrap();
+        let op_value = Value::new(&op_result);
+
+        Reshape {
+            input: RefCell::new(input),
+            output: RefCell::new(op_value.clone()),
+            gradient: RefCell::new(op_value),
+            shape
+        }
+    }
+
+    /// Get input value of reshape operation
+    pub fn input(&self) -> RefMut<'_, dyn Node> {
+        self.input.borrow_mut()
+    }
+
+}
+
+
+impl<V> Node for Reshape<V>
+where
+    V: Node,
+{
+
+    /// Perform forward pass of reshape operation
+    fn forward(&mut self) {
+
+        self.input().forward();
+
+        let values = self.input().value().values().to_vec();
+        let op_result = NDArray::array(self.shape.clone(), values).unwrap();
+        self.output = Value::new(&op_result).into();
+    }
+
+    /// Perform backward pass of reshape operation
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let shape = self.input().value().shape().values();
+        let input_grad = NDArray::array(shape, upstream_gradient.values().to_vec()).unwrap();
+        self.input().backward(input_grad);
+    }
+
+    /// Get output value of reshape operation
+    fn value(&self) -> NDArray<f64> {
+        self.output.borrow().val().clone()
+    }
+
+    /// Get gradient of reshape operation
+    fn grad(&self) -> NDArray<f64> {
+        self.gradient.borrow().val().clone()
+    }
+
+    /// Set gradient of reshape operation
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        self.gradient = Value::new(&upstream_gradient).into();
+    }
+
+    /// Get inputs of reshape operation in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.input()]
+    }
+}
@@ -0,0 +1,437 @@
+use dendritic_ndarray::ndarray::NDArray;
+use crate::node::Value;
+
+
+/// Methods shared by optimizers that update parameters from their gradients.
+/// Parameters must be passed in the same order on every step,
+/// per parameter state is tracked by position.
+pub trait Optimizer {
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]);
+    fn learning_rate(&self) -> f64;
+    fn set_learning_rate(&mut self, learning_rate: f64);
+}
+
+
+/// Get state buffer for parameter, created with zeros on first use or shape change
+fn state_buffer<'a>(
+    state: &'a mut Vec<NDArray<f64>>,
+    index: usize,
+    parameter: &NDArray<f64>) -> &'a mut NDArray<f64> {
+
+    while state.len() <= index {
+        state.push(NDArray::new(parameter.shape().values()).unwrap());
+    }
+
+    if state[index].shape().values() != parameter.shape().values() {
+        state[index] = NDArray::new(parameter.shape().values()).unwrap();
+    }
+
+    &mut state[index]
+}
+
+
+/// Get gradient of parameter, panics if shape does not match the parameter
+fn checked_grad(parameter: &Value<NDArray<f64>>) -> NDArray<f64> {
+    let grad = parameter.grad();
+    if grad.shape().values() != parameter.val().shape().values() {
+        panic!(
+            "Optimizer: Gradient shape {:?} does not match parameter shape {:?}",
+            grad.shape().values(), parameter.val().shape().values()
+        );
+    }
+    grad
+}
+
+
+/// Apply update to each element of a parameter value
+fn update(
+    parameter: &mut Value<NDArray<f64>>,
+    values: Vec<f64>) {
+
+    let shape = parameter.val().shape().values();
+    let result = NDArray::array(shape, values).unwrap();
+    parameter.set_val(&result);
+}
+
+
+/// Stochastic gradient descent with optional momentum and nesterov acceleration
+#[derive(Debug, Clone)]
+pub struct SGD {
+    pub learning_rate: f64,
+    pub momentum: f64,
+    pub nesterov: bool,
+    velocity: Vec<NDArray<f64>>
+}
+
+
+impl SGD {
+
+    /// Create new instance of vanilla gradient descent
+    pub fn new(learning_rate: f64) -> SGD {
+        SGD {
+            learning_rate,
+            momentum: 0.0,
+            nesterov: false,
+            velocity: Vec::new()
+        }
+    }
+
+    /// Create new instance of gradient descent with momentum
+    pub fn with_momentum(
+        learning_rate: f64,
+        momentum: f64,
+        nesterov: bool) -> SGD {
+
+        SGD {
+            learning_rate,
+            momentum,
+            nesterov,
+            velocity: Vec::new()
+        }
+    }
+
+}
+
+
+impl Optimizer for SGD {
+
+    /// Update parameters with v = mu * v + g, p = p - lr * v
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+
+        for (index, parameter) in parameters.iter_mut().enumerate() {
+
+            let param = parameter.val();
+            let grad = checked_grad(parameter);
+            let velocity = state_buffer(&mut self.velocity, index, &param);
+
+            let mut values: Vec<f64> = Vec::new();
+            for idx in 0..param.size() {
+                let g = grad.idx(idx);
+                let v = self.momentum * velocity.idx(idx) + g;
+                velocity.set_idx(idx, v).unwrap();
+
+                let direction = if self.nesterov { g + self.momentum * v } else { v };
+                values.push(param.idx(idx) - self.learning_rate * direction);
+            }
+
+            update(parameter, values);
+        }
+    }
+
+    /// Get learning rate of optimizer
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Set learning rate of optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// Adam optimizer with bias corrected first and second moment estimates
+#[derive(Debug, Clone)]
+pub struct Adam {
+    pub learning_rate: f64,
+    pub beta_1: f64,
+    pub beta_2: f64,
+    pub epsilon: f64,
+    timestep: i32,
+    first_moment: Vec<NDArray<f64>>,
+    second_moment: Vec<NDArray<f64>>
+}
+
+
+impl Adam {
+
+    /// Create new instance of adam with default betas (0.9, 0.999)
+    pub fn new(learning_rate: f64) -> Adam {
+        Adam::with_betas(learning_rate, 0.9, 0.999, 1e-8)
+    }
+
+    /// Create new instance of adam with custom decay rates
+    pub fn with_betas(
+        learning_rate: f64,
+        beta_1: f64,
+        beta_2: f64,
+        epsilon: f64) -> Adam {
+
+        Adam {
+            learning_rate,
+            beta_1,
+            beta_2,
+            epsilon,
+            timestep: 0,
+            first_moment: Vec::new(),
+            second_moment: Vec::new()
+        }
+    }
+
+}
+
+
+/// Shared adam update, weight decay is applied directly to the parameters (AdamW)
+#[allow(clippy::too_many_arguments)]
+fn adam_step(
+    parameters: &mut [Value<NDArray<f64>>],
+    first_moment: &mut Vec<NDArray<f64>>,
+    second_moment: &mut Vec<NDArray<f64>>,
+    timestep: i32,
+    learning_rate: f64,
+    betas: (f64, f64),
+    epsilon: f64,
+    weight_decay: f64) {
+
+    let (beta_1, beta_2) = betas;
+    let correction_1 = 1.0 - beta_1.powi(timestep);
+    let correction_2 = 1.0 - beta_2.powi(timestep);
+
+    for (index, parameter) in parameters.iter_mut().enumerate() {
+
+        let param = parameter.val();
+        let grad = checked_grad(parameter);
+        let m = state_buffer(first_moment, index, &param);
+        let mut m_values: Vec<f64> = Vec::new();
+        for idx in 0..param.size() {
+            let m_t = beta_1 * m.idx(idx) + (1.0 - beta_1) * grad.idx(idx);
+            m.set_idx(idx, m_t).unwrap();
+            m_values.push(m_t);
+        }
+
+        let v = state_buffer(second_moment, index, &param);
+        let mut values: Vec<f64> = Vec::new();
+        for (idx, m_t) in m_values.iter().enumerate() {
+            let g = grad.idx(idx);
+            let v_t = beta_2 * v.idx(idx) + (1.0 - beta_2) * g * g;
+            v.set_idx(idx, v_t).unwrap();
+
+            let m_hat = m_t / correction_1;
+            let v_hat = v_t / correction_2;
+            let p = param.idx(idx);
+            let step = m_hat / (v_hat.sqrt() + epsilon) + weight_decay * p;
+            values.push(p - learning_rate * step);
+        }
+
+        update(parameter, values);
+    }
+}
+
+
+impl Optimizer for Adam {
+
+    /// Update parameters with bias corrected moment estimates
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+        self.timestep += 1;
+        adam_step(
+            parameters,
+            &mut self.first_moment,
+            &mut self.second_moment,
+            self.timestep,
+            self.learning_rate,
+            (self.beta_1, self.beta_2),
+            self.epsilon,
+            0.0
+        );
+    }
+
+    /// Get learning rate of optimizer
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Set learning rate of optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// Adam optimizer with weight decay decoupled from the gradient
+#[derive(Debug, Clone)]
+pub struct AdamW {
+    pub learning_rate: f64,
+    pub beta_1: f64,
+    pub beta_2: f64,
+    pub epsilon: f64,
+    pub weight_decay: f64,
+    timestep: i32,
+    first_moment: Vec<NDArray<f64>>,
+    second_moment: Vec<NDArray<f64>>
+}
+
+
+impl AdamW {
+
+    /// Create new instance of adamw with default betas (0.9, 0.999)
+    pub fn new(learning_rate: f64, weight_decay: f64) -> AdamW {
+        AdamW {
+            learning_rate,
+            beta_1: 0.9,
+            beta_2: 0.999,
+            epsilon: 1e-8,
+            weight_decay,
+            timestep: 0,
+            first_moment: Vec::new(),
+            second_moment: Vec::new()
+        }
+    }
+
+}
+
+
+impl Optimizer for AdamW {
+
+    /// Update parameters with adam step and decoupled weight decay
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+        self.timestep += 1;
+        adam_step(
+            parameters,
+            &mut self.first_moment,
+            &mut self.second_moment,
+            self.timestep,
+            self.learning_rate,
+            (self.beta_1, self.beta_2),
+            self.epsilon,
+            self.weight_decay
+        );
+    }
+
+    /// Get learning rate of optimizer
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Set learning rate of optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// RMSProp optimizer, scales gradients by running average of their magnitude
+#[derive(Debug, Clone)]
+pub struct RMSProp {
+    pub learning_rate: f64,
+    pub alpha: f64,
+    pub epsilon: f64,
+    square_avg: Vec<NDArray<f64>>
+}
+
+
+impl RMSProp {
+
+    /// Create new instance of rmsprop with smoothing constant of 0.99
+    pub fn new(learning_rate: f64) -> RMSProp {
+        RMSProp::with_alpha(learning_rate, 0.99)
+    }
+
+    /// Create new instance of rmsprop with custom smoothing constant
+    pub fn with_alpha(learning_rate: f64, alpha: f64) -> RMSProp {
+        RMSProp {
+            learning_rate,
+            alpha,
+            epsilon: 1e-8,
+            square_avg: Vec::new()
+        }
+    }
+
+}
+
+
+impl Optimizer for RMSProp {
+
+    /// Update parameters with s = a * s + (1 - a) * g^2, p = p - lr * g / sqrt(s)
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+
+        for (index, parameter) in parameters.iter_mut().enumerate() {
+
+            let param = parameter.val();
+            let grad = checked_grad(parameter);
+            let square_avg = state_buffer(&mut self.square_avg, index, &param);
+
+            let mut values: Vec<f64> = Vec::new();
+            for idx in 0..param.size() {
+                let g = grad.idx(idx);
+                let s = self.alpha * square_avg.idx(idx) + (1.0 - self.alpha) * g * g;
+                square_avg.set_idx(idx, s).unwrap();
+                values.push(param.idx(idx) - self.learning_rate * g / (s.sqrt() + self.epsilon));
+            }
+
+            update(parameter, values);
+        }
+    }
+
+    /// Get learning rate of optimizer
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Set learning rate of optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
+
+
+/// Adagrad optimizer, scales gradients by their accumulated magnitude
+#[derive(Debug, Clone)]
+pub struct Adagrad {
+    pub learning_rate: f64,
+    pub epsilon: f64,
+    square_sum: Vec<NDArray<f64>>
+}
+
+
+impl Adagrad {
+
+    /// Create new instance of adagrad
+    pub fn new(learning_rate: f64) -> Adagrad {
+        Adagrad {
+            learning_rate,
+            epsilon: 1e-10,
+            square_sum: Vec::new()
+        }
+    }
+
+}
+
+
+impl Optimizer for Adagrad {
+
+    /// Update parameters with s = s + g^2, p = p - lr * g / sqrt(s)
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+
+        for (index, parameter) in parameters.iter_mut().enumerate() {
+
+            let param = parameter.val();
+            let grad = checked_grad(parameter);
+            let square_sum = state_buffer(&mut self.square_sum, index, &param);
+
+            let mut values: Vec<f64> = Vec::new();
+            for idx in 0..param.size() {
+                let g = grad.idx(idx);
+                let s = square_sum.idx(idx) + g * g;
+                square_sum.set_idx(idx, s).unwrap();
+                values.push(param.idx(idx) - self.learning_rate * g / (s.sqrt() + self.epsilon));
+            }
+
+            update(parameter, values);
+        }
+    }
+
+    /// Get learning rate of optimizer
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    /// Set learning rate of optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.learning_rate = learning_rate;
+    }
+
+}
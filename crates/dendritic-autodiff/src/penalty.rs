@@ -0,0 +1,299 @@
+use dendritic_ndarray::ndarray::NDArray;
+use crate::node::Value;
+use crate::optim::Optimizer;
+
+
+/// Methods shared by weight penalties added to a training objective.
+/// `gradient` is the plain derivative of `penalty` with respect to the weights,
+/// subgradients use zero where the penalty is not differentiable
+pub trait Penalty {
+    fn penalty(&self, weights: &NDArray<f64>) -> f64;
+    fn gradient(&self, weights: &NDArray<f64>) -> NDArray<f64>;
+
+    /// Add penalty gradient to the gradient stored on a parameter, returns the penalty
+    fn apply(&self, parameter: &mut Value<NDArray<f64>>) -> f64 {
+        let weights = parameter.val();
+        let grad = parameter.grad();
+        let penalty_grad = self.gradient(&weights);
+        let values: Vec<f64> = grad.values().iter()
+            .zip(penalty_grad.values().iter())
+            .map(|(g, p)| g + p)
+            .collect();
+        parameter.set_grad(&NDArray::array(weights.shape().values(), values).unwrap());
+        self.penalty(&weights)
+    }
+
+    /// Combine with another penalty, values and gradients are summed
+    fn and<P: Penalty>(self, other: P) -> Combined<Self, P>
+    where
+        Self: Sized,
+    {
+        Combined { first: self, second: other }
+    }
+}
+
+
+/// Apply function to each weight
+fn map(weights: &NDArray<f64>, op: impl Fn(f64) -> f64) -> NDArray<f64> {
+    let values: Vec<f64> = weights.values().iter().map(|w| op(*w)).collect();
+    NDArray::array(weights.shape().values(), values).unwrap()
+}
+
+
+/// Sign of a weight with sign(0) = 0
+fn sign(w: f64) -> f64 {
+    if w == 0.0 { 0.0 } else { w.signum() }
+}
+
+
+/// L1 penalty lambda * sum(|w|)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct L1 {
+    pub lambda: f64
+}
+
+
+impl L1 {
+
+    /// Create new instance of L1 penalty
+    pub fn new(lambda: f64) -> L1 {
+        L1 { lambda }
+    }
+
+}
+
+
+impl Penalty for L1 {
+
+    /// Penalty value of weights
+    fn penalty(&self, weights: &NDArray<f64>) -> f64 {
+        self.lambda * weights.values().iter().map(|w| w.abs()).sum::<f64>()
+    }
+
+    /// Subgradient lambda * sign(w)
+    fn gradient(&self, weights: &NDArray<f64>) -> NDArray<f64> {
+        map(weights, |w| self.lambda * sign(w))
+    }
+
+}
+
+
+/// L2 penalty lambda * sum(w^2)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct L2 {
+    pub lambda: f64
+}
+
+
+impl L2 {
+
+    /// Create new instance of L2 penalty
+    pub fn new(lambda: f64) -> L2 {
+        L2 { lambda }
+    }
+
+}
+
+
+impl Penalty for L2 {
+
+    /// Penalty value of weights
+    fn penalty(&self, weights: &NDArray<f64>) -> f64 {
+        self.lambda * weights.values().iter().map(|w| w * w).sum::<f64>()
+    }
+
+    /// Gradient 2 * lambda * w
+    fn gradient(&self, weights: &NDArray<f64>) -> NDArray<f64> {
+        map(weights, |w| 2.0 * self.lambda * w)
+    }
+
+}
+
+
+/// Elastic net penalty lambda * (l1_ratio * sum(|w|) + (1 - l1_ratio) * sum(w^2))
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElasticNet {
+    pub lambda: f64,
+    pub l1_ratio: f64
+}
+
+
+impl ElasticNet {
+
+    /// Create new instance of elastic net penalty, `l1_ratio` must be in [0, 1]
+    pub fn new(lambda: f64, l1_ratio: f64) -> Result<ElasticNet, String> {
+        if !(0.0..=1.0).contains(&l1_ratio) {
+            return Err("ElasticNet: L1 ratio must be between 0 and 1".to_string());
+        }
+        Ok(ElasticNet { lambda, l1_ratio })
+    }
+
+}
+
+
+impl Penalty for ElasticNet {
+
+    /// Penalty value of weights
+    fn penalty(&self, weights: &NDArray<f64>) -> f64 {
+        L1::new(self.lambda * self.l1_ratio).penalty(weights) +
+        L2::new(self.lambda * (1.0 - self.l1_ratio)).penalty(weights)
+    }
+
+    /// Gradient lambda * (l1_ratio * sign(w) + 2 * (1 - l1_ratio) * w)
+    fn gradient(&self, weights: &NDArray<f64>) -> NDArray<f64> {
+        map(weights, |w| {
+            self.lambda * (self.l1_ratio * sign(w) + 2.0 * (1.0 - self.l1_ratio) * w)
+        })
+    }
+
+}
+
+
+/// Group lasso penalty lambda * sum_g sqrt(|g|) * ||w_g||_2.
+/// Groups hold indices into the flattened weights, so whole groups are driven to zero together
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupLasso {
+    pub lambda: f64,
+    pub groups: Vec<Vec<usize>>
+}
+
+
+impl GroupLasso {
+
+    /// Create new instance of group lasso penalty
+    pub fn new(lambda: f64, groups: Vec<Vec<usize>>) -> GroupLasso {
+        GroupLasso { lambda, groups }
+    }
+
+    /// Group lasso with one group per row of a (rows, cols) weight matrix,
+    /// penalizing every output weight of a feature together
+    pub fn by_rows(lambda: f64, rows: usize, cols: usize) -> GroupLasso {
+        let groups = (0..rows)
+            .map(|row| (row * cols..(row + 1) * cols).collect())
+            .collect();
+        GroupLasso { lambda, groups }
+    }
+
+    /// L2 norm of the weights in a group
+    fn group_norm(weights: &NDArray<f64>, group: &[usize]) -> f64 {
+        group.iter().map(|idx| weights.values()[*idx].powi(2)).sum::<f64>().sqrt()
+    }
+
+}
+
+
+impl Penalty for GroupLasso {
+
+    /// Penalty value of weights
+    fn penalty(&self, weights: &NDArray<f64>) -> f64 {
+        self.groups.iter()
+            .map(|group| (group.len() as f64).sqrt() * GroupLasso::group_norm(weights, group))
+            .sum::<f64>() * self.lambda
+    }
+
+    /// Gradient lambda * sqrt(|g|) * w_g / ||w_g||, zero for groups that are all zero
+    fn gradient(&self, weights: &NDArray<f64>) -> NDArray<f64> {
+        let mut values = vec![0.0; weights.size()];
+        for group in self.groups.iter() {
+            let norm = GroupLasso::group_norm(weights, group);
+            if norm == 0.0 {
+                continue;
+            }
+            let scale = self.lambda * (group.len() as f64).sqrt() / norm;
+            for idx in group.iter() {
+                values[*idx] += scale * weights.values()[*idx];
+            }
+        }
+        NDArray::array(weights.shape().values(), values).unwrap()
+    }
+
+}
+
+
+/// Sum of two penalties, built with `Penalty::and`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combined<A, B> {
+    pub first: A,
+    pub second: B
+}
+
+
+impl<A: Penalty, B: Penalty> Penalty for Combined<A, B> {
+
+    /// Sum of both penalty values
+    fn penalty(&self, weights: &NDArray<f64>) -> f64 {
+        self.first.penalty(weights) + self.second.penalty(weights)
+    }
+
+    /// Sum of both penalty gradients
+    fn gradient(&self, weights: &NDArray<f64>) -> NDArray<f64> {
+        let first = self.first.gradient(weights);
+        let second = self.second.gradient(weights);
+        let values: Vec<f64> = first.values().iter()
+            .zip(second.values().iter())
+            .map(|(a, b)| a + b)
+            .collect();
+        NDArray::array(weights.shape().values(), values).unwrap()
+    }
+
+}
+
+
+/// Optimizer wrapper adding penalty gradients to attached parameters before every step.
+/// Penalties attach to parameters by position in the slice passed to `step`
+pub struct Penalized<O: Optimizer> {
+    pub optimizer: O,
+    penalties: Vec<(usize, Box<dyn Penalty>)>,
+    last_penalty: f64
+}
+
+
+impl<O: Optimizer> Penalized<O> {
+
+    /// Create new instance of optimizer without penalties
+    pub fn new(optimizer: O) -> Penalized<O> {
+        Penalized {
+            optimizer,
+            penalties: Vec::new(),
+            last_penalty: 0.0
+        }
+    }
+
+    /// Attach penalty to the parameter at `index`
+    pub fn attach<P: Penalty + 'static>(mut self, index: usize, penalty: P) -> Penalized<O> {
+        self.penalties.push((index, Box::new(penalty)));
+        self
+    }
+
+    /// Total penalty of the parameters on the last step
+    pub fn last_penalty(&self) -> f64 {
+        self.last_penalty
+    }
+
+}
+
+
+impl<O: Optimizer> Optimizer for Penalized<O> {
+
+    /// Add penalty gradients then update parameters with the inner optimizer
+    fn step(&mut self, parameters: &mut [Value<NDArray<f64>>]) {
+        self.last_penalty = 0.0;
+        for (index, penalty) in self.penalties.iter() {
+            if let Some(parameter) = parameters.get_mut(*index) {
+                self.last_penalty += penalty.apply(parameter);
+            }
+        }
+        self.optimizer.step(parameters);
+    }
+
+    /// Get learning rate of inner optimizer
+    fn learning_rate(&self) -> f64 {
+        self.optimizer.learning_rate()
+    }
+
+    /// Set learning rate of inner optimizer
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.optimizer.set_learning_rate(learning_rate);
+    }
+
+}
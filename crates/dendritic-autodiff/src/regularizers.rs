@@ -3,6 +3,7 @@ use dendritic_ndarray::ops::*;
 use std::cell::{RefCell, RefMut}; 
 use crate::node::{Node, Value}; 
 
+/// L2 penalty lambda * sum(w^2) of weights (rhs) scaled by lambda (lhs)
 pub struct L2Regularization<RHS, LHS> 
 where
     RHS: Node,
@@ -11,8 +12,7 @@ where
     pub rhs: RefCell<RHS>,
     pub lhs: RefCell<LHS>,
     pub output: RefCell<Value<NDArray<f64>>>,
-    pub gradient: RefCell<Value<NDArray<f64>>>,
-    pub learning_rate: f64
+    pub gradient: RefCell<Value<NDArray<f64>>>
 }
 
 
@@ -23,7 +23,7 @@ where
 {
 
     /// Create new instance of L2 regularization operation
-    pub fn new(rhs: RHS, lhs: LHS, learning_rate: f64) -> Self {
+    pub fn new(rhs: RHS, lhs: LHS) -> Self {
 
         let weights = rhs.value();
         let w_square = weights.square().unwrap();
@@ -35,8 +35,7 @@ where
             rhs: RefCell::new(rhs),
             lhs: RefCell::new(lhs),
             output: RefCell::new(op_value.clone()),
-            gradient: RefCell::new(op_value),
-            learning_rate: learning_rate
+            gradient: RefCell::new(op_value)
         }
     }
 
@@ -72,12 +71,22 @@ where
         self.output = Value::new(&op_result).into(); 
     } 
 
-    /// Perform backward pass on L2 regularization
+    /// Perform backward pass on L2 regularization.
+    /// Weights receive upstream * 2 * lambda * w and lambda receives upstream * sum(w^2)
     fn backward(&mut self, upstream_gradient: NDArray<f64>) {
-        let lr = self.learning_rate / upstream_gradient.size() as f64;
-        let alpha = self.lhs().value().scalar_mult(2.0 * lr).unwrap();
-        let weight_update = self.rhs().value().scale_mult(alpha).unwrap();
-        self.gradient = Value::new(&weight_update).into();
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let upstream = upstream_gradient.values()[0];
+        let weights = self.rhs().value();
+        let lambda = self.lhs().value().values()[0];
+
+        let w_grad = weights.scalar_mult(2.0 * lambda * upstream).unwrap();
+        let w_square = weights.square().unwrap().sum().unwrap();
+        let lambda_grad = w_square.scalar_mult(upstream).unwrap();
+
+        self.rhs().backward(w_grad);
+        self.lhs().backward(lambda_grad);
     }
 
     /// Get output value of L2 regularization
@@ -94,9 +103,15 @@ where
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
         self.gradient = Value::new(&upstream_gradient).into();
     } 
+
+    /// Get inputs of L2 regularization in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
 }
 
 
+/// L1 penalty lambda * sum(|w|) of weights (rhs) scaled by lambda (lhs)
 pub struct L1Regularization<RHS, LHS> 
 where
     RHS: Node,
@@ -105,8 +120,7 @@ where
     pub rhs: RefCell<RHS>,
     pub lhs: RefCell<LHS>,
     pub output: RefCell<Value<NDArray<f64>>>,
-    pub gradient: RefCell<Value<NDArray<f64>>>,
-    pub learning_rate: f64
+    pub gradient: RefCell<Value<NDArray<f64>>>
 }
 
 
@@ -117,7 +131,7 @@ where
 {
 
     /// Create new instance of L1 regularization operation
-    pub fn new(rhs: RHS, lhs: LHS, learning_rate: f64) -> Self {
+    pub fn new(rhs: RHS, lhs: LHS) -> Self {
 
         let weights = rhs.value();
         let w_abs = weights.abs().unwrap();
@@ -129,8 +143,7 @@ where
             rhs: RefCell::new(rhs),
             lhs: RefCell::new(lhs),
             output: RefCell::new(op_value.clone()),
-            gradient: RefCell::new(op_value),
-            learning_rate: learning_rate
+            gradient: RefCell::new(op_value)
         }
     }
 
@@ -166,14 +179,23 @@ where
         self.output = Value::new(&op_result).into(); 
     } 
 
-    /// Perform backward pass on L1 regularization
+    /// Perform backward pass on L1 regularization.
+    /// Weights receive the subgradient upstream * lambda * sign(w), zero at w = 0,
+    /// and lambda receives upstream * sum(|w|)
     fn backward(&mut self, upstream_gradient: NDArray<f64>) {
-        let lr = self.learning_rate / upstream_gradient.size() as f64;
-        let alpha = self.lhs().value().scalar_mult(lr).unwrap();
-        let sig = self.rhs().value().signum().unwrap();
-        let weight_update = sig.scale_mult(alpha).unwrap();
-        self.gradient = Value::new(&weight_update).into();
-        
+
+        self.gradient = Value::new(&upstream_gradient).into();
+
+        let upstream = upstream_gradient.values()[0];
+        let weights = self.rhs().value();
+        let lambda = self.lhs().value().values()[0];
+
+        let w_grad = weights.signum().unwrap().scalar_mult(lambda * upstream).unwrap();
+        let w_abs = weights.abs().unwrap().sum().unwrap();
+        let lambda_grad = w_abs.scalar_mult(upstream).unwrap();
+
+        self.rhs().backward(w_grad);
+        self.lhs().backward(lambda_grad);
     }
 
     /// Get output value of L1 regularization
@@ -190,4 +212,9 @@ where
     fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
         self.gradient = Value::new(&upstream_gradient).into();
     } 
+
+    /// Get inputs of L1 regularization in data flow order
+    fn inputs(&self) -> Vec<RefMut<'_, dyn Node>> {
+        vec![self.rhs(), self.lhs()]
+    }
 }
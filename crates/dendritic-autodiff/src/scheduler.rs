@@ -0,0 +1,309 @@
+use std::f64::consts::PI;
+use crate::optim::Optimizer;
+
+
+/// Methods shared by learning rate schedulers.
+/// `step` is called once per epoch with the monitored loss,
+/// schedulers that only depend on the epoch ignore the loss.
+pub trait Scheduler {
+    fn step(&mut self, loss: f64) -> f64;
+    fn learning_rate(&self) -> f64;
+
+    /// Advance scheduler and apply the new learning rate to an optimizer
+    fn step_optimizer(&mut self, optimizer: &mut dyn Optimizer, loss: f64) {
+        let learning_rate = self.step(loss);
+        optimizer.set_learning_rate(learning_rate);
+    }
+}
+
+
+/// Decay learning rate by gamma every `step_size` epochs
+#[derive(Debug, Clone)]
+pub struct StepLR {
+    pub base_learning_rate: f64,
+    pub step_size: usize,
+    pub gamma: f64,
+    epoch: usize
+}
+
+
+impl StepLR {
+
+    /// Create new instance of step decay scheduler
+    pub fn new(learning_rate: f64, step_size: usize, gamma: f64) -> StepLR {
+        StepLR {
+            base_learning_rate: learning_rate,
+            step_size: step_size.max(1),
+            gamma,
+            epoch: 0
+        }
+    }
+
+}
+
+
+impl Scheduler for StepLR {
+
+    /// Advance one epoch
+    fn step(&mut self, _loss: f64) -> f64 {
+        self.epoch += 1;
+        self.learning_rate()
+    }
+
+    /// Get learning rate with lr = base * gamma^(epoch / step_size)
+    fn learning_rate(&self) -> f64 {
+        let decays = (self.epoch / self.step_size) as i32;
+        self.base_learning_rate * self.gamma.powi(decays)
+    }
+
+}
+
+
+/// Decay learning rate by gamma every epoch
+#[derive(Debug, Clone)]
+pub struct ExponentialLR {
+    pub base_learning_rate: f64,
+    pub gamma: f64,
+    epoch: usize
+}
+
+
+impl ExponentialLR {
+
+    /// Create new instance of exponential decay scheduler
+    pub fn new(learning_rate: f64, gamma: f64) -> ExponentialLR {
+        ExponentialLR {
+            base_learning_rate: learning_rate,
+            gamma,
+            epoch: 0
+        }
+    }
+
+}
+
+
+impl Scheduler for ExponentialLR {
+
+    /// Advance one epoch
+    fn step(&mut self, _loss: f64) -> f64 {
+        self.epoch += 1;
+        self.learning_rate()
+    }
+
+    /// Get learning rate with lr = base * gamma^epoch
+    fn learning_rate(&self) -> f64 {
+        self.base_learning_rate * self.gamma.powi(self.epoch as i32)
+    }
+
+}
+
+
+/// Anneal learning rate from base to minimum along a half cosine.
+/// With warm restarts the schedule starts over after each period,
+/// the period length is multiplied by `period_mult` on every restart.
+#[derive(Debug, Clone)]
+pub struct CosineAnnealing {
+    pub base_learning_rate: f64,
+    pub min_learning_rate: f64,
+    pub period: usize,
+    pub period_mult: usize,
+    pub restarts: bool,
+    current_period: usize,
+    epoch: usize
+}
+
+
+impl CosineAnnealing {
+
+    /// Create new instance of cosine annealing, stays at minimum after one period
+    pub fn new(
+        learning_rate: f64,
+        min_learning_rate: f64,
+        period: usize) -> CosineAnnealing {
+
+        CosineAnnealing {
+            base_learning_rate: learning_rate,
+            min_learning_rate,
+            period: period.max(1),
+            period_mult: 1,
+            restarts: false,
+            current_period: period.max(1),
+            epoch: 0
+        }
+    }
+
+    /// Create new instance of cosine annealing with warm restarts
+    pub fn with_restarts(
+        learning_rate: f64,
+        min_learning_rate: f64,
+        period: usize,
+        period_mult: usize) -> CosineAnnealing {
+
+        CosineAnnealing {
+            restarts: true,
+            period_mult: period_mult.max(1),
+            ..CosineAnnealing::new(learning_rate, min_learning_rate, period)
+        }
+    }
+
+}
+
+
+impl Scheduler for CosineAnnealing {
+
+    /// Advance one epoch, restarting the cycle when the period ends
+    fn step(&mut self, _loss: f64) -> f64 {
+        self.epoch += 1;
+        if self.restarts && self.epoch >= self.current_period {
+            self.epoch = 0;
+            self.current_period *= self.period_mult;
+        }
+        self.learning_rate()
+    }
+
+    /// Get learning rate with lr = min + (base - min) * (1 + cos(pi * t / T)) / 2
+    fn learning_rate(&self) -> f64 {
+        let t = self.epoch.min(self.current_period) as f64;
+        let ratio = t / self.current_period as f64;
+        let range = self.base_learning_rate - self.min_learning_rate;
+        self.min_learning_rate + 0.5 * range * (1.0 + (PI * ratio).cos())
+    }
+
+}
+
+
+/// Linearly increase learning rate over the first epochs,
+/// optionally handing off to another scheduler once warmup is done
+pub struct LinearWarmup {
+    pub target_learning_rate: f64,
+    pub warmup_epochs: usize,
+    scheduler: Option<Box<dyn Scheduler>>,
+    epoch: usize
+}
+
+
+impl LinearWarmup {
+
+    /// Create new instance of linear warmup to a constant learning rate
+    pub fn new(learning_rate: f64, warmup_epochs: usize) -> LinearWarmup {
+        LinearWarmup {
+            target_learning_rate: learning_rate,
+            warmup_epochs,
+            scheduler: None,
+            epoch: 0
+        }
+    }
+
+    /// Create new instance of linear warmup followed by another scheduler.
+    /// Warmup targets the starting learning rate of that scheduler
+    pub fn with_scheduler<S: Scheduler + 'static>(
+        warmup_epochs: usize,
+        scheduler: S) -> LinearWarmup {
+
+        LinearWarmup {
+            target_learning_rate: scheduler.learning_rate(),
+            warmup_epochs,
+            scheduler: Some(Box::new(scheduler)),
+            epoch: 0
+        }
+    }
+
+}
+
+
+impl Scheduler for LinearWarmup {
+
+    /// Advance one epoch, stepping the wrapped scheduler after warmup
+    fn step(&mut self, loss: f64) -> f64 {
+        self.epoch += 1;
+        if self.epoch > self.warmup_epochs {
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step(loss);
+            }
+        }
+        self.learning_rate()
+    }
+
+    /// Get learning rate with lr = target * (epoch + 1) / warmup_epochs during warmup
+    fn learning_rate(&self) -> f64 {
+        if self.epoch < self.warmup_epochs {
+            let progress = (self.epoch + 1) as f64 / self.warmup_epochs as f64;
+            return self.target_learning_rate * progress;
+        }
+
+        match &self.scheduler {
+            Some(scheduler) => scheduler.learning_rate(),
+            None => self.target_learning_rate
+        }
+    }
+
+}
+
+
+/// Reduce learning rate by a factor when the monitored loss stops improving
+#[derive(Debug, Clone)]
+pub struct ReduceLROnPlateau {
+    pub factor: f64,
+    pub patience: usize,
+    pub threshold: f64,
+    pub min_learning_rate: f64,
+    learning_rate: f64,
+    best: f64,
+    bad_epochs: usize
+}
+
+
+impl ReduceLROnPlateau {
+
+    /// Create new instance of plateau scheduler.
+    /// Loss has to improve by a relative threshold of 1e-4 to count as better
+    pub fn new(
+        learning_rate: f64,
+        factor: f64,
+        patience: usize) -> ReduceLROnPlateau {
+
+        ReduceLROnPlateau {
+            factor,
+            patience,
+            threshold: 1e-4,
+            min_learning_rate: 0.0,
+            learning_rate,
+            best: f64::INFINITY,
+            bad_epochs: 0
+        }
+    }
+
+    /// Best loss seen so far
+    pub fn best(&self) -> f64 {
+        self.best
+    }
+
+}
+
+
+impl Scheduler for ReduceLROnPlateau {
+
+    /// Record loss, reducing learning rate after `patience` epochs without improvement
+    fn step(&mut self, loss: f64) -> f64 {
+        let improved = self.best.is_infinite() || loss < self.best - self.threshold * self.best.abs();
+        if improved {
+            self.best = loss;
+            self.bad_epochs = 0;
+        } else {
+            self.bad_epochs += 1;
+        }
+
+        if self.bad_epochs > self.patience {
+            self.learning_rate = (self.learning_rate * self.factor).max(self.min_learning_rate);
+            self.bad_epochs = 0;
+        }
+
+        self.learning_rate
+    }
+
+    /// Get current learning rate
+    fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+}
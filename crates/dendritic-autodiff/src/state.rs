@@ -0,0 +1,181 @@
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Read, Write};
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_ndarray::ops::*;
+use crate::node::Value;
+
+
+/// Format version written with every state dict
+pub const STATE_DICT_VERSION: usize = 1;
+
+
+/// Named parameter array stored in a state dict
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub value: NDArray<f64>
+}
+
+
+/// Snapshot of named parameter arrays in registration order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateDict {
+    pub version: usize,
+    pub parameters: Vec<Entry>
+}
+
+
+impl Default for StateDict {
+    fn default() -> Self {
+        StateDict::new()
+    }
+}
+
+
+impl StateDict {
+
+    /// Create new empty state dict with the current format version
+    pub fn new() -> StateDict {
+        StateDict {
+            version: STATE_DICT_VERSION,
+            parameters: Vec::new()
+        }
+    }
+
+    /// Add parameter array, replacing any array stored under the same name
+    pub fn insert(&mut self, name: &str, value: NDArray<f64>) {
+        match self.parameters.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => entry.value = value,
+            None => self.parameters.push(Entry { name: name.to_string(), value })
+        }
+    }
+
+    /// Get parameter array by name
+    pub fn get(&self, name: &str) -> Option<&NDArray<f64>> {
+        self.parameters.iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| &entry.value)
+    }
+
+    /// Names of stored parameters in order
+    pub fn names(&self) -> Vec<String> {
+        self.parameters.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    /// Save state dict to a single json file
+    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+        let filename_format = format!("{filepath}.json");
+        let file = File::create(filename_format)?;
+        let mut writer = BufWriter::new(file);
+        let json_string = serde_json::to_string_pretty(&self)?;
+        writer.write_all(json_string.as_bytes())?;
+        Ok(())
+    }
+
+    /// Load state dict saved with `save`, rejecting unknown format versions
+    pub fn load(filepath: &str) -> std::io::Result<StateDict> {
+        let filename_format = format!("{filepath}.json");
+        let mut file = File::open(filename_format)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        let instance: StateDict = serde_json::from_str(&contents)?;
+
+        if instance.version != STATE_DICT_VERSION {
+            let msg = format!(
+                "StateDict: Unsupported version {}, expected {}",
+                instance.version, STATE_DICT_VERSION
+            );
+            return Err(Error::new(ErrorKind::InvalidData, msg));
+        }
+
+        Ok(instance)
+    }
+
+}
+
+
+/// Registry of named trainable parameters.
+/// Implementors only list their parameters, state dict conversion and
+/// persistence are provided on top of `named_parameters`
+pub trait Parameters {
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)>;
+
+    /// Copy current value of every parameter into a state dict
+    fn state_dict(&self) -> StateDict {
+        let mut state = StateDict::new();
+        for (name, parameter) in self.named_parameters() {
+            state.insert(&name, parameter.val());
+        }
+        state
+    }
+
+    /// Overwrite parameter values from a state dict. Every parameter must be present
+    /// with a matching shape and no extra entries are allowed, nothing is
+    /// changed unless the whole state dict is valid
+    fn load_state_dict(&mut self, state: &StateDict) -> Result<(), String> {
+
+        let parameters = self.named_parameters();
+        for (name, parameter) in parameters.iter() {
+            let value = match state.get(name) {
+                Some(value) => value,
+                None => {
+                    let msg = format!("StateDict: Missing parameter {}", name);
+                    return Err(msg);
+                }
+            };
+
+            let expected = parameter.val().shape().values();
+            if value.shape().values() != expected {
+                let msg = format!(
+                    "StateDict: Shape mismatch for {}, expected {:?} got {:?}",
+                    name, expected, value.shape().values()
+                );
+                return Err(msg);
+            }
+        }
+
+        for name in state.names() {
+            if !parameters.iter().any(|(param_name, _)| *param_name == name) {
+                let msg = format!("StateDict: Unexpected parameter {}", name);
+                return Err(msg);
+            }
+        }
+
+        for (name, mut parameter) in parameters {
+            parameter.set_val(state.get(&name).unwrap());
+        }
+
+        Ok(())
+    }
+
+    /// Save every parameter to `{dir}/state_dict.json`
+    fn save_state_dict(&self, dir: &str) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        self.state_dict().save(&format!("{}/state_dict", dir))
+    }
+
+    /// Load parameters saved with `save_state_dict`. Directories from older saves
+    /// with one array file per parameter name are read as well
+    fn load_state_dict_from(&mut self, dir: &str) -> std::io::Result<()> {
+
+        let state_path = format!("{}/state_dict", dir);
+        let state = if Path::new(&format!("{}.json", state_path)).exists() {
+            StateDict::load(&state_path)?
+        } else {
+            let mut state = StateDict::new();
+            for (name, _) in self.named_parameters() {
+                let value = NDArray::load(&format!("{}/{}", dir, name))?;
+                state.insert(&name, value);
+            }
+            state
+        };
+
+        match self.load_state_dict(&state) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(Error::new(ErrorKind::InvalidData, err))
+        }
+    }
+}
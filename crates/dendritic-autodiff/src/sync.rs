@@ -0,0 +1,243 @@
+use dendritic_ndarray::ndarray::NDArray;
+use rayon::prelude::*;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::node::{Node, Value, zeros_like, accumulate};
+use crate::optim::Optimizer;
+
+
+/// Inputs and targets of one shard
+type Shard = (NDArray<f64>, NDArray<f64>);
+
+/// Weighted loss and gradients computed on one shard
+type ShardResult = Result<(f64, Vec<NDArray<f64>>), String>;
+
+
+/// Thread safe value node, clones share storage through `Arc<RwLock<T>>`.
+/// Parameters held in sync values can be shared between threads, graphs are still
+/// built per thread from `Value` snapshots (see `data_parallel_gradients`).
+/// Gradients are summed over backward passes like `Value`
+#[derive(Debug, Clone, Default)]
+pub struct SyncValue<T> {
+    pub value: Arc<RwLock<T>>,
+    pub gradient: Arc<RwLock<T>>,
+    accumulate: Arc<AtomicBool>
+}
+
+
+impl<T: Clone> SyncValue<T> {
+
+    /// Create new instance of thread safe value
+    pub fn new(value: &T) -> SyncValue<T> {
+        SyncValue {
+            value: Arc::new(RwLock::new(value.clone())),
+            gradient: Arc::new(RwLock::new(value.clone())),
+            accumulate: Arc::new(AtomicBool::new(false))
+        }
+    }
+
+    /// Create thread safe value from value and gradient of a value node
+    pub fn from_value(value: &Value<T>) -> SyncValue<T> {
+        SyncValue {
+            value: Arc::new(RwLock::new(value.val())),
+            gradient: Arc::new(RwLock::new(value.grad())),
+            accumulate: Arc::new(AtomicBool::new(false))
+        }
+    }
+
+    /// Get value associated with structure
+    pub fn val(&self) -> T {
+        self.value.read().unwrap().clone()
+    }
+
+    /// Get gradient of value
+    pub fn grad(&self) -> T {
+        self.gradient.read().unwrap().clone()
+    }
+
+    /// Set value associated with structure
+    pub fn set_val(&self, val: &T) {
+        *self.value.write().unwrap() = val.clone();
+    }
+
+    /// Set gradient of value, overwriting the stored gradient
+    pub fn set_grad(&self, grad: &T) {
+        *self.gradient.write().unwrap() = grad.clone();
+    }
+
+    /// Snapshot as a single threaded value node for building a graph
+    pub fn to_value(&self) -> Value<T> {
+        let mut value = Value::new(&self.val());
+        value.set_grad(&self.grad());
+        value
+    }
+
+}
+
+
+impl Node for SyncValue<NDArray<f64>> {
+
+    /// Forward operation for a value
+    fn forward(&mut self) {}
+
+    /// Add gradient from upstream in backward pass
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+        let total = match self.accumulate.load(Ordering::SeqCst) {
+            true => accumulate(&SyncValue::grad(self), upstream_gradient),
+            false => upstream_gradient
+        };
+        SyncValue::set_grad(self, &total);
+        self.accumulate.store(true, Ordering::SeqCst);
+    }
+
+    /// Retrieve value from node in computation graph
+    fn value(&self) -> NDArray<f64> {
+        self.val()
+    }
+
+    /// Retrieve gradient from node in computation graph
+    fn grad(&self) -> NDArray<f64> {
+        SyncValue::grad(self)
+    }
+
+    /// Set gradient from upstream for value
+    fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+        SyncValue::set_grad(self, &upstream_gradient);
+    }
+
+    /// Clones of a value share the same storage and therefore the same id
+    fn node_id(&self) -> usize {
+        Arc::as_ptr(&self.value) as usize
+    }
+
+    /// Reset gradient of value to zero
+    fn zero_grad(&mut self) {
+        SyncValue::set_grad(self, &zeros_like(&self.val()));
+    }
+
+}
+
+
+/// Split rows of inputs and targets into at most `num_shards` contiguous shards
+pub fn shards(
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    num_shards: usize) -> Result<Vec<Shard>, String> {
+
+    if num_shards == 0 {
+        return Err("Shards: Number of shards must be greater than 0".to_string());
+    }
+
+    if x.rank() < 2 || y.rank() < 2 || x.shape().dim(0) != y.shape().dim(0) {
+        let msg = format!(
+            "Shards: Inputs {:?} and targets {:?} must have the same number of rows",
+            x.shape().values(), y.shape().values()
+        );
+        return Err(msg);
+    }
+
+    let rows = x.shape().dim(0);
+    let shard_rows = rows.div_ceil(num_shards).max(1);
+
+    let split = |array: &NDArray<f64>, start: usize, end: usize| {
+        let mut shape = array.shape().values();
+        let width = array.size() / rows;
+        shape[0] = end - start;
+        NDArray::array(shape, array.values()[start * width..end * width].to_vec())
+    };
+
+    let mut result: Vec<Shard> = Vec::new();
+    for start in (0..rows).step_by(shard_rows) {
+        let end = (start + shard_rows).min(rows);
+        result.push((split(x, start, end)?, split(y, start, end)?));
+    }
+
+    Ok(result)
+}
+
+
+/// Compute gradients of a loss on shards of the data in parallel and average them.
+/// `loss_builder` builds the loss graph of one shard from parameter snapshots, inputs and targets.
+/// Shard gradients are weighted by shard rows, so for mean losses the result matches the
+/// gradient over the full batch. Averaged gradients are stored on the parameters and the
+/// weighted average loss is returned
+pub fn data_parallel_gradients<N, F>(
+    parameters: &[SyncValue<NDArray<f64>>],
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    num_shards: usize,
+    loss_builder: F) -> Result<f64, String>
+where
+    N: Node,
+    F: Fn(&[Value<NDArray<f64>>], Value<NDArray<f64>>, Value<NDArray<f64>>) -> N + Sync,
+{
+
+    let rows = x.shape().dim(0) as f64;
+    let results: Vec<ShardResult> = shards(x, y, num_shards)?
+        .into_par_iter()
+        .map(|(x_shard, y_shard)| {
+            let weight = x_shard.shape().dim(0) as f64 / rows;
+            let snapshots: Vec<Value<NDArray<f64>>> = parameters.iter()
+                .map(|p| Value::new(&p.val()))
+                .collect();
+
+            let mut loss = loss_builder(&snapshots, Value::new(&x_shard), Value::new(&y_shard));
+            let output = loss.value();
+            if output.size() != 1 {
+                return Err("Data Parallel: Loss must be a scalar".to_string());
+            }
+
+            loss.backward(NDArray::fill(output.shape().values(), 1.0)?);
+            let grads: Vec<NDArray<f64>> = snapshots.iter().map(|s| s.grad()).collect();
+            Ok((output.values()[0] * weight, grads.into_iter().map(|g| scale(&g, weight)).collect()))
+        })
+        .collect();
+
+    let mut total_loss = 0.0;
+    let mut totals: Vec<Option<NDArray<f64>>> = vec![None; parameters.len()];
+    for result in results {
+        let (loss, grads) = result?;
+        total_loss += loss;
+        for (total, grad) in totals.iter_mut().zip(grads) {
+            *total = Some(match total.take() {
+                Some(sum) => add(&sum, &grad)?,
+                None => grad
+            });
+        }
+    }
+
+    for (parameter, total) in parameters.iter().zip(totals) {
+        if let Some(grad) = total {
+            parameter.set_grad(&grad);
+        }
+    }
+
+    Ok(total_loss)
+}
+
+
+/// Apply one optimizer step to thread safe parameters using their stored gradients
+pub fn step_parameters(optimizer: &mut dyn Optimizer, parameters: &[SyncValue<NDArray<f64>>]) {
+    let mut values: Vec<Value<NDArray<f64>>> = parameters.iter().map(|p| p.to_value()).collect();
+    optimizer.step(&mut values);
+    for (parameter, value) in parameters.iter().zip(values.iter()) {
+        parameter.set_val(&value.val());
+    }
+}
+
+
+/// Multiply every element by a weight
+fn scale(array: &NDArray<f64>, weight: f64) -> NDArray<f64> {
+    let values: Vec<f64> = array.values().iter().map(|v| v * weight).collect();
+    NDArray::array(array.shape().values(), values).unwrap()
+}
+
+
+/// Elementwise sum of two arrays with the same shape
+fn add(a: &NDArray<f64>, b: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+    if a.shape().values() != b.shape().values() {
+        return Err("Data Parallel: Shard gradients have different shapes".to_string());
+    }
+    let values: Vec<f64> = a.values().iter().zip(b.values().iter()).map(|(x, y)| x + y).collect();
+    NDArray::array(a.shape().values(), values)
+}
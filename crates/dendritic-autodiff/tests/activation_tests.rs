@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod activation_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*; 
+    use dendritic_autodiff::activations::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-9, "{} != {}", a, e);
+        }
+    }
+
+    #[test]
+    fn test_sigmoid_node() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![3, 1], vec![-1.0, 0.0, 2.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let mut sig = Sigmoid::new(x_value.clone());
+        sig.forward();
+
+        let expected: Vec<f64> = x.values().iter()
+            .map(|v| 1.0 / (1.0 + (-v).exp()))
+            .collect();
+        assert_close(sig.value().values(), &expected);
+
+        let upstream: NDArray<f64> = NDArray::fill(vec![3, 1], 1.0).unwrap();
+        sig.backward(upstream);
+
+        let expected_grad: Vec<f64> = expected.iter()
+            .map(|s| s * (1.0 - s))
+            .collect();
+        assert_close(x_value.grad().values(), &expected_grad);
+        assert_eq!(x_value.grad().values()[1], 0.25);
+
+    }
+
+    #[test]
+    fn test_tanh_node() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 1], vec![0.0, 0.5]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let mut tanh = Tanh::new(x_value.clone());
+        tanh.forward();
+        assert_close(tanh.value().values(), &[0.0, 0.5_f64.tanh()]);
+
+        let upstream: NDArray<f64> = NDArray::fill(vec![2, 1], 2.0).unwrap();
+        tanh.backward(upstream);
+
+        let t = 0.5_f64.tanh();
+        assert_close(x_value.grad().values(), &[2.0, 2.0 * (1.0 - t * t)]);
+
+    }
+
+    #[test]
+    fn test_relu_nodes() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![4, 1], vec![-2.0, -0.5, 0.5, 3.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let upstream: NDArray<f64> = NDArray::fill(vec![4, 1], 1.0).unwrap();
+
+        let mut relu = ReLU::new(x_value.clone());
+        relu.forward();
+        relu.backward(upstream.clone());
+        assert_eq!(relu.value().values(), &vec![0.0, 0.0, 0.5, 3.0]);
+        assert_eq!(x_value.grad().values(), &vec![0.0, 0.0, 1.0, 1.0]);
+
+        let mut leaky = LeakyReLU::new(x_value.clone(), 0.1);
+        leaky.forward();
+        leaky.zero_grad();
+        leaky.backward(upstream);
+        assert_close(leaky.value().values(), &[-0.2, -0.05, 0.5, 3.0]);
+        assert_eq!(x_value.grad().values(), &vec![0.1, 0.1, 1.0, 1.0]);
+
+    }
+
+    #[test]
+    fn test_softmax_node() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![0.25, 1.23, -0.8, 1.0, 1.0, 1.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let mut softmax = Softmax::new(x_value.clone());
+        softmax.forward();
+
+        let expected = vec![
+            0.24910360124886244, 0.663725645234628, 0.08717075351650967,
+            1.0/3.0, 1.0/3.0, 1.0/3.0
+        ];
+        assert_close(softmax.value().values(), &expected);
+
+        /* gradient of a single output selects a row of the softmax jacobian */
+        let upstream: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+        ).unwrap();
+        softmax.backward(upstream);
+
+        let s = &expected;
+        let expected_grad = vec![
+            s[0] * (1.0 - s[0]), -s[0] * s[1], -s[0] * s[2],
+            0.0, 0.0, 0.0
+        ];
+        assert_close(x_value.grad().values(), &expected_grad);
+
+    }
+
+    #[test]
+    fn test_log_softmax_node() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![1, 3], vec![1000.0, 1001.0, 1002.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let mut log_softmax = LogSoftmax::new(x_value.clone());
+        log_softmax.forward();
+
+        /* stable for large inputs */
+        let log_sum_exp = 1002.0 + (1.0 + (-1.0_f64).exp() + (-2.0_f64).exp()).ln();
+        let expected: Vec<f64> = x.values().iter()
+            .map(|v| v - log_sum_exp)
+            .collect();
+        assert_close(log_softmax.value().values(), &expected);
+
+        let upstream: NDArray<f64> = NDArray::array(
+            vec![1, 3], vec![0.0, 0.0, 1.0]
+        ).unwrap();
+        log_softmax.backward(upstream);
+
+        let softmax = softmax_rows(&x);
+        let expected_grad = vec![
+            -softmax.values()[0], 
+            -softmax.values()[1], 
+            1.0 - softmax.values()[2]
+        ];
+        assert_close(x_value.grad().values(), &expected_grad);
+
+    }
+
+}
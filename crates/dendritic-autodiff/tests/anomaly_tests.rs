@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod anomaly_tests {
+
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::activations::*;
+    use dendritic_autodiff::anomaly::*;
+    use dendritic_autodiff::hooks::*;
+
+    #[test]
+    fn test_check_forward() {
+
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 0.0]).unwrap());
+        let y = Value::new(&NDArray::array(vec![1, 2], vec![2.0, 0.0]).unwrap());
+        let node = Sigmoid::new(Div::new(x.clone(), y));
+        assert!(!is_finite(&Div::new(x.clone(), x.clone()).value()));
+
+        /* 0 / 0 is reported at the division, not at the sigmoid consuming it */
+        let err = check_forward(&node).unwrap_err();
+        assert!(err.contains("forward pass of Div"), "{}", err);
+
+        let finite = Sigmoid::new(Add::new(x.clone(), x));
+        assert!(check_forward(&finite).is_ok());
+    }
+
+    #[test]
+    fn test_check_backward() {
+
+        /* sqrt has an infinite derivative at zero */
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![0.0, 4.0]).unwrap());
+        let w = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap());
+        let mut node = Mul::new(Pow::new(x.clone(), 0.5), w);
+        assert!(check_forward(&node).is_ok());
+
+        node.backward(NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap());
+        let err = check_backward(&node).unwrap_err();
+        assert!(err.contains("backward pass of Pow for input 0 (Value)"), "{}", err);
+
+        node.backward(NDArray::array(vec![1, 2], vec![f64::NAN, 1.0]).unwrap());
+        let err = check_backward(&node).unwrap_err();
+        assert!(err.contains("upstream gradient passed to backward pass of Mul"), "{}", err);
+    }
+
+    #[test]
+    fn test_detect_anomaly_disabled() {
+
+        /* anomaly detection is off by default and never halts */
+        assert!(!anomaly_detection());
+        let x = Value::new(&NDArray::array(vec![1, 1], vec![f64::INFINITY]).unwrap());
+        let mut node = Hooked::new("infinite", Add::new(x.clone(), x));
+        node.forward();
+        detect_forward_anomaly(&node);
+        node.backward(NDArray::array(vec![1, 1], vec![f64::NAN]).unwrap());
+        detect_backward_anomaly(&node);
+    }
+
+    #[test]
+    #[should_panic(expected = "Non-finite value produced in forward pass of logits")]
+    fn test_hooked_forward_anomaly() {
+
+        set_anomaly_detection(true);
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![f64::NAN, 1.0]).unwrap());
+        let w = Value::new(&NDArray::array(vec![2, 1], vec![1.0, 1.0]).unwrap());
+        let mut node = Hooked::new("logits", MatMul::new(x, w));
+        node.forward();
+    }
+
+    #[test]
+    #[should_panic(expected = "backward pass of sqrt for input 0 (Value)")]
+    fn test_hooked_backward_anomaly() {
+
+        set_anomaly_detection(true);
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![0.0, 0.0]).unwrap());
+        let mut node = Sigmoid::new(Hooked::new("sqrt", Pow::new(x, 0.5)));
+        node.forward();
+        node.backward(NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "forward pass of Log")]
+    fn test_detect_forward_anomaly() {
+
+        set_anomaly_detection(true);
+        let x = Value::new(&NDArray::array(vec![1, 1], vec![-1.0]).unwrap());
+        let node = Add::new(Log::new(x.clone()), x);
+        detect_forward_anomaly(&node);
+    }
+
+    #[test]
+    fn test_backward_hooks() {
+
+        let log: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        let w = Value::new(&NDArray::array(vec![1, 2], vec![3.0, 4.0]).unwrap());
+
+        let mut node = Hooked::new("scale", Mul::new(x.clone(), w));
+        assert_eq!(node.op_name(), "scale");
+
+        let pre_log = log.clone();
+        node.register_pre_backward_hook(move |name, upstream| {
+            pre_log.borrow_mut().push(format!("pre {} {:?}", name, upstream.values()));
+        });
+
+        let post_log = log.clone();
+        node.register_post_backward_hook(move |name, inner| {
+            let grads: Vec<Vec<f64>> = inner.inputs().iter().map(|i| i.grad().values().clone()).collect();
+            post_log.borrow_mut().push(format!("post {} {} {:?}", name, inner.op_name(), grads));
+        });
+
+        node.backward(NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap());
+        assert_eq!(*log.borrow(), vec![
+            "pre scale [1.0, 1.0]".to_string(),
+            "post scale Mul [[3.0, 4.0], [1.0, 2.0]]".to_string()
+        ]);
+        assert_eq!(x.grad().values(), &vec![3.0, 4.0]);
+        assert_eq!(node.grad().values(), &vec![1.0, 1.0]);
+
+        node.clear_hooks();
+        node.backward(NDArray::array(vec![1, 2], vec![2.0, 2.0]).unwrap());
+        assert_eq!(log.borrow().len(), 2);
+    }
+
+}
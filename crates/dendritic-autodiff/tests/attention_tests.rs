@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod attention_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::attention::*;
+    use dendritic_autodiff::gradcheck::*;
+
+    const EPS: f64 = 1e-6;
+    const TOL: f64 = 1e-5;
+
+    fn sample(shape: Vec<usize>, offset: usize) -> NDArray<f64> {
+        let size: usize = shape.iter().product();
+        let values: Vec<f64> = (0..size)
+            .map(|i| ((i * 7 + offset) % 11) as f64 * 0.2 - 1.0)
+            .collect();
+        NDArray::array(shape, values).unwrap()
+    }
+
+    #[test]
+    fn test_sinusoidal_encoding() {
+
+        let encoding = sinusoidal_encoding(4, 6);
+        assert_eq!(encoding.shape().values(), vec![4, 6]);
+
+        /* position zero is sin(0) = 0 and cos(0) = 1 */
+        assert_eq!(&encoding.values()[..6], &[0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+
+        let angle = 3.0 / 10000_f64.powf(2.0 / 6.0);
+        assert!((encoding.values()[3 * 6 + 2] - angle.sin()).abs() < 1e-12);
+        assert!((encoding.values()[3 * 6 + 3] - angle.cos()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_causal_mask() {
+
+        let mask = causal_mask(3);
+        assert_eq!(mask.values(), &vec![
+            1.0, 0.0, 0.0,
+            1.0, 1.0, 0.0,
+            1.0, 1.0, 1.0
+        ]);
+    }
+
+    #[test]
+    fn test_attention_forward() {
+
+        /* identical keys give uniform weights so output is the mean of values */
+        let query = NDArray::array(vec![2, 2], vec![1.0, 0.0, 0.0, 1.0]).unwrap();
+        let key = NDArray::array(vec![3, 2], vec![1.0; 6]).unwrap();
+        let value = NDArray::array(vec![3, 1], vec![1.0, 2.0, 6.0]).unwrap();
+
+        let mut attention = ScaledDotProductAttention::new(
+            Value::new(&query),
+            Value::new(&key),
+            Value::new(&value)
+        );
+        assert_eq!(attention.value().shape().values(), vec![2, 1]);
+        assert!(attention.value().values().iter().all(|v| (v - 3.0).abs() < 1e-12));
+
+        /* masked keys get zero weight */
+        let mask = NDArray::array(vec![2, 3], vec![1.0, 1.0, 0.0, 1.0, 0.0, 0.0]).unwrap();
+        attention.set_mask(Some(mask));
+        assert!((attention.value().values()[0] - 1.5).abs() < 1e-12);
+        assert!((attention.value().values()[1] - 1.0).abs() < 1e-12);
+
+        let weights = attention.attention_weights();
+        assert_eq!(weights.shape().values(), vec![1, 2, 3]);
+        assert_eq!(weights.values(), &vec![0.5, 0.5, 0.0, 1.0, 0.0, 0.0]);
+
+        /* scores are scaled by 1 / sqrt(d) */
+        let query = NDArray::array(vec![1, 4], vec![1.0, 1.0, 1.0, 1.0]).unwrap();
+        let key = NDArray::array(vec![2, 4], vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0]).unwrap();
+        let value = NDArray::array(vec![2, 1], vec![1.0, 0.0]).unwrap();
+        let attention = ScaledDotProductAttention::new(
+            Value::new(&query),
+            Value::new(&key),
+            Value::new(&value)
+        );
+        let expected = 2.0_f64.exp() / (2.0_f64.exp() + 1.0);
+        assert!((attention.value().values()[0] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_multi_head_forward() {
+
+        /* each head matches single head attention on its slice of features */
+        let query = sample(vec![2, 3, 4], 1);
+        let key = sample(vec![2, 5, 4], 2);
+        let value = sample(vec![2, 5, 6], 3);
+
+        let attention = ScaledDotProductAttention::with_heads(
+            Value::new(&query),
+            Value::new(&key),
+            Value::new(&value),
+            2
+        );
+        let output = attention.value();
+        assert_eq!(output.shape().values(), vec![2, 3, 6]);
+        assert_eq!(attention.attention_weights().shape().values(), vec![2, 2, 3, 5]);
+
+        let slice = |x: &NDArray<f64>, start: usize, width: usize| {
+            let shape = x.shape().values();
+            let values: Vec<f64> = x.values()
+                .chunks(shape[2])
+                .flat_map(|row| row[start..start + width].to_vec())
+                .collect();
+            NDArray::array(vec![shape[0], shape[1], width], values).unwrap()
+        };
+
+        let head = ScaledDotProductAttention::new(
+            Value::new(&slice(&query, 2, 2)),
+            Value::new(&slice(&key, 2, 2)),
+            Value::new(&slice(&value, 3, 3))
+        );
+        let expected = head.value();
+        let actual = slice(&output, 3, 3);
+        for (a, e) in actual.values().iter().zip(expected.values().iter()) {
+            assert!((a - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_attention_gradcheck() {
+
+        let inputs = vec![sample(vec![3, 4], 1), sample(vec![5, 4], 2), sample(vec![5, 2], 3)];
+        let result = gradcheck(
+            |v| ScaledDotProductAttention::new(v[0].clone(), v[1].clone(), v[2].clone()),
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+
+        let inputs = vec![sample(vec![2, 3, 4], 1), sample(vec![2, 5, 4], 2), sample(vec![2, 5, 6], 3)];
+        let result = gradcheck(
+            |v| ScaledDotProductAttention::with_heads(v[0].clone(), v[1].clone(), v[2].clone(), 2),
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    fn test_masked_attention_gradcheck() {
+
+        let inputs = vec![sample(vec![2, 4, 4], 1), sample(vec![2, 4, 4], 2), sample(vec![2, 4, 4], 3)];
+        let result = gradcheck(
+            |v| {
+                let mut attention = ScaledDotProductAttention::with_heads(
+                    v[0].clone(), v[1].clone(), v[2].clone(), 2
+                );
+                attention.set_mask(Some(causal_mask(4)));
+                attention
+            },
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+
+        /* per sample mask with a fully masked row */
+        let mut mask = NDArray::fill(vec![2, 4, 4], 1.0).unwrap();
+        for j in 0..4 {
+            mask.set_idx(16 + j, 0.0).unwrap();
+        }
+        let result = gradcheck(
+            |v| {
+                let mut attention = ScaledDotProductAttention::new(v[0].clone(), v[1].clone(), v[2].clone());
+                attention.set_mask(Some(mask.clone()));
+                attention
+            },
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    fn test_reshape() {
+
+        let x = sample(vec![2, 6], 1);
+        let mut reshape = Reshape::new(Value::new(&x), vec![2, 3, 2]);
+        assert_eq!(reshape.value().shape().values(), vec![2, 3, 2]);
+        assert_eq!(reshape.value().values(), x.values());
+
+        reshape.backward(NDArray::fill(vec![2, 3, 2], 2.0).unwrap());
+        assert_eq!(reshape.input().grad().shape().values(), vec![2, 6]);
+
+        let inputs = vec![sample(vec![4, 3], 2), sample(vec![3, 2], 5)];
+        let result = gradcheck(
+            |v| Reshape::new(MatMul::new(v[0].clone(), v[1].clone()), vec![2, 4]),
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+}
@@ -255,8 +255,7 @@ mod autodiff_test {
 
         let mut reg = L2Regularization::new(
             weights.clone(),
-            lambda_val.clone(),
-            0.01
+            lambda_val.clone()
         );
 
         reg.forward();
@@ -273,12 +272,9 @@ mod autodiff_test {
     #[test]
     fn test_l2_regularization_backward() {
 
-        let y_path = "data/linear_modeling_data/outputs";
         let w_path = "data/linear_modeling_data/weights_reg";
         let w: NDArray<f64> = NDArray::load(w_path).unwrap();
-        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
 
-        let learning_rate = 0.01;
         let lambda: NDArray<f64> = NDArray::array(
             vec![1, 1], vec![2.0]
         ).unwrap();
@@ -288,14 +284,13 @@ mod autodiff_test {
 
         let mut reg = L2Regularization::new(
             weights.clone(),
-            lambda_val.clone(),
-            learning_rate
+            lambda_val.clone()
         );
 
         let expected_shape = vec![1, 1];
         let expected_val = vec![24.0]; 
         let expected_grad_shape = vec![3, 1];
-        let expected_grad_values = vec![0.016, 0.016, 0.016];
+        let expected_grad_values = vec![8.0, 8.0, 8.0];
 
         reg.forward();
         let output_binding = reg.output.borrow().clone();
@@ -305,12 +300,19 @@ mod autodiff_test {
         assert_eq!(outputs.values(), &expected_val);
         assert_eq!(outputs.shape().values(), expected_shape); 
 
-        reg.backward(y);
-        let grad = reg.grad();
+        /* gradients are plain dL/dw = 2 * lambda * w scaled by upstream */
+        reg.backward(NDArray::fill(vec![1, 1], 1.0).unwrap());
+        let grad = weights.grad();
 
         assert_eq!(grad.rank(), 2); 
         assert_eq!(grad.values(), &expected_grad_values);
         assert_eq!(grad.shape().values(), expected_grad_shape); 
+        assert_eq!(lambda_val.grad().values(), &vec![12.0]);
+        assert_eq!(reg.grad().values(), &vec![1.0]);
+
+        reg.zero_grad();
+        reg.backward(NDArray::fill(vec![1, 1], 0.5).unwrap());
+        assert_eq!(weights.grad().values(), &vec![4.0, 4.0, 4.0]);
 
     }
 
@@ -318,11 +320,8 @@ mod autodiff_test {
     #[test]
     fn test_l1_regularization_forward() {
 
-        let y_path = "data/linear_modeling_data/outputs";
         let w_path = "data/linear_modeling_data/weights_reg";
-
         let w: NDArray<f64> = NDArray::load(w_path).unwrap();
-        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
 
         let lambda: NDArray<f64> = NDArray::array(
             vec![1, 1], vec![2.0]
@@ -331,16 +330,14 @@ mod autodiff_test {
         let expected_shape = vec![1, 1];
         let expected_val = vec![12.0];
         let expected_grad_shape = vec![3, 1];
-        let expected_grad = vec![0.004, 0.004, 0.004];
+        let expected_grad = vec![2.0, 2.0, 2.0];
 
         let weights = Value::new(&w);
         let lambda_val = Value::new(&lambda);
-        let learning_rate = 0.01;
 
         let mut reg = L1Regularization::new(
             weights.clone(),
-            lambda_val.clone(),
-            learning_rate
+            lambda_val.clone()
         );
 
         reg.forward();
@@ -351,14 +348,286 @@ mod autodiff_test {
         assert_eq!(outputs.values(), &expected_val);
         assert_eq!(outputs.shape().values(), expected_shape); 
 
-        reg.backward(y);
-        let grad = reg.grad();
+        /* gradients are plain dL/dw = lambda * sign(w) scaled by upstream */
+        reg.backward(NDArray::fill(vec![1, 1], 1.0).unwrap());
+        let grad = weights.grad();
 
         assert_eq!(grad.rank(), 2); 
         assert_eq!(grad.values(), &expected_grad);
         assert_eq!(grad.shape().values(), expected_grad_shape); 
+        assert_eq!(lambda_val.grad().values(), &vec![6.0]);
+
+    }
+
+
+    #[test]
+    fn test_add_node_broadcast() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+        ).unwrap();
+
+        let b: NDArray<f64> = NDArray::array(
+            vec![1, 3], vec![0.5, 1.0, 1.5]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let b_value = Value::new(&b);
+
+        let mut add_op = Add::new(x_value.clone(), b_value.clone());
+        add_op.forward();
+
+        let expected_output = vec![1.5, 3.0, 4.5, 4.5, 6.0, 7.5];
+        assert_eq!(add_op.value().shape().values(), vec![2, 3]);
+        assert_eq!(add_op.value().values(), &expected_output);
+
+        let upstream: NDArray<f64> = NDArray::fill(vec![2, 3], 1.0).unwrap();
+        add_op.backward(upstream);
+
+        assert_eq!(x_value.grad().shape().values(), vec![2, 3]);
+        assert_eq!(x_value.grad().values(), &vec![1.0; 6]);
+        assert_eq!(b_value.grad().shape().values(), vec![1, 3]);
+        assert_eq!(b_value.grad().values(), &vec![2.0, 2.0, 2.0]);
+
+    }
+
+
+    #[test]
+    fn test_value_gradient_accumulation() {
+
+        let mut x = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        let upstream: NDArray<f64> = NDArray::array(vec![1, 2], vec![1.0, 3.0]).unwrap();
+
+        /* set_grad overwrites and does not make the next pass add to it */
+        x.set_grad(&NDArray::array(vec![1, 2], vec![5.0, 5.0]).unwrap());
+        Node::backward(&mut x, upstream.clone());
+        assert_eq!(x.grad().values(), &vec![1.0, 3.0]);
+
+        Node::backward(&mut x, upstream.clone());
+        assert_eq!(x.grad().values(), &vec![2.0, 6.0]);
+
+        x.set_grad(&NDArray::array(vec![1, 2], vec![0.5, 0.5]).unwrap());
+        assert_eq!(x.grad().values(), &vec![0.5, 0.5]);
+
+        Node::zero_grad(&mut x);
+        Node::backward(&mut x, upstream);
+        assert_eq!(x.grad().values(), &vec![1.0, 3.0]);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "Accumulate: Gradient shape [1, 2] does not match upstream gradient shape [2, 1]")]
+    fn test_value_gradient_shape_mismatch() {
+
+        let mut x = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        Node::backward(&mut x, NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap());
+        Node::backward(&mut x, NDArray::array(vec![2, 1], vec![1.0, 1.0]).unwrap());
+    }
+
+
+    #[test]
+    fn test_sub_mul_div_nodes() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 2], vec![2.0, 4.0, 6.0, 8.0]
+        ).unwrap();
+
+        let y: NDArray<f64> = NDArray::array(
+            vec![2, 2], vec![1.0, 2.0, 4.0, 8.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let y_value = Value::new(&y);
+        let upstream: NDArray<f64> = NDArray::fill(vec![2, 2], 1.0).unwrap();
+
+        let mut sub_op = Sub::new(x_value.clone(), y_value.clone());
+        sub_op.forward();
+        sub_op.backward(upstream.clone());
+        assert_eq!(sub_op.value().values(), &vec![1.0, 2.0, 2.0, 0.0]);
+        assert_eq!(x_value.grad().values(), &vec![1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(y_value.grad().values(), &vec![-1.0, -1.0, -1.0, -1.0]);
+
+        let mut mul_op = Mul::new(x_value.clone(), y_value.clone());
+        mul_op.forward();
+        mul_op.zero_grad();
+        mul_op.backward(upstream.clone());
+        assert_eq!(mul_op.value().values(), &vec![2.0, 8.0, 24.0, 64.0]);
+        assert_eq!(x_value.grad().values(), y.values());
+        assert_eq!(y_value.grad().values(), x.values());
+
+        let mut div_op = Div::new(x_value.clone(), y_value.clone());
+        div_op.forward();
+        div_op.zero_grad();
+        div_op.backward(upstream);
+        assert_eq!(div_op.value().values(), &vec![2.0, 2.0, 1.5, 1.0]);
+        assert_eq!(x_value.grad().values(), &vec![1.0, 0.5, 0.25, 0.125]);
+        assert_eq!(y_value.grad().values(), &vec![-2.0, -1.0, -0.375, -0.125]);
+
+    }
+
+
+    #[test]
+    fn test_mul_node_scalar_broadcast() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![3, 1], vec![1.0, 2.0, 3.0]
+        ).unwrap();
+
+        let lambda: NDArray<f64> = NDArray::array(
+            vec![1, 1], vec![2.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let lambda_value = Value::new(&lambda);
+
+        let mut mul_op = Mul::new(x_value.clone(), lambda_value.clone());
+        mul_op.forward();
+        assert_eq!(mul_op.value().values(), &vec![2.0, 4.0, 6.0]);
+
+        let upstream: NDArray<f64> = NDArray::fill(vec![3, 1], 1.0).unwrap();
+        mul_op.backward(upstream);
 
-    } 
+        assert_eq!(x_value.grad().values(), &vec![2.0, 2.0, 2.0]);
+        assert_eq!(lambda_value.grad().shape().values(), vec![1, 1]);
+        assert_eq!(lambda_value.grad().values(), &vec![6.0]);
 
+    }
+
+
+    #[test]
+    fn test_unary_nodes() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![3, 1], vec![1.0, 2.0, 4.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let upstream: NDArray<f64> = NDArray::fill(vec![3, 1], 1.0).unwrap();
+
+        let mut pow_op = Pow::new(x_value.clone(), 3.0);
+        pow_op.forward();
+        pow_op.backward(upstream.clone());
+        assert_eq!(pow_op.value().values(), &vec![1.0, 8.0, 64.0]);
+        assert_eq!(x_value.grad().values(), &vec![3.0, 12.0, 48.0]);
+
+        let mut exp_op = Exp::new(x_value.clone());
+        exp_op.forward();
+        exp_op.zero_grad();
+        exp_op.backward(upstream.clone());
+        let expected_exp: Vec<f64> = x.values().iter().map(|v| v.exp()).collect();
+        assert_eq!(exp_op.value().values(), &expected_exp);
+        assert_eq!(x_value.grad().values(), &expected_exp);
+
+        let mut log_op = Log::new(x_value.clone());
+        log_op.forward();
+        log_op.zero_grad();
+        log_op.backward(upstream.clone());
+        let expected_log: Vec<f64> = x.values().iter().map(|v| v.ln()).collect();
+        assert_eq!(log_op.value().values(), &expected_log);
+        assert_eq!(x_value.grad().values(), &vec![1.0, 0.5, 0.25]);
+
+        let mut neg_op = Neg::new(x_value.clone());
+        neg_op.forward();
+        neg_op.zero_grad();
+        neg_op.backward(upstream);
+        assert_eq!(neg_op.value().values(), &vec![-1.0, -2.0, -4.0]);
+        assert_eq!(x_value.grad().values(), &vec![-1.0, -1.0, -1.0]);
+
+    }
+
+
+    #[test]
+    fn test_reduction_nodes() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let upstream: NDArray<f64> = NDArray::array(
+            vec![1, 1], vec![2.0]
+        ).unwrap();
+
+        let mut sum_op = Sum::new(x_value.clone());
+        sum_op.forward();
+        assert_eq!(sum_op.value().shape().values(), vec![1, 1]);
+        assert_eq!(sum_op.value().values(), &vec![10.0]);
+
+        sum_op.backward(upstream.clone());
+        assert_eq!(x_value.grad().shape().values(), vec![2, 2]);
+        assert_eq!(x_value.grad().values(), &vec![2.0, 2.0, 2.0, 2.0]);
+
+        let mut mean_op = Mean::new(x_value.clone());
+        mean_op.forward();
+        assert_eq!(mean_op.value().values(), &vec![2.5]);
+
+        mean_op.zero_grad();
+        mean_op.backward(upstream);
+        assert_eq!(x_value.grad().values(), &vec![0.5, 0.5, 0.5, 0.5]);
+
+    }
+
+
+    #[test]
+    fn test_matmul_node() {
+
+        let x_path = "data/linear_modeling_data/inputs"; 
+        let y_path = "data/linear_modeling_data/outputs";
+        let w_path = "data/linear_modeling_data/weights";
+
+        let x: NDArray<f64> = NDArray::load(x_path).unwrap();
+        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
+        let w: NDArray<f64> = NDArray::load(w_path).unwrap();
+
+        let x_value = Value::new(&x); 
+        let w_value = Value::new(&w); 
+
+        let mut matmul_op = MatMul::new(x_value.clone(), w_value.clone());
+        matmul_op.forward();
+        assert_eq!(matmul_op.value().shape().values(), vec![5, 1]);
+
+        let error = matmul_op.value().subtract(y).unwrap();
+        matmul_op.backward(error);
+
+        /* weights receive X^T * error, inputs receive error * W^T */
+        let expected_w_grad = vec![-230.0, -300.0, -370.0];
+        assert_eq!(w_value.grad().shape().values(), w.shape().values());
+        assert_eq!(w_value.grad().values(), &expected_w_grad);
+        assert_eq!(x_value.grad().shape().values(), x.shape().values());
+
+    }
+
+
+    #[test]
+    fn test_composed_graph() {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 1], vec![1.0, 2.0]
+        ).unwrap();
+
+        let w: NDArray<f64> = NDArray::array(
+            vec![2, 1], vec![3.0, 4.0]
+        ).unwrap();
+
+        let x_value = Value::new(&x);
+        let w_value = Value::new(&w);
+
+        /* mean((x * w)^2) */
+        let mut graph = Mean::new(
+            Pow::new(Mul::new(x_value.clone(), w_value.clone()), 2.0)
+        );
+        graph.forward();
+        assert_eq!(graph.value().values(), &vec![36.5]);
+
+        let upstream: NDArray<f64> = NDArray::array(
+            vec![1, 1], vec![1.0]
+        ).unwrap();
+        graph.backward(upstream);
+
+        /* d/dw = x * (x * w) */
+        assert_eq!(w_value.grad().values(), &vec![3.0, 16.0]);
+        assert_eq!(x_value.grad().values(), &vec![9.0, 32.0]);
+
+    }
 
 }
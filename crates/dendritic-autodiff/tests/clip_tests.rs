@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod clip_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_autodiff::clip::*;
+
+    fn parameters() -> Vec<Value<NDArray<f64>>> {
+        let mut w = Value::new(&NDArray::array(vec![2, 1], vec![1.0, 1.0]).unwrap());
+        let mut b = Value::new(&NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+        w.set_grad(&NDArray::array(vec![2, 1], vec![3.0, -4.0]).unwrap());
+        b.set_grad(&NDArray::array(vec![1, 1], vec![12.0]).unwrap());
+        vec![w, b]
+    }
+
+    #[test]
+    fn test_clip_grad_value() {
+
+        let mut params = parameters();
+        clip_grad_value(&mut params, 3.5);
+        assert_eq!(params[0].grad().values(), &vec![3.0, -3.5]);
+        assert_eq!(params[1].grad().values(), &vec![3.5]);
+        assert_eq!(params[0].val().values(), &vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_clip_grad_norm() {
+
+        /* global norm of (3, -4, 12) is 13 */
+        let mut params = parameters();
+        assert_eq!(grad_norm(&params), 13.0);
+        let norm = clip_grad_norm(&mut params, 6.5);
+        assert_eq!(norm, 13.0);
+        assert_eq!(params[0].grad().values(), &vec![1.5, -2.0]);
+        assert_eq!(params[1].grad().values(), &vec![6.0]);
+        assert!((grad_norm(&params) - 6.5).abs() < 1e-12);
+
+        /* gradients under the limit are left untouched */
+        let mut params = parameters();
+        assert_eq!(clip_grad_norm(&mut params, 20.0), 13.0);
+        assert_eq!(params[1].grad().values(), &vec![12.0]);
+    }
+
+    #[test]
+    fn test_clipped_optimizer() {
+
+        let mut params = parameters();
+        let mut optimizer = Clipped::by_norm(SGD::new(0.1), 1.3);
+        optimizer.step(&mut params);
+        assert_eq!(optimizer.last_norm(), 13.0);
+        assert!((params[0].val().values()[0] - 0.97).abs() < 1e-12);
+        assert!((params[0].val().values()[1] - 1.04).abs() < 1e-12);
+        assert!((params[1].val().values()[0] - 0.88).abs() < 1e-12);
+
+        let mut params = parameters();
+        let mut optimizer = Clipped::by_value(SGD::new(1.0), 1.0);
+        optimizer.step(&mut params);
+        assert_eq!(params[0].val().values(), &vec![0.0, 2.0]);
+        assert_eq!(params[1].val().values(), &vec![0.0]);
+
+        optimizer.set_learning_rate(0.5);
+        assert_eq!(optimizer.learning_rate(), 0.5);
+        assert_eq!(optimizer.optimizer.learning_rate(), 0.5);
+    }
+
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod custom_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::gradcheck::*;
+    use dendritic_autodiff::graph::*;
+    use dendritic_autodiff::custom::*;
+
+    /// Apply function to each element of an array
+    fn map(array: &NDArray<f64>, op: impl Fn(f64) -> f64) -> NDArray<f64> {
+        let values: Vec<f64> = array.values().iter().map(|v| op(*v)).collect();
+        NDArray::array(array.shape().values(), values).unwrap()
+    }
+
+    /// Elementwise product of two arrays
+    fn mult(a: &NDArray<f64>, b: &NDArray<f64>) -> NDArray<f64> {
+        let values: Vec<f64> = a.values().iter().zip(b.values().iter()).map(|(x, y)| x * y).collect();
+        NDArray::array(a.shape().values(), values).unwrap()
+    }
+
+    /// Cube of every element implemented as a plain struct
+    struct Cube;
+
+    impl Function for Cube {
+        fn forward(&self, inputs: &[NDArray<f64>]) -> Result<NDArray<f64>, String> {
+            Ok(map(&inputs[0], |x| x.powi(3)))
+        }
+
+        fn backward(
+            &self,
+            inputs: &[NDArray<f64>],
+            _output: &NDArray<f64>,
+            upstream_gradient: &NDArray<f64>) -> Result<Vec<NDArray<f64>>, String> {
+            Ok(vec![mult(&map(&inputs[0], |x| 3.0 * x * x), upstream_gradient)])
+        }
+    }
+
+    #[test]
+    fn test_closure_op() {
+
+        let softplus = function(
+            "softplus",
+            |inputs: &[NDArray<f64>]| Ok(map(&inputs[0], |x| (1.0 + x.exp()).ln())),
+            |inputs: &[NDArray<f64>], _output: &NDArray<f64>, upstream: &NDArray<f64>| {
+                Ok(vec![mult(&map(&inputs[0], |x| 1.0 / (1.0 + (-x).exp())), upstream)])
+            }
+        );
+
+        let x = Value::new(&NDArray::array(vec![1, 3], vec![-1.0, 0.0, 2.0]).unwrap());
+        let mut node = CustomOp::new(softplus.clone(), vec![Box::new(x.clone())]).unwrap();
+        assert!((node.value().values()[1] - 2.0_f64.ln()).abs() < 1e-12);
+        assert_eq!(node.op_name(), "softplus");
+
+        node.backward(NDArray::array(vec![1, 3], vec![1.0, 1.0, 1.0]).unwrap());
+        assert!((x.grad().values()[1] - 0.5).abs() < 1e-12);
+
+        let inputs = [NDArray::array(vec![2, 2], vec![-1.5, 0.3, 0.0, 2.0]).unwrap()];
+        let check = gradcheck(
+            |v| CustomOp::new(softplus.clone(), vec![Box::new(v[0].clone())]).unwrap(),
+            &inputs, 1e-6, 1e-6
+        ).unwrap();
+        assert!(check.passed(), "{:?}", check);
+    }
+
+    #[test]
+    fn test_multi_input_op() {
+
+        /* f(x, y) = x * y^2 */
+        let weighted = function(
+            "weighted",
+            |inputs: &[NDArray<f64>]| Ok(mult(&inputs[0], &map(&inputs[1], |y| y * y))),
+            |inputs: &[NDArray<f64>], _output: &NDArray<f64>, upstream: &NDArray<f64>| {
+                let dx = mult(&map(&inputs[1], |y| y * y), upstream);
+                let dy = mult(&mult(&inputs[0], &map(&inputs[1], |y| 2.0 * y)), upstream);
+                Ok(vec![dx, dy])
+            }
+        );
+
+        let inputs = [
+            NDArray::array(vec![2, 2], vec![1.0, -2.0, 0.5, 3.0]).unwrap(),
+            NDArray::array(vec![2, 2], vec![0.5, 1.5, -1.0, 2.0]).unwrap()
+        ];
+        let check = gradcheck(
+            |v| CustomOp::new(
+                weighted.clone(),
+                vec![Box::new(v[0].clone()), Box::new(v[1].clone())]
+            ).unwrap(),
+            &inputs, 1e-6, 1e-6
+        ).unwrap();
+        assert_eq!(check.mismatches.len(), 2);
+        assert!(check.passed(), "{:?}", check);
+    }
+
+    #[test]
+    fn test_custom_op_in_graph() {
+
+        /* cube of a matmul feeding a builtin add, gradients flow through every node */
+        let inputs = [
+            NDArray::array(vec![2, 2], vec![0.5, -1.0, 1.5, 0.25]).unwrap(),
+            NDArray::array(vec![2, 1], vec![1.0, 0.5]).unwrap(),
+            NDArray::array(vec![2, 1], vec![0.1, -0.2]).unwrap()
+        ];
+        let build = |v: &[Value<NDArray<f64>>]| Add::new(
+            CustomOp::new(Cube, vec![Box::new(MatMul::new(v[0].clone(), v[1].clone()))]).unwrap(),
+            v[2].clone()
+        );
+
+        let check = gradcheck(build, &inputs, 1e-6, 1e-6).unwrap();
+        assert!(check.passed(), "{:?}", check);
+
+        let values: Vec<Value<NDArray<f64>>> = inputs.iter().map(Value::new).collect();
+        let mut node = build(&values);
+        node.forward();
+        let expected = [0.1, 1.625_f64.powi(3) - 0.2];
+        assert!(node.value().values().iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let dot = to_dot(&node, false);
+        assert!(dot.contains("CustomOp\\nshape [2, 1]"), "{}", dot);
+        assert!(dot.contains("MatMul"), "{}", dot);
+        assert_eq!(node.rhs().inputs().len(), 1);
+    }
+
+    #[test]
+    fn test_custom_op_errors() {
+
+        assert!(CustomOp::new(Cube, Vec::new()).is_err());
+
+        let failing = function(
+            "failing",
+            |_inputs: &[NDArray<f64>]| Err("bad input".to_string()),
+            |_inputs: &[NDArray<f64>], _output: &NDArray<f64>, _upstream: &NDArray<f64>| Ok(Vec::new())
+        );
+        let x = Value::new(&NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+        assert_eq!(CustomOp::new(failing, vec![Box::new(x)]).err(), Some("bad input".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "CustomOp: broken returned 0 gradients for 1 inputs")]
+    fn test_custom_op_gradient_count() {
+
+        let broken = function(
+            "broken",
+            |inputs: &[NDArray<f64>]| Ok(inputs[0].clone()),
+            |_inputs: &[NDArray<f64>], _output: &NDArray<f64>, _upstream: &NDArray<f64>| Ok(Vec::new())
+        );
+        let x = Value::new(&NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+        let mut node = CustomOp::new(broken, vec![Box::new(x)]).unwrap();
+        node.backward(NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+    }
+
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod derivatives_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::activations::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::derivatives::*;
+    use dendritic_autodiff::dual::*;
+
+    fn close(a: &[f64], b: &[f64], tol: f64) -> bool {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol)
+    }
+
+    #[test]
+    fn test_gradient() {
+
+        let x = NDArray::array(vec![3, 1], vec![1.0, -2.0, 0.5]).unwrap();
+        let grad = gradient(|v| Sum::new(Pow::new(v, 3.0)), &x).unwrap();
+        assert!(close(grad.values(), &[3.0, 12.0, 0.75], 1e-12));
+
+        /* only scalar functions have a gradient */
+        assert!(gradient(|v| Pow::new(v, 2.0), &x).is_err());
+    }
+
+    #[test]
+    fn test_jacobian() {
+
+        /* jacobian of A * x is A */
+        let a = NDArray::array(vec![3, 2], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let x = NDArray::array(vec![2, 1], vec![0.3, -0.7]).unwrap();
+        let jac = jacobian(|v| MatMul::new(Value::new(&a), v), &x).unwrap();
+        assert_eq!(jac.shape().values(), vec![3, 2]);
+        assert!(close(jac.values(), a.values(), 1e-12));
+
+        /* elementwise sigmoid has a diagonal jacobian */
+        let x = NDArray::array(vec![1, 3], vec![0.0, 1.0, -2.0]).unwrap();
+        let jac = jacobian(Sigmoid::new, &x).unwrap();
+        assert_eq!(jac.shape().values(), vec![3, 3]);
+        for i in 0..3 {
+            let s = 1.0 / (1.0 + (-x.values()[i]).exp());
+            for j in 0..3 {
+                let expected = if i == j { s * (1.0 - s) } else { 0.0 };
+                assert!((jac.values()[i * 3 + j] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hessian() {
+
+        /* hessian of sum(x^3) is diag(6x) */
+        let x = NDArray::array(vec![3, 1], vec![1.0, -2.0, 0.5]).unwrap();
+        let cubic = |v: &NDArray<Dual<Dual<f64>>>| Ok(v.map(|d| d.powf(3.0)).sum());
+        let hess = hessian(cubic, &x).unwrap();
+        assert_eq!(hess.shape().values(), vec![3, 3]);
+        assert!(close(hess.values(), &[
+            6.0, 0.0, 0.0,
+            0.0, -12.0, 0.0,
+            0.0, 0.0, 3.0
+        ], 1e-12));
+
+        let v = NDArray::array(vec![3, 1], vec![2.0, 1.0, -4.0]).unwrap();
+        let product = hvp(cubic, &x, &v).unwrap();
+        assert!(close(product.values(), &[12.0, -12.0, -12.0], 1e-12));
+    }
+
+    #[test]
+    fn test_logistic_hessian() {
+
+        /* hessian of mean logistic loss is X^T diag(p (1 - p)) X / n */
+        let features = NDArray::array(vec![4, 2], vec![
+            1.0, 0.5,
+            -0.3, 2.0,
+            0.8, -1.0,
+            1.5, 0.2
+        ]).unwrap();
+        let y = NDArray::array(vec![4, 1], vec![1.0, 0.0, 1.0, 0.0]).unwrap();
+        let w = NDArray::array(vec![2, 1], vec![0.2, -0.4]).unwrap();
+
+        let features_dual = NDArray::<Dual<Dual<f64>>>::constant(&features);
+        let y_dual = NDArray::<Dual<Dual<f64>>>::constant(&y);
+        let loss = |v: &NDArray<Dual<Dual<f64>>>| -> Result<Dual<Dual<f64>>, String> {
+            let p = features_dual.dot(v)?.map(|z| z.sigmoid());
+            let likelihood = p.zip(&y_dual, |p, y| y * p.ln() + (-y + 1.0) * (-p + 1.0).ln())?;
+            Ok(likelihood.sum() / -4.0)
+        };
+
+        /* value of the dual loss matches the reverse mode graph */
+        let reverse = BinaryCrossEntropy::new(
+            Sigmoid::new(MatMul::new(Value::new(&features), Value::new(&w))),
+            Value::new(&y)
+        );
+        let input = NDArray::<Dual<Dual<f64>>>::constant(&w);
+        assert!((loss(&input).unwrap().real.real - reverse.value().values()[0]).abs() < 1e-12);
+
+        let mut expected = [0.0; 4];
+        for row in features.values().chunks(2) {
+            let z = row[0] * w.values()[0] + row[1] * w.values()[1];
+            let p = 1.0 / (1.0 + (-z).exp());
+            for i in 0..2 {
+                for j in 0..2 {
+                    expected[i * 2 + j] += p * (1.0 - p) * row[i] * row[j] / 4.0;
+                }
+            }
+        }
+
+        let hess = hessian(loss, &w).unwrap();
+        assert!(close(hess.values(), &expected, 1e-12));
+
+        /* hessian vector product matches the full hessian */
+        let v = NDArray::array(vec![2, 1], vec![1.0, -3.0]).unwrap();
+        let product = hvp(loss, &w, &v).unwrap();
+        let dense = [
+            expected[0] * 1.0 - expected[1] * 3.0,
+            expected[2] * 1.0 - expected[3] * 3.0
+        ];
+        assert!(close(product.values(), &dense, 1e-12));
+
+        let zero = hvp(loss, &w, &NDArray::new(vec![2, 1]).unwrap()).unwrap();
+        assert_eq!(zero.values(), &vec![0.0, 0.0]);
+        assert!(hvp(loss, &w, &NDArray::new(vec![1, 2]).unwrap()).is_err());
+    }
+
+}
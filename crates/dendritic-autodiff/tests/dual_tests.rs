@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod dual_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::activations::*;
+    use dendritic_autodiff::derivatives::*;
+    use dendritic_autodiff::dual::*;
+
+    fn close(a: &[f64], b: &[f64], tol: f64) -> bool {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol)
+    }
+
+    #[test]
+    fn test_dual_arithmetic() {
+
+        let x = Dual::variable(2.0);
+        let c = Dual::constant(3.0);
+
+        assert_eq!(x + c, Dual::new(5.0, 1.0));
+        assert_eq!(x - c, Dual::new(-1.0, 1.0));
+        assert_eq!(x * c, Dual::new(6.0, 3.0));
+        assert_eq!(x * x, Dual::new(4.0, 4.0));
+        assert_eq!(-x, Dual::new(-2.0, -1.0));
+        assert_eq!(c / x, Dual::new(1.5, -0.75));
+        assert_eq!(x * 2.0 + 1.0, Dual::new(5.0, 2.0));
+        assert_eq!((x - 1.0) / 2.0, Dual::new(0.5, 0.5));
+    }
+
+    #[test]
+    fn test_derivative() {
+
+        let x = 0.7;
+        assert!((derivative(|d| d.exp(), x) - x.exp()).abs() < 1e-12);
+        assert!((derivative(|d| d.ln(), x) - 1.0 / x).abs() < 1e-12);
+        assert!((derivative(|d| d.powf(3.0), x) - 3.0 * x * x).abs() < 1e-12);
+        assert!((derivative(|d| d.sqrt(), x) - 0.5 / x.sqrt()).abs() < 1e-12);
+        assert!((derivative(|d| d.sin(), x) - x.cos()).abs() < 1e-12);
+        assert!((derivative(|d| d.cos(), x) + x.sin()).abs() < 1e-12);
+        assert!((derivative(|d| d.tanh(), x) - (1.0 - x.tanh().powi(2))).abs() < 1e-12);
+
+        let s = 1.0 / (1.0 + (-x).exp());
+        assert!((derivative(|d| d.sigmoid(), x) - s * (1.0 - s)).abs() < 1e-12);
+        assert_eq!(derivative(|d| d.relu(), -1.0), 0.0);
+        assert_eq!(derivative(|d| d.relu(), 1.0), 1.0);
+        assert_eq!(derivative(|d| d.abs(), -1.0), -1.0);
+
+        /* chain rule through composed functions */
+        let f = |d: Dual<f64>| (d * d + 1.0).ln().sin();
+        let expected = (x * x + 1.0_f64).ln().cos() * 2.0 * x / (x * x + 1.0);
+        assert!((derivative(f, x) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dual_array() {
+
+        let real = NDArray::array(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let tangent = NDArray::array(vec![2, 2], vec![1.0, 0.0, 0.0, 1.0]).unwrap();
+        let a = NDArray::<Dual<f64>>::from_parts(&real, &tangent).unwrap();
+        assert_eq!(a.real(), real);
+        assert_eq!(a.tangent(), tangent);
+        assert!(NDArray::<Dual<f64>>::from_parts(&real, &NDArray::new(vec![4, 1]).unwrap()).is_err());
+
+        let b = NDArray::<Dual<f64>>::constant(&NDArray::array(vec![2, 1], vec![1.0, -1.0]).unwrap());
+        let product = a.dot(&b).unwrap();
+        assert_eq!(product.real().values(), &vec![-1.0, -1.0]);
+        assert_eq!(product.tangent().values(), &vec![1.0, -1.0]);
+        assert!(b.dot(&b).is_err());
+
+        let row = NDArray::<Dual<f64>>::constant(&NDArray::array(vec![1, 2], vec![10.0, 20.0]).unwrap());
+        let shifted = a.zip(&row, |x, y| x + y).unwrap();
+        assert_eq!(shifted.real().values(), &vec![11.0, 22.0, 13.0, 24.0]);
+        assert!(a.zip(&b, |x, y| x + y).is_err());
+
+        let squared = a.map(|x| x * x);
+        assert_eq!(squared.tangent().values(), &vec![2.0, 0.0, 0.0, 8.0]);
+        assert_eq!(a.sum(), Dual::new(10.0, 2.0));
+    }
+
+    #[test]
+    fn test_jvp_matches_reverse_mode() {
+
+        /* forward mode J v against reverse mode jacobian of sigmoid(X w) */
+        let features = NDArray::array(vec![4, 3], vec![
+            1.0, 0.5, -0.2,
+            -0.3, 2.0, 0.1,
+            0.8, -1.0, 0.4,
+            1.5, 0.2, -0.6
+        ]).unwrap();
+        let w = NDArray::array(vec![3, 1], vec![0.2, -0.4, 0.3]).unwrap();
+        let v = NDArray::array(vec![3, 1], vec![1.0, 0.5, -2.0]).unwrap();
+
+        let x_dual = NDArray::<Dual<f64>>::constant(&features);
+        let (output, tangent) = jvp(
+            |w| Ok(x_dual.dot(w)?.map(|z| z.sigmoid())),
+            &w, &v
+        ).unwrap();
+        assert_eq!(output.shape().values(), vec![4, 1]);
+
+        let reverse = Sigmoid::new(MatMul::new(Value::new(&features), Value::new(&w)));
+        assert!(close(output.values(), reverse.value().values(), 1e-12));
+
+        let jac = jacobian(
+            |w| Sigmoid::new(MatMul::new(Value::new(&features), w)),
+            &w
+        ).unwrap();
+        let expected: Vec<f64> = jac.values()
+            .chunks(3)
+            .map(|row| row.iter().zip(v.values().iter()).map(|(j, v)| j * v).sum())
+            .collect();
+        assert!(close(tangent.values(), &expected, 1e-12));
+
+        /* directional derivative of a scalar loss matches gradient . v */
+        let loss = |w: &NDArray<Dual<f64>>| -> Result<NDArray<Dual<f64>>, String> {
+            let total = w.map(|d| d.powf(3.0)).sum();
+            NDArray::array(vec![1, 1], vec![total])
+        };
+        let (_, directional) = jvp(loss, &w, &v).unwrap();
+        let grad = gradient(|w| Sum::new(Pow::new(w, 3.0)), &w).unwrap();
+        let dot: f64 = grad.values().iter().zip(v.values().iter()).map(|(g, v)| g * v).sum();
+        assert!((directional.values()[0] - dot).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nested_dual() {
+
+        /* dual numbers over duals carry exact second derivatives in the e1 e2 part */
+        let x = 0.7_f64;
+        let d = Dual::new(Dual::variable(x), Dual::constant(1.0));
+        assert!((d.sin().dual.dual + x.sin()).abs() < 1e-15);
+        assert!((d.powf(3.0).dual.dual - 6.0 * x).abs() < 1e-12);
+
+        let s = 1.0 / (1.0 + (-x).exp());
+        assert!((d.sigmoid().dual.dual - s * (1.0 - s) * (1.0 - 2.0 * s)).abs() < 1e-15);
+        assert!(((d * d + 1.0).ln().dual.dual - 2.0 * (1.0 - x * x) / (1.0 + x * x).powi(2)).abs() < 1e-12);
+    }
+
+}
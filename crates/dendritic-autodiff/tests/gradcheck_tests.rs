@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod gradcheck_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_ndarray::ops::*;
+    use dendritic_autodiff::node::*; 
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::activations::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::gradcheck::*;
+
+    const EPS: f64 = 1e-6;
+    const TOL: f64 = 1e-6;
+
+    /// Node with a deliberately wrong backward pass
+    struct DoubleGrad {
+        input: Value<NDArray<f64>>
+    }
+
+    impl Node for DoubleGrad {
+        fn forward(&mut self) {}
+        fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+            let grad = upstream_gradient.scalar_mult(2.0).unwrap();
+            self.input.backward(grad);
+        }
+        fn value(&self) -> NDArray<f64> { self.input.val() }
+        fn grad(&self) -> NDArray<f64> { self.input.grad() }
+        fn set_grad(&mut self, upstream_gradient: NDArray<f64>) {
+            self.input.set_grad(&upstream_gradient);
+        }
+    }
+
+    fn inputs() -> (NDArray<f64>, NDArray<f64>) {
+
+        let x: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![0.5, -1.2, 2.0, 1.5, 0.3, -0.7]
+        ).unwrap();
+
+        let y: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![1.1, 0.4, -0.9, 2.2, -1.3, 0.8]
+        ).unwrap();
+
+        (x, y)
+    }
+
+    #[test]
+    fn test_gradcheck_binary_ops() {
+
+        let (x, y) = inputs();
+        let b: NDArray<f64> = NDArray::array(
+            vec![1, 3], vec![0.2, -0.4, 1.5]
+        ).unwrap();
+
+        let add = gradcheck(|v| Add::new(v[0].clone(), v[1].clone()), &[x.clone(), b.clone()], EPS, TOL).unwrap();
+        let sub = gradcheck(|v| Sub::new(v[0].clone(), v[1].clone()), &[x.clone(), b.clone()], EPS, TOL).unwrap();
+        let mul = gradcheck(|v| Mul::new(v[0].clone(), v[1].clone()), &[x.clone(), y.clone()], EPS, TOL).unwrap();
+        let div = gradcheck(|v| Div::new(v[0].clone(), v[1].clone()), &[x.clone(), y.clone()], EPS, TOL).unwrap();
+
+        assert!(add.passed(), "{:?}", add);
+        assert!(sub.passed(), "{:?}", sub);
+        assert!(mul.passed(), "{:?}", mul);
+        assert!(div.passed(), "{:?}", div);
+        assert_eq!(add.mismatches.len(), 2);
+
+        let w = y.transpose().unwrap();
+        let matmul = gradcheck(|v| MatMul::new(v[0].clone(), v[1].clone()), &[x, w], EPS, TOL).unwrap();
+        assert!(matmul.passed(), "{:?}", matmul);
+
+    }
+
+    #[test]
+    fn test_gradcheck_shared_operand() {
+
+        let (x, y) = inputs();
+
+        /* x * x and x * y + x * x use the same leaf more than once */
+        let square = gradcheck(|v| Mul::new(v[0].clone(), v[0].clone()), std::slice::from_ref(&x), EPS, TOL).unwrap();
+        let mixed = gradcheck(
+            |v| Add::new(Mul::new(v[0].clone(), v[1].clone()), Mul::new(v[0].clone(), v[0].clone())),
+            &[x.clone(), y],
+            EPS,
+            TOL
+        ).unwrap();
+        assert!(square.passed(), "{:?}", square);
+        assert!(mixed.passed(), "{:?}", mixed);
+
+        let x_value = Value::new(&x);
+        let mut node = Mul::new(x_value.clone(), x_value.clone());
+        node.forward();
+        node.backward(NDArray::fill(vec![2, 3], 1.0).unwrap());
+        assert_eq!(x_value.grad(), x.scalar_mult(2.0).unwrap());
+
+        /* without zeroing a second pass adds to the first */
+        node.backward(NDArray::fill(vec![2, 3], 1.0).unwrap());
+        assert_eq!(x_value.grad(), x.scalar_mult(4.0).unwrap());
+
+        node.zero_grad();
+        node.backward(NDArray::fill(vec![2, 3], 1.0).unwrap());
+        assert_eq!(x_value.grad(), x.scalar_mult(2.0).unwrap());
+
+    }
+
+    #[test]
+    fn test_gradcheck_unary_ops() {
+
+        let (x, _y) = inputs();
+        let positive = x.abs().unwrap().scalar_add(0.5).unwrap();
+        let x = [x];
+
+        let checks = vec![
+            gradcheck(|v| Pow::new(v[0].clone(), 3.0), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| Exp::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| Log::new(v[0].clone()), &[positive], EPS, TOL).unwrap(),
+            gradcheck(|v| Neg::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| Sum::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| Mean::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+        ];
+
+        for check in &checks {
+            assert!(check.passed(), "{:?}", check);
+        }
+
+    }
+
+    #[test]
+    fn test_gradcheck_activations() {
+
+        let (x, _y) = inputs();
+        let x = [x];
+
+        let checks = vec![
+            gradcheck(|v| Sigmoid::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| Tanh::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| ReLU::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| LeakyReLU::new(v[0].clone(), 0.01), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| Softmax::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+            gradcheck(|v| LogSoftmax::new(v[0].clone()), &x, EPS, TOL).unwrap(),
+        ];
+
+        for check in &checks {
+            assert!(check.passed(), "{:?}", check);
+        }
+
+    }
+
+    #[test]
+    fn test_gradcheck_losses() {
+
+        let (x, y) = inputs();
+        let probs = softmax_rows(&x);
+        let targets: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![0.0, 1.0, 0.0, 1.0, 0.0, 0.0]
+        ).unwrap();
+
+        let checks = vec![
+            gradcheck(|v| MSE::new(v[0].clone(), v[1].clone()), &[x.clone(), y.clone()], EPS, TOL).unwrap(),
+            gradcheck(|v| MAE::new(v[0].clone(), v[1].clone()), &[x.clone(), y.clone()], EPS, TOL).unwrap(),
+            gradcheck(|v| Huber::new(v[0].clone(), v[1].clone(), 1.0), &[x.clone(), y.clone()], EPS, TOL).unwrap(),
+            gradcheck(|v| BinaryCrossEntropy::new(v[0].clone(), v[1].clone()), &[probs.clone(), targets.clone()], EPS, TOL).unwrap(),
+            gradcheck(|v| BinaryCrossEntropyWithLogits::new(v[0].clone(), v[1].clone()), &[x.clone(), targets.clone()], EPS, TOL).unwrap(),
+            gradcheck(|v| CategoricalCrossEntropy::new(v[0].clone(), v[1].clone()), &[probs, targets.clone()], EPS, TOL).unwrap(),
+            gradcheck(|v| CategoricalCrossEntropyWithLogits::new(v[0].clone(), v[1].clone()), &[x, targets], EPS, TOL).unwrap(),
+        ];
+
+        for check in &checks {
+            assert!(check.passed(), "{:?}", check);
+        }
+
+    }
+
+    #[test]
+    fn test_gradcheck_composed_graph() {
+
+        let (x, y) = inputs();
+        let w: NDArray<f64> = NDArray::array(
+            vec![3, 2], vec![0.1, -0.3, 0.5, 0.2, -0.4, 0.6]
+        ).unwrap();
+        let b: NDArray<f64> = NDArray::array(
+            vec![1, 2], vec![0.05, -0.05]
+        ).unwrap();
+        let targets = y.axis_indices(1, vec![0, 1]).unwrap();
+
+        let check = gradcheck(
+            |v| MSE::new(
+                Tanh::new(Add::new(MatMul::new(v[0].clone(), v[1].clone()), v[2].clone())),
+                v[3].clone()
+            ),
+            &[x, w, b, targets], EPS, TOL
+        ).unwrap();
+
+        assert!(check.passed(), "{:?}", check);
+        assert!(check.max_error() < TOL);
+
+    }
+
+    #[test]
+    fn test_gradcheck_detects_wrong_backward() {
+
+        let (x, _y) = inputs();
+        let check = gradcheck(
+            |v| DoubleGrad { input: v[0].clone() },
+            &[x], EPS, TOL
+        ).unwrap();
+
+        assert!(!check.passed());
+        let mismatch = &check.mismatches[0];
+        assert!((mismatch.analytic - 2.0 * mismatch.numerical).abs() < 1e-6);
+
+    }
+
+    #[test]
+    fn test_gradcheck_invalid_epsilon() {
+
+        let (x, _y) = inputs();
+        let check = gradcheck(|v| Exp::new(v[0].clone()), &[x], 0.0, TOL);
+        assert!(check.is_err());
+
+    }
+
+}
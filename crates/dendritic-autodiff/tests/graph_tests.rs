@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod graph_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::graph::*;
+
+    #[test]
+    fn test_op_name() {
+
+        let x = Value::new(&NDArray::array(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]).unwrap());
+        let w = Value::new(&NDArray::array(vec![2, 1], vec![0.5, -0.5]).unwrap());
+
+        let node = MatMul::new(x.clone(), w.clone());
+        assert_eq!(node.op_name(), "MatMul");
+        assert_eq!(x.op_name(), "Value");
+        assert_eq!(node.inputs().len(), 2);
+        assert!(x.inputs().is_empty());
+
+        /* clones of a value share an id */
+        assert_eq!(x.node_id(), x.clone().node_id());
+        assert_ne!(x.node_id(), w.node_id());
+    }
+
+    #[test]
+    fn test_to_dot() {
+
+        let x = Value::new(&NDArray::array(vec![3, 2], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap());
+        let w = Value::new(&NDArray::array(vec![2, 1], vec![0.5, -0.5]).unwrap());
+        let b = Value::new(&NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+
+        let linear = ScaleAdd::new(Dot::new(x, w), b);
+        let dot = linear.to_dot();
+
+        assert!(dot.starts_with("digraph computation_graph {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert!(dot.contains("n0 [label=\"Value\\nshape [3, 2]\", shape=ellipse];"));
+        assert!(dot.contains("n2 [label=\"Dot\\nshape [3, 1]\", shape=box];"));
+        assert!(dot.contains("n4 [label=\"ScaleAdd\\nshape [3, 1]\", shape=box];"));
+        assert!(!dot.contains("grad norm"));
+
+        /* edges run from inputs to the op consuming them, in data flow order */
+        let edges: Vec<&str> = dot.lines().filter(|l| l.contains("->")).map(|l| l.trim()).collect();
+        assert_eq!(edges, vec!["n0 -> n2;", "n1 -> n2;", "n2 -> n4;", "n3 -> n4;"]);
+    }
+
+    #[test]
+    fn test_to_dot_shared_value_and_grads() {
+
+        /* x appears twice but is exported once */
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![3.0, 4.0]).unwrap());
+        let y = Value::new(&NDArray::array(vec![1, 2], vec![0.0, 0.0]).unwrap());
+        let mut loss = MSE::new(Mul::new(x.clone(), x.clone()), y);
+        loss.forward();
+        loss.backward(NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+
+        let dot = loss.to_dot_with_grads();
+        assert_eq!(dot.matches("label=\"Value").count(), 2);
+        assert_eq!(dot.matches("->").count(), 4);
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("MSE\\nshape [1, 1]\\ngrad norm 1.000000"));
+
+        /* dMSE/dy_pred = 2 (y_pred - y) / n = (9, 16) */
+        let norm = (81.0_f64 + 256.0).sqrt();
+        assert!(dot.contains(&format!("Mul\\nshape [1, 2]\\ngrad norm {:.6}", norm)));
+        assert_eq!(to_dot(&loss, true), dot);
+
+        let path = std::env::temp_dir().join("dendritic_graph_test.dot");
+        let path = path.to_str().unwrap();
+        loss.save_dot(path, false).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), loss.to_dot());
+        std::fs::remove_file(path).unwrap();
+    }
+
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod init_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::init::*;
+
+    fn mean(array: &NDArray<f64>) -> f64 {
+        array.values().iter().sum::<f64>() / array.size() as f64
+    }
+
+    fn std(array: &NDArray<f64>) -> f64 {
+        let m = mean(array);
+        let var = array.values().iter().map(|v| (v - m).powi(2)).sum::<f64>() / array.size() as f64;
+        var.sqrt()
+    }
+
+    #[test]
+    fn test_constant_init() {
+
+        let z = zeros(vec![2, 3]).unwrap();
+        assert_eq!(z.shape().values(), vec![2, 3]);
+        assert!(z.values().iter().all(|v| *v == 0.0));
+
+        let c = constant(vec![3], 0.5).unwrap();
+        assert_eq!(c.values(), &vec![0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_random_init() {
+
+        let u = uniform(vec![100, 50], -0.5, 2.0, 7).unwrap();
+        assert!(u.values().iter().all(|v| *v >= -0.5 && *v < 2.0));
+        assert!((mean(&u) - 0.75).abs() < 0.05);
+        assert!(uniform(vec![2, 2], 1.0, 1.0, 7).is_err());
+
+        let n = normal(vec![100, 50], 1.0, 3.0, 7).unwrap();
+        assert!((mean(&n) - 1.0).abs() < 0.15);
+        assert!((std(&n) - 3.0).abs() < 0.1);
+        assert!(normal(vec![2, 2], 0.0, -1.0, 7).is_err());
+
+        /* same seed gives the same values, different seeds do not */
+        assert_eq!(normal(vec![4, 4], 0.0, 1.0, 3).unwrap(), normal(vec![4, 4], 0.0, 1.0, 3).unwrap());
+        assert_ne!(normal(vec![4, 4], 0.0, 1.0, 3).unwrap(), normal(vec![4, 4], 0.0, 1.0, 4).unwrap());
+    }
+
+    #[test]
+    fn test_fan_scaled_init() {
+
+        /* (fan_in, fan_out) = (200, 100) */
+        let shape = vec![200, 100];
+
+        let xu = xavier_uniform(shape.clone(), 1).unwrap();
+        let limit = (6.0 / 300.0_f64).sqrt();
+        assert!(xu.values().iter().all(|v| v.abs() <= limit));
+        assert!((std(&xu) - limit / 3.0_f64.sqrt()).abs() < 0.005);
+
+        let xn = xavier_normal(shape.clone(), 1).unwrap();
+        assert!((std(&xn) - (2.0 / 300.0_f64).sqrt()).abs() < 0.005);
+
+        let hu = he_uniform(shape.clone(), 1).unwrap();
+        let limit = (6.0 / 200.0_f64).sqrt();
+        assert!(hu.values().iter().all(|v| v.abs() <= limit));
+
+        let hn = he_normal(shape.clone(), 1).unwrap();
+        assert!((std(&hn) - 0.1).abs() < 0.005);
+        assert!(he_normal(vec![0, 3], 1).is_err());
+    }
+
+    #[test]
+    fn test_orthogonal_init() {
+
+        for shape in [vec![6, 3], vec![3, 6], vec![4, 4]] {
+            let (rows, cols) = (shape[0], shape[1]);
+            let q = orthogonal(shape, 2.0, 11).unwrap();
+
+            /* Q^T Q = gain^2 I for tall matrices, Q Q^T = gain^2 I for wide ones */
+            let (count, length) = if rows >= cols { (cols, rows) } else { (rows, cols) };
+            let at = |k: usize, i: usize| {
+                if rows >= cols { q.values()[i * cols + k] } else { q.values()[k * cols + i] }
+            };
+            for a in 0..count {
+                for b in 0..count {
+                    let dot: f64 = (0..length).map(|i| at(a, i) * at(b, i)).sum();
+                    let expected = if a == b { 4.0 } else { 0.0 };
+                    assert!((dot - expected).abs() < 1e-10);
+                }
+            }
+        }
+
+        assert!(orthogonal(vec![4], 1.0, 11).is_err());
+    }
+
+    #[test]
+    fn test_initializer_stream() {
+
+        /* one seeded initializer gives reproducible but distinct parameters */
+        let mut first = Initializer::new(Init::HeNormal, 5);
+        let mut second = Initializer::new(Init::HeNormal, 5);
+        let a = first.sample(vec![3, 2]).unwrap();
+        let b = first.sample(vec![3, 2]).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a, second.sample(vec![3, 2]).unwrap());
+        assert_eq!(b, second.sample(vec![3, 2]).unwrap());
+        assert_eq!(first.init, Init::HeNormal);
+
+        let r = Initializer::random(Init::XavierUniform).sample(vec![2, 2]).unwrap();
+        assert_eq!(r.shape().values(), vec![2, 2]);
+    }
+
+}
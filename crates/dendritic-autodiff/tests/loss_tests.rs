@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod loss_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*; 
+    use dendritic_autodiff::activations::*;
+    use dendritic_autodiff::loss::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-9, "{} != {}", a, e);
+        }
+    }
+
+    #[test]
+    fn test_mse_node() {
+
+        let y_pred: NDArray<f64> = NDArray::array(
+            vec![4, 1], vec![1.0, 2.0, 3.0, 4.0]
+        ).unwrap();
+
+        let y_true: NDArray<f64> = NDArray::array(
+            vec![4, 1], vec![1.0, 1.0, 5.0, 4.0]
+        ).unwrap();
+
+        let pred_value = Value::new(&y_pred);
+        let true_value = Value::new(&y_true);
+
+        let mut loss = MSE::new(pred_value.clone(), true_value.clone());
+        loss.forward();
+
+        assert_eq!(loss.value().shape().values(), vec![1, 1]);
+        assert_eq!(loss.loss(), 1.25);
+
+        loss.backprop();
+        assert_eq!(pred_value.grad().values(), &vec![0.0, 0.5, -1.0, 0.0]);
+        assert_eq!(true_value.grad().values(), &vec![0.0, -0.5, 1.0, 0.0]);
+
+    }
+
+    #[test]
+    fn test_mae_and_huber_nodes() {
+
+        let y_pred: NDArray<f64> = NDArray::array(
+            vec![3, 1], vec![0.5, 3.0, -4.0]
+        ).unwrap();
+
+        let y_true: NDArray<f64> = NDArray::fill(vec![3, 1], 0.0).unwrap();
+        let pred_value = Value::new(&y_pred);
+        let true_value = Value::new(&y_true);
+
+        let mut mae = MAE::new(pred_value.clone(), true_value.clone());
+        mae.forward();
+        mae.backprop();
+        assert_eq!(mae.loss(), 2.5);
+        assert_close(pred_value.grad().values(), &[1.0/3.0, 1.0/3.0, -1.0/3.0]);
+
+        /* residuals under delta are quadratic, the rest linear */
+        let mut huber = Huber::new(pred_value.clone(), true_value.clone(), 1.0);
+        huber.forward();
+        huber.zero_grad();
+        huber.backprop();
+        assert_close(&[huber.loss()], &[(0.125 + 2.5 + 3.5) / 3.0]);
+        assert_close(pred_value.grad().values(), &[0.5/3.0, 1.0/3.0, -1.0/3.0]);
+
+    }
+
+    #[test]
+    fn test_binary_cross_entropy_nodes() {
+
+        let logits: NDArray<f64> = NDArray::array(
+            vec![3, 1], vec![-1.0, 0.5, 2.0]
+        ).unwrap();
+
+        let y_true: NDArray<f64> = NDArray::array(
+            vec![3, 1], vec![0.0, 1.0, 1.0]
+        ).unwrap();
+
+        /* sigmoid followed by bce */
+        let logits_value = Value::new(&logits);
+        let mut bce = BinaryCrossEntropy::new(
+            Sigmoid::new(logits_value.clone()),
+            Value::new(&y_true)
+        );
+        bce.forward();
+        bce.backprop();
+        let composed_loss = bce.loss();
+        let composed_grad = logits_value.grad();
+
+        /* fused version on logits */
+        let fused_value = Value::new(&logits);
+        let mut bce_logits = BinaryCrossEntropyWithLogits::new(
+            fused_value.clone(),
+            Value::new(&y_true)
+        );
+        bce_logits.forward();
+        bce_logits.backprop();
+
+        let expected_grad: Vec<f64> = logits.values().iter()
+            .zip(y_true.values().iter())
+            .map(|(z, t)| (1.0 / (1.0 + (-z).exp()) - t) / 3.0)
+            .collect();
+
+        assert_close(&[bce_logits.loss()], &[composed_loss]);
+        assert_close(fused_value.grad().values(), &expected_grad);
+        assert_close(composed_grad.values(), &expected_grad);
+
+    }
+
+    #[test]
+    fn test_bce_logits_large_values() {
+
+        let logits: NDArray<f64> = NDArray::array(
+            vec![2, 1], vec![-800.0, 800.0]
+        ).unwrap();
+
+        let y_true: NDArray<f64> = NDArray::array(
+            vec![2, 1], vec![0.0, 1.0]
+        ).unwrap();
+
+        let mut loss = BinaryCrossEntropyWithLogits::new(
+            Value::new(&logits),
+            Value::new(&y_true)
+        );
+        loss.forward();
+        assert_eq!(loss.loss(), 0.0);
+        assert!(loss.loss().is_finite());
+
+    }
+
+    #[test]
+    fn test_categorical_cross_entropy_nodes() {
+
+        let logits: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![1.0, 2.0, 0.5, 0.1, 0.2, 3.0]
+        ).unwrap();
+
+        let y_true: NDArray<f64> = NDArray::array(
+            vec![2, 3], vec![0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+        ).unwrap();
+
+        /* softmax followed by cce */
+        let logits_value = Value::new(&logits);
+        let mut cce = CategoricalCrossEntropy::new(
+            Softmax::new(logits_value.clone()),
+            Value::new(&y_true)
+        );
+        cce.forward();
+        cce.backprop();
+
+        /* fused version on logits */
+        let fused_value = Value::new(&logits);
+        let mut cce_logits = CategoricalCrossEntropyWithLogits::new(
+            fused_value.clone(),
+            Value::new(&y_true)
+        );
+        cce_logits.forward();
+        cce_logits.backprop();
+
+        let probs = softmax_rows(&logits);
+        let expected_loss = -(probs.values()[1].ln() + probs.values()[5].ln()) / 2.0;
+        let expected_grad: Vec<f64> = probs.values().iter()
+            .zip(y_true.values().iter())
+            .map(|(p, t)| (p - t) / 2.0)
+            .collect();
+
+        assert_close(&[cce.loss()], &[expected_loss]);
+        assert_close(&[cce_logits.loss()], &[expected_loss]);
+        assert_close(logits_value.grad().values(), &expected_grad);
+        assert_close(fused_value.grad().values(), &expected_grad);
+
+    }
+
+}
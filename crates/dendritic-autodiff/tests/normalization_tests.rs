@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod normalization_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::normalization::*;
+    use dendritic_autodiff::gradcheck::*;
+
+    const EPS: f64 = 1e-6;
+    const TOL: f64 = 1e-5;
+
+    fn sample(shape: Vec<usize>) -> NDArray<f64> {
+        let size: usize = shape.iter().product();
+        let values: Vec<f64> = (0..size)
+            .map(|i| ((i * 7 + 3) % 11) as f64 * 0.3 - 1.2)
+            .collect();
+        NDArray::array(shape, values).unwrap()
+    }
+
+    #[test]
+    fn test_dropout() {
+
+        let x = NDArray::array(vec![4, 50], vec![1.0; 200]).unwrap();
+        let mut dropout = Dropout::new(Value::new(&x), 0.25, 42);
+        assert!(dropout.is_training());
+
+        /* kept elements are scaled by 1 / (1 - p) */
+        let output = dropout.value();
+        let kept = output.values().iter().filter(|v| **v != 0.0).count();
+        assert!(output.values().iter().all(|v| *v == 0.0 || (v - 1.0 / 0.75).abs() < 1e-12));
+        assert!(kept > 120 && kept < 180);
+
+        /* gradient only flows through kept elements */
+        let upstream = NDArray::array(vec![4, 50], vec![1.0; 200]).unwrap();
+        dropout.backward(upstream);
+        let mask = dropout.mask();
+        assert_eq!(dropout.input().grad().values(), mask.values());
+
+        /* same seed gives same mask, forward draws a new mask */
+        let same = Dropout::new(Value::new(&x), 0.25, 42);
+        assert_eq!(same.value().values(), output.values());
+        dropout.forward();
+        assert_ne!(dropout.value().values(), output.values());
+
+        /* evaluation mode is the identity */
+        dropout.eval();
+        dropout.forward();
+        assert_eq!(dropout.value().values(), x.values());
+    }
+
+    #[test]
+    #[should_panic(expected = "must be in [0, 1)")]
+    fn test_dropout_invalid_probability() {
+        let x = NDArray::array(vec![1, 1], vec![1.0]).unwrap();
+        Dropout::new(Value::new(&x), 1.0, 0);
+    }
+
+    #[test]
+    fn test_batch_norm_forward() {
+
+        let x = sample(vec![6, 3]);
+        let gamma = NDArray::array(vec![1, 3], vec![1.0, 1.0, 1.0]).unwrap();
+        let beta = NDArray::array(vec![1, 3], vec![0.0, 0.0, 0.0]).unwrap();
+        let mut bn = BatchNorm::new(Value::new(&x), Value::new(&gamma), Value::new(&beta));
+        bn.forward();
+
+        /* every channel has zero mean and unit variance */
+        let output = bn.value();
+        for c in 0..3 {
+            let column: Vec<f64> = (0..6).map(|r| output.values()[r * 3 + c]).collect();
+            let mean = column.iter().sum::<f64>() / 6.0;
+            let var = column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 6.0;
+            assert!(mean.abs() < 1e-9);
+            assert!((var - 1.0).abs() < 1e-3);
+        }
+
+        /* running statistics move towards the batch statistics */
+        let column: Vec<f64> = (0..6).map(|r| x.values()[r * 3]).collect();
+        let mean = column.iter().sum::<f64>() / 6.0;
+        let var = column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 5.0;
+        assert!((bn.running_mean().values()[0] - 0.1 * mean).abs() < 1e-12);
+        assert!((bn.running_var().values()[0] - (0.9 + 0.1 * var)).abs() < 1e-12);
+
+        /* evaluation mode uses running statistics and leaves them untouched */
+        bn.eval();
+        bn.forward();
+        let running_mean = bn.running_mean();
+        let expected = (x.values()[0] - running_mean.values()[0])
+            / (bn.running_var().values()[0] + 1e-5).sqrt();
+        assert!((bn.value().values()[0] - expected).abs() < 1e-12);
+        bn.forward();
+        assert_eq!(bn.running_mean().values(), running_mean.values());
+    }
+
+    #[test]
+    fn test_batch_norm_gradcheck() {
+
+        let gamma = NDArray::array(vec![1, 3], vec![0.5, 1.5, -1.0]).unwrap();
+        let beta = NDArray::array(vec![1, 3], vec![0.1, -0.2, 0.3]).unwrap();
+
+        let result = gradcheck(
+            |v| BatchNorm::new(v[0].clone(), v[1].clone(), v[2].clone()),
+            &[sample(vec![5, 3]), gamma.clone(), beta.clone()],
+            EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+
+        /* (N, C, H, W) input normalizes each channel over batch and spatial axes */
+        let result = gradcheck(
+            |v| BatchNorm::new(v[0].clone(), v[1].clone(), v[2].clone()),
+            &[sample(vec![2, 3, 2, 2]), gamma, beta],
+            EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    fn test_batch_norm_eval_gradcheck() {
+
+        let gamma = NDArray::array(vec![1, 2], vec![2.0, 0.5]).unwrap();
+        let beta = NDArray::array(vec![1, 2], vec![0.0, 1.0]).unwrap();
+
+        let result = gradcheck(
+            |v| {
+                let mut bn = BatchNorm::new(v[0].clone(), v[1].clone(), v[2].clone());
+                bn.eval();
+                bn
+            },
+            &[sample(vec![4, 2]), gamma, beta],
+            EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    fn test_layer_norm() {
+
+        let x = sample(vec![3, 4]);
+        let gamma = NDArray::array(vec![1, 4], vec![1.0; 4]).unwrap();
+        let beta = NDArray::array(vec![1, 4], vec![0.0; 4]).unwrap();
+        let ln = LayerNorm::new(Value::new(&x), Value::new(&gamma), Value::new(&beta));
+
+        for row in ln.value().values().chunks(4) {
+            let mean = row.iter().sum::<f64>() / 4.0;
+            let var = row.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 4.0;
+            assert!(mean.abs() < 1e-9);
+            assert!((var - 1.0).abs() < 1e-3);
+        }
+
+        let gamma = NDArray::array(vec![1, 4], vec![0.5, 1.5, -1.0, 2.0]).unwrap();
+        let beta = NDArray::array(vec![1, 4], vec![0.1, -0.2, 0.3, 0.0]).unwrap();
+        let result = gradcheck(
+            |v| LayerNorm::new(v[0].clone(), v[1].clone(), v[2].clone()),
+            &[x, gamma, beta],
+            EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    #[should_panic(expected = "gamma and beta have")]
+    fn test_layer_norm_shape_mismatch() {
+
+        let x = sample(vec![2, 4]);
+        let gamma = NDArray::array(vec![1, 3], vec![1.0; 3]).unwrap();
+        let beta = NDArray::array(vec![1, 3], vec![0.0; 3]).unwrap();
+        LayerNorm::new(Value::new(&x), Value::new(&gamma), Value::new(&beta));
+    }
+
+}
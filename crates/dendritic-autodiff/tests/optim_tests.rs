@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod optim_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::optim::*;
+
+    /// Minimize f(p) = sum((p - target)^2) and return final parameter values
+    fn minimize<O: Optimizer>(optimizer: &mut O, steps: usize) -> Vec<f64> {
+
+        let target = [3.0, -2.0];
+        let init = NDArray::array(vec![2, 1], vec![0.0, 0.0]).unwrap();
+        let mut param = Value::new(&init);
+
+        for _ in 0..steps {
+            let grad: Vec<f64> = param.val().values().iter()
+                .zip(target.iter())
+                .map(|(p, t)| 2.0 * (p - t))
+                .collect();
+            param.set_grad(&NDArray::array(vec![2, 1], grad).unwrap());
+            optimizer.step(&mut [param.clone()]);
+        }
+
+        param.val().values().to_vec()
+    }
+
+    fn assert_converged(values: &[f64], tol: f64) {
+        assert!((values[0] - 3.0).abs() < tol, "got {:?}", values);
+        assert!((values[1] + 2.0).abs() < tol, "got {:?}", values);
+    }
+
+    #[test]
+    fn test_sgd_step() {
+
+        let mut optimizer = SGD::new(0.1);
+        let init = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let grad = NDArray::array(vec![2, 1], vec![0.5, -1.0]).unwrap();
+        let mut param = Value::new(&init);
+        param.set_grad(&grad);
+
+        optimizer.step(&mut [param.clone()]);
+        assert_eq!(param.val().values(), &vec![0.95, 2.1]);
+
+        let values = minimize(&mut SGD::new(0.1), 100);
+        assert_converged(&values, 1e-6);
+    }
+
+    #[test]
+    fn test_sgd_momentum() {
+
+        let mut optimizer = SGD::with_momentum(0.1, 0.9, false);
+        let init = NDArray::array(vec![1, 1], vec![1.0]).unwrap();
+        let grad = NDArray::array(vec![1, 1], vec![1.0]).unwrap();
+        let mut param = Value::new(&init);
+
+        param.set_grad(&grad);
+        optimizer.step(&mut [param.clone()]);
+        assert!((param.val().values()[0] - 0.9).abs() < 1e-12);
+
+        /* velocity carries over: v = 0.9 * 1 + 1 */
+        param.set_grad(&grad);
+        optimizer.step(&mut [param.clone()]);
+        assert!((param.val().values()[0] - 0.71).abs() < 1e-12);
+
+        let values = minimize(&mut SGD::with_momentum(0.05, 0.9, false), 300);
+        assert_converged(&values, 1e-4);
+
+        let values = minimize(&mut SGD::with_momentum(0.05, 0.9, true), 300);
+        assert_converged(&values, 1e-4);
+    }
+
+    #[test]
+    fn test_adam() {
+
+        /* first adam step moves each parameter by the learning rate */
+        let mut optimizer = Adam::new(0.1);
+        let init = NDArray::array(vec![2, 1], vec![1.0, 1.0]).unwrap();
+        let grad = NDArray::array(vec![2, 1], vec![4.0, -0.5]).unwrap();
+        let mut param = Value::new(&init);
+        param.set_grad(&grad);
+        optimizer.step(&mut [param.clone()]);
+
+        let values = param.val().values().to_vec();
+        assert!((values[0] - 0.9).abs() < 1e-6);
+        assert!((values[1] - 1.1).abs() < 1e-6);
+
+        let values = minimize(&mut Adam::new(0.1), 1000);
+        assert_converged(&values, 1e-3);
+    }
+
+    #[test]
+    fn test_adamw_weight_decay() {
+
+        /* zero gradient, only decoupled weight decay moves the parameter */
+        let mut optimizer = AdamW::new(0.1, 0.5);
+        let init = NDArray::array(vec![1, 1], vec![2.0]).unwrap();
+        let grad = NDArray::new(vec![1, 1]).unwrap();
+        let mut param = Value::new(&init);
+        param.set_grad(&grad);
+        optimizer.step(&mut [param.clone()]);
+        assert!((param.val().values()[0] - 1.9).abs() < 1e-9);
+
+        /* decay pulls minimum towards zero */
+        let values = minimize(&mut AdamW::new(0.05, 0.1), 2000);
+        assert!(values[0] < 3.0 && values[0] > 2.0);
+        assert!(values[1] > -2.0 && values[1] < -1.0);
+    }
+
+    #[test]
+    fn test_rmsprop_adagrad() {
+
+        let values = minimize(&mut RMSProp::new(0.01), 2000);
+        assert_converged(&values, 1e-2);
+
+        let values = minimize(&mut Adagrad::new(0.5), 2000);
+        assert_converged(&values, 1e-3);
+    }
+
+    #[test]
+    fn test_learning_rate_access() {
+
+        let mut optimizer: Box<dyn Optimizer> = Box::new(Adam::new(0.01));
+        assert_eq!(optimizer.learning_rate(), 0.01);
+        optimizer.set_learning_rate(0.001);
+        assert_eq!(optimizer.learning_rate(), 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match parameter shape")]
+    fn test_gradient_shape_mismatch() {
+
+        let mut optimizer = SGD::new(0.1);
+        let init = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let grad = NDArray::array(vec![1, 1], vec![1.0]).unwrap();
+        let mut param = Value::new(&init);
+        param.set_grad(&grad);
+        optimizer.step(&mut [param]);
+    }
+
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod penalty_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::regularizers::*;
+    use dendritic_autodiff::gradcheck::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_autodiff::penalty::*;
+
+    fn weights() -> NDArray<f64> {
+        NDArray::array(vec![2, 2], vec![0.5, -1.5, 0.0, 2.0]).unwrap()
+    }
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-6)
+    }
+
+    /// Central difference gradient of a penalty
+    fn numerical<P: Penalty>(penalty: &P, w: &NDArray<f64>) -> Vec<f64> {
+        (0..w.size()).map(|idx| {
+            let mut plus = w.clone();
+            let mut minus = w.clone();
+            plus.set_idx(idx, w.values()[idx] + 1e-6).unwrap();
+            minus.set_idx(idx, w.values()[idx] - 1e-6).unwrap();
+            (penalty.penalty(&plus) - penalty.penalty(&minus)) / 2e-6
+        }).collect()
+    }
+
+    #[test]
+    fn test_regularization_gradcheck() {
+
+        /* weights away from zero so L1 is differentiable */
+        let w = NDArray::array(vec![3, 1], vec![0.5, -1.5, 2.0]).unwrap();
+        let lambda = NDArray::array(vec![1, 1], vec![0.3]).unwrap();
+        let inputs = [w, lambda];
+
+        let l2 = gradcheck(|v| L2Regularization::new(v[0].clone(), v[1].clone()), &inputs, 1e-6, 1e-6).unwrap();
+        let l1 = gradcheck(|v| L1Regularization::new(v[0].clone(), v[1].clone()), &inputs, 1e-6, 1e-6).unwrap();
+        let reg = gradcheck(|v| Regularization::new(v[0].clone(), v[1].clone()), &inputs, 1e-6, 1e-6).unwrap();
+        assert!(l2.passed(), "{:?}", l2);
+        assert!(l1.passed(), "{:?}", l1);
+        assert!(reg.passed(), "{:?}", reg);
+    }
+
+    #[test]
+    fn test_penalties() {
+
+        let w = weights();
+
+        let l1 = L1::new(0.5);
+        assert_eq!(l1.penalty(&w), 2.0);
+        assert_eq!(l1.gradient(&w).values(), &vec![0.5, -0.5, 0.0, 0.5]);
+
+        let l2 = L2::new(0.5);
+        assert_eq!(l2.penalty(&w), 3.25);
+        assert_eq!(l2.gradient(&w).values(), &vec![0.5, -1.5, 0.0, 2.0]);
+
+        /* elastic net interpolates between L1 and L2 */
+        let enet = ElasticNet::new(2.0, 0.25).unwrap();
+        assert!((enet.penalty(&w) - (L1::new(0.5).penalty(&w) + L2::new(1.5).penalty(&w))).abs() < 1e-12);
+        assert!(ElasticNet::new(1.0, 1.5).is_err());
+
+        let combined = L1::new(0.5).and(L2::new(0.5));
+        assert_eq!(combined.penalty(&w), 5.25);
+        assert_eq!(combined.gradient(&w).values(), &vec![1.0, -2.0, 0.0, 2.5]);
+
+        let smooth = NDArray::array(vec![2, 2], vec![0.5, -1.5, 0.7, 2.0]).unwrap();
+        assert!(close(enet.gradient(&smooth).values(), &numerical(&enet, &smooth)));
+        assert!(close(combined.gradient(&smooth).values(), &numerical(&combined, &smooth)));
+    }
+
+    #[test]
+    fn test_group_lasso() {
+
+        /* rows are groups: (0.5, -1.5) and (0, 2) */
+        let w = weights();
+        let group = GroupLasso::by_rows(0.1, 2, 2);
+        assert_eq!(group.groups, vec![vec![0, 1], vec![2, 3]]);
+
+        let expected = 0.1 * 2.0_f64.sqrt() * (2.5_f64.sqrt() + 2.0);
+        assert!((group.penalty(&w) - expected).abs() < 1e-12);
+        assert!(close(group.gradient(&w).values(), &numerical(&group, &w)));
+
+        /* groups at zero get a zero subgradient */
+        let zero = NDArray::array(vec![2, 2], vec![0.0, 0.0, 1.0, 0.0]).unwrap();
+        let grad = group.gradient(&zero);
+        assert_eq!(&grad.values()[..2], &[0.0, 0.0]);
+        assert!((grad.values()[2] - 0.1 * 2.0_f64.sqrt()).abs() < 1e-12);
+
+        let custom = GroupLasso::new(1.0, vec![vec![1, 3]]);
+        assert!((custom.penalty(&w) - 2.0_f64.sqrt() * 2.5).abs() < 1e-12);
+        assert_eq!(custom.gradient(&w).values()[0], 0.0);
+    }
+
+    #[test]
+    fn test_penalty_apply() {
+
+        /* data gradient from a graph plus the penalty gradient */
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        let mut w = Value::new(&NDArray::array(vec![2, 1], vec![1.0, -1.0]).unwrap());
+        let mut node = MatMul::new(x, w.clone());
+        node.backward(NDArray::fill(vec![1, 1], 1.0).unwrap());
+        assert_eq!(w.grad().values(), &vec![1.0, 2.0]);
+
+        let value = L2::new(0.5).apply(&mut w);
+        assert_eq!(value, 1.0);
+        assert_eq!(w.grad().values(), &vec![2.0, 1.0]);
+        assert_eq!(w.val().values(), &vec![1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_penalized_optimizer() {
+
+        /* penalty gradients are independent of the learning rate */
+        let mut w = Value::new(&NDArray::array(vec![2, 1], vec![1.0, -2.0]).unwrap());
+        let mut b = Value::new(&NDArray::array(vec![1, 1], vec![3.0]).unwrap());
+        w.set_grad(&NDArray::new(vec![2, 1]).unwrap());
+        b.set_grad(&NDArray::new(vec![1, 1]).unwrap());
+
+        let mut optimizer = Penalized::new(SGD::new(0.1)).attach(0, L1::new(1.0).and(L2::new(0.5)));
+        let mut params = [w.clone(), b.clone()];
+        optimizer.step(&mut params);
+
+        assert!((optimizer.last_penalty() - 5.5).abs() < 1e-12);
+        assert!(close(w.val().values(), &[0.8, -1.7]));
+        assert_eq!(b.val().values(), &vec![3.0]);
+
+        optimizer.set_learning_rate(0.01);
+        assert_eq!(optimizer.learning_rate(), 0.01);
+        assert_eq!(optimizer.optimizer.learning_rate(), 0.01);
+    }
+
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod scheduler_tests {
+
+    use dendritic_autodiff::optim::*;
+    use dendritic_autodiff::scheduler::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    /// Learning rates for the first `epochs` epochs, including the initial one
+    fn schedule<S: Scheduler>(scheduler: &mut S, epochs: usize) -> Vec<f64> {
+        let mut rates = vec![scheduler.learning_rate()];
+        for _ in 1..epochs {
+            rates.push(scheduler.step(0.0));
+        }
+        rates
+    }
+
+    #[test]
+    fn test_step_lr() {
+
+        let mut scheduler = StepLR::new(0.1, 2, 0.5);
+        let rates = schedule(&mut scheduler, 6);
+        let expected = [0.1, 0.1, 0.05, 0.05, 0.025, 0.025];
+        for (rate, exp) in rates.iter().zip(expected.iter()) {
+            assert!(close(*rate, *exp), "{:?}", rates);
+        }
+    }
+
+    #[test]
+    fn test_exponential_lr() {
+
+        let mut scheduler = ExponentialLR::new(1.0, 0.9);
+        let rates = schedule(&mut scheduler, 4);
+        let expected = [1.0, 0.9, 0.81, 0.729];
+        for (rate, exp) in rates.iter().zip(expected.iter()) {
+            assert!(close(*rate, *exp), "{:?}", rates);
+        }
+    }
+
+    #[test]
+    fn test_cosine_annealing() {
+
+        let mut scheduler = CosineAnnealing::new(1.0, 0.0, 4);
+        let rates = schedule(&mut scheduler, 7);
+        assert!(close(rates[0], 1.0));
+        assert!(close(rates[2], 0.5));
+        assert!(close(rates[4], 0.0));
+        assert!(close(rates[6], 0.0));
+        for window in rates[..5].windows(2) {
+            assert!(window[1] < window[0]);
+        }
+    }
+
+    #[test]
+    fn test_cosine_warm_restarts() {
+
+        let mut scheduler = CosineAnnealing::with_restarts(1.0, 0.1, 2, 2);
+        let rates = schedule(&mut scheduler, 8);
+
+        /* first cycle has 2 epochs, second cycle 4 epochs */
+        assert!(close(rates[0], 1.0));
+        assert!(close(rates[1], 0.55));
+        assert!(close(rates[2], 1.0));
+        assert!(close(rates[4], 0.55));
+        assert!(close(rates[6], 1.0));
+    }
+
+    #[test]
+    fn test_linear_warmup() {
+
+        let mut scheduler = LinearWarmup::new(0.4, 4);
+        let rates = schedule(&mut scheduler, 6);
+        let expected = [0.1, 0.2, 0.3, 0.4, 0.4, 0.4];
+        for (rate, exp) in rates.iter().zip(expected.iter()) {
+            assert!(close(*rate, *exp), "{:?}", rates);
+        }
+
+        /* hand off to step decay after warmup */
+        let mut scheduler = LinearWarmup::with_scheduler(2, StepLR::new(0.2, 1, 0.5));
+        let rates = schedule(&mut scheduler, 5);
+        let expected = [0.1, 0.2, 0.2, 0.1, 0.05];
+        for (rate, exp) in rates.iter().zip(expected.iter()) {
+            assert!(close(*rate, *exp), "{:?}", rates);
+        }
+    }
+
+    #[test]
+    fn test_reduce_on_plateau() {
+
+        let mut scheduler = ReduceLROnPlateau::new(0.1, 0.5, 1);
+        assert!(close(scheduler.step(1.0), 0.1));
+        assert!(close(scheduler.step(0.5), 0.1));
+        assert!(close(scheduler.step(0.5), 0.1));
+        assert!(close(scheduler.step(0.6), 0.05));
+        assert_eq!(scheduler.best(), 0.5);
+
+        /* improvement resets patience */
+        assert!(close(scheduler.step(0.4), 0.05));
+        assert!(close(scheduler.step(0.4), 0.05));
+        assert!(close(scheduler.step(0.4), 0.025));
+
+        scheduler.min_learning_rate = 0.02;
+        scheduler.step(0.4);
+        assert!(close(scheduler.step(0.4), 0.02));
+    }
+
+    #[test]
+    fn test_step_optimizer() {
+
+        let mut optimizer = SGD::new(0.1);
+        let mut scheduler = StepLR::new(0.1, 1, 0.1);
+        scheduler.step_optimizer(&mut optimizer, 0.0);
+        assert!(close(optimizer.learning_rate(), 0.01));
+
+        let mut optimizer: Box<dyn Optimizer> = Box::new(Adam::new(0.1));
+        scheduler.step_optimizer(optimizer.as_mut(), 0.0);
+        assert!(close(optimizer.learning_rate(), 0.001));
+    }
+
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod state_tests {
+
+    use std::fs;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_ndarray::ops::*;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::state::*;
+
+    struct Affine {
+        weights: Value<NDArray<f64>>,
+        bias: Value<NDArray<f64>>
+    }
+
+    impl Affine {
+        fn new(weights: Vec<f64>, bias: f64) -> Affine {
+            Affine {
+                weights: Value::new(&NDArray::array(vec![2, 1], weights).unwrap()),
+                bias: Value::new(&NDArray::array(vec![1, 1], vec![bias]).unwrap())
+            }
+        }
+    }
+
+    impl Parameters for Affine {
+        fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+            vec![
+                ("weights".to_string(), self.weights.clone()),
+                ("bias".to_string(), self.bias.clone())
+            ]
+        }
+    }
+
+    fn temp_dir(name: &str) -> String {
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        dir.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_state_dict() {
+
+        let model = Affine::new(vec![1.0, 2.0], 3.0);
+        let mut state = model.state_dict();
+        assert_eq!(state.version, STATE_DICT_VERSION);
+        assert_eq!(state.names(), vec!["weights".to_string(), "bias".to_string()]);
+        assert_eq!(state.get("bias").unwrap().values(), &vec![3.0]);
+        assert!(state.get("missing").is_none());
+
+        /* state dict holds copies, not the live parameters */
+        state.insert("bias", NDArray::array(vec![1, 1], vec![5.0]).unwrap());
+        assert_eq!(state.parameters.len(), 2);
+        assert_eq!(model.bias.val().values(), &vec![3.0]);
+
+        let mut other = Affine::new(vec![0.0, 0.0], 0.0);
+        let shared = other.weights.clone();
+        other.load_state_dict(&state).unwrap();
+        assert_eq!(other.bias.val().values(), &vec![5.0]);
+        assert_eq!(shared.val().values(), &vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_load_state_dict_validation() {
+
+        let mut model = Affine::new(vec![1.0, 2.0], 3.0);
+
+        let mut wrong_shape = model.state_dict();
+        wrong_shape.insert("weights", NDArray::array(vec![1, 2], vec![7.0, 8.0]).unwrap());
+        wrong_shape.insert("bias", NDArray::array(vec![1, 1], vec![9.0]).unwrap());
+        let err = model.load_state_dict(&wrong_shape).unwrap_err();
+        assert_eq!(err, "StateDict: Shape mismatch for weights, expected [2, 1] got [1, 2]");
+
+        /* nothing is written when validation fails */
+        assert_eq!(model.bias.val().values(), &vec![3.0]);
+
+        let mut missing = StateDict::new();
+        missing.insert("weights", model.weights.val());
+        let err = model.load_state_dict(&missing).unwrap_err();
+        assert_eq!(err, "StateDict: Missing parameter bias");
+
+        let mut extra = model.state_dict();
+        extra.insert("scale", NDArray::array(vec![1, 1], vec![1.0]).unwrap());
+        let err = model.load_state_dict(&extra).unwrap_err();
+        assert_eq!(err, "StateDict: Unexpected parameter scale");
+    }
+
+    #[test]
+    fn test_state_dict_save_load() {
+
+        let dir = temp_dir("dendritic_state_test");
+        let model = Affine::new(vec![0.5, -1.5], 0.25);
+        model.save_state_dict(&dir).unwrap();
+
+        let mut loaded = Affine::new(vec![0.0, 0.0], 0.0);
+        loaded.load_state_dict_from(&dir).unwrap();
+        assert_eq!(loaded.state_dict(), model.state_dict());
+
+        /* unknown format versions are rejected */
+        let path = format!("{}/state_dict", dir);
+        let mut state = StateDict::load(&path).unwrap();
+        state.version = STATE_DICT_VERSION + 1;
+        state.save(&path).unwrap();
+        let err = loaded.load_state_dict_from(&dir).unwrap_err();
+        assert!(err.to_string().contains("StateDict: Unsupported version"), "{}", err);
+
+        /* shape errors surface as invalid data */
+        let wide = Affine {
+            weights: Value::new(&NDArray::array(vec![1, 3], vec![1.0, 2.0, 3.0]).unwrap()),
+            bias: Value::new(&NDArray::array(vec![1, 1], vec![0.0]).unwrap())
+        };
+        wide.save_state_dict(&dir).unwrap();
+        let err = loaded.load_state_dict_from(&dir).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_load_legacy_directory() {
+
+        /* older saves wrote one array file per parameter */
+        let dir = temp_dir("dendritic_state_legacy_test");
+        fs::create_dir_all(&dir).unwrap();
+        NDArray::array(vec![2, 1], vec![4.0, 5.0]).unwrap().save(&format!("{}/weights", dir)).unwrap();
+        NDArray::array(vec![1, 1], vec![6.0]).unwrap().save(&format!("{}/bias", dir)).unwrap();
+
+        let mut model = Affine::new(vec![0.0, 0.0], 0.0);
+        model.load_state_dict_from(&dir).unwrap();
+        assert_eq!(model.weights.val().values(), &vec![4.0, 5.0]);
+        assert_eq!(model.bias.val().values(), &vec![6.0]);
+
+        let empty = temp_dir("dendritic_state_empty_test");
+        assert!(model.load_state_dict_from(&empty).is_err());
+    }
+
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod sync_tests {
+
+    use std::thread;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::ops::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_autodiff::sync::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn data() -> (NDArray<f64>, NDArray<f64>) {
+        let x: Vec<f64> = (0..20).map(|i| ((i * 7 + 3) % 11) as f64 * 0.2 - 1.0).collect();
+        let y: Vec<f64> = x.chunks(2).map(|r| 2.0 * r[0] - 3.0 * r[1] + 0.5).collect();
+        (
+            NDArray::array(vec![10, 2], x).unwrap(),
+            NDArray::array(vec![10, 1], y).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_sync_value() {
+
+        assert_send_sync::<SyncValue<NDArray<f64>>>();
+
+        let value = SyncValue::new(&NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        let shared = value.clone();
+        assert_eq!(value.node_id(), shared.node_id());
+
+        /* writes from another thread are visible through every clone */
+        let handle = thread::spawn(move || {
+            shared.set_val(&NDArray::array(vec![1, 2], vec![3.0, 4.0]).unwrap());
+            shared.set_grad(&NDArray::array(vec![1, 2], vec![0.5, 0.5]).unwrap());
+        });
+        handle.join().unwrap();
+        assert_eq!(value.val().values(), &vec![3.0, 4.0]);
+        assert_eq!(value.grad().values(), &vec![0.5, 0.5]);
+
+        let snapshot = value.to_value();
+        assert_eq!(snapshot.val(), value.val());
+        assert_eq!(snapshot.grad(), value.grad());
+        assert_eq!(SyncValue::from_value(&snapshot).val(), value.val());
+
+        /* sync values work as graph leaves, set_grad overwrites and backward passes add */
+        let x = Value::new(&NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap());
+        let mut node = Add::new(x, value.clone());
+        node.backward(NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        assert_eq!(value.grad().values(), &vec![1.0, 2.0]);
+        node.backward(NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        assert_eq!(value.grad().values(), &vec![2.0, 4.0]);
+
+        node.zero_grad();
+        node.backward(NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap());
+        assert_eq!(value.grad().values(), &vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_shards() {
+
+        let (x, y) = data();
+        let parts = shards(&x, &y, 3).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].0.shape().values(), vec![4, 2]);
+        assert_eq!(parts[2].0.shape().values(), vec![2, 2]);
+        assert_eq!(parts[2].1.values(), &y.values()[8..].to_vec());
+
+        assert!(shards(&x, &y, 0).is_err());
+        assert!(shards(&x, &NDArray::new(vec![3, 1]).unwrap(), 2).is_err());
+        assert_eq!(shards(&x, &y, 20).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_data_parallel_gradients() {
+
+        let (x, y) = data();
+        let w = NDArray::array(vec![2, 1], vec![0.1, -0.2]).unwrap();
+        let b = NDArray::array(vec![1, 1], vec![0.3]).unwrap();
+
+        let builder = |p: &[Value<NDArray<f64>>], x: Value<NDArray<f64>>, y: Value<NDArray<f64>>| {
+            MSE::new(Add::new(MatMul::new(x, p[0].clone()), p[1].clone()), y)
+        };
+
+        /* full batch gradient on a single thread */
+        let weights = Value::new(&w);
+        let bias = Value::new(&b);
+        let mut loss = builder(&[weights.clone(), bias.clone()], Value::new(&x), Value::new(&y));
+        loss.backward(NDArray::fill(vec![1, 1], 1.0).unwrap());
+
+        let parameters = vec![SyncValue::new(&w), SyncValue::new(&b)];
+        let average = data_parallel_gradients(&parameters, &x, &y, 3, builder).unwrap();
+
+        assert!((average - loss.value().values()[0]).abs() < 1e-12);
+        for (parallel, serial) in parameters.iter().zip([weights, bias].iter()) {
+            for (a, b) in parallel.grad().values().iter().zip(serial.grad().values().iter()) {
+                assert!((a - b).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_data_parallel_training() {
+
+        let (x, y) = data();
+        let parameters = vec![
+            SyncValue::new(&NDArray::new(vec![2, 1]).unwrap()),
+            SyncValue::new(&NDArray::new(vec![1, 1]).unwrap())
+        ];
+
+        let mut optimizer = Adam::new(0.05);
+        let mut losses: Vec<f64> = Vec::new();
+        for _ in 0..300 {
+            let loss = data_parallel_gradients(&parameters, &x, &y, 4, |p, x, y| {
+                MSE::new(Add::new(MatMul::new(x, p[0].clone()), p[1].clone()), y)
+            }).unwrap();
+            step_parameters(&mut optimizer, &parameters);
+            losses.push(loss);
+        }
+
+        assert!(losses.last().unwrap() < &1e-3);
+        let weights = parameters[0].val();
+        assert!((weights.values()[0] - 2.0).abs() < 0.05);
+        assert!((weights.values()[1] + 3.0).abs() < 0.05);
+    }
+
+}
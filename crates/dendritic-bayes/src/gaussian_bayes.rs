@@ -1,6 +1,7 @@
 use std::fs;
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
+use dendritic::estimator::{Fit, Predict, PredictProba, Score, accuracy_score};
 use crate::shared::*;
 
 
@@ -93,7 +94,7 @@ impl GaussianNB {
 
     /// Predict likelihood for a given feature
     pub fn predict_feature(
-        &mut self,
+        &self,
         feature_col: usize,
         value: f64, 
         class: f64) -> f64 {
@@ -109,59 +110,108 @@ impl GaussianNB {
         )
     }
 
-    /// Fit prediction for a given row sample
-    pub fn fit_row(&mut self, x: NDArray<f64>) -> Result<f64, String> {
-     
+    /// Unnormalized posterior of every class for a given row sample
+    pub fn class_scores(&self, x: &NDArray<f64>) -> Result<Vec<f64>, String> {
+
         if x.shape().dim(0) != self.features.shape().dim(1) {
             let msg = "row sample not equal to features column count";
             return Err(msg.to_string());
         }
-        
-        let mut largest_prob: f64 = 0.0;
-        let mut predict_class: f64 = 0.0;
-        let class_count = class_idxs(&self.outputs);
-        for class in 0..class_count.len() {
 
-            let mut sum = 1.0;
-            let class_prob_calc = class_probabilities(
-                &self.outputs,
-                class_idxs(&self.outputs)
-            );
-            let class_prob = class_prob_calc[class as usize];
+        let class_prob_calc = class_probabilities(
+            &self.outputs,
+            class_idxs(&self.outputs)
+        );
 
+        let mut scores: Vec<f64> = Vec::new();
+        for (class, class_prob) in class_prob_calc.iter().enumerate() {
+            let mut sum = 1.0;
             for (idx, item) in x.values().iter().enumerate() {
                 let pred = self.predict_feature(idx, *item, class as f64);
                 sum *= pred; 
             }
-
-            if sum * class_prob > largest_prob {
-                largest_prob = sum * class_prob; 
-                predict_class = class as f64;
-            }
+            scores.push(sum * class_prob);
         }
 
-        Ok(predict_class)
+        Ok(scores)
+    }
+
+    /// Predict class index for a given row sample
+    pub fn predict_row(&self, x: NDArray<f64>) -> Result<f64, String> {
+        let scores = self.class_scores(&x)?;
+        Ok(max_class(&scores) as f64)
     }
 
-    /// Fit all rows and give prediction for each feature
-    pub fn fit(&mut self, x: NDArray<f64>) -> Result<NDArray<f64>, String> {
-        if x.shape().dim(1) != self.features.shape().dim(1) {
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for GaussianNB {
+
+    /// Replace samples and rebuild likelihoods of gaussian naive bayes
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_samples("GaussianNB", x, y)?;
+        self.features = x.clone();
+        self.outputs = y.clone();
+        self.build_likelihoods();
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for GaussianNB {
+    type Output = NDArray<f64>;
+
+    /// Predict class label for every row sample
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if x.rank() != 2 || x.shape().dim(1) != self.features.shape().dim(1) {
             let msg = "row sample not equal to features column count";
             return Err(msg.to_string());
         }
 
+        let classes = self.outputs.unique();
         let mut preds: Vec<f64> = Vec::new();
-        let rows = x.shape().dim(0); 
-        for row in 0..rows {
-            let item = x.axis(0, row).unwrap();
-            let pred = self.fit_row(item).unwrap();
-            preds.push(pred);
+        for row in 0..x.shape().dim(0) {
+            let item = x.axis(0, row)?;
+            let pred = self.predict_row(item)?;
+            preds.push(classes[pred as usize]);
         }
 
-        Ok(NDArray::array(
-            vec![preds.len(), 1],
-            preds
-        ).unwrap())
+        NDArray::array(vec![preds.len(), 1], preds)
     }
+
+}
+
+
+impl PredictProba<NDArray<f64>> for GaussianNB {
+    type Output = NDArray<f64>;
+
+    /// Normalized posterior of every class for every row sample
+    fn predict_proba(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if x.rank() != 2 || x.shape().dim(1) != self.features.shape().dim(1) {
+            let msg = "row sample not equal to features column count";
+            return Err(msg.to_string());
+        }
+
+        let mut probabilities: Vec<f64> = Vec::new();
+        for row in 0..x.shape().dim(0) {
+            let scores = self.class_scores(&x.axis(0, row)?)?;
+            probabilities.append(&mut normalize(scores));
+        }
+
+        let classes = class_idxs(&self.outputs).len();
+        NDArray::array(vec![x.shape().dim(0), classes], probabilities)
+    }
+
 }
 
+
+impl Score<NDArray<f64>, NDArray<f64>> for GaussianNB {
+
+    /// Accuracy of gaussian naive bayes predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(y.values(), y_pred.values())
+    }
+
+}
@@ -14,6 +14,7 @@
 //! use dendritic_ndarray::ops::*;
 //! use dendritic_bayes::naive_bayes::*;
 //! use dendritic_bayes::gaussian_bayes::*;
+//! use dendritic::estimator::*;
 
 //!
 //! fn main() {
@@ -26,21 +27,28 @@
 //!     let target = NDArray::load(y_path).unwrap();
 //!
 //!     // Create instance of naive bayes model
-//!     let mut nb_clf = NaiveBayes::new(
+//!     let nb_clf = NaiveBayes::new(
 //!         &features,
 //!         &target
 //!     ).unwrap();
 //!
-//!     // Create instance of guassian bayes model
-//!     let mut gb_clf = GaussianNB::new(
+//!     // Make prediction with first row of features
+//!     let row1 = features.axis(0, 0).unwrap();
+//!     let nb_pred = nb_clf.predict_row(row1.clone());
+//!
+//!     // Create instance of guassian bayes model on continuous features
+//!     let x_path = "data/prostate_cancer/inputs";
+//!     let y_path = "data/prostate_cancer/outputs";
+//!     let features = NDArray::load(x_path).unwrap();
+//!     let target = NDArray::load(y_path).unwrap();
+//!     let gb_clf = GaussianNB::new(
 //!         &features,
 //!         &target
 //!     ).unwrap();
-//!     
-//!     // Make prediction with first row of features
-//!     let row1 = features.axis(0, 0).unwrap();
-//!     let nb_pred = nb_clf.fit(row1.clone());
-//!     let gb_pred = gb_clf.fit(row1.clone()); // This will take in references eventually
+//!
+//!     // Predict every row and measure accuracy through the shared estimator traits
+//!     let gb_preds = gb_clf.predict(&features).unwrap();
+//!     let accuracy = gb_clf.score(&features, &target).unwrap();
 //! }
 //! ```
 //! ## Disclaimer
@@ -1,6 +1,7 @@
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
 use std::collections::HashSet;
+use dendritic::estimator::{Fit, Predict, PredictProba, Score, accuracy_score};
 use crate::shared::*;
 
 #[derive(Debug)]
@@ -136,23 +137,19 @@ impl NaiveBayes {
 
     }
 
-    /// Predict likelihood of feature occurring
-    pub fn predict_feature(
-        &mut self, 
-        feature_col: usize,
-        value: f64, 
-        class: f64) -> f64 {
-
-        /* count number of features associated with class */
+    /// Frequency and likelihood tables of a feature column
+    fn tables(&self, feature_col: usize) -> (NDArray<f64>, NDArray<f64>) {
         let class_indices = class_idxs(&self.outputs);
         let freq_table = self.frequency_table(
             self.features.axis(1, feature_col).unwrap(),
-            class_indices.clone()
+            class_indices
         ).unwrap();
         let lh_table = self.likelihood_table(freq_table.clone());
+        (freq_table, lh_table)
+    }
 
-        self.frequencies.push(freq_table);
-        self.likelihoods.push(lh_table.clone());
+    /// Look up likelihood of a feature value for a class
+    fn lookup(lh_table: &NDArray<f64>, value: f64, class: f64) -> f64 {
 
         /* search first col of lh table */
         let mut row_idx = 0;
@@ -165,44 +162,136 @@ impl NaiveBayes {
 
         /* calculate bayes theorem with likelihood and frequency table */
         let row_select = lh_table.axis(1, row_idx).unwrap();
-        let feature_occurrence = row_select.values()[class as usize + 1];
-        feature_occurrence 
+        row_select.values()[class as usize + 1]
     }
 
+    /// Predict likelihood of feature occurring, the tables used are recorded
+    /// in `frequencies` and `likelihoods`
+    pub fn predict_feature(
+        &mut self, 
+        feature_col: usize,
+        value: f64, 
+        class: f64) -> f64 {
 
-    /// Fit naive bayes model to all feature probabilities
-    pub fn fit(&mut self, data: NDArray<f64>) -> usize {
+        let (freq_table, lh_table) = self.tables(feature_col);
+        self.frequencies.push(freq_table);
+        self.likelihoods.push(lh_table.clone());
+        NaiveBayes::lookup(&lh_table, value, class)
+    }
+
+    /// Likelihood of feature occurring without recording tables
+    pub fn feature_likelihood(
+        &self, 
+        feature_col: usize,
+        value: f64, 
+        class: f64) -> f64 {
+
+        let (_, lh_table) = self.tables(feature_col);
+        NaiveBayes::lookup(&lh_table, value, class)
+    }
 
-        let mut largest_prob: f64 = 0.0;
-        let mut predict_class: usize = 0;
-        let class_count = class_idxs(&self.outputs);
-        for class in 0..class_count.len() {
+    /// Unnormalized posterior of every class for a given row sample
+    pub fn class_scores(&self, data: &NDArray<f64>) -> Vec<f64> {
 
-            let class_prob = class_probabilities(
-                &self.outputs,
-                class_idxs(&self.outputs)
-            )[class]; 
+        let class_prob_calc = class_probabilities(
+            &self.outputs,
+            class_idxs(&self.outputs)
+        );
 
+        let mut scores: Vec<f64> = Vec::new();
+        for (class, class_prob) in class_prob_calc.iter().enumerate() {
             let mut sum = 1.0;
             for (idx, item) in data.values().iter().enumerate() {
-
-                let predict = self.predict_feature(
-                    idx, 
-                    *item, 
-                    class as f64
-                );
-                sum *= predict;
+                sum *= self.feature_likelihood(idx, *item, class as f64);
             }
+            scores.push(sum * class_prob);
+        }
 
-            if sum * class_prob > largest_prob {
-                largest_prob = sum * class_prob; 
-                predict_class = class;
-            }
- 
+        scores
+    }
+
+    /// Predict class index for a given row sample
+    pub fn predict_row(&self, data: NDArray<f64>) -> usize {
+        max_class(&self.class_scores(&data))
+    }
+
+    /// Check input rows have one value per feature column
+    fn check_features(&self, x: &NDArray<f64>) -> Result<(), String> {
+        let expected = self.features.shape().dim(1);
+        if x.rank() != 2 || x.shape().dim(1) != expected {
+            let msg = format!(
+                "NaiveBayes: Expected {} features, got shape {:?}",
+                expected, x.shape().values()
+            );
+            return Err(msg);
+        }
+        Ok(())
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for NaiveBayes {
+
+    /// Replace samples of naive bayes and clear recorded tables
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_samples("NaiveBayes", x, y)?;
+        self.features = x.clone();
+        self.outputs = y.clone();
+        self.frequencies.clear();
+        self.likelihoods.clear();
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for NaiveBayes {
+    type Output = NDArray<f64>;
+
+    /// Predict class label for every row sample
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.check_features(x)?;
+
+        let classes = self.outputs.unique();
+        let mut preds: Vec<f64> = Vec::new();
+        for row in 0..x.shape().dim(0) {
+            let pred = self.predict_row(x.axis(0, row)?);
+            preds.push(classes[pred]);
         }
 
-        predict_class
+        NDArray::array(vec![preds.len(), 1], preds)
+    }
+
+}
+
+
+impl PredictProba<NDArray<f64>> for NaiveBayes {
+    type Output = NDArray<f64>;
+
+    /// Normalized posterior of every class for every row sample
+    fn predict_proba(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.check_features(x)?;
+
+        let mut probabilities: Vec<f64> = Vec::new();
+        for row in 0..x.shape().dim(0) {
+            let scores = self.class_scores(&x.axis(0, row)?);
+            probabilities.append(&mut normalize(scores));
+        }
+
+        let classes = class_idxs(&self.outputs).len();
+        NDArray::array(vec![x.shape().dim(0), classes], probabilities)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for NaiveBayes {
+
+    /// Accuracy of naive bayes predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(y.values(), y_pred.values())
     }
-    
 
 }
@@ -34,3 +34,45 @@ pub fn gaussian_probability(x: f64, mu: f64, sigma: f64) -> f64 {
     let e_second = second.exp();
     (1.0 / denom) * e_second
 }
+
+
+/// Index of the largest class score, ties and all zero scores keep the first class
+pub fn max_class(scores: &[f64]) -> usize {
+    let mut largest_prob: f64 = 0.0;
+    let mut predict_class: usize = 0;
+    for (class, score) in scores.iter().enumerate() {
+        if *score > largest_prob {
+            largest_prob = *score;
+            predict_class = class;
+        }
+    }
+    predict_class
+}
+
+/// Normalize class scores to probabilities, all zero scores become uniform
+pub fn normalize(scores: Vec<f64>) -> Vec<f64> {
+    let total: f64 = scores.iter().sum();
+    if total <= 0.0 {
+        let count = scores.len() as f64;
+        return scores.iter().map(|_| 1.0 / count).collect();
+    }
+    scores.iter().map(|score| score / total).collect()
+}
+
+/// Check features and targets can be used to fit a bayes model
+pub fn check_samples(
+    name: &str,
+    features: &NDArray<f64>,
+    outputs: &NDArray<f64>) -> Result<(), String> {
+
+    let rows = features.shape().dim(0);
+    if features.rank() != 2 || outputs.shape().values() != vec![rows, 1] {
+        let msg = format!(
+            "{}: Target shape {:?} does not match {} feature rows",
+            name, outputs.shape().values(), rows
+        );
+        return Err(msg);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod estimator_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_ndarray::ops::*;
+    use dendritic_bayes::gaussian_bayes::*;
+    use dendritic_bayes::naive_bayes::*;
+    use dendritic_bayes::shared::*;
+
+    /// Generic evaluation only relying on the shared traits
+    fn fit_score<M>(model: &mut M, x: &NDArray<f64>, y: &NDArray<f64>) -> f64
+    where
+        M: Fit<NDArray<f64>, NDArray<f64>> + Score<NDArray<f64>, NDArray<f64>>,
+    {
+        model.fit(x, y).unwrap();
+        model.score(x, y).unwrap()
+    }
+
+    #[test]
+    fn test_shared_utils() {
+        assert_eq!(max_class(&[0.1, 0.4, 0.4]), 1);
+        assert_eq!(max_class(&[0.0, 0.0]), 0);
+        assert_eq!(normalize(vec![1.0, 3.0]), vec![0.25, 0.75]);
+        assert_eq!(normalize(vec![0.0, 0.0]), vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_gaussian_estimator() {
+
+        let features: NDArray<f64> = NDArray::load("data/prostate_cancer/inputs").unwrap();
+        let target: NDArray<f64> = NDArray::load("data/prostate_cancer/outputs").unwrap();
+
+        let mut clf = GaussianNB::new(&features, &target).unwrap();
+        let accuracy = fit_score(&mut clf, &features, &target);
+        assert!((accuracy - (1.0 - 0.21428571428571427)).abs() < 1e-12);
+
+        let proba = clf.predict_proba(&features).unwrap();
+        assert_eq!(proba.shape().values(), vec![14, 2]);
+        for row in proba.values().chunks(2) {
+            assert!((row[0] + row[1] - 1.0).abs() < 1e-12);
+        }
+
+        let short = NDArray::array(vec![2, 1], vec![0.0, 1.0]).unwrap();
+        let err = clf.fit(&features, &short).unwrap_err();
+        assert_eq!(err, "GaussianNB: Target shape [2, 1] does not match 14 feature rows");
+    }
+
+    #[test]
+    fn test_naive_estimator() {
+
+        let features: NDArray<f64> = NDArray::load("data/weather_multi_feature/inputs").unwrap();
+        let target: NDArray<f64> = NDArray::load("data/weather_multi_feature/outputs").unwrap();
+
+        let mut clf = NaiveBayes::new(&features, &target).unwrap();
+        let accuracy = fit_score(&mut clf, &features, &target);
+        assert!((accuracy - (1.0 - 0.07142857142857142)).abs() < 1e-12);
+
+        /* predictions are reported as the original class labels */
+        let labels = target.scalar_mult(2.0).unwrap().scalar_add(3.0).unwrap();
+        assert_eq!(fit_score(&mut clf, &features, &labels), accuracy);
+        let preds = Predict::predict(&clf, &features).unwrap();
+        assert!(preds.values().iter().all(|label| *label == 3.0 || *label == 5.0));
+
+        let proba = clf.predict_proba(&features).unwrap();
+        assert_eq!(proba.shape().values(), vec![14, 2]);
+        assert!(clf.frequencies.is_empty());
+
+        let wide = NDArray::array(vec![1, 2], vec![1.0, 1.0]).unwrap();
+        assert!(clf.predict_proba(&wide).is_err());
+    }
+
+}
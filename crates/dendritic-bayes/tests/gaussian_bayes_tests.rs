@@ -6,6 +6,7 @@ mod gaussian_bayes_tests {
     use dendritic_ndarray::ops::*;
     use dendritic_bayes::gaussian_bayes::*;
     use dendritic_bayes::shared::*; 
+    use dendritic::estimator::*;
 
     #[test]
     fn test_gaussian_bayes() {
@@ -146,7 +147,7 @@ mod gaussian_bayes_tests {
         assert_eq!(features.shape().values(), vec![14, 2]);
         assert_eq!(target.shape().values(), vec![14, 1]);
 
-        let mut clf = GaussianNB::new(
+        let clf = GaussianNB::new(
             &features,
             &target
         ).unwrap();
@@ -167,7 +168,7 @@ mod gaussian_bayes_tests {
 
 
     #[test]
-    fn test_fit_row() {
+    fn test_predict_row() {
 
         let x_path = "data/prostate_cancer/inputs";
         let y_path = "data/prostate_cancer/outputs";
@@ -178,7 +179,7 @@ mod gaussian_bayes_tests {
         assert_eq!(features.shape().values(), vec![14, 2]);
         assert_eq!(target.shape().values(), vec![14, 1]);
 
-        let mut clf = GaussianNB::new(
+        let clf = GaussianNB::new(
             &features,
             &target
         ).unwrap();
@@ -195,7 +196,7 @@ mod gaussian_bayes_tests {
         let rows = features.shape().dim(0); 
         for row in 0..rows {
             let item = features.axis(0, row).unwrap();
-            let pred = clf.fit_row(item).unwrap();
+            let pred = clf.predict_row(item).unwrap();
             preds.push(pred);
         }
 
@@ -212,7 +213,7 @@ mod gaussian_bayes_tests {
             vec![0.8, 59.0]
         ).unwrap();
 
-        let pred2 = clf.fit_row(bad_row1); 
+        let pred2 = clf.predict_row(bad_row1); 
         assert_eq!(
             pred2.unwrap_err(),
             "row sample not equal to features column count"
@@ -221,7 +222,7 @@ mod gaussian_bayes_tests {
 
 
     #[test]
-    fn test_fit() {
+    fn test_predict() {
 
         let x_path = "data/prostate_cancer/inputs";
         let y_path = "data/prostate_cancer/outputs";
@@ -232,7 +233,7 @@ mod gaussian_bayes_tests {
         assert_eq!(features.shape().values(), vec![14, 2]);
         assert_eq!(target.shape().values(), vec![14, 1]);
 
-        let mut clf = GaussianNB::new(
+        let clf = GaussianNB::new(
             &features,
             &target
         ).unwrap();
@@ -240,7 +241,7 @@ mod gaussian_bayes_tests {
         assert_eq!(clf.features.shape().values(), vec![14, 2]); 
         assert_eq!(clf.outputs.shape().values(), vec![14, 1]);
 
-        let preds = clf.fit(features).unwrap();
+        let preds = clf.predict(&features).unwrap();
         let error = mse(&target, &preds).unwrap();
         assert_eq!(error, 0.21428571428571427);
 
@@ -249,7 +250,7 @@ mod gaussian_bayes_tests {
             vec![0.8, 59.0]
         ).unwrap();
 
-        let pred2 = clf.fit(bad_row1); 
+        let pred2 = clf.predict(&bad_row1); 
         assert_eq!(
             pred2.unwrap_err(),
             "row sample not equal to features column count"
@@ -277,13 +278,13 @@ mod gaussian_bayes_tests {
 
         clf.save("data/models/weather").unwrap(); 
 
-        let mut clf2 = GaussianNB::load(
+        let clf2 = GaussianNB::load(
             "data/models/weather",
             &features,
             &target
         ).unwrap();
 
-        let preds = clf2.fit(features).unwrap();
+        let preds = clf2.predict(&features).unwrap();
         let error = mse(&target, &preds).unwrap();
         assert_eq!(error, 0.21428571428571427);
 
@@ -426,7 +426,7 @@ mod naive_bayes_tests {
 
 
     #[test]
-    fn test_fit() {
+    fn test_predict_row() {
 
         let x_path_2 = "data/weather_multi_feature/inputs";
         let y_path_2 = "data/weather_multi_feature/outputs";
@@ -438,18 +438,18 @@ mod naive_bayes_tests {
         assert_eq!(features2.shape().values(), vec![14, 4]);
         assert_eq!(target2.shape().values(), vec![14, 1]);
 
-        let mut clf2 = NaiveBayes::new(
+        let clf2 = NaiveBayes::new(
             &features2,
             &target2
         ).unwrap();
 
-        let predict = clf2.fit(row1);
+        let predict = clf2.predict_row(row1);
         assert_eq!(predict, 0); 
 
         let mut predictions: Vec<f64> = Vec::new();
         for row in 0..features2.shape().dim(0) {
             let item = features2.axis(0, row).unwrap();
-            let predict = clf2.fit(item);
+            let predict = clf2.predict_row(item);
             predictions.push(predict as f64);
         }
 
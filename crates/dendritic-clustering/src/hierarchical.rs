@@ -1,5 +1,6 @@
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
+use dendritic::estimator::Fit;
 
 
 #[derive(Debug)]
@@ -236,7 +237,8 @@ impl HierarchicalClustering {
     }
 
 
-    pub fn fit_transform(&mut self) {
+    /// Find the closest pair of clusters for the next merge, merging is not implemented yet
+    pub fn merge_step(&mut self) {
 
         let d_matrix = self.distance_matrix.clone();
         let nonzero = d_matrix.nonzero();
@@ -251,3 +253,25 @@ impl HierarchicalClustering {
     }
 
 }
+
+
+impl Fit<NDArray<f64>> for HierarchicalClustering {
+
+    /// Replace data and compute its distance matrix, merging clusters is not
+    /// implemented yet so no predictions are available
+    fn fit(&mut self, x: &NDArray<f64>, _y: &()) -> Result<(), String> {
+
+        let rows = x.shape().dim(0);
+        if x.rank() != 2 || rows <= 1 {
+            let msg = "Not enough rows in sample data";
+            return Err(msg.to_string());
+        }
+
+        self.data = x.clone();
+        self.distance_matrix = NDArray::new(vec![rows, rows]).unwrap();
+        self.clusters = Vec::new();
+        self.calculate_distance_matrix();
+        Ok(())
+    }
+
+}
@@ -1,5 +1,6 @@
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
+use dendritic::estimator::{Fit, Predict, Transform, Score};
 
 
 #[derive(Debug)]
@@ -67,26 +68,31 @@ impl KMeans {
         self.centroids = centroids;
     }
 
-    /// Assign clusters based on distance metric
-    pub fn assign_clusters(&self) -> NDArray<f64> {
+    /// Distance of every sample row to every centroid
+    pub fn distances(&self, data: &NDArray<f64>) -> Result<NDArray<f64>, String> {
 
         let mut total: Vec<f64> = Vec::new();
-        let samples = self.data.shape().dim(0);
+        let samples = data.shape().dim(0);
         for row in 0..samples {
-            let item = self.data.axis(0, row).unwrap();
+            let item = data.axis(0, row)?;
             for centroid in &self.centroids {
                 let dist = (self.distance_metric)(
                     &item, 
                     centroid
-                ).unwrap();
+                )?;
                 total.push(dist);
             }
         }
 
         NDArray::array(
-            vec![samples, self.k],
+            vec![samples, self.centroids.len()],
             total
-        ).unwrap().argmin(0).unwrap()
+        )
+    }
+
+    /// Assign clusters based on distance metric
+    pub fn assign_clusters(&self) -> NDArray<f64> {
+        self.distances(&self.data).unwrap().argmin(0).unwrap()
     }
 
     /// Calculate centroids for K means clustering model
@@ -114,8 +120,8 @@ impl KMeans {
         self.centroids = centroids;
     }
 
-    /// Fit clusters for K means
-    pub fn fit(&mut self) -> NDArray<f64> {
+    /// Fit clusters on the data given to `new` and return the cluster of every row
+    pub fn fit_predict(&mut self) -> NDArray<f64> {
 
         let mut assigned_clusters = self.assign_clusters();
         for _epoch in 0..self.max_iter {
@@ -127,3 +133,78 @@ impl KMeans {
     }
 
 }
+
+
+impl KMeans {
+
+    /// Check input rows have the same number of features as the data
+    fn check_features(&self, x: &NDArray<f64>) -> Result<(), String> {
+        let expected = self.data.shape().dim(1);
+        if x.rank() != 2 || x.shape().dim(1) != expected {
+            let msg = format!(
+                "KMeans: Expected {} features, got shape {:?}",
+                expected, x.shape().values()
+            );
+            return Err(msg);
+        }
+        Ok(())
+    }
+
+}
+
+
+impl Fit<NDArray<f64>> for KMeans {
+
+    /// Replace data, start centroids at the first k rows and run all iterations
+    fn fit(&mut self, x: &NDArray<f64>, _y: &()) -> Result<(), String> {
+
+        if x.rank() != 2 || x.shape().dim(0) <= self.k {
+            return Err("Not enough rows in sample data".to_string());
+        }
+
+        self.data = x.clone();
+        self.set_centroids(&(0..self.k).collect());
+        self.fit_predict();
+        Ok(())
+    }
+
+}
+
+
+impl Transform<NDArray<f64>> for KMeans {
+    type Output = NDArray<f64>;
+
+    /// Distance of every row to every centroid
+    fn transform(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.check_features(x)?;
+        self.distances(x)
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for KMeans {
+    type Output = NDArray<f64>;
+
+    /// Index of the nearest centroid for every row
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.transform(x)?.argmin(0)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, ()> for KMeans {
+
+    /// Negative sum of squared distances from every row to its nearest centroid
+    fn score(&self, x: &NDArray<f64>, _y: &()) -> Result<f64, String> {
+        let distances = self.transform(x)?;
+        let cols = distances.shape().dim(1);
+        let inertia: f64 = distances.values()
+            .chunks(cols)
+            .map(|row| row.iter().fold(f64::INFINITY, |a, &b| a.min(b)).powi(2))
+            .sum();
+        Ok(-inertia)
+    }
+
+}
@@ -35,7 +35,7 @@
 //!     ).unwrap();
 //!
 //!     // Get centroids
-//!     let final_centroids = clf.fit();
+//!     let final_centroids = clf.fit_predict();
 //!     let centroids_unique = final_centroids.unique();
 //! }
 //! ```
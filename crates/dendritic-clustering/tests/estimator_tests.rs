@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod estimator_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_knn::distance::*;
+    use dendritic_clustering::k_means::*;
+    use dendritic_clustering::hierarchical::*;
+
+    /// Generic clustering only relying on the shared traits
+    fn cluster<M>(model: &mut M, x: &NDArray<f64>) -> NDArray<f64>
+    where
+        M: Fit<NDArray<f64>> + Predict<NDArray<f64>, Output = NDArray<f64>>,
+    {
+        model.fit(x, &()).unwrap();
+        model.predict(x).unwrap()
+    }
+
+    fn blobs() -> NDArray<f64> {
+        NDArray::array(vec![6, 2], vec![
+            0.0, 0.0, 10.0, 10.0, 0.5, 0.0,
+            10.5, 10.0, 0.0, 0.5, 10.0, 10.5
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_kmeans_estimator() {
+
+        let x = blobs();
+        let other = NDArray::array(vec![3, 2], vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0]).unwrap();
+        let mut clf = KMeans::new(&other, 2, 10, euclidean).unwrap();
+
+        let labels = cluster(&mut clf, &x);
+        assert_eq!(labels.values(), &vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(clf.data.shape().values(), vec![6, 2]);
+
+        let x_new = NDArray::array(vec![2, 2], vec![9.0, 9.0, 1.0, 0.0]).unwrap();
+        assert_eq!(clf.predict(&x_new).unwrap().values(), &vec![1.0, 0.0]);
+
+        let distances = clf.transform(&x_new).unwrap();
+        assert_eq!(distances.shape().values(), vec![2, 2]);
+
+        /* score is the negative inertia, a perfect fit scores zero */
+        let score = clf.score(&x, &()).unwrap();
+        assert!(score < 0.0 && score > -1.0, "{}", score);
+        let centers = NDArray::array(vec![1, 2], clf.centroids()[0].values().clone()).unwrap();
+        assert!(clf.score(&centers, &()).unwrap().abs() < 1e-12);
+
+        let wide = NDArray::array(vec![1, 3], vec![1.0, 1.0, 1.0]).unwrap();
+        assert!(clf.predict(&wide).is_err());
+        assert!(clf.fit(&other.axis_indices(0, vec![0, 1]).unwrap(), &()).is_err());
+    }
+
+    #[test]
+    fn test_hierarchical_fit() {
+
+        let x = NDArray::array(vec![3, 1], vec![0.0, 1.0, 3.0]).unwrap();
+        let y = NDArray::array(vec![2, 1], vec![0.0, 1.0]).unwrap();
+        let mut clf = HierarchicalClustering::new(&y, euclidean).unwrap();
+
+        clf.fit(&x, &()).unwrap();
+        assert_eq!(clf.distance_matrix().shape().values(), vec![3, 3]);
+        assert_eq!(clf.distance_matrix().values(), &vec![
+            0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0,
+            3.0, 2.0, 0.0
+        ]);
+    }
+
+}
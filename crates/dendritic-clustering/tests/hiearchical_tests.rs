@@ -115,7 +115,7 @@ mod hierarchical_clustering_tests {
             vec![5, 2]
         );
 
-        //clf.fit_transform();
+        //clf.merge_step();
 
     } 
 
@@ -165,7 +165,7 @@ mod kmeans_tests {
         clf.set_centroids(&idxs);
 
         let expected = vec![0.0, 2.0, 1.0, 0.0, 1.0, 1.0, 2.0, 0.0];
-        let final_centroids = clf.fit();
+        let final_centroids = clf.fit_predict();
 
         assert_eq!(final_centroids.shape().values(), vec![8, 1]); 
         assert_eq!(
@@ -197,7 +197,7 @@ mod kmeans_tests {
         ];
 
         let mut idx = 0;
-        let final_centroids = clf.fit();
+        let final_centroids = clf.fit_predict();
         let centroids_unique = final_centroids.unique();
         for item in &centroids_unique {
             let idxs = final_centroids.value_indices(*item);
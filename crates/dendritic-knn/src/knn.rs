@@ -1,5 +1,6 @@
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
+use dendritic::estimator::{Fit, Predict, PredictProba, Score, accuracy_score, r2_score};
 use crate::utils::*;
 
 
@@ -17,8 +18,24 @@ pub struct KNN {
 
 impl KNN {
 
-    /// Fit new dataset to the K nearest neighbors classifier model
-    pub fn fit(
+    /// Create unfitted K nearest neighbors classifier, samples are provided through `Fit`
+    pub fn new(
+        k: usize,
+        distance_metric: fn(
+            y1: &NDArray<f64>, 
+            y2: &NDArray<f64>) -> Result<f64, String>
+        ) -> KNN {
+
+        Self {
+            features: NDArray::new(vec![0, 0]).unwrap(),
+            outputs: NDArray::new(vec![0, 1]).unwrap(),
+            k,
+            distance_metric
+        }
+    }
+
+    /// Create K nearest neighbors classifier model holding a dataset
+    pub fn with_data(
         features: &NDArray<f64>, 
         outputs: &NDArray<f64>,
         k: usize,
@@ -75,7 +92,7 @@ impl KNN {
     }
 
     /// Predict nearest neighbors for all dataset samples
-    pub fn predict(&self, point: &NDArray<f64>) -> NDArray<f64> {
+    pub fn predict_rows(&self, point: &NDArray<f64>) -> NDArray<f64> {
 
         let mut preds: Vec<f64> =Vec::new();
         let rows = point.shape().dim(0); 
@@ -105,9 +122,25 @@ pub struct KNNRegressor {
 
 impl KNNRegressor {
 
+    /// Create unfitted K nearest neighbors regression model, samples are provided through `Fit`
+    pub fn new(
+        k: usize,
+        distance_metric: fn(
+            y1: &NDArray<f64>, 
+            y2: &NDArray<f64>) -> Result<f64, String>
+        ) -> KNNRegressor {
+
+        Self {
+            features: NDArray::new(vec![0, 0]).unwrap(),
+            outputs: NDArray::new(vec![0, 1]).unwrap(),
+            k,
+            distance_metric
+        }
+    }
+
     
-    /// Fit new dataset to the K nearest neighbors regression model
-    pub fn fit(
+    /// Create K nearest neighbors regression model holding a dataset
+    pub fn with_data(
         features: &NDArray<f64>, 
         outputs: &NDArray<f64>,
         k: usize,
@@ -154,7 +187,7 @@ impl KNNRegressor {
 
 
     /// Predict all samples for KNN regression
-    pub fn predict(&self, point: &NDArray<f64>) -> NDArray<f64> {
+    pub fn predict_rows(&self, point: &NDArray<f64>) -> NDArray<f64> {
 
         let mut preds: Vec<f64> =Vec::new();
         let rows = point.shape().dim(0); 
@@ -168,3 +201,102 @@ impl KNNRegressor {
     }
 
 }
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for KNN {
+
+    /// Store samples and class targets used for neighbor lookups
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_samples("KNN", x, y, self.k)?;
+        self.features = x.clone();
+        self.outputs = y.clone();
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for KNN {
+    type Output = NDArray<f64>;
+
+    /// Predict majority class of the nearest neighbors for every sample
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        check_fitted("KNN", &self.features, x)?;
+        Ok(self.predict_rows(x))
+    }
+
+}
+
+
+impl PredictProba<NDArray<f64>> for KNN {
+    type Output = NDArray<f64>;
+
+    /// Fraction of nearest neighbors voting for each class in sorted class order
+    fn predict_proba(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        check_fitted("KNN", &self.features, x)?;
+
+        let classes = self.outputs.unique();
+        let rows = x.shape().dim(0);
+        let mut probabilities: Vec<f64> = Vec::new();
+        for row in 0..rows {
+            let item = x.axis(0, row)?;
+            let distances = calculate_distances(self.distance_metric, &self.features, &item)?;
+            let row_idxs: Vec<usize> = distances.iter().take(self.k).map(|(_, idx)| *idx).collect();
+            let targets = self.outputs.indice_query(row_idxs)?;
+            for class in &classes {
+                let cnt = targets.values().iter().filter(|&n| n == class).count();
+                probabilities.push(cnt as f64 / self.k as f64);
+            }
+        }
+
+        NDArray::array(vec![rows, classes.len()], probabilities)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for KNN {
+
+    /// Accuracy of nearest neighbor class predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(y.values(), y_pred.values())
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for KNNRegressor {
+
+    /// Store samples and targets used for neighbor lookups
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_samples("KNNRegressor", x, y, self.k)?;
+        self.features = x.clone();
+        self.outputs = y.clone();
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for KNNRegressor {
+    type Output = NDArray<f64>;
+
+    /// Predict average target of the nearest neighbors for every sample
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        check_fitted("KNNRegressor", &self.features, x)?;
+        Ok(self.predict_rows(x))
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for KNNRegressor {
+
+    /// R2 score of nearest neighbor regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
@@ -21,14 +21,14 @@
 //!    let (x_train, x_test) = x.split(0, 0.80).unwrap(); // split rows with 80/20 split
 //!    let (y_train, y_test) = y.split(0, 0.80).unwrap();
 //!
-//!    let clf = KNN::fit(
+//!    let clf = KNN::with_data(
 //!        &x_train, 
 //!        &y_train, 
 //!        4, 
 //!        euclidean
 //!    ).unwrap();
 //!
-//!    let predictions = clf.predict(&x_test);
+//!    let predictions = clf.predict_rows(&x_test);
 //!    println!("Actual: {:?}", predictions.values());
 //!    println!("Prediction: {:?}", y_test.values()); 
 //!
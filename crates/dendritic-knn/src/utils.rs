@@ -26,3 +26,51 @@ pub fn calculate_distances(
     distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
     Ok(distances.to_vec())
 }
+
+
+/// Check samples and targets can be stored by a neighbors model
+pub fn check_samples(
+    name: &str,
+    features: &NDArray<f64>,
+    outputs: &NDArray<f64>,
+    k: usize) -> Result<(), String> {
+
+    let rows = features.shape().dim(0);
+    if features.rank() != 2 || outputs.shape().values() != vec![rows, 1] {
+        let msg = format!(
+            "{}: Target shape {:?} does not match {} feature rows",
+            name, outputs.shape().values(), rows
+        );
+        return Err(msg);
+    }
+
+    if k == 0 || k > rows {
+        let msg = format!("{}: k must be between 1 and {} samples, got {}", name, rows, k);
+        return Err(msg);
+    }
+
+    Ok(())
+}
+
+
+/// Check model holds samples with the same number of features as the input
+pub fn check_fitted(
+    name: &str,
+    features: &NDArray<f64>,
+    x: &NDArray<f64>) -> Result<(), String> {
+
+    if features.size() == 0 {
+        return Err(format!("{}: Model must be fitted before predicting", name));
+    }
+
+    let expected = features.shape().dim(1);
+    if x.rank() != 2 || x.shape().dim(1) != expected {
+        let msg = format!(
+            "{}: Expected {} features, got shape {:?}",
+            name, expected, x.shape().values()
+        );
+        return Err(msg);
+    }
+
+    Ok(())
+}
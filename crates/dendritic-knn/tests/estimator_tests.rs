@@ -0,0 +1,66 @@
+
+#[cfg(test)]
+mod estimator_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_knn::knn::*;
+    use dendritic_knn::distance::*;
+
+    /// Generic evaluation only relying on the shared traits
+    fn fit_score<M>(model: &mut M, x: &NDArray<f64>, y: &NDArray<f64>) -> f64
+    where
+        M: Fit<NDArray<f64>, NDArray<f64>> + Score<NDArray<f64>, NDArray<f64>>,
+    {
+        model.fit(x, y).unwrap();
+        model.score(x, y).unwrap()
+    }
+
+    fn features() -> NDArray<f64> {
+        NDArray::array(vec![6, 2], vec![
+            1.0, 1.0, 1.5, 1.0, 1.0, 1.5,
+            8.0, 8.0, 8.5, 8.0, 8.0, 8.5
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_knn_estimator() {
+
+        let x = features();
+        let y = NDArray::array(vec![6, 1], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+
+        let mut model = KNN::new(3, euclidean);
+        let x_test = NDArray::array(vec![1, 2], vec![1.2, 1.2]).unwrap();
+        let err = Predict::predict(&model, &x_test).unwrap_err();
+        assert_eq!(err, "KNN: Model must be fitted before predicting");
+
+        assert_eq!(fit_score(&mut model, &x, &y), 1.0);
+
+        let x_test = NDArray::array(vec![2, 2], vec![1.2, 1.2, 7.0, 7.5]).unwrap();
+        let proba = model.predict_proba(&x_test).unwrap();
+        assert_eq!(proba.shape().values(), vec![2, 2]);
+        assert_eq!(proba.values(), &vec![1.0, 0.0, 0.0, 1.0]);
+
+        let wide = NDArray::array(vec![1, 3], vec![1.0, 1.0, 1.0]).unwrap();
+        assert!(model.score(&wide, &y).is_err());
+
+        let mut large_k = KNN::new(7, euclidean);
+        let err = large_k.fit(&x, &y).unwrap_err();
+        assert_eq!(err, "KNN: k must be between 1 and 6 samples, got 7");
+    }
+
+    #[test]
+    fn test_knn_regressor_estimator() {
+
+        let x = features();
+        let y = NDArray::array(vec![6, 1], vec![1.0, 1.2, 0.8, 5.0, 5.4, 4.6]).unwrap();
+
+        let mut model = KNNRegressor::new(3, manhattan);
+        assert!(fit_score(&mut model, &x, &y) > 0.95);
+
+        let x_test = NDArray::array(vec![1, 2], vec![8.1, 8.1]).unwrap();
+        let y_pred = Predict::predict(&model, &x_test).unwrap();
+        assert!((y_pred.values()[0] - 5.0).abs() < 1e-12);
+    }
+
+}
@@ -21,7 +21,7 @@ mod knn_tests {
         assert_eq!(target.shape().values(), vec![7, 1]);
 
         let k = 7;
-        let clf = KNN::fit(&features, &target, k, euclidean).unwrap();
+        let clf = KNN::with_data(&features, &target, k, euclidean).unwrap();
 
         assert_eq!(clf.features.shape().values(), vec![7, 2]);
         assert_eq!(clf.outputs.shape().values(), vec![7, 1]);
@@ -32,7 +32,7 @@ mod knn_tests {
             vec![20.0, 40.0]
         ).unwrap();
 
-        let clf_bad = KNN::fit(&bad_features, &target, k, euclidean);
+        let clf_bad = KNN::with_data(&bad_features, &target, k, euclidean);
         assert_eq!(
             clf_bad.unwrap_err(),
             "Feature rows must match output rows"
@@ -63,7 +63,7 @@ mod knn_tests {
         ).unwrap();
 
         let k = 5;
-        let _clf = KNN::fit(&features, &target, k, euclidean).unwrap();
+        let _clf = KNN::with_data(&features, &target, k, euclidean).unwrap();
         let distances = calculate_distances(
             euclidean,
             &features,
@@ -113,7 +113,7 @@ mod knn_tests {
         ).unwrap();
 
         let k = 5;
-        let clf = KNN::fit(&features, &target, k, euclidean).unwrap();
+        let clf = KNN::with_data(&features, &target, k, euclidean).unwrap();
         let pred = clf.predict_sample(&test_point);
         assert_eq!(pred, 0.0); 
     }
@@ -141,8 +141,8 @@ mod knn_tests {
         ).unwrap();
 
         let k = 5;
-        let clf = KNN::fit(&features, &target, k, euclidean).unwrap();
-        let preds = clf.predict(&test_point);
+        let clf = KNN::with_data(&features, &target, k, euclidean).unwrap();
+        let preds = clf.predict_rows(&test_point);
 
         assert_eq!(preds.shape().values(), vec![3, 1]);
         assert_eq!(
@@ -165,7 +165,7 @@ mod knn_tests {
         assert_eq!(target.shape().values(), vec![10, 1]);
 
         let k = 7;
-        let clf = KNNRegressor::fit(
+        let clf = KNNRegressor::with_data(
             &features, 
             &target, 
             k, 
@@ -181,7 +181,7 @@ mod knn_tests {
             vec![20.0, 40.0]
         ).unwrap();
 
-        let clf_bad = KNN::fit(&bad_features, &target, k, euclidean);
+        let clf_bad = KNN::with_data(&bad_features, &target, k, euclidean);
         assert_eq!(
             clf_bad.unwrap_err(),
             "Feature rows must match output rows"
@@ -208,7 +208,7 @@ mod knn_tests {
         ).unwrap();
 
         let k = 3;
-        let clf = KNNRegressor::fit(
+        let clf = KNNRegressor::with_data(
             &features, 
             &target, 
             k, 
@@ -242,14 +242,14 @@ mod knn_tests {
         ).unwrap();
 
         let k = 3;
-        let clf = KNNRegressor::fit(
+        let clf = KNNRegressor::with_data(
             &features, 
             &target, 
             k, 
             euclidean
         ).unwrap();
 
-        let preds = clf.predict(&test_point);
+        let preds = clf.predict_rows(&test_point);
 
         assert_eq!(preds.shape().values(), vec![3, 1]);
         assert_eq!(
@@ -52,3 +52,76 @@ pub fn categorical_cross_entropy(y_hat: &NDArray<f64>, y_true: &NDArray<f64>) ->
 
 }
 
+
+/// Mean absolute error function
+pub fn mae(y_true: &NDArray<f64>, y_pred: &NDArray<f64>) -> Result<f64, String> {
+
+    if y_true.size() != y_pred.size() {
+        return Err("Size of y values do not match".to_string());
+    }
+
+    let result: f64 = y_true.values().iter()
+        .zip(y_pred.values().iter())
+        .map(|(t, p)| (t - p).abs())
+        .sum();
+
+    Ok(result / y_true.size() as f64)
+}
+
+/// Huber loss, quadratic for residuals under delta and linear above
+pub fn huber(y_true: &NDArray<f64>, y_pred: &NDArray<f64>, delta: f64) -> Result<f64, String> {
+
+    if y_true.size() != y_pred.size() {
+        return Err("Size of y values do not match".to_string());
+    }
+
+    let result: f64 = y_true.values().iter()
+        .zip(y_pred.values().iter())
+        .map(|(t, p)| {
+            let residual = (t - p).abs();
+            if residual <= delta {
+                0.5 * residual * residual
+            } else {
+                delta * (residual - 0.5 * delta)
+            }
+        })
+        .sum();
+
+    Ok(result / y_true.size() as f64)
+}
+
+/// Binary cross entropy of raw logits, computed without overflow
+pub fn binary_cross_entropy_logits(y_hat: &NDArray<f64>, y_true: &NDArray<f64>) -> Result<f64, String> {
+
+    if y_true.size() != y_hat.size() {
+        return Err("Size of y values do not match".to_string());
+    }
+
+    let result: f64 = y_hat.values().iter()
+        .zip(y_true.values().iter())
+        .map(|(z, t)| z.max(0.0) - z * t + (-z.abs()).exp().ln_1p())
+        .sum();
+
+    Ok(result / y_hat.size() as f64)
+}
+
+/// Categorical cross entropy of raw logits with one row per sample,
+/// averaged over every element like `categorical_cross_entropy`
+pub fn categorical_cross_entropy_logits(y_hat: &NDArray<f64>, y_true: &NDArray<f64>) -> Result<f64, String> {
+
+    if y_true.size() != y_hat.size() {
+        return Err("Size of y values do not match".to_string());
+    }
+
+    let cols = y_hat.shape().dim(y_hat.rank() - 1);
+    let mut result = 0.0;
+    for (z_row, t_row) in y_hat.values().chunks(cols).zip(y_true.values().chunks(cols)) {
+
+        /* log softmax with the row max subtracted for stability */
+        let max = z_row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let log_sum = z_row.iter().map(|z| (z - max).exp()).sum::<f64>().ln() + max;
+        result += z_row.iter().zip(t_row.iter()).map(|(z, t)| -t * (z - log_sum)).sum::<f64>();
+    }
+
+    Ok(result / y_hat.size() as f64)
+}
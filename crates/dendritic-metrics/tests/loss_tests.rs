@@ -5,6 +5,7 @@ mod loss_tests {
     use dendritic_ndarray::ndarray::NDArray;
     use dendritic_metrics::loss::*;
     use dendritic_metrics::utils::*; 
+    use dendritic_ndarray::ops::*;
 
 
     #[test]
@@ -45,6 +46,50 @@ mod loss_tests {
         assert_eq!(result_2, 0.11889164797957748);  
     } 
 
+    #[test]
+    fn test_regression_losses() {
+
+        let y_true: NDArray<f64> = NDArray::array(vec![3, 1], vec![0.0, 0.0, 0.0]).unwrap();
+        let y_pred: NDArray<f64> = NDArray::array(vec![3, 1], vec![0.5, 3.0, -4.0]).unwrap();
+
+        assert_eq!(mae(&y_true, &y_pred).unwrap(), 2.5);
+        assert_eq!(huber(&y_true, &y_pred, 1.0).unwrap(), (0.125 + 2.5 + 3.5) / 3.0);
+
+        let short: NDArray<f64> = NDArray::array(vec![2, 1], vec![0.0, 0.0]).unwrap();
+        assert!(mae(&short, &y_pred).is_err());
+        assert!(huber(&short, &y_pred, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_cross_entropy_logits() {
+
+        let logits: NDArray<f64> = NDArray::array(vec![3, 1], vec![-1.0, 0.5, 2.0]).unwrap();
+        let labels: NDArray<f64> = NDArray::array(vec![3, 1], vec![0.0, 1.0, 1.0]).unwrap();
+        let probs = logits.apply(|z| 1.0 / (1.0 + (-z).exp())).unwrap();
+
+        let expected = binary_cross_entropy(&probs, &labels).unwrap();
+        let result = binary_cross_entropy_logits(&logits, &labels).unwrap();
+        assert!((result - expected).abs() < 1e-12);
+
+        /* large logits stay finite */
+        let large: NDArray<f64> = NDArray::array(vec![2, 1], vec![800.0, -800.0]).unwrap();
+        let large_labels: NDArray<f64> = NDArray::array(vec![2, 1], vec![0.0, 1.0]).unwrap();
+        assert_eq!(binary_cross_entropy_logits(&large, &large_labels).unwrap(), 800.0);
+
+        let logits: NDArray<f64> = NDArray::array(vec![2, 3], vec![1.0, 2.0, 0.5, -1.0, 0.0, 3.0]).unwrap();
+        let one_hot: NDArray<f64> = NDArray::array(vec![2, 3], vec![0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).unwrap();
+        let mut softmax: Vec<f64> = Vec::new();
+        for row in logits.values().chunks(3) {
+            let total: f64 = row.iter().map(|z| z.exp()).sum();
+            softmax.extend(row.iter().map(|z| z.exp() / total));
+        }
+        let probs = NDArray::array(vec![2, 3], softmax).unwrap();
+
+        let expected = categorical_cross_entropy(&probs, &one_hot).unwrap();
+        let result = categorical_cross_entropy_logits(&logits, &one_hot).unwrap();
+        assert!((result - expected).abs() < 1e-12);
+    }
+
     #[test]
     fn test_gini_impurity_index() {
 
@@ -11,6 +11,7 @@ use dendritic_ndarray::ops::*;
 use dendritic_regression::logistic::Logistic;
 use dendritic_preprocessing::standard_scalar::*;
 use dendritic_metrics::activations::*;
+use dendritic_autodiff::optim::SGD;
 
 #[derive(Debug, serde::Deserialize, Serialize)]
 pub struct Record {
@@ -59,8 +60,7 @@ impl DiabetesModel {
             model: Logistic::new(
                 NDArray::new(vec![1, 1]).unwrap(),
                 NDArray::new(vec![1, 1]).unwrap(),
-                sigmoid_vec,
-                learning_rate
+                SGD::new(learning_rate)
             ).unwrap()
         }
     }
@@ -196,8 +196,7 @@ impl DiabetesModel {
         self.model = Logistic::new(
             x_train_processed, 
             y_train, 
-            sigmoid_vec,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
 
         self.model.sgd(1000, true, 5); 
@@ -256,8 +255,7 @@ impl DiabetesModel {
             filepath,
             x_train_processed, 
             y_train,
-            sigmoid_vec,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
     }
 
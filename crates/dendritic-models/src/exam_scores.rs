@@ -10,6 +10,7 @@ use dendritic_ndarray::ops::*;
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_regression::ridge::Ridge;
 use dendritic_preprocessing::standard_scalar::*; 
+use dendritic_autodiff::optim::SGD;
 
 
 #[derive(Serialize, Debug)]
@@ -55,7 +56,7 @@ impl ExamScoresModel {
                 &NDArray::new(vec![1, 1]).unwrap(),
                 &NDArray::new(vec![1, 1]).unwrap(),
                 0.0001,
-                0.01
+                SGD::new(0.01)
             ).unwrap()
         }
     }
@@ -183,7 +184,7 @@ impl ExamScoresModel {
             &x_train_processed, 
             &y_train, 
             0.00001, 
-            0.3
+            SGD::new(0.3)
         ).unwrap();
 
         self.model.sgd(1000, true, 5);
@@ -199,7 +200,7 @@ impl ExamScoresModel {
             filepath,
             &x_train_processed, 
             &y_train,
-            0.0001, 0.1
+            0.1, SGD::new(0.0001)
         ).unwrap();
     }
 
@@ -11,6 +11,7 @@ use dendritic_ndarray::ops::*;
 use dendritic_regression::logistic::Logistic;
 use dendritic_preprocessing::standard_scalar::*;
 use dendritic_metrics::activations::*;
+use dendritic_autodiff::optim::SGD;
 
 #[derive(Debug, serde::Deserialize, Serialize)]
 pub struct Record {
@@ -55,8 +56,7 @@ impl ExampleModel {
             model: Logistic::new(
                 NDArray::new(vec![1, 1]).unwrap(),
                 NDArray::new(vec![1, 1]).unwrap(),
-                sigmoid_vec,
-                learning_rate
+                SGD::new(learning_rate)
             ).unwrap()
         }
     }
@@ -189,8 +189,7 @@ impl ExampleModel {
         self.model = Logistic::new(
             x_train_processed, 
             y_train, 
-            sigmoid_vec,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
 
         self.model.sgd(1500, true, 5); 
@@ -212,8 +211,7 @@ impl ExampleModel {
             filepath,
             x_train, 
             y_train,
-            sigmoid_vec,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
     }
 
@@ -9,6 +9,7 @@ use arrow_array::array::{Float64Array};
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_regression::logistic::Logistic;
 use dendritic_metrics::activations::*;
+use dendritic_autodiff::optim::SGD;
 
 #[derive(Serialize)]
 pub struct Suicide {
@@ -49,8 +50,7 @@ impl SuicideDetectionModel {
             model: Logistic::new(
                  NDArray::new(vec![1, 1]).unwrap(),
                  NDArray::new(vec![1, 1]).unwrap(),
-                 sigmoid_vec,
-                 0.01
+                 SGD::new(0.01)
             ).unwrap()
         }
     }
@@ -162,7 +162,7 @@ impl SuicideDetectionModel {
     pub fn train_model(&mut self) {
         let batch = self.create_batch(); 
         let (inputs, outputs) = self.process_features(batch);
-        self.model = Logistic::new(inputs, outputs, sigmoid_vec, 0.1).unwrap();
+        self.model = Logistic::new(inputs, outputs, SGD::new(0.1)).unwrap();
         self.model.train(5000, true);
     }
 
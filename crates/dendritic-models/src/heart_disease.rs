@@ -10,6 +10,7 @@ use dendritic_ndarray::ops::*;
 use dendritic_regression::logistic::Logistic;
 use dendritic_preprocessing::standard_scalar::*;
 use dendritic_metrics::activations::*;
+use dendritic_autodiff::optim::SGD;
 
 
 #[derive(Debug, serde::Deserialize, Serialize)]
@@ -66,8 +67,7 @@ impl HeartModel {
             model: Logistic::new(
                 &NDArray::new(vec![1, 1]).unwrap(),
                 &NDArray::new(vec![1, 1]).unwrap(),
-                sigmoid_vec,
-                learning_rate
+                SGD::new(learning_rate)
             ).unwrap()
         }
     }
@@ -220,8 +220,7 @@ impl HeartModel {
         self.model = Logistic::new(
             &x_train_processed, 
             &y_train, 
-            sigmoid_vec,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
 
         self.model.sgd(5000, true, 5); 
@@ -291,8 +290,7 @@ impl HeartModel {
             filepath,
             &x_train_processed, 
             &y_train,
-            sigmoid_vec,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
     }
 
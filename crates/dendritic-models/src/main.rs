@@ -13,10 +13,10 @@ use dendritic_trees::random_forest::*;
 use dendritic_preprocessing::encoding::*;
 use dendritic_preprocessing::standard_scalar::*;
 use dendritic_metrics::loss::*;
-use dendritic_metrics::activations::*;
 use dendritic_metrics::utils::*;
 use dendritic_knn::knn::*;
 use dendritic_knn::distance::*; 
+use dendritic_autodiff::optim::SGD;
 
 fn diabetes_model() {
 
@@ -28,8 +28,7 @@ fn diabetes_model() {
     let mut log_model = Logistic::new(
         &x_train,
         &y_train,
-        sigmoid_vec,
-        0.01
+        SGD::new(0.01)
     ).unwrap();
 
     log_model.sgd(5000, true, 5);
@@ -57,8 +56,7 @@ fn breast_cancer_model() {
 	"../data/breast_cancer",
         &x_train,
         &y_train,
-        sigmoid_vec,
-        0.001
+        SGD::new(0.001)
     ).unwrap();
 
     //log_model.sgd(1500, true, 5);
@@ -91,8 +89,7 @@ fn iris_model() {
     let mut log_model = MultiClassLogistic::new(
         &x_train,
         &y_train_encoded,
-        softmax,
-        0.1
+        SGD::new(0.1)
     ).unwrap();
 
     log_model.sgd(500, true, 5);
@@ -142,8 +139,7 @@ fn alzheimers_model() {
     let mut log_model = Logistic::new(
         &x_train,
         &y_train,
-        sigmoid_vec,
-        1.0
+        SGD::new(1.0)
     ).unwrap();
 
     log_model.train(1500, true);
@@ -209,14 +205,14 @@ fn iris_knn_classifier() {
     let (x_train, x_test) = x.split(0, 0.80).unwrap();
     let (y_train, y_test) = y.split(0, 0.80).unwrap();
 
-    let clf = KNN::fit(
+    let clf = KNN::with_data(
         &x_train, 
         &y_train, 
         4, 
         euclidean
     ).unwrap();
 
-    let predictions = clf.predict(&x_test);
+    let predictions = clf.predict_rows(&x_test);
     println!("Actual: {:?}", predictions.values());
     println!("Prediction: {:?}", y_test.values()); 
 }
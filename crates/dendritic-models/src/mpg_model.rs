@@ -9,6 +9,7 @@ use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
 use dendritic_regression::linear::Linear;
 use dendritic_preprocessing::standard_scalar::*; 
+use dendritic_autodiff::optim::SGD;
 
 
 #[derive(Debug, serde::Deserialize, Serialize)]
@@ -55,7 +56,7 @@ impl MPGModel {
             model: Linear::new(
                 &NDArray::new(vec![1, 1]).unwrap(),
                 &NDArray::new(vec![1, 1]).unwrap(),
-                learning_rate
+                SGD::new(learning_rate)
             ).unwrap()
         }
     }
@@ -180,7 +181,7 @@ impl MPGModel {
         self.model = Linear::new(
             &x_train_processed, 
             &y_train_processed, 
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
 
         self.model.sgd(1000, true, 5); 
@@ -211,7 +212,7 @@ impl MPGModel {
             filepath,
             &x_train_processed, 
             &y_train_processed,
-            self.learning_rate
+            SGD::new(self.learning_rate)
         ).unwrap();
     }
 
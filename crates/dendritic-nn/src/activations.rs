@@ -0,0 +1,276 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::{Node, Value};
+use dendritic_autodiff::activations as nodes;
+use crate::module::Module;
+
+
+/// Activation node applied to an input value that is swapped on every forward pass
+struct ActivationLayer<N: Node> {
+    input: Value<NDArray<f64>>,
+    node: N
+}
+
+
+impl<N: Node> ActivationLayer<N> {
+
+    /// Create activation layer from a builder for the autodiff node
+    fn new<F: Fn(Value<NDArray<f64>>) -> N>(builder: F) -> ActivationLayer<N> {
+        let input = Value::new(&NDArray::new(vec![1, 1]).unwrap());
+        let node = builder(input.clone());
+        ActivationLayer { input, node }
+    }
+
+    /// Forward pass of activation node
+    fn forward(&mut self, input: &NDArray<f64>) -> NDArray<f64> {
+        self.input.set_val(input);
+        self.node.forward();
+        self.node.value()
+    }
+
+    /// Backward pass of activation node, returns gradient with respect to input
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.node.backward(upstream_gradient);
+        self.input.grad()
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.node.zero_grad();
+    }
+
+}
+
+
+/// Rectified linear unit layer
+pub struct ReLU {
+    layer: ActivationLayer<nodes::ReLU<Value<NDArray<f64>>>>
+}
+
+
+impl ReLU {
+
+    /// Create new instance of relu layer
+    pub fn new() -> ReLU {
+        ReLU { layer: ActivationLayer::new(nodes::ReLU::new) }
+    }
+
+}
+
+
+impl Default for ReLU {
+    fn default() -> Self {
+        ReLU::new()
+    }
+}
+
+
+impl Module for ReLU {
+
+    /// Forward pass of relu layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        Ok(self.layer.forward(input))
+    }
+
+    /// Backward pass of relu layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.layer.zero_grad();
+    }
+
+    /// Relu layer has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+}
+
+
+/// Leaky rectified linear unit layer with slope `alpha` for negative inputs
+pub struct LeakyReLU {
+    layer: ActivationLayer<nodes::LeakyReLU<Value<NDArray<f64>>>>
+}
+
+
+impl LeakyReLU {
+
+    /// Create new instance of leaky relu layer
+    pub fn new(alpha: f64) -> LeakyReLU {
+        LeakyReLU {
+            layer: ActivationLayer::new(|input| nodes::LeakyReLU::new(input, alpha))
+        }
+    }
+
+}
+
+
+impl Module for LeakyReLU {
+
+    /// Forward pass of leaky relu layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        Ok(self.layer.forward(input))
+    }
+
+    /// Backward pass of leaky relu layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.layer.zero_grad();
+    }
+
+    /// Leaky relu layer has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+}
+
+
+/// Sigmoid activation layer
+pub struct Sigmoid {
+    layer: ActivationLayer<nodes::Sigmoid<Value<NDArray<f64>>>>
+}
+
+
+impl Sigmoid {
+
+    /// Create new instance of sigmoid layer
+    pub fn new() -> Sigmoid {
+        Sigmoid { layer: ActivationLayer::new(nodes::Sigmoid::new) }
+    }
+
+}
+
+
+impl Default for Sigmoid {
+    fn default() -> Self {
+        Sigmoid::new()
+    }
+}
+
+
+impl Module for Sigmoid {
+
+    /// Forward pass of sigmoid layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        Ok(self.layer.forward(input))
+    }
+
+    /// Backward pass of sigmoid layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.layer.zero_grad();
+    }
+
+    /// Sigmoid layer has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+}
+
+
+/// Hyperbolic tangent activation layer
+pub struct Tanh {
+    layer: ActivationLayer<nodes::Tanh<Value<NDArray<f64>>>>
+}
+
+
+impl Tanh {
+
+    /// Create new instance of tanh layer
+    pub fn new() -> Tanh {
+        Tanh { layer: ActivationLayer::new(nodes::Tanh::new) }
+    }
+
+}
+
+
+impl Default for Tanh {
+    fn default() -> Self {
+        Tanh::new()
+    }
+}
+
+
+impl Module for Tanh {
+
+    /// Forward pass of tanh layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        Ok(self.layer.forward(input))
+    }
+
+    /// Backward pass of tanh layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.layer.zero_grad();
+    }
+
+    /// Tanh layer has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+}
+
+
+/// Row wise softmax layer, turns scores into class probabilities
+pub struct Softmax {
+    layer: ActivationLayer<nodes::Softmax<Value<NDArray<f64>>>>
+}
+
+
+impl Softmax {
+
+    /// Create new instance of softmax layer
+    pub fn new() -> Softmax {
+        Softmax { layer: ActivationLayer::new(nodes::Softmax::new) }
+    }
+
+}
+
+
+impl Default for Softmax {
+    fn default() -> Self {
+        Softmax::new()
+    }
+}
+
+
+impl Module for Softmax {
+
+    /// Forward pass of softmax layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        Ok(self.layer.forward(input))
+    }
+
+    /// Backward pass of softmax layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.layer.zero_grad();
+    }
+
+    /// Softmax layer has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+}
@@ -0,0 +1,440 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::{Node, Value};
+use dendritic_autodiff::ops::{Add, MatMul, Reshape};
+use dendritic_autodiff::attention::{ScaledDotProductAttention, sinusoidal_encoding};
+use rand::prelude::*;
+use dendritic_autodiff::init::{Init, Initializer};
+use crate::module::Module;
+use crate::layers::Dense;
+use crate::activations::ReLU;
+use crate::normalization::LayerNorm;
+
+
+/// Affine projection of flattened (batch * seq_len, d_model) input
+type Projection = Add<MatMul<Value<NDArray<f64>>, Value<NDArray<f64>>>, Value<NDArray<f64>>>;
+
+/// Attention over reshaped query, key and value projections
+type AttentionCore = ScaledDotProductAttention<Reshape<Projection>, Reshape<Projection>, Reshape<Projection>>;
+
+/// Full multi head attention graph ending in the output projection
+type AttentionNode = Add<MatMul<Reshape<AttentionCore>, Value<NDArray<f64>>>, Value<NDArray<f64>>>;
+
+/// Input plus broadcast positional encoding
+type EncodingNode = Add<Value<NDArray<f64>>, Value<NDArray<f64>>>;
+
+
+/// Weights drawn from U(-a, a) with a = sqrt(6 / (input_dim + output_dim))
+fn xavier(input_dim: usize, output_dim: usize, initializer: &mut Initializer) -> Value<NDArray<f64>> {
+    Value::new(&initializer.sample(vec![input_dim, output_dim]).unwrap())
+}
+
+
+/// Copy of array with a new shape
+fn reshape(input: &NDArray<f64>, shape: Vec<usize>) -> NDArray<f64> {
+    NDArray::array(shape, input.values().to_vec()).unwrap()
+}
+
+
+/// Elementwise sum of two arrays with the same shape
+fn add(a: &NDArray<f64>, b: &NDArray<f64>) -> NDArray<f64> {
+    let values: Vec<f64> = a.values().iter().zip(b.values().iter()).map(|(x, y)| x + y).collect();
+    NDArray::array(a.shape().values(), values).unwrap()
+}
+
+
+/// Multi head attention over (batch, seq_len, d_model) inputs.
+/// Query, key and value are projected, split into heads for scaled dot product
+/// attention and the concatenated heads are projected back to d_model features
+pub struct MultiHeadAttention {
+    pub query_weights: Value<NDArray<f64>>,
+    pub query_bias: Value<NDArray<f64>>,
+    pub key_weights: Value<NDArray<f64>>,
+    pub key_bias: Value<NDArray<f64>>,
+    pub value_weights: Value<NDArray<f64>>,
+    pub value_bias: Value<NDArray<f64>>,
+    pub output_weights: Value<NDArray<f64>>,
+    pub output_bias: Value<NDArray<f64>>,
+    num_heads: usize,
+    mask: Option<NDArray<f64>>,
+    inputs: Vec<Value<NDArray<f64>>>,
+    node: Option<AttentionNode>,
+    output_shape: Vec<usize>
+}
+
+
+impl MultiHeadAttention {
+
+    /// Create new instance of multi head attention with random initialization
+    pub fn new(d_model: usize, num_heads: usize) -> Result<MultiHeadAttention, String> {
+        let seed: u64 = rand::thread_rng().gen();
+        MultiHeadAttention::with_seed(d_model, num_heads, seed)
+    }
+
+    /// Create new instance of multi head attention with seeded initialization,
+    /// `d_model` must be divisible by `num_heads`
+    pub fn with_seed(
+        d_model: usize,
+        num_heads: usize,
+        seed: u64) -> Result<MultiHeadAttention, String> {
+
+        if num_heads == 0 || !d_model.is_multiple_of(num_heads) {
+            let msg = format!(
+                "MultiHeadAttention: Model size {} is not divisible by {} heads",
+                d_model, num_heads
+            );
+            return Err(msg);
+        }
+
+        let mut initializer = Initializer::new(Init::XavierUniform, seed);
+        let bias = || Value::new(&NDArray::new(vec![1, d_model]).unwrap());
+
+        Ok(MultiHeadAttention {
+            query_weights: xavier(d_model, d_model, &mut initializer),
+            query_bias: bias(),
+            key_weights: xavier(d_model, d_model, &mut initializer),
+            key_bias: bias(),
+            value_weights: xavier(d_model, d_model, &mut initializer),
+            value_bias: bias(),
+            output_weights: xavier(d_model, d_model, &mut initializer),
+            output_bias: bias(),
+            num_heads,
+            mask: None,
+            inputs: Vec::new(),
+            node: None,
+            output_shape: Vec::new()
+        })
+    }
+
+    /// Number of features of inputs and outputs
+    pub fn d_model(&self) -> usize {
+        self.query_weights.val().shape().dim(0)
+    }
+
+    /// Number of attention heads
+    pub fn num_heads(&self) -> usize {
+        self.num_heads
+    }
+
+    /// Set (query_len, key_len) or (batch, query_len, key_len) mask, zeros block attention
+    pub fn set_mask(&mut self, mask: &NDArray<f64>) {
+        self.mask = Some(mask.clone());
+    }
+
+    /// Remove mask so every position attends to every key
+    pub fn clear_mask(&mut self) {
+        self.mask = None;
+    }
+
+    /// Attention weights of last forward pass with shape (batch, heads, query_len, key_len)
+    pub fn attention_weights(&self) -> Option<NDArray<f64>> {
+        self.node.as_ref().map(|node| node.rhs.borrow().rhs.borrow().input.borrow().attention_weights())
+    }
+
+    /// Gradients with respect to query, key and value inputs of last backward pass
+    pub fn input_grads(&self) -> Vec<NDArray<f64>> {
+        self.inputs.iter()
+            .zip(self.input_shapes())
+            .map(|(input, shape)| reshape(&input.grad(), shape))
+            .collect()
+    }
+
+    /// Shapes of the last query, key and value inputs
+    fn input_shapes(&self) -> Vec<Vec<usize>> {
+        let d_model = self.d_model();
+        let (batch, query_len) = (self.output_shape[0], self.output_shape[1]);
+        let key_len = self.inputs[1].val().shape().dim(0) / batch;
+        vec![
+            vec![batch, query_len, d_model],
+            vec![batch, key_len, d_model],
+            vec![batch, key_len, d_model]
+        ]
+    }
+
+    /// Attend from query sequence to key and value sequences, all (batch, seq_len, d_model)
+    pub fn attend(
+        &mut self,
+        query: &NDArray<f64>,
+        key: &NDArray<f64>,
+        value: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+        let d_model = self.d_model();
+        for input in [query, key, value] {
+            if input.rank() != 3 || input.shape().dim(2) != d_model {
+                let msg = format!(
+                    "MultiHeadAttention: Expected input of shape (batch, seq_len, {}), got {:?}",
+                    d_model, input.shape().values()
+                );
+                return Err(msg);
+            }
+        }
+
+        let (batch, query_len) = (query.shape().dim(0), query.shape().dim(1));
+        let key_len = key.shape().dim(1);
+
+        self.inputs = [query, key, value].iter()
+            .map(|input| {
+                let rows = input.shape().dim(0) * input.shape().dim(1);
+                Value::new(&reshape(input, vec![rows, d_model]))
+            })
+            .collect();
+
+        let project = |input: &Value<NDArray<f64>>, weights: &Value<NDArray<f64>>, bias: &Value<NDArray<f64>>, len: usize| {
+            Reshape::new(
+                Add::new(MatMul::new(input.clone(), weights.clone()), bias.clone()),
+                vec![batch, len, d_model]
+            )
+        };
+
+        let mut core = ScaledDotProductAttention::with_heads(
+            project(&self.inputs[0], &self.query_weights, &self.query_bias, query_len),
+            project(&self.inputs[1], &self.key_weights, &self.key_bias, key_len),
+            project(&self.inputs[2], &self.value_weights, &self.value_bias, key_len),
+            self.num_heads
+        );
+        if self.mask.is_some() {
+            core.set_mask(self.mask.clone());
+        }
+
+        let node = Add::new(
+            MatMul::new(
+                Reshape::new(core, vec![batch * query_len, d_model]),
+                self.output_weights.clone()
+            ),
+            self.output_bias.clone()
+        );
+
+        self.output_shape = vec![batch, query_len, d_model];
+        let output = reshape(&node.value(), self.output_shape.clone());
+        self.node = Some(node);
+        Ok(output)
+    }
+
+}
+
+
+impl Module for MultiHeadAttention {
+
+    /// Self attention forward pass, input is used as query, key and value
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.attend(input, input, input)
+    }
+
+    /// Backward pass of attention, returns summed gradient of query, key and value inputs.
+    /// Use `input_grads` to get them separately
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+
+        let rows = self.output_shape[0] * self.output_shape[1];
+        let grad = reshape(&upstream_gradient, vec![rows, self.d_model()]);
+        match self.node.as_mut() {
+            Some(node) => node.backward(grad),
+            None => panic!("MultiHeadAttention: Backward called before forward")
+        }
+
+        let grads = self.input_grads();
+        let total = add(&add(&grads[0], &grads[1]), &grads[2]);
+        reshape(&total, self.output_shape.clone())
+    }
+
+    /// Projection weights and biases of query, key, value and output
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![
+            self.query_weights.clone(),
+            self.query_bias.clone(),
+            self.key_weights.clone(),
+            self.key_bias.clone(),
+            self.value_weights.clone(),
+            self.value_bias.clone(),
+            self.output_weights.clone(),
+            self.output_bias.clone()
+        ]
+    }
+
+    /// Names of projection weights and biases
+    fn parameter_names(&self) -> Vec<String> {
+        ["query", "key", "value", "output"].iter()
+            .flat_map(|projection| vec![format!("{}_weights", projection), format!("{}_bias", projection)])
+            .collect()
+    }
+
+}
+
+
+/// Adds sinusoidal positional encoding to (batch, seq_len, d_model) inputs
+pub struct PositionalEncoding {
+    encoding: NDArray<f64>,
+    input: Value<NDArray<f64>>,
+    node: Option<EncodingNode>
+}
+
+
+impl PositionalEncoding {
+
+    /// Create new instance of positional encoding for sequences up to `max_len` steps
+    pub fn new(max_len: usize, d_model: usize) -> PositionalEncoding {
+        PositionalEncoding {
+            encoding: sinusoidal_encoding(max_len, d_model),
+            input: Value::new(&NDArray::new(vec![1, 1, d_model]).unwrap()),
+            node: None
+        }
+    }
+
+    /// Encoding table of shape (max_len, d_model)
+    pub fn encoding(&self) -> NDArray<f64> {
+        self.encoding.clone()
+    }
+
+}
+
+
+impl Module for PositionalEncoding {
+
+    /// Add encoding of the first seq_len positions to every sample
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+        let (max_len, d_model) = (self.encoding.shape().dim(0), self.encoding.shape().dim(1));
+        let shape = input.shape().values();
+        if input.rank() != 3 || shape[1] > max_len || shape[2] != d_model {
+            let msg = format!(
+                "PositionalEncoding: Expected input of shape (batch, seq_len <= {}, {}), got {:?}",
+                max_len, d_model, shape
+            );
+            return Err(msg);
+        }
+
+        let positions = self.encoding.values()[..shape[1] * d_model].to_vec();
+        let positions = NDArray::array(vec![1, shape[1], d_model], positions).unwrap();
+
+        self.input = Value::new(input);
+        let node = Add::new(self.input.clone(), Value::new(&positions));
+        let output = node.value();
+        self.node = Some(node);
+        Ok(output)
+    }
+
+    /// Encoding is constant so gradient passes through unchanged
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        match self.node.as_mut() {
+            Some(node) => node.backward(upstream_gradient),
+            None => panic!("PositionalEncoding: Backward called before forward")
+        }
+        self.input.grad()
+    }
+
+    /// Positional encoding has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+}
+
+
+/// Transformer encoder block on (batch, seq_len, d_model) inputs.
+/// Computes h = norm(x + attention(x)) followed by out = norm(h + feed_forward(h))
+/// where the feed forward network is dense, relu, dense applied at every position
+pub struct TransformerEncoderLayer {
+    pub attention: MultiHeadAttention,
+    pub attention_norm: LayerNorm,
+    pub feed_forward_in: Dense,
+    pub feed_forward_out: Dense,
+    pub feed_forward_norm: LayerNorm,
+    activation: ReLU,
+    input_shape: Vec<usize>
+}
+
+
+impl TransformerEncoderLayer {
+
+    /// Create new instance of encoder layer with random initialization
+    pub fn new(
+        d_model: usize,
+        num_heads: usize,
+        feed_forward_dim: usize) -> Result<TransformerEncoderLayer, String> {
+        let seed: u64 = rand::thread_rng().gen();
+        TransformerEncoderLayer::with_seed(d_model, num_heads, feed_forward_dim, seed)
+    }
+
+    /// Create new instance of encoder layer with seeded initialization
+    pub fn with_seed(
+        d_model: usize,
+        num_heads: usize,
+        feed_forward_dim: usize,
+        seed: u64) -> Result<TransformerEncoderLayer, String> {
+
+        Ok(TransformerEncoderLayer {
+            attention: MultiHeadAttention::with_seed(d_model, num_heads, seed)?,
+            attention_norm: LayerNorm::new(d_model),
+            feed_forward_in: Dense::with_seed(d_model, feed_forward_dim, seed.wrapping_add(1)),
+            feed_forward_out: Dense::with_seed(feed_forward_dim, d_model, seed.wrapping_add(2)),
+            feed_forward_norm: LayerNorm::new(d_model),
+            activation: ReLU::new(),
+            input_shape: Vec::new()
+        })
+    }
+
+    /// Set attention mask, see `MultiHeadAttention::set_mask`
+    pub fn set_mask(&mut self, mask: &NDArray<f64>) {
+        self.attention.set_mask(mask);
+    }
+
+    /// Remove attention mask
+    pub fn clear_mask(&mut self) {
+        self.attention.clear_mask();
+    }
+
+}
+
+
+impl Module for TransformerEncoderLayer {
+
+    /// Forward pass of attention and feed forward sub layers
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+        let attended = self.attention.forward(input)?;
+        let hidden = self.attention_norm.forward(&add(input, &attended))?;
+
+        let shape = hidden.shape().values();
+        let rows = reshape(&hidden, vec![shape[0] * shape[1], shape[2]]);
+        let expanded = self.activation.forward(&self.feed_forward_in.forward(&rows)?)?;
+        let projected = reshape(&self.feed_forward_out.forward(&expanded)?, shape.clone());
+
+        self.input_shape = shape;
+        self.feed_forward_norm.forward(&add(&hidden, &projected))
+    }
+
+    /// Backward pass through both residual branches
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+
+        let shape = self.input_shape.clone();
+        let residual_grad = self.feed_forward_norm.backward(upstream_gradient);
+
+        let rows = reshape(&residual_grad, vec![shape[0] * shape[1], shape[2]]);
+        let expanded_grad = self.activation.backward(self.feed_forward_out.backward(rows));
+        let hidden_grad = reshape(&self.feed_forward_in.backward(expanded_grad), shape);
+        let hidden_grad = add(&residual_grad, &hidden_grad);
+
+        let attention_residual = self.attention_norm.backward(hidden_grad);
+        let attention_grad = self.attention.backward(attention_residual.clone());
+        add(&attention_residual, &attention_grad)
+    }
+
+    /// Parameters of attention, normalization and feed forward sub layers
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        let mut parameters = self.attention.parameters();
+        parameters.extend(self.attention_norm.parameters());
+        parameters.extend(self.feed_forward_in.parameters());
+        parameters.extend(self.feed_forward_out.parameters());
+        parameters.extend(self.feed_forward_norm.parameters());
+        parameters
+    }
+
+    /// Reset gradients of every sub layer
+    fn zero_grad(&mut self) {
+        self.attention.zero_grad();
+        self.attention_norm.zero_grad();
+        self.feed_forward_in.zero_grad();
+        self.activation.zero_grad();
+        self.feed_forward_out.zero_grad();
+        self.feed_forward_norm.zero_grad();
+    }
+
+}
@@ -0,0 +1,149 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::{Node, Value};
+use dendritic_autodiff::ops::{Add, MatMul};
+use dendritic_autodiff::init::{Init, Initializer};
+use rand::prelude::*;
+use dendritic_autodiff::state::Parameters;
+use crate::module::{Module, named_parameters};
+
+
+/// Graph computing the affine transform of a dense layer
+type DenseNode = Add<MatMul<Value<NDArray<f64>>, Value<NDArray<f64>>>, Value<NDArray<f64>>>;
+
+
+/// Fully connected layer computing `input * weights + bias`
+pub struct Dense {
+    pub weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>,
+    input: Value<NDArray<f64>>,
+    node: DenseNode
+}
+
+
+/// Alias for dense layer
+pub type Linear = Dense;
+
+
+impl Dense {
+
+    /// Create new instance of dense layer with randomly initialized weights
+    pub fn new(input_dim: usize, output_dim: usize) -> Dense {
+        let seed: u64 = rand::thread_rng().gen();
+        Dense::with_seed(input_dim, output_dim, seed)
+    }
+
+    /// Create new instance of dense layer with seeded weight initialization.
+    /// Weights are drawn from U(-a, a) with a = sqrt(6 / (input_dim + output_dim)), bias starts at zero
+    pub fn with_seed(input_dim: usize, output_dim: usize, seed: u64) -> Dense {
+        let mut initializer = Initializer::new(Init::XavierUniform, seed);
+        Dense::with_initializer(input_dim, output_dim, &mut initializer).unwrap()
+    }
+
+    /// Create new instance of dense layer with weights drawn from an initializer, bias starts at zero
+    pub fn with_initializer(
+        input_dim: usize,
+        output_dim: usize,
+        initializer: &mut Initializer) -> Result<Dense, String> {
+
+        let weights = initializer.sample(vec![input_dim, output_dim])?;
+        let bias = NDArray::new(vec![1, output_dim]).unwrap();
+        Dense::from_parameters(&weights, &bias)
+    }
+
+    /// Create new instance of dense layer from existing weights and bias
+    pub fn from_parameters(
+        weights: &NDArray<f64>,
+        bias: &NDArray<f64>) -> Result<Dense, String> {
+
+        if weights.rank() != 2 {
+            return Err("Dense: Weights must be of rank 2".to_string());
+        }
+
+        let output_dim = weights.shape().dim(1);
+        if bias.shape().values() != vec![1, output_dim] {
+            let msg = format!(
+                "Dense: Bias shape {:?} must be (1, {})",
+                bias.shape().values(), output_dim
+            );
+            return Err(msg);
+        }
+
+        let input = Value::new(&NDArray::new(vec![1, weights.shape().dim(0)]).unwrap());
+        let weights = Value::new(weights);
+        let bias = Value::new(bias);
+
+        let node = Add::new(
+            MatMul::new(input.clone(), weights.clone()),
+            bias.clone()
+        );
+
+        Ok(Dense {
+            weights,
+            bias,
+            input,
+            node
+        })
+    }
+
+    /// Number of input features of layer
+    pub fn input_dim(&self) -> usize {
+        self.weights.val().shape().dim(0)
+    }
+
+    /// Number of output features of layer
+    pub fn output_dim(&self) -> usize {
+        self.weights.val().shape().dim(1)
+    }
+
+}
+
+
+impl Module for Dense {
+
+    /// Forward pass of dense layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if input.rank() != 2 || input.shape().dim(1) != self.input_dim() {
+            let msg = format!(
+                "Dense: Input has shape {:?}, layer expects (batch, {})",
+                input.shape().values(), self.input_dim()
+            );
+            return Err(msg);
+        }
+
+        self.input.set_val(input);
+        self.node.forward();
+        Ok(self.node.value())
+    }
+
+    /// Backward pass of dense layer, adds weight and bias gradients
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.node.backward(upstream_gradient);
+        self.input.grad()
+    }
+
+    /// Reset gradients of weights, bias and the cached input
+    fn zero_grad(&mut self) {
+        self.node.zero_grad();
+    }
+
+    /// Weights and bias of dense layer
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.weights.clone(), self.bias.clone()]
+    }
+
+    /// Names of dense layer parameters
+    fn parameter_names(&self) -> Vec<String> {
+        vec!["weights".to_string(), "bias".to_string()]
+    }
+
+}
+
+
+impl Parameters for Dense {
+
+    /// Weights and bias of dense layer
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        named_parameters(self)
+    }
+
+}
@@ -0,0 +1,69 @@
+//! # Dendritic Neural Network Crate
+//!
+//! This crate contains layers and containers for building neural networks on top of the dendritic autodiff engine.
+//! Each layer computes its forward and backward pass with autodiff nodes and exposes its trainable parameters,
+//! so any optimizer from the autodiff crate can update them.
+//!
+//! ## Features
+//! - **Module**: Shared trait for layers and containers (forward, backward and named parameters), `Sequential` and `Dense` save and load state dicts
+//! - **Layers**: Dense (linear) layers with bias
+//! - **Activations**: ReLU, leaky relu, sigmoid, tanh and softmax layers
+//! - **Sequential**: Container chaining layers together with a `fit` loop for mini batch training
+//! - **Normalization**: Dropout, BatchNorm1d, BatchNorm2d and LayerNorm layers, switched with `train()` and `eval()`
+//! - **Recurrent**: RNN, LSTM and GRU cells with multi step wrappers, masking, truncated BPTT and bidirectional mode
+//! - **Attention**: Multi head attention with masking, sinusoidal positional encoding and a transformer encoder layer
+//!
+//! ## Example Usage
+//! This is an example of training a multi layer perceptron on the iris dataset
+//! ```rust
+//! use dendritic_ndarray::ndarray::NDArray;
+//! use dendritic_datasets::iris::*;
+//! use dendritic_preprocessing::encoding::*;
+//! use dendritic_preprocessing::standard_scalar::*;
+//! use dendritic_autodiff::loss::*;
+//! use dendritic_autodiff::optim::*;
+//! use dendritic_nn::module::*;
+//! use dendritic_nn::layers::*;
+//! use dendritic_nn::activations::*;
+//! use dendritic_nn::sequential::*;
+//!
+//! fn main() {
+//!
+//!     let data_path = "../dendritic-datasets/data/iris.parquet";
+//!     let (x_train, y_train) = load_iris(data_path).unwrap();
+//!     let x_train = min_max_scalar(x_train).unwrap();
+//!
+//!     let mut encoder = OneHotEncoding::new(y_train.clone()).unwrap();
+//!     let y_train_encoded = encoder.transform();
+//!
+//!     let mut model = Sequential::new();
+//!     model.add(Dense::new(4, 16));
+//!     model.add(ReLU::new());
+//!     model.add(Dense::new(16, 3));
+//!     model.add(Softmax::new());
+//!     model.set_optimizer(Adam::new(0.01));
+//!
+//!     let history = model.fit(
+//!         &x_train,
+//!         y_train_encoded,
+//!         CategoricalCrossEntropy::new,
+//!         100,
+//!         16,
+//!         true
+//!     ).unwrap();
+//!
+//!     let y_pred = model.predict(&x_train).unwrap();
+//! }
+//! ```
+//! ## Disclaimer
+//! The dendritic project is a toy machine learning library built for learning and research purposes.
+//! It is not advised by the maintainer to use this library as a production ready machine learning library.
+//! This is a project that is still very much a work in progress.
+
+pub mod module;
+pub mod layers;
+pub mod activations;
+pub mod sequential;
+pub mod normalization;
+pub mod recurrent;
+pub mod attention;
@@ -0,0 +1,45 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::Value;
+
+
+/// Methods shared by every layer and container in a network.
+/// `forward` errors when the input shape does not fit the module.
+/// `backward` takes the gradient of the loss with respect to the module output,
+/// adds gradients to the parameters and returns the gradient with respect to the input.
+/// Gradients are not reset by `backward`, call `zero_grad` before every backward pass.
+pub trait Module {
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String>;
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64>;
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>>;
+
+    /// Names of parameters in the order of `parameters`, defaults to their position
+    fn parameter_names(&self) -> Vec<String> {
+        (0..self.parameters().len()).map(|idx| idx.to_string()).collect()
+    }
+
+    /// Reset gradients of every parameter to zero, layers that cache their input reset it too
+    fn zero_grad(&mut self) {
+        for mut parameter in self.parameters() {
+            parameter.zero_grad();
+        }
+    }
+
+    /// Switch between training and evaluation behavior, layers without modes ignore it
+    fn set_training(&mut self, _training: bool) {}
+
+    /// Put module in training mode
+    fn train(&mut self) {
+        self.set_training(true);
+    }
+
+    /// Put module in evaluation mode
+    fn eval(&mut self) {
+        self.set_training(false);
+    }
+}
+
+
+/// Pair every parameter of a module with its name
+pub fn named_parameters<M: Module + ?Sized>(module: &M) -> Vec<(String, Value<NDArray<f64>>)> {
+    module.parameter_names().into_iter().zip(module.parameters()).collect()
+}
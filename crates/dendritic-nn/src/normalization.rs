@@ -0,0 +1,345 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::{Node, Value};
+use dendritic_autodiff::normalization as nodes;
+use rand::prelude::*;
+use crate::module::Module;
+
+
+/// Batch normalization node over value inputs
+type BatchNormNode = nodes::BatchNorm<Value<NDArray<f64>>, Value<NDArray<f64>>, Value<NDArray<f64>>>;
+
+/// Layer normalization node over value inputs
+type LayerNormNode = nodes::LayerNorm<Value<NDArray<f64>>, Value<NDArray<f64>>, Value<NDArray<f64>>>;
+
+
+/// Inverted dropout layer, only active in training mode
+pub struct Dropout {
+    input: Value<NDArray<f64>>,
+    node: nodes::Dropout<Value<NDArray<f64>>>
+}
+
+
+impl Dropout {
+
+    /// Create new instance of dropout layer with random seed
+    pub fn new(probability: f64) -> Result<Dropout, String> {
+        let seed: u64 = rand::thread_rng().gen();
+        Dropout::with_seed(probability, seed)
+    }
+
+    /// Create new instance of dropout layer with seeded masks
+    pub fn with_seed(probability: f64, seed: u64) -> Result<Dropout, String> {
+
+        if !(0.0..1.0).contains(&probability) {
+            return Err("Dropout: Probability must be in [0, 1)".to_string());
+        }
+
+        let input = Value::new(&NDArray::new(vec![1, 1]).unwrap());
+        let node = nodes::Dropout::new(input.clone(), probability, seed);
+        Ok(Dropout { input, node })
+    }
+
+    /// Check if layer is in training mode
+    pub fn is_training(&self) -> bool {
+        self.node.is_training()
+    }
+
+}
+
+
+impl Module for Dropout {
+
+    /// Forward pass of dropout layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.input.set_val(input);
+        self.node.forward();
+        Ok(self.node.value())
+    }
+
+    /// Backward pass of dropout layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.node.backward(upstream_gradient);
+        self.input.grad()
+    }
+
+    /// Reset gradient of the cached input
+    fn zero_grad(&mut self) {
+        self.node.zero_grad();
+    }
+
+    /// Dropout layer has no parameters
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        Vec::new()
+    }
+
+    /// Enable or disable dropout
+    fn set_training(&mut self, training: bool) {
+        if training { self.node.train() } else { self.node.eval() }
+    }
+
+}
+
+
+/// Shared state of batch normalization layers
+struct BatchNormLayer {
+    gamma: Value<NDArray<f64>>,
+    beta: Value<NDArray<f64>>,
+    input: Value<NDArray<f64>>,
+    node: BatchNormNode,
+    rank: usize
+}
+
+
+impl BatchNormLayer {
+
+    /// Create batch normalization for inputs of given rank with `num_features` channels
+    fn new(num_features: usize, rank: usize) -> BatchNormLayer {
+
+        let mut shape = vec![1, num_features];
+        shape.resize(rank, 1);
+
+        let gamma = Value::new(&NDArray::fill(vec![1, num_features], 1.0).unwrap());
+        let beta = Value::new(&NDArray::new(vec![1, num_features]).unwrap());
+        let input = Value::new(&NDArray::new(shape).unwrap());
+        let node = nodes::BatchNorm::new(input.clone(), gamma.clone(), beta.clone());
+
+        BatchNormLayer { gamma, beta, input, node, rank }
+    }
+
+    /// Forward pass, checks input rank first
+    fn forward(&mut self, input: &NDArray<f64>, name: &str) -> Result<NDArray<f64>, String> {
+        let num_features = self.gamma.val().shape().dim(1);
+        if input.rank() != self.rank || input.shape().dim(1) != num_features {
+            let msg = format!(
+                "{}: Expected input of rank {} with {} channels, got shape {:?}",
+                name, self.rank, num_features, input.shape().values()
+            );
+            return Err(msg);
+        }
+
+        self.input.set_val(input);
+        self.node.forward();
+        Ok(self.node.value())
+    }
+
+    /// Backward pass, returns gradient with respect to input
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.node.backward(upstream_gradient);
+        self.input.grad()
+    }
+
+    /// Switch node between batch and running statistics
+    fn set_training(&mut self, training: bool) {
+        if training { self.node.train() } else { self.node.eval() }
+    }
+
+}
+
+
+/// Batch normalization for (N, C) inputs
+pub struct BatchNorm1d {
+    layer: BatchNormLayer
+}
+
+
+impl BatchNorm1d {
+
+    /// Create new instance of batch normalization over `num_features` columns
+    pub fn new(num_features: usize) -> BatchNorm1d {
+        BatchNorm1d { layer: BatchNormLayer::new(num_features, 2) }
+    }
+
+    /// Set momentum used to update running statistics
+    pub fn set_momentum(&mut self, momentum: f64) {
+        self.layer.node.momentum = momentum;
+    }
+
+    /// Running mean of each feature
+    pub fn running_mean(&self) -> NDArray<f64> {
+        self.layer.node.running_mean()
+    }
+
+    /// Running variance of each feature
+    pub fn running_var(&self) -> NDArray<f64> {
+        self.layer.node.running_var()
+    }
+
+    /// Check if layer is in training mode
+    pub fn is_training(&self) -> bool {
+        self.layer.node.is_training()
+    }
+
+}
+
+
+impl Module for BatchNorm1d {
+
+    /// Forward pass of batch normalization layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.layer.forward(input, "BatchNorm1d")
+    }
+
+    /// Backward pass of batch normalization layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradients of scale, shift and the cached input
+    fn zero_grad(&mut self) {
+        self.layer.node.zero_grad();
+    }
+
+    /// Scale and shift of batch normalization layer
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.layer.gamma.clone(), self.layer.beta.clone()]
+    }
+
+    /// Names of scale and shift parameters
+    fn parameter_names(&self) -> Vec<String> {
+        vec!["gamma".to_string(), "beta".to_string()]
+    }
+
+    /// Use batch statistics in training and running statistics in evaluation
+    fn set_training(&mut self, training: bool) {
+        self.layer.set_training(training);
+    }
+
+}
+
+
+/// Batch normalization for (N, C, H, W) inputs, statistics are shared across each channel
+pub struct BatchNorm2d {
+    layer: BatchNormLayer
+}
+
+
+impl BatchNorm2d {
+
+    /// Create new instance of batch normalization over `num_channels` channels
+    pub fn new(num_channels: usize) -> BatchNorm2d {
+        BatchNorm2d { layer: BatchNormLayer::new(num_channels, 4) }
+    }
+
+    /// Set momentum used to update running statistics
+    pub fn set_momentum(&mut self, momentum: f64) {
+        self.layer.node.momentum = momentum;
+    }
+
+    /// Running mean of each channel
+    pub fn running_mean(&self) -> NDArray<f64> {
+        self.layer.node.running_mean()
+    }
+
+    /// Running variance of each channel
+    pub fn running_var(&self) -> NDArray<f64> {
+        self.layer.node.running_var()
+    }
+
+    /// Check if layer is in training mode
+    pub fn is_training(&self) -> bool {
+        self.layer.node.is_training()
+    }
+
+}
+
+
+impl Module for BatchNorm2d {
+
+    /// Forward pass of batch normalization layer
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.layer.forward(input, "BatchNorm2d")
+    }
+
+    /// Backward pass of batch normalization layer
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.layer.backward(upstream_gradient)
+    }
+
+    /// Reset gradients of scale, shift and the cached input
+    fn zero_grad(&mut self) {
+        self.layer.node.zero_grad();
+    }
+
+    /// Scale and shift of batch normalization layer
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.layer.gamma.clone(), self.layer.beta.clone()]
+    }
+
+    /// Names of scale and shift parameters
+    fn parameter_names(&self) -> Vec<String> {
+        vec!["gamma".to_string(), "beta".to_string()]
+    }
+
+    /// Use batch statistics in training and running statistics in evaluation
+    fn set_training(&mut self, training: bool) {
+        self.layer.set_training(training);
+    }
+
+}
+
+
+/// Layer normalization over the features of each sample
+pub struct LayerNorm {
+    pub gamma: Value<NDArray<f64>>,
+    pub beta: Value<NDArray<f64>>,
+    input: Value<NDArray<f64>>,
+    node: LayerNormNode
+}
+
+
+impl LayerNorm {
+
+    /// Create new instance of layer normalization over `normalized_dim` features
+    pub fn new(normalized_dim: usize) -> LayerNorm {
+
+        let gamma = Value::new(&NDArray::fill(vec![1, normalized_dim], 1.0).unwrap());
+        let beta = Value::new(&NDArray::new(vec![1, normalized_dim]).unwrap());
+        let input = Value::new(&NDArray::new(vec![1, normalized_dim]).unwrap());
+        let node = nodes::LayerNorm::new(input.clone(), gamma.clone(), beta.clone());
+
+        LayerNorm { gamma, beta, input, node }
+    }
+
+}
+
+
+impl Module for LayerNorm {
+
+    /// Forward pass of layer normalization
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        let normalized_dim = self.gamma.val().shape().dim(1);
+        if input.shape().values().last() != Some(&normalized_dim) {
+            let msg = format!(
+                "LayerNorm: Expected inputs with {} features in the last axis, got {:?}",
+                normalized_dim, input.shape().values()
+            );
+            return Err(msg);
+        }
+
+        self.input.set_val(input);
+        self.node.forward();
+        Ok(self.node.value())
+    }
+
+    /// Backward pass of layer normalization
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        self.node.backward(upstream_gradient);
+        self.input.grad()
+    }
+
+    /// Reset gradients of scale, shift and the cached input
+    fn zero_grad(&mut self) {
+        self.node.zero_grad();
+    }
+
+    /// Scale and shift of layer normalization
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.gamma.clone(), self.beta.clone()]
+    }
+
+    /// Names of scale and shift parameters
+    fn parameter_names(&self) -> Vec<String> {
+        vec!["gamma".to_string(), "beta".to_string()]
+    }
+
+}
@@ -0,0 +1,801 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::{Node, Value};
+use dendritic_autodiff::ops::{elementwise, Add, MatMul, Mul, Sub};
+use dendritic_autodiff::activations::{Sigmoid, Tanh};
+use dendritic_autodiff::custom::{CustomOp, Function};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use crate::module::Module;
+
+
+/// Parameter or input of a step graph
+type Param = Value<NDArray<f64>>;
+
+/// Columns of a parameter belonging to one gate
+type Slice = CustomOp<Block>;
+
+/// Pre activation x * W_k + h_prev * U_k + b_k of gate block k
+type GateInput = Add<Add<MatMul<Param, Slice>, MatMul<Param, Slice>>, Slice>;
+
+/// Elman step graph tanh(x * W + h_prev * U + b)
+type RNNNode = Tanh<Add<Add<MatMul<Param, Param>, MatMul<Param, Param>>, Param>>;
+
+/// Lstm cell state f * c_prev + i * g
+type LSTMStateNode = Add<Mul<Sigmoid<GateInput>, Param>, Mul<Sigmoid<GateInput>, Tanh<GateInput>>>;
+
+/// Lstm step graph o * tanh(c)
+type LSTMNode = Mul<Sigmoid<GateInput>, Tanh<LSTMStateNode>>;
+
+/// Gru candidate tanh(x * W_n + b_n + r * (h_prev * U_n))
+type GRUCandidate = Tanh<Add<Add<MatMul<Param, Slice>, Slice>, Mul<Sigmoid<GateInput>, MatMul<Param, Slice>>>>;
+
+/// Gru step graph (1 - z) * n + z * h_prev
+type GRUNode = Add<Mul<Sub<Param, Sigmoid<GateInput>>, GRUCandidate>, Mul<Sigmoid<GateInput>, Param>>;
+
+
+/// Combine two arrays elementwise with broadcasting
+fn zip(a: &NDArray<f64>, b: &NDArray<f64>, op: fn(f64, f64) -> f64) -> NDArray<f64> {
+    elementwise(a, b, op).unwrap()
+}
+
+
+/// Columns [start, end) of a rank 2 array
+fn columns(a: &NDArray<f64>, start: usize, end: usize) -> NDArray<f64> {
+    let cols = a.shape().dim(1);
+    let values: Vec<f64> = a.values()
+        .chunks(cols)
+        .flat_map(|row| row[start..end].to_vec())
+        .collect();
+    NDArray::array(vec![a.shape().dim(0), end - start], values).unwrap()
+}
+
+
+/// Concatenate rank 2 arrays along columns
+fn hstack(parts: &[NDArray<f64>]) -> NDArray<f64> {
+    let rows = parts[0].shape().dim(0);
+    let cols: usize = parts.iter().map(|p| p.shape().dim(1)).sum();
+    let mut values: Vec<f64> = Vec::with_capacity(rows * cols);
+    for row in 0..rows {
+        for part in parts {
+            let width = part.shape().dim(1);
+            values.extend_from_slice(&part.values()[row * width..(row + 1) * width]);
+        }
+    }
+    NDArray::array(vec![rows, cols], values).unwrap()
+}
+
+
+/// Uniform initialization in (-1 / sqrt(hidden), 1 / sqrt(hidden))
+fn uniform(shape: Vec<usize>, hidden_size: usize, rng: &mut StdRng) -> NDArray<f64> {
+    let limit = 1.0 / (hidden_size as f64).sqrt();
+    let size: usize = shape.iter().product();
+    let values: Vec<f64> = (0..size).map(|_| rng.gen_range(-limit..limit)).collect();
+    NDArray::array(shape, values).unwrap()
+}
+
+
+/// Columns [start, end) of a parameter, the gradient is scattered back into the full parameter
+struct Block {
+    start: usize,
+    end: usize
+}
+
+
+impl Function for Block {
+
+    /// Select block columns
+    fn forward(&self, inputs: &[NDArray<f64>]) -> Result<NDArray<f64>, String> {
+        Ok(columns(&inputs[0], self.start, self.end))
+    }
+
+    /// Place upstream gradient in the block columns, other columns get zero
+    fn backward(
+        &self,
+        inputs: &[NDArray<f64>],
+        _output: &NDArray<f64>,
+        upstream_gradient: &NDArray<f64>) -> Result<Vec<NDArray<f64>>, String> {
+
+        let shape = inputs[0].shape().values();
+        let (cols, width) = (shape[1], self.end - self.start);
+        let mut values = vec![0.0; shape[0] * cols];
+        for (row, grad) in upstream_gradient.values().chunks(width).enumerate() {
+            values[row * cols + self.start..row * cols + self.end].copy_from_slice(grad);
+        }
+        Ok(vec![NDArray::array(shape, values)?])
+    }
+
+    fn name(&self) -> String {
+        "Block".to_string()
+    }
+
+}
+
+
+/// Block `gate` of a parameter holding gates of width `size` side by side
+fn block(parameter: &Param, gate: usize, size: usize) -> Slice {
+    let function = Block { start: gate * size, end: (gate + 1) * size };
+    CustomOp::new(function, vec![Box::new(parameter.clone())]).unwrap()
+}
+
+
+/// Gate pre activation built from the gate blocks of W, U and b
+fn gate_input(
+    parameters: [&Param; 3],
+    input: &Param,
+    hidden_prev: &Param,
+    gate: usize,
+    size: usize) -> GateInput {
+
+    let [weights, recurrent_weights, bias] = parameters;
+    Add::new(
+        Add::new(
+            MatMul::new(input.clone(), block(weights, gate, size)),
+            MatMul::new(hidden_prev.clone(), block(recurrent_weights, gate, size))
+        ),
+        block(bias, gate, size)
+    )
+}
+
+
+/// Methods shared by recurrent cells that process one timestep of a batch.
+/// State is a list of (batch, hidden) arrays, the first entry is the hidden output.
+/// `step` builds a computation graph over the shared parameter values and
+/// `backward_step` backpropagates it, adding parameter gradients to the stored gradients
+/// and returning the gradient with respect to the input and the previous state.
+pub trait RecurrentCell {
+    type Cache;
+
+    fn input_size(&self) -> usize;
+    fn hidden_size(&self) -> usize;
+    fn num_states(&self) -> usize;
+    fn step(&self, input: &NDArray<f64>, state: &[NDArray<f64>]) -> (Vec<NDArray<f64>>, Self::Cache);
+    fn backward_step(
+        &mut self,
+        cache: &mut Self::Cache,
+        state_grad: &[NDArray<f64>]) -> (NDArray<f64>, Vec<NDArray<f64>>);
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>>;
+
+    /// Reset gradients of every parameter to zeros
+    fn zero_grad(&mut self) {
+        for mut parameter in self.parameters() {
+            parameter.zero_grad();
+        }
+    }
+}
+
+
+/// Elman recurrent cell with h = tanh(x * W + h_prev * U + b)
+pub struct RNNCell {
+    pub weights: Value<NDArray<f64>>,
+    pub recurrent_weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>
+}
+
+
+/// Graph and step values saved by the forward step of a rnn cell
+pub struct RNNCache {
+    input: Param,
+    hidden_prev: Param,
+    node: RNNNode
+}
+
+
+impl RNNCell {
+
+    /// Create new instance of rnn cell with random initialization
+    pub fn new(input_size: usize, hidden_size: usize) -> RNNCell {
+        let seed: u64 = rand::thread_rng().gen();
+        RNNCell::with_seed(input_size, hidden_size, seed)
+    }
+
+    /// Create new instance of rnn cell with seeded initialization
+    pub fn with_seed(input_size: usize, hidden_size: usize, seed: u64) -> RNNCell {
+        let mut rng = StdRng::seed_from_u64(seed);
+        RNNCell {
+            weights: Value::new(&uniform(vec![input_size, hidden_size], hidden_size, &mut rng)),
+            recurrent_weights: Value::new(&uniform(vec![hidden_size, hidden_size], hidden_size, &mut rng)),
+            bias: Value::new(&uniform(vec![1, hidden_size], hidden_size, &mut rng))
+        }
+    }
+
+}
+
+
+impl RecurrentCell for RNNCell {
+    type Cache = RNNCache;
+
+    fn input_size(&self) -> usize {
+        self.weights.val().shape().dim(0)
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.weights.val().shape().dim(1)
+    }
+
+    fn num_states(&self) -> usize {
+        1
+    }
+
+    /// Compute next hidden state
+    fn step(&self, input: &NDArray<f64>, state: &[NDArray<f64>]) -> (Vec<NDArray<f64>>, RNNCache) {
+        let input = Value::new(input);
+        let hidden_prev = Value::new(&state[0]);
+
+        let node = Tanh::new(
+            Add::new(
+                Add::new(
+                    MatMul::new(input.clone(), self.weights.clone()),
+                    MatMul::new(hidden_prev.clone(), self.recurrent_weights.clone())
+                ),
+                self.bias.clone()
+            )
+        );
+
+        (vec![node.value()], RNNCache { input, hidden_prev, node })
+    }
+
+    /// Backpropagate through one step of the rnn cell
+    fn backward_step(
+        &mut self,
+        cache: &mut RNNCache,
+        state_grad: &[NDArray<f64>]) -> (NDArray<f64>, Vec<NDArray<f64>>) {
+
+        cache.node.backward(state_grad[0].clone());
+        (cache.input.grad(), vec![cache.hidden_prev.grad()])
+    }
+
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.weights.clone(), self.recurrent_weights.clone(), self.bias.clone()]
+    }
+
+}
+
+
+/// Long short term memory cell with input, forget, cell and output gates (in that order).
+/// Gate weights are stored side by side, W has shape (input, 4 * hidden)
+pub struct LSTMCell {
+    pub weights: Value<NDArray<f64>>,
+    pub recurrent_weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>
+}
+
+
+/// Graph and step values saved by the forward step of a lstm cell
+pub struct LSTMCache {
+    input: Param,
+    hidden_prev: Param,
+    cell_prev: Param,
+    node: LSTMNode
+}
+
+
+impl LSTMCell {
+
+    /// Create new instance of lstm cell with random initialization
+    pub fn new(input_size: usize, hidden_size: usize) -> LSTMCell {
+        let seed: u64 = rand::thread_rng().gen();
+        LSTMCell::with_seed(input_size, hidden_size, seed)
+    }
+
+    /// Create new instance of lstm cell with seeded initialization, forget gate bias starts at 1
+    pub fn with_seed(input_size: usize, hidden_size: usize, seed: u64) -> LSTMCell {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let weights = uniform(vec![input_size, 4 * hidden_size], hidden_size, &mut rng);
+        let recurrent_weights = uniform(vec![hidden_size, 4 * hidden_size], hidden_size, &mut rng);
+        let mut bias = NDArray::new(vec![1, 4 * hidden_size]).unwrap();
+        for idx in hidden_size..2 * hidden_size {
+            bias.set_idx(idx, 1.0).unwrap();
+        }
+
+        LSTMCell {
+            weights: Value::new(&weights),
+            recurrent_weights: Value::new(&recurrent_weights),
+            bias: Value::new(&bias)
+        }
+    }
+
+}
+
+
+impl RecurrentCell for LSTMCell {
+    type Cache = LSTMCache;
+
+    fn input_size(&self) -> usize {
+        self.weights.val().shape().dim(0)
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.recurrent_weights.val().shape().dim(0)
+    }
+
+    fn num_states(&self) -> usize {
+        2
+    }
+
+    /// Compute next hidden and cell state
+    fn step(&self, input: &NDArray<f64>, state: &[NDArray<f64>]) -> (Vec<NDArray<f64>>, LSTMCache) {
+        let input = Value::new(input);
+        let hidden_prev = Value::new(&state[0]);
+        let cell_prev = Value::new(&state[1]);
+
+        let size = self.hidden_size();
+        let parameters = [&self.weights, &self.recurrent_weights, &self.bias];
+        let gate = |idx: usize| gate_input(parameters, &input, &hidden_prev, idx, size);
+
+        let cell = Add::new(
+            Mul::new(Sigmoid::new(gate(1)), cell_prev.clone()),
+            Mul::new(Sigmoid::new(gate(0)), Tanh::new(gate(2)))
+        );
+        let node = Mul::new(Sigmoid::new(gate(3)), Tanh::new(cell));
+
+        let cell = node.lhs.borrow().input.borrow().value();
+        let cache = LSTMCache { input, hidden_prev, cell_prev, node };
+        (vec![cache.node.value(), cell], cache)
+    }
+
+    /// Backpropagate through one step of the lstm cell
+    fn backward_step(
+        &mut self,
+        cache: &mut LSTMCache,
+        state_grad: &[NDArray<f64>]) -> (NDArray<f64>, Vec<NDArray<f64>>) {
+
+        cache.node.backward(state_grad[0].clone());
+
+        /* gradient of the cell state output enters below the tanh */
+        cache.node.lhs.get_mut().input.get_mut().backward(state_grad[1].clone());
+
+        let state_grads = vec![cache.hidden_prev.grad(), cache.cell_prev.grad()];
+        (cache.input.grad(), state_grads)
+    }
+
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.weights.clone(), self.recurrent_weights.clone(), self.bias.clone()]
+    }
+
+}
+
+
+/// Gated recurrent unit cell with reset, update and candidate gates (in that order).
+/// Candidate is n = tanh(x * W_n + b_n + r * (h_prev * U_n)), h = (1 - z) * n + z * h_prev
+pub struct GRUCell {
+    pub weights: Value<NDArray<f64>>,
+    pub recurrent_weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>
+}
+
+
+/// Graph and step values saved by the forward step of a gru cell
+pub struct GRUCache {
+    input: Param,
+    hidden_prev: Param,
+    node: GRUNode
+}
+
+
+impl GRUCell {
+
+    /// Create new instance of gru cell with random initialization
+    pub fn new(input_size: usize, hidden_size: usize) -> GRUCell {
+        let seed: u64 = rand::thread_rng().gen();
+        GRUCell::with_seed(input_size, hidden_size, seed)
+    }
+
+    /// Create new instance of gru cell with seeded initialization
+    pub fn with_seed(input_size: usize, hidden_size: usize, seed: u64) -> GRUCell {
+        let mut rng = StdRng::seed_from_u64(seed);
+        GRUCell {
+            weights: Value::new(&uniform(vec![input_size, 3 * hidden_size], hidden_size, &mut rng)),
+            recurrent_weights: Value::new(&uniform(vec![hidden_size, 3 * hidden_size], hidden_size, &mut rng)),
+            bias: Value::new(&uniform(vec![1, 3 * hidden_size], hidden_size, &mut rng))
+        }
+    }
+
+}
+
+
+impl RecurrentCell for GRUCell {
+    type Cache = GRUCache;
+
+    fn input_size(&self) -> usize {
+        self.weights.val().shape().dim(0)
+    }
+
+    fn hidden_size(&self) -> usize {
+        self.recurrent_weights.val().shape().dim(0)
+    }
+
+    fn num_states(&self) -> usize {
+        1
+    }
+
+    /// Compute next hidden state
+    fn step(&self, input: &NDArray<f64>, state: &[NDArray<f64>]) -> (Vec<NDArray<f64>>, GRUCache) {
+        let input = Value::new(input);
+        let hidden_prev = Value::new(&state[0]);
+
+        let size = self.hidden_size();
+        let parameters = [&self.weights, &self.recurrent_weights, &self.bias];
+        let gate = |idx: usize| gate_input(parameters, &input, &hidden_prev, idx, size);
+
+        let candidate = Tanh::new(
+            Add::new(
+                Add::new(MatMul::new(input.clone(), block(&self.weights, 2, size)), block(&self.bias, 2, size)),
+                Mul::new(Sigmoid::new(gate(0)), MatMul::new(hidden_prev.clone(), block(&self.recurrent_weights, 2, size)))
+            )
+        );
+
+        /* nodes own their inputs, so the update gate is built once for each term */
+        let ones = Value::new(&NDArray::fill(vec![1, size], 1.0).unwrap());
+        let node = Add::new(
+            Mul::new(Sub::new(ones, Sigmoid::new(gate(1))), candidate),
+            Mul::new(Sigmoid::new(gate(1)), hidden_prev.clone())
+        );
+
+        (vec![node.value()], GRUCache { input, hidden_prev, node })
+    }
+
+    /// Backpropagate through one step of the gru cell
+    fn backward_step(
+        &mut self,
+        cache: &mut GRUCache,
+        state_grad: &[NDArray<f64>]) -> (NDArray<f64>, Vec<NDArray<f64>>) {
+
+        cache.node.backward(state_grad[0].clone());
+        (cache.input.grad(), vec![cache.hidden_prev.grad()])
+    }
+
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        vec![self.weights.clone(), self.recurrent_weights.clone(), self.bias.clone()]
+    }
+
+}
+
+
+/// Build (batch, seq_len) mask with ones for the first `lengths[b]` steps of each sample
+pub fn sequence_mask(lengths: &[usize], seq_len: usize) -> Result<NDArray<f64>, String> {
+    if lengths.iter().any(|l| *l > seq_len) {
+        return Err("Sequence length larger than number of timesteps".to_string());
+    }
+
+    let values: Vec<f64> = lengths.iter()
+        .flat_map(|l| (0..seq_len).map(move |t| if t < *l { 1.0 } else { 0.0 }))
+        .collect();
+    NDArray::array(vec![lengths.len(), seq_len], values)
+}
+
+
+/// Rows of (batch, seq_len, features) input at timestep t
+fn timestep(input: &NDArray<f64>, t: usize) -> NDArray<f64> {
+    let shape = input.shape().values();
+    let (seq_len, features) = (shape[1], shape[2]);
+    let values: Vec<f64> = (0..shape[0])
+        .flat_map(|b| {
+            let start = (b * seq_len + t) * features;
+            input.values()[start..start + features].to_vec()
+        })
+        .collect();
+    NDArray::array(vec![shape[0], features], values).unwrap()
+}
+
+
+/// Keep rows of `next` where mask is one and rows of `prev` where it is zero
+fn select(mask: &[f64], next: &NDArray<f64>, prev: &NDArray<f64>) -> NDArray<f64> {
+    let cols = next.shape().dim(1);
+    let values: Vec<f64> = next.values().iter()
+        .zip(prev.values().iter())
+        .enumerate()
+        .map(|(idx, (n, p))| if mask[idx / cols] > 0.0 { *n } else { *p })
+        .collect();
+    NDArray::array(next.shape().values(), values).unwrap()
+}
+
+
+/// Scale rows by mask
+fn mask_rows(mask: &[f64], input: &NDArray<f64>) -> NDArray<f64> {
+    let cols = input.shape().dim(1);
+    let values: Vec<f64> = input.values().iter()
+        .enumerate()
+        .map(|(idx, v)| v * mask[idx / cols])
+        .collect();
+    NDArray::array(input.shape().values(), values).unwrap()
+}
+
+
+/// Forward pass results of one direction
+struct Pass<C: RecurrentCell> {
+    caches: Vec<C::Cache>,
+    outputs: Vec<NDArray<f64>>,
+    final_hidden: NDArray<f64>
+}
+
+
+/// Multi step wrapper running a recurrent cell over (batch, seq_len, features) inputs.
+/// Output is (batch, seq_len, hidden) or the last hidden state (batch, hidden)
+/// when `return_sequences` is off. Bidirectional wrappers concatenate both directions.
+pub struct Recurrent<C: RecurrentCell> {
+    pub return_sequences: bool,
+    forward_cell: C,
+    backward_cell: Option<C>,
+    mask: Option<NDArray<f64>>,
+    bptt_steps: Option<usize>,
+    passes: Vec<Pass<C>>,
+    input_shape: Vec<usize>
+}
+
+
+/// Recurrent layer with elman cells
+pub type RNN = Recurrent<RNNCell>;
+
+/// Recurrent layer with lstm cells
+pub type LSTM = Recurrent<LSTMCell>;
+
+/// Recurrent layer with gru cells
+pub type GRU = Recurrent<GRUCell>;
+
+
+impl<C: RecurrentCell> Recurrent<C> {
+
+    /// Create new instance of recurrent layer running cell from first to last step
+    pub fn new(cell: C) -> Recurrent<C> {
+        Recurrent {
+            return_sequences: true,
+            forward_cell: cell,
+            backward_cell: None,
+            mask: None,
+            bptt_steps: None,
+            passes: Vec::new(),
+            input_shape: Vec::new()
+        }
+    }
+
+    /// Create new instance of bidirectional recurrent layer,
+    /// second cell runs from last to first step
+    pub fn bidirectional(forward_cell: C, backward_cell: C) -> Result<Recurrent<C>, String> {
+        if forward_cell.input_size() != backward_cell.input_size()
+            || forward_cell.hidden_size() != backward_cell.hidden_size() {
+            return Err("Recurrent: Bidirectional cells must have matching sizes".to_string());
+        }
+
+        let mut layer = Recurrent::new(forward_cell);
+        layer.backward_cell = Some(backward_cell);
+        Ok(layer)
+    }
+
+    /// Check if layer runs in both directions
+    pub fn is_bidirectional(&self) -> bool {
+        self.backward_cell.is_some()
+    }
+
+    /// Return full output sequence or only the last hidden state
+    pub fn set_return_sequences(&mut self, return_sequences: bool) {
+        self.return_sequences = return_sequences;
+    }
+
+    /// Set (batch, seq_len) mask for the next passes, masked steps keep the previous state
+    /// and produce zero output. Use `sequence_mask` to build one from sequence lengths
+    pub fn set_mask(&mut self, mask: &NDArray<f64>) {
+        self.mask = Some(mask.clone());
+    }
+
+    /// Remove mask so every step is processed
+    pub fn clear_mask(&mut self) {
+        self.mask = None;
+    }
+
+    /// Truncate backpropagation through time to chunks of `steps` timesteps,
+    /// `None` backpropagates through the full sequence
+    pub fn set_bptt_steps(&mut self, steps: Option<usize>) {
+        self.bptt_steps = steps.map(|s| s.max(1));
+    }
+
+    /// Number of output features per timestep
+    pub fn output_size(&self) -> usize {
+        let directions = if self.is_bidirectional() { 2 } else { 1 };
+        self.forward_cell.hidden_size() * directions
+    }
+
+    /// Mask column for timestep t
+    fn mask_at(&self, t: usize, batch: usize) -> Vec<f64> {
+        match &self.mask {
+            Some(mask) => {
+                let seq_len = mask.shape().dim(1);
+                (0..batch).map(|b| mask.values()[b * seq_len + t]).collect()
+            },
+            None => vec![1.0; batch]
+        }
+    }
+
+    /// Order timesteps are processed in for a direction
+    fn order(seq_len: usize, reverse: bool) -> Vec<usize> {
+        if reverse { (0..seq_len).rev().collect() } else { (0..seq_len).collect() }
+    }
+
+    /// Run one direction over the sequence
+    fn run(&self, cell: &C, input: &NDArray<f64>, reverse: bool) -> Pass<C> {
+
+        let shape = input.shape().values();
+        let (batch, seq_len) = (shape[0], shape[1]);
+        let zeros = NDArray::new(vec![batch, cell.hidden_size()]).unwrap();
+        let mut state: Vec<NDArray<f64>> = vec![zeros.clone(); cell.num_states()];
+
+        let mut caches: Vec<C::Cache> = Vec::new();
+        let mut outputs: Vec<NDArray<f64>> = vec![zeros; seq_len];
+        for t in Recurrent::<C>::order(seq_len, reverse) {
+            let mask = self.mask_at(t, batch);
+            let (next, cache) = cell.step(&timestep(input, t), &state);
+
+            state = next.iter()
+                .zip(state.iter())
+                .map(|(n, p)| select(&mask, n, p))
+                .collect();
+            outputs[t] = mask_rows(&mask, &state[0]);
+            caches.push(cache);
+        }
+
+        Pass { caches, outputs, final_hidden: state[0].clone() }
+    }
+
+    /// Backpropagate one direction, returns gradient with respect to each input timestep
+    fn run_backward(
+        cell: &mut C,
+        pass: &mut Pass<C>,
+        masks: &[Vec<f64>],
+        output_grads: &[NDArray<f64>],
+        final_grad: &NDArray<f64>,
+        bptt_steps: Option<usize>,
+        reverse: bool) -> Vec<NDArray<f64>> {
+
+        let seq_len = output_grads.len();
+        let zeros = NDArray::new(final_grad.shape().values()).unwrap();
+        let mut state_grad: Vec<NDArray<f64>> = vec![zeros.clone(); cell.num_states()];
+        state_grad[0] = final_grad.clone();
+
+        let order = Recurrent::<C>::order(seq_len, reverse);
+        let mut input_grads: Vec<NDArray<f64>> = vec![NDArray::new(vec![1, 1]).unwrap(); seq_len];
+        for (position, t) in order.iter().enumerate().rev() {
+            let mask = &masks[*t];
+            state_grad[0] = zip(&state_grad[0], &mask_rows(mask, &output_grads[*t]), |a, b| a + b);
+
+            /* masked rows pass their gradient straight to the previous state */
+            let active: Vec<NDArray<f64>> = state_grad.iter().map(|g| mask_rows(mask, g)).collect();
+            let (x_grad, prev_grad) = cell.backward_step(&mut pass.caches[position], &active);
+            input_grads[*t] = x_grad;
+
+            state_grad = prev_grad.iter()
+                .zip(state_grad.iter())
+                .map(|(p, g)| select(mask, p, g))
+                .collect();
+
+            if let Some(steps) = bptt_steps {
+                if position % steps == 0 {
+                    state_grad = vec![zeros.clone(); cell.num_states()];
+                }
+            }
+        }
+
+        input_grads
+    }
+
+}
+
+
+impl<C: RecurrentCell> Module for Recurrent<C> {
+
+    /// Forward pass over every timestep of (batch, seq_len, features) input
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+        let shape = input.shape().values();
+        if input.rank() != 3 || shape[2] != self.forward_cell.input_size() {
+            let msg = format!(
+                "Recurrent: Expected input of shape (batch, seq_len, {}), got {:?}",
+                self.forward_cell.input_size(), shape
+            );
+            return Err(msg);
+        }
+
+        if let Some(mask) = &self.mask {
+            if mask.shape().values() != vec![shape[0], shape[1]] {
+                let msg = format!(
+                    "Recurrent: Mask shape {:?} does not match (batch, seq_len) {:?}",
+                    mask.shape().values(), &shape[..2]
+                );
+                return Err(msg);
+            }
+        }
+
+        let mut passes = vec![self.run(&self.forward_cell, input, false)];
+        if let Some(cell) = &self.backward_cell {
+            passes.push(self.run(cell, input, true));
+        }
+
+        let (batch, seq_len) = (shape[0], shape[1]);
+        let output = if self.return_sequences {
+            let mut values: Vec<f64> = Vec::new();
+            for b in 0..batch {
+                for t in 0..seq_len {
+                    for pass in passes.iter() {
+                        let hidden = pass.outputs[t].shape().dim(1);
+                        values.extend_from_slice(&pass.outputs[t].values()[b * hidden..(b + 1) * hidden]);
+                    }
+                }
+            }
+            NDArray::array(vec![batch, seq_len, self.output_size()], values).unwrap()
+        } else {
+            let finals: Vec<NDArray<f64>> = passes.iter().map(|p| p.final_hidden.clone()).collect();
+            hstack(&finals)
+        };
+
+        self.passes = passes;
+        self.input_shape = shape;
+        Ok(output)
+    }
+
+    /// Backpropagation through time, returns gradient with respect to the input sequence
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+
+        let (batch, seq_len, features) = (self.input_shape[0], self.input_shape[1], self.input_shape[2]);
+        let hidden = self.forward_cell.hidden_size();
+        let masks: Vec<Vec<f64>> = (0..seq_len).map(|t| self.mask_at(t, batch)).collect();
+        let zeros = NDArray::new(vec![batch, hidden]).unwrap();
+
+        let directions = self.passes.len();
+        let mut grads: Vec<Vec<NDArray<f64>>> = Vec::new();
+        for direction in 0..directions {
+
+            /* split upstream gradient into per step gradients of this direction */
+            let (output_grads, final_grad) = if self.return_sequences {
+                let width = self.output_size();
+                let steps: Vec<NDArray<f64>> = (0..seq_len).map(|t| {
+                    let values: Vec<f64> = (0..batch).flat_map(|b| {
+                        let start = (b * seq_len + t) * width + direction * hidden;
+                        upstream_gradient.values()[start..start + hidden].to_vec()
+                    }).collect();
+                    NDArray::array(vec![batch, hidden], values).unwrap()
+                }).collect();
+                (steps, zeros.clone())
+            } else {
+                let final_grad = columns(&upstream_gradient, direction * hidden, (direction + 1) * hidden);
+                (vec![zeros.clone(); seq_len], final_grad)
+            };
+
+            let cell = if direction == 0 {
+                &mut self.forward_cell
+            } else {
+                self.backward_cell.as_mut().unwrap()
+            };
+
+            grads.push(Recurrent::run_backward(
+                cell,
+                &mut self.passes[direction],
+                &masks,
+                &output_grads,
+                &final_grad,
+                self.bptt_steps,
+                direction == 1
+            ));
+        }
+
+        let mut values = vec![0.0; batch * seq_len * features];
+        for direction_grads in grads.iter() {
+            for (t, grad) in direction_grads.iter().enumerate() {
+                for b in 0..batch {
+                    for f in 0..features {
+                        values[(b * seq_len + t) * features + f] += grad.values()[b * features + f];
+                    }
+                }
+            }
+        }
+
+        NDArray::array(vec![batch, seq_len, features], values).unwrap()
+    }
+
+    /// Parameters of forward cell followed by backward cell
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        let mut parameters = self.forward_cell.parameters();
+        if let Some(cell) = &self.backward_cell {
+            parameters.extend(cell.parameters());
+        }
+        parameters
+    }
+
+}
@@ -0,0 +1,230 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::Value;
+use dendritic_autodiff::loss::Loss;
+use dendritic_autodiff::optim::{Optimizer, SGD};
+use dendritic_autodiff::anomaly::{detect_forward_anomaly, detect_backward_anomaly};
+use dendritic_autodiff::scheduler::Scheduler;
+use dendritic_autodiff::state::Parameters;
+use crate::module::{Module, named_parameters};
+
+
+/// Split an ndarray along its first axis into consecutive batches, last batch keeps the remainder
+pub fn batches(
+    input: &NDArray<f64>,
+    batch_size: usize) -> Result<Vec<NDArray<f64>>, String> {
+
+    if batch_size == 0 {
+        return Err("Batch size must be greater than 0".to_string());
+    }
+
+    if input.rank() < 2 {
+        return Err("NDArray must be at least of rank 2".to_string());
+    }
+
+    let shape = input.shape().values();
+    let rows = shape[0];
+    let cols: usize = shape[1..].iter().product();
+    let mut result: Vec<NDArray<f64>> = Vec::new();
+
+    for start in (0..rows).step_by(batch_size) {
+        let end = (start + batch_size).min(rows);
+        let values = input.values()[start * cols..end * cols].to_vec();
+        let mut batch_shape = shape.clone();
+        batch_shape[0] = end - start;
+        result.push(NDArray::array(batch_shape, values)?);
+    }
+
+    Ok(result)
+}
+
+
+/// Container that chains modules, output of each layer is the input of the next
+pub struct Sequential {
+    layers: Vec<Box<dyn Module>>,
+    optimizer: Box<dyn Optimizer>,
+    scheduler: Option<Box<dyn Scheduler>>
+}
+
+
+impl Sequential {
+
+    /// Create new empty sequential container trained with gradient descent (lr 0.01)
+    pub fn new() -> Sequential {
+        Sequential {
+            layers: Vec::new(),
+            optimizer: Box::new(SGD::new(0.01)),
+            scheduler: None
+        }
+    }
+
+    /// Append layer to end of container
+    pub fn add<M: Module + 'static>(&mut self, layer: M) {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// Number of layers in container
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Set optimizer used to update parameters during training
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+    /// Set scheduler that updates the optimizer learning rate after every epoch
+    pub fn set_scheduler<S: Scheduler + 'static>(&mut self, scheduler: S) {
+        self.optimizer.set_learning_rate(scheduler.learning_rate());
+        self.scheduler = Some(Box::new(scheduler));
+    }
+
+    /// Make prediction with forward pass through every layer
+    pub fn predict(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.forward(input)
+    }
+
+    /// Train container with mini batch gradient descent, layers are put in training mode.
+    /// Gradients of every layer are reset before each batch.
+    /// `loss_builder` creates the loss node from prediction and target values,
+    /// for example `MSE::new` or `CategoricalCrossEntropy::new`.
+    /// Returns average loss of every epoch
+    pub fn fit<L, F>(
+        &mut self,
+        x: &NDArray<f64>,
+        y: &NDArray<f64>,
+        loss_builder: F,
+        epochs: usize,
+        batch_size: usize,
+        log_output: bool) -> Result<Vec<f64>, String>
+    where
+        L: Loss,
+        F: Fn(Value<NDArray<f64>>, Value<NDArray<f64>>) -> L,
+    {
+
+        if self.layers.is_empty() {
+            return Err("Sequential: Container has no layers".to_string());
+        }
+
+        if x.shape().dim(0) != y.shape().dim(0) {
+            return Err("Sequential: Inputs and outputs must have same number of rows".to_string());
+        }
+
+        let x_train = batches(x, batch_size)?;
+        let y_train = batches(y, batch_size)?;
+        self.train();
+
+        /* loss graph is built from the first batch, later batches swap the values */
+        let mut prediction = Value::new(&y_train[0]);
+        let mut target = Value::new(&y_train[0]);
+        let mut loss: Option<L> = None;
+
+        let mut history: Vec<f64> = Vec::new();
+        for epoch in 0..epochs {
+
+            let mut epoch_loss = 0.0;
+            for (x_batch, y_batch) in x_train.iter().zip(y_train.iter()) {
+
+                let y_pred = self.forward(x_batch)?;
+                prediction.set_val(&y_pred);
+                target.set_val(y_batch);
+
+                let loss = loss.get_or_insert_with(|| loss_builder(prediction.clone(), target.clone()));
+                loss.forward();
+                detect_forward_anomaly(loss);
+                loss.zero_grad();
+                loss.backprop();
+                detect_backward_anomaly(loss);
+                epoch_loss += loss.loss();
+
+                self.zero_grad();
+                self.backward(prediction.grad());
+                self.optimizer.step(&mut self.parameters());
+            }
+
+            epoch_loss /= x_train.len() as f64;
+            history.push(epoch_loss);
+
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), epoch_loss);
+            }
+
+            if log_output {
+                println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, epoch_loss);
+            }
+        }
+
+        Ok(history)
+    }
+
+}
+
+
+impl Default for Sequential {
+    fn default() -> Self {
+        Sequential::new()
+    }
+}
+
+
+impl Module for Sequential {
+
+    /// Forward pass through every layer in order
+    fn forward(&mut self, input: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        let mut output = input.clone();
+        for layer in self.layers.iter_mut() {
+            output = layer.forward(&output)?;
+        }
+        Ok(output)
+    }
+
+    /// Backward pass through every layer in reverse order
+    fn backward(&mut self, upstream_gradient: NDArray<f64>) -> NDArray<f64> {
+        let mut gradient = upstream_gradient;
+        for layer in self.layers.iter_mut().rev() {
+            gradient = layer.backward(gradient);
+        }
+        gradient
+    }
+
+    /// Parameters of every layer in order
+    fn parameters(&self) -> Vec<Value<NDArray<f64>>> {
+        self.layers.iter()
+            .flat_map(|layer| layer.parameters())
+            .collect()
+    }
+
+    /// Parameter names prefixed with the position of their layer
+    fn parameter_names(&self) -> Vec<String> {
+        self.layers.iter()
+            .enumerate()
+            .flat_map(|(idx, layer)| {
+                layer.parameter_names().into_iter().map(move |name| format!("{}.{}", idx, name))
+            })
+            .collect()
+    }
+
+    /// Reset gradients of every layer
+    fn zero_grad(&mut self) {
+        for layer in self.layers.iter_mut() {
+            layer.zero_grad();
+        }
+    }
+
+    /// Switch every layer between training and evaluation mode
+    fn set_training(&mut self, training: bool) {
+        for layer in self.layers.iter_mut() {
+            layer.set_training(training);
+        }
+    }
+
+}
+
+
+impl Parameters for Sequential {
+
+    /// Named parameters of every layer in order
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        named_parameters(self)
+    }
+
+}
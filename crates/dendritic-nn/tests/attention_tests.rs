@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod attention_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::gradcheck::*;
+    use dendritic_autodiff::attention::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_nn::module::*;
+    use dendritic_nn::attention::*;
+    use dendritic_nn::sequential::*;
+
+    const EPS: f64 = 1e-6;
+    const TOL: f64 = 1e-5;
+
+    fn sample(shape: Vec<usize>) -> NDArray<f64> {
+        let size: usize = shape.iter().product();
+        let values: Vec<f64> = (0..size)
+            .map(|i| ((i * 7 + 3) % 11) as f64 * 0.2 - 1.0)
+            .collect();
+        NDArray::array(shape, values).unwrap()
+    }
+
+    /// Node wrapping a module so gradcheck can perturb input and parameters.
+    /// First value is the input, the rest are the module parameters
+    struct ModuleNode<M: Module> {
+        module: M,
+        values: Vec<Value<NDArray<f64>>>,
+        output: NDArray<f64>
+    }
+
+    impl<M: Module> Node for ModuleNode<M> {
+
+        fn forward(&mut self) {
+            for (mut parameter, value) in self.module.parameters().into_iter().zip(self.values[1..].iter()) {
+                parameter.set_val(&value.val());
+            }
+            self.output = self.module.forward(&self.values[0].val()).unwrap();
+        }
+
+        fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+            self.module.zero_grad();
+            let input_grad = self.module.backward(upstream_gradient);
+            self.values[0].set_grad(&input_grad);
+            for (parameter, value) in self.module.parameters().iter().zip(self.values[1..].iter_mut()) {
+                value.set_grad(&parameter.grad());
+            }
+        }
+
+        fn value(&self) -> NDArray<f64> {
+            self.output.clone()
+        }
+
+        fn grad(&self) -> NDArray<f64> {
+            self.values[0].grad()
+        }
+
+        fn set_grad(&mut self, _upstream_gradient: NDArray<f64>) {}
+    }
+
+    fn check<M, F>(module_builder: F, input: NDArray<f64>)
+    where
+        M: Module,
+        F: Fn() -> M,
+    {
+        let mut inputs = vec![input];
+        inputs.extend(module_builder().parameters().iter().map(|p| p.val()));
+
+        let result = gradcheck(
+            |values| {
+                let mut node = ModuleNode {
+                    module: module_builder(),
+                    values: values.to_vec(),
+                    output: NDArray::new(vec![1, 1]).unwrap()
+                };
+                node.forward();
+                node
+            },
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    fn test_multi_head_attention() {
+
+        assert!(MultiHeadAttention::new(6, 4).is_err());
+
+        let mut attention = MultiHeadAttention::with_seed(4, 2, 7).unwrap();
+        assert_eq!(attention.d_model(), 4);
+        assert_eq!(attention.num_heads(), 2);
+        assert_eq!(attention.parameters().len(), 8);
+        assert!(attention.attention_weights().is_none());
+
+        let x = sample(vec![2, 3, 4]);
+        let output = attention.forward(&x).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 3, 4]);
+
+        /* weights of every query row sum to one */
+        let weights = attention.attention_weights().unwrap();
+        assert_eq!(weights.shape().values(), vec![2, 2, 3, 3]);
+        for row in weights.values().chunks(3) {
+            assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+
+        /* causal mask blocks future positions */
+        attention.set_mask(&causal_mask(3));
+        attention.forward(&x).unwrap();
+        let weights = attention.attention_weights().unwrap();
+        for (idx, w) in weights.values().iter().enumerate() {
+            let (i, j) = ((idx / 3) % 3, idx % 3);
+            if j > i {
+                assert_eq!(*w, 0.0);
+            }
+        }
+
+        /* cross attention keeps query length and splits input gradients */
+        attention.clear_mask();
+        let memory = sample(vec![2, 5, 4]);
+        let output = attention.attend(&x, &memory, &memory).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 3, 4]);
+        attention.backward(NDArray::fill(vec![2, 3, 4], 1.0).unwrap());
+        let grads = attention.input_grads();
+        assert_eq!(grads[0].shape().values(), vec![2, 3, 4]);
+        assert_eq!(grads[1].shape().values(), vec![2, 5, 4]);
+        assert_eq!(grads[2].shape().values(), vec![2, 5, 4]);
+    }
+
+    #[test]
+    fn test_multi_head_attention_gradcheck() {
+
+        check(|| MultiHeadAttention::with_seed(4, 2, 3).unwrap(), sample(vec![2, 3, 4]));
+        check(|| {
+            let mut attention = MultiHeadAttention::with_seed(4, 2, 3).unwrap();
+            attention.set_mask(&causal_mask(3));
+            attention
+        }, sample(vec![2, 3, 4]));
+    }
+
+    #[test]
+    fn test_positional_encoding() {
+
+        let mut encoding = PositionalEncoding::new(10, 4);
+        assert_eq!(encoding.encoding().shape().values(), vec![10, 4]);
+
+        let x = NDArray::new(vec![2, 3, 4]).unwrap();
+        let output = encoding.forward(&x).unwrap();
+        let table = sinusoidal_encoding(3, 4);
+        assert_eq!(&output.values()[..12], table.values().as_slice());
+        assert_eq!(&output.values()[12..], table.values().as_slice());
+
+        let upstream = sample(vec![2, 3, 4]);
+        assert_eq!(encoding.backward(upstream.clone()).values(), upstream.values());
+        assert!(encoding.parameters().is_empty());
+    }
+
+    #[test]
+    fn test_encoder_layer_gradcheck() {
+
+        assert!(TransformerEncoderLayer::new(6, 4, 8).is_err());
+
+        let mut layer = TransformerEncoderLayer::with_seed(4, 2, 8, 5).unwrap();
+        assert_eq!(layer.parameters().len(), 8 + 2 + 2 + 2 + 2);
+        let output = layer.forward(&sample(vec![2, 3, 4])).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 3, 4]);
+
+        check(|| TransformerEncoderLayer::with_seed(4, 2, 8, 5).unwrap(), sample(vec![2, 3, 4]));
+        check(|| {
+            let mut layer = TransformerEncoderLayer::with_seed(4, 2, 8, 5).unwrap();
+            layer.set_mask(&causal_mask(3));
+            layer
+        }, sample(vec![2, 3, 4]));
+    }
+
+    #[test]
+    fn test_encoder_training() {
+
+        /* learn to copy the first token of each sequence to every position */
+        let x = sample(vec![6, 4, 4]);
+        let mut y_values: Vec<f64> = Vec::new();
+        for b in 0..6 {
+            let first = &x.values()[b * 16..b * 16 + 4];
+            for _ in 0..4 {
+                y_values.extend_from_slice(first);
+            }
+        }
+        let y = NDArray::array(vec![6, 4, 4], y_values).unwrap();
+
+        let mut model = Sequential::new();
+        model.add(PositionalEncoding::new(8, 4));
+        model.add(TransformerEncoderLayer::with_seed(4, 2, 16, 11).unwrap());
+        model.set_optimizer(Adam::new(0.01));
+
+        let history = model.fit(&x, &y, MSE::new, 100, 3, false).unwrap();
+        assert!(history.last().unwrap() < &(history[0] * 0.5));
+    }
+
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod layer_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::init::*;
+    use dendritic_nn::module::*;
+    use dendritic_nn::layers::*;
+    use dendritic_nn::activations::*;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_dense_forward_backward() {
+
+        let weights = NDArray::array(vec![2, 3], vec![
+            1.0, 0.0, -1.0,
+            2.0, 1.0, 0.5
+        ]).unwrap();
+        let bias = NDArray::array(vec![1, 3], vec![0.1, 0.2, 0.3]).unwrap();
+        let mut layer = Dense::from_parameters(&weights, &bias).unwrap();
+
+        assert_eq!(layer.input_dim(), 2);
+        assert_eq!(layer.output_dim(), 3);
+
+        let x = NDArray::array(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let output = layer.forward(&x).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 3]);
+        assert!(close(output.values(), &[5.1, 2.2, 0.3, 11.1, 4.2, -0.7]));
+
+        let upstream = NDArray::array(vec![2, 3], vec![1.0; 6]).unwrap();
+        let input_grad = layer.backward(upstream);
+
+        /* dx = g * W^T, dW = x^T * g, db = column sums of g */
+        assert_eq!(input_grad.shape().values(), vec![2, 2]);
+        assert!(close(input_grad.values(), &[0.0, 3.5, 0.0, 3.5]));
+        assert!(close(layer.weights.grad().values(), &[4.0, 4.0, 4.0, 6.0, 6.0, 6.0]));
+        assert!(close(layer.bias.grad().values(), &[2.0, 2.0, 2.0]));
+
+        /* gradients add up over backward passes until they are zeroed */
+        let upstream = NDArray::array(vec![2, 3], vec![1.0; 6]).unwrap();
+        let input_grad = layer.backward(upstream.clone());
+        assert!(close(input_grad.values(), &[0.0, 7.0, 0.0, 7.0]));
+        assert!(close(layer.bias.grad().values(), &[4.0, 4.0, 4.0]));
+
+        layer.zero_grad();
+        let input_grad = layer.backward(upstream);
+        assert!(close(input_grad.values(), &[0.0, 3.5, 0.0, 3.5]));
+        assert!(close(layer.bias.grad().values(), &[2.0, 2.0, 2.0]));
+
+        let params = layer.parameters();
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].val().shape().values(), vec![2, 3]);
+        assert_eq!(params[1].val().shape().values(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_dense_initialization() {
+
+        let layer = Dense::with_seed(4, 8, 7);
+        let same = Dense::with_seed(4, 8, 7);
+        let limit = (6.0_f64 / 12.0).sqrt();
+
+        assert_eq!(layer.weights.val().shape().values(), vec![4, 8]);
+        assert_eq!(layer.weights.val().values(), same.weights.val().values());
+        assert!(layer.weights.val().values().iter().all(|w| w.abs() <= limit));
+        assert!(layer.bias.val().values().iter().all(|b| *b == 0.0));
+
+        let linear: Linear = Linear::new(3, 2);
+        assert_eq!(linear.weights.val().shape().values(), vec![3, 2]);
+
+        /* seeded layers share one initializer stream across the network */
+        let mut initializer = Initializer::new(Init::HeNormal, 3);
+        let first = Dense::with_initializer(4, 8, &mut initializer).unwrap();
+        let second = Dense::with_initializer(8, 2, &mut initializer).unwrap();
+        assert_eq!(first.weights.val(), he_normal(vec![4, 8], 3).unwrap());
+        assert_eq!(second.weights.val().shape().values(), vec![8, 2]);
+        assert!(second.bias.val().values().iter().all(|b| *b == 0.0));
+
+        let mut invalid = Initializer::new(Init::Normal(0.0, -1.0), 3);
+        assert!(Dense::with_initializer(4, 8, &mut invalid).is_err());
+    }
+
+    #[test]
+    fn test_dense_invalid_parameters() {
+
+        let weights = NDArray::array(vec![2, 3], vec![0.0; 6]).unwrap();
+        let bias = NDArray::array(vec![1, 2], vec![0.0; 2]).unwrap();
+        let layer = Dense::from_parameters(&weights, &bias);
+        assert!(layer.is_err());
+    }
+
+    #[test]
+    fn test_dense_input_mismatch() {
+
+        let mut layer = Dense::with_seed(2, 3, 0);
+        let x = NDArray::array(vec![1, 3], vec![1.0, 2.0, 3.0]).unwrap();
+        let err = layer.forward(&x).unwrap_err();
+        assert_eq!(err, "Dense: Input has shape [1, 3], layer expects (batch, 2)");
+    }
+
+    #[test]
+    fn test_activation_layers() {
+
+        let x = NDArray::array(vec![1, 3], vec![-1.0, 0.5, 2.0]).unwrap();
+        let upstream = NDArray::array(vec![1, 3], vec![1.0, 1.0, 1.0]).unwrap();
+
+        let mut relu = ReLU::new();
+        assert!(close(relu.forward(&x).unwrap().values(), &[0.0, 0.5, 2.0]));
+        assert!(close(relu.backward(upstream.clone()).values(), &[0.0, 1.0, 1.0]));
+        assert!(relu.parameters().is_empty());
+
+        let mut leaky = LeakyReLU::new(0.1);
+        assert!(close(leaky.forward(&x).unwrap().values(), &[-0.1, 0.5, 2.0]));
+        assert!(close(leaky.backward(upstream.clone()).values(), &[0.1, 1.0, 1.0]));
+
+        let mut sigmoid = Sigmoid::new();
+        let s = sigmoid.forward(&x).unwrap();
+        let grad = sigmoid.backward(upstream.clone());
+        for idx in 0..3 {
+            let expected = s.values()[idx] * (1.0 - s.values()[idx]);
+            assert!((grad.values()[idx] - expected).abs() < 1e-12);
+        }
+
+        let mut tanh = Tanh::new();
+        let t = tanh.forward(&x).unwrap();
+        let grad = tanh.backward(upstream.clone());
+        for idx in 0..3 {
+            let expected = 1.0 - t.values()[idx].powi(2);
+            assert!((grad.values()[idx] - expected).abs() < 1e-12);
+        }
+
+        /* softmax rows sum to one, uniform upstream gives zero gradient */
+        let mut softmax = Softmax::new();
+        let p = softmax.forward(&x).unwrap();
+        assert!((p.values().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        let grad = softmax.backward(upstream);
+        assert!(grad.values().iter().all(|g| g.abs() < 1e-12));
+
+        /* layers accept new batch shapes on every forward pass */
+        let batch = NDArray::array(vec![2, 2], vec![-1.0, 1.0, 2.0, -2.0]).unwrap();
+        assert!(close(relu.forward(&batch).unwrap().values(), &[0.0, 1.0, 2.0, 0.0]));
+    }
+
+}
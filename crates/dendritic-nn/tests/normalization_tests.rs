@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod normalization_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_nn::module::*;
+    use dendritic_nn::layers::*;
+    use dendritic_nn::activations::*;
+    use dendritic_nn::sequential::*;
+    use dendritic_nn::normalization::*;
+
+    fn sample(shape: Vec<usize>) -> NDArray<f64> {
+        let size: usize = shape.iter().product();
+        let values: Vec<f64> = (0..size)
+            .map(|i| ((i * 7 + 3) % 11) as f64 * 0.5 - 2.0)
+            .collect();
+        NDArray::array(shape, values).unwrap()
+    }
+
+    #[test]
+    fn test_dropout_layer() {
+
+        let x = NDArray::array(vec![10, 10], vec![1.0; 100]).unwrap();
+        let mut dropout = Dropout::with_seed(0.5, 1).unwrap();
+        assert!(dropout.is_training());
+
+        let output = dropout.forward(&x).unwrap();
+        let dropped = output.values().iter().filter(|v| **v == 0.0).count();
+        assert!(dropped > 30 && dropped < 70);
+        assert!(output.values().iter().all(|v| *v == 0.0 || *v == 2.0));
+
+        let grad = dropout.backward(x.clone());
+        assert_eq!(grad.values(), output.values());
+
+        dropout.eval();
+        assert!(!dropout.is_training());
+        assert_eq!(dropout.forward(&x).unwrap().values(), x.values());
+
+        assert!(Dropout::new(1.0).is_err());
+        assert!(Dropout::new(-0.1).is_err());
+    }
+
+    #[test]
+    fn test_batch_norm_1d() {
+
+        let x = sample(vec![8, 3]);
+        let mut bn = BatchNorm1d::new(3);
+        bn.set_momentum(1.0);
+        assert_eq!(bn.parameters().len(), 2);
+
+        /* momentum of one copies the batch statistics */
+        let output = bn.forward(&x).unwrap();
+        let column: Vec<f64> = (0..8).map(|r| x.values()[r * 3 + 1]).collect();
+        let mean = column.iter().sum::<f64>() / 8.0;
+        let var = column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 7.0;
+        assert!((bn.running_mean().values()[1] - mean).abs() < 1e-12);
+        assert!((bn.running_var().values()[1] - var).abs() < 1e-12);
+
+        /* evaluation with a single sample uses running statistics */
+        bn.eval();
+        let row = NDArray::array(vec![1, 3], x.values()[3..6].to_vec()).unwrap();
+        let single = bn.forward(&row).unwrap();
+        let var_biased = var * 7.0 / 8.0;
+        let expected = (x.values()[4] - mean) / (var + 1e-5).sqrt();
+        assert!((single.values()[1] - expected).abs() < 1e-9);
+        let train_value = (x.values()[4] - mean) / (var_biased + 1e-5).sqrt();
+        assert!((output.values()[4] - train_value).abs() < 1e-9);
+
+        let grad = bn.backward(NDArray::array(vec![1, 3], vec![1.0, 1.0, 1.0]).unwrap());
+        assert!((grad.values()[1] - 1.0 / (var + 1e-5).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_batch_norm_2d_rank() {
+        let mut bn = BatchNorm2d::new(2);
+        let err = bn.forward(&sample(vec![4, 2])).unwrap_err();
+        assert_eq!(err, "BatchNorm2d: Expected input of rank 4 with 2 channels, got shape [4, 2]");
+    }
+
+    #[test]
+    fn test_batch_norm_2d() {
+
+        let x = sample(vec![2, 2, 3, 3]);
+        let mut bn = BatchNorm2d::new(2);
+        let output = bn.forward(&x).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 2, 3, 3]);
+
+        /* each channel is normalized over batch and spatial positions */
+        for c in 0..2 {
+            let values: Vec<f64> = output.values().iter()
+                .enumerate()
+                .filter(|(idx, _)| (idx / 9) % 2 == c)
+                .map(|(_, v)| *v)
+                .collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            assert_eq!(values.len(), 18);
+            assert!(mean.abs() < 1e-9);
+        }
+
+        let grad = bn.backward(sample(vec![2, 2, 3, 3]));
+        assert_eq!(grad.shape().values(), vec![2, 2, 3, 3]);
+        assert_eq!(bn.parameters()[0].grad().shape().values(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_layer_norm_layer() {
+
+        let x = sample(vec![3, 5]);
+        let mut ln = LayerNorm::new(5);
+        let output = ln.forward(&x).unwrap();
+        for row in output.values().chunks(5) {
+            assert!((row.iter().sum::<f64>() / 5.0).abs() < 1e-9);
+        }
+
+        /* evaluation does not change layer normalization */
+        ln.eval();
+        assert_eq!(ln.forward(&x).unwrap().values(), output.values());
+        let grad = ln.backward(sample(vec![3, 5]));
+        assert_eq!(grad.shape().values(), vec![3, 5]);
+    }
+
+    #[test]
+    fn test_sequential_modes() {
+
+        let x = sample(vec![16, 4]);
+        let y: Vec<f64> = x.values().chunks(4)
+            .map(|row| if row[0] + row[1] > 0.0 { 1.0 } else { 0.0 })
+            .collect();
+        let y = NDArray::array(vec![16, 1], y).unwrap();
+
+        let mut model = Sequential::new();
+        model.add(Dense::with_seed(4, 16, 5));
+        model.add(BatchNorm1d::new(16));
+        model.add(ReLU::new());
+        model.add(Dropout::with_seed(0.2, 6).unwrap());
+        model.add(LayerNorm::new(16));
+        model.add(Dense::with_seed(16, 1, 7));
+        model.add(Sigmoid::new());
+        model.set_optimizer(Adam::new(0.01));
+
+        let history = model.fit(&x, &y, BinaryCrossEntropy::new, 200, 8, false).unwrap();
+        assert!(history[199] < history[0]);
+
+        /* evaluation mode is deterministic */
+        model.eval();
+        let first = model.predict(&x).unwrap();
+        let second = model.predict(&x).unwrap();
+        assert_eq!(first.values(), second.values());
+
+        let correct = first.values().iter()
+            .zip(y.values().iter())
+            .filter(|(p, t)| p.round() == **t)
+            .count();
+        assert!(correct >= 14, "correct {}", correct);
+
+        /* training mode draws new dropout masks */
+        model.train();
+        let third = model.predict(&x).unwrap();
+        assert_ne!(third.values(), first.values());
+    }
+
+}
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod recurrent_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_autodiff::node::*;
+    use dendritic_autodiff::gradcheck::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_nn::module::*;
+    use dendritic_nn::layers::*;
+    use dendritic_nn::activations::*;
+    use dendritic_nn::recurrent::*;
+    use dendritic_nn::sequential::*;
+
+    const EPS: f64 = 1e-6;
+    const TOL: f64 = 1e-5;
+
+    fn sample(shape: Vec<usize>) -> NDArray<f64> {
+        let size: usize = shape.iter().product();
+        let values: Vec<f64> = (0..size)
+            .map(|i| ((i * 7 + 3) % 11) as f64 * 0.2 - 1.0)
+            .collect();
+        NDArray::array(shape, values).unwrap()
+    }
+
+    /// Node wrapping a recurrent layer so gradcheck can perturb input and parameters.
+    /// First value is the input sequence, the rest are the layer parameters
+    struct LayerNode<C: RecurrentCell> {
+        layer: Recurrent<C>,
+        values: Vec<Value<NDArray<f64>>>,
+        output: NDArray<f64>
+    }
+
+    impl<C: RecurrentCell> LayerNode<C> {
+
+        fn new(layer: Recurrent<C>, values: &[Value<NDArray<f64>>]) -> LayerNode<C> {
+            let mut node = LayerNode {
+                layer,
+                values: values.to_vec(),
+                output: NDArray::new(vec![1, 1]).unwrap()
+            };
+            node.forward();
+            node
+        }
+
+        fn inputs(layer: &Recurrent<C>, input: NDArray<f64>) -> Vec<NDArray<f64>> {
+            let mut inputs = vec![input];
+            inputs.extend(layer.parameters().iter().map(|p| p.val()));
+            inputs
+        }
+    }
+
+    impl<C: RecurrentCell> Node for LayerNode<C> {
+
+        fn forward(&mut self) {
+            for (mut parameter, value) in self.layer.parameters().into_iter().zip(self.values[1..].iter()) {
+                parameter.set_val(&value.val());
+            }
+            self.output = self.layer.forward(&self.values[0].val()).unwrap();
+        }
+
+        fn backward(&mut self, upstream_gradient: NDArray<f64>) {
+            self.layer.zero_grad();
+            let input_grad = self.layer.backward(upstream_gradient);
+            self.values[0].set_grad(&input_grad);
+            for (parameter, value) in self.layer.parameters().iter().zip(self.values[1..].iter_mut()) {
+                value.set_grad(&parameter.grad());
+            }
+        }
+
+        fn value(&self) -> NDArray<f64> {
+            self.output.clone()
+        }
+
+        fn grad(&self) -> NDArray<f64> {
+            self.values[0].grad()
+        }
+
+        fn set_grad(&mut self, _upstream_gradient: NDArray<f64>) {}
+    }
+
+    fn check<C, F>(layer_builder: F, input: NDArray<f64>)
+    where
+        C: RecurrentCell,
+        F: Fn() -> Recurrent<C>,
+    {
+        let inputs = LayerNode::inputs(&layer_builder(), input);
+        let result = gradcheck(
+            |values| LayerNode::new(layer_builder(), values),
+            &inputs, EPS, TOL
+        ).unwrap();
+        assert!(result.passed(), "{:?}", result.mismatches);
+    }
+
+    #[test]
+    fn test_rnn_gradcheck() {
+
+        check(|| RNN::new(RNNCell::with_seed(3, 4, 7)), sample(vec![2, 5, 3]));
+        check(|| {
+            let mut layer = RNN::new(RNNCell::with_seed(3, 4, 7));
+            layer.set_return_sequences(false);
+            layer
+        }, sample(vec![2, 5, 3]));
+    }
+
+    #[test]
+    fn test_lstm_gradcheck() {
+
+        check(|| LSTM::new(LSTMCell::with_seed(3, 4, 11)), sample(vec![2, 4, 3]));
+        check(|| {
+            let mut layer = LSTM::new(LSTMCell::with_seed(3, 4, 11));
+            layer.set_return_sequences(false);
+            layer
+        }, sample(vec![2, 4, 3]));
+    }
+
+    #[test]
+    fn test_gru_gradcheck() {
+
+        check(|| GRU::new(GRUCell::with_seed(3, 4, 13)), sample(vec![2, 4, 3]));
+        check(|| {
+            let mut layer = GRU::new(GRUCell::with_seed(3, 4, 13));
+            layer.set_return_sequences(false);
+            layer
+        }, sample(vec![2, 4, 3]));
+    }
+
+    #[test]
+    fn test_bidirectional() {
+
+        let layer = RNN::bidirectional(RNNCell::with_seed(3, 4, 1), RNNCell::with_seed(3, 5, 2));
+        assert!(layer.is_err());
+
+        let mut layer = LSTM::bidirectional(
+            LSTMCell::with_seed(3, 4, 1),
+            LSTMCell::with_seed(3, 4, 2)
+        ).unwrap();
+        assert!(layer.is_bidirectional());
+        assert_eq!(layer.output_size(), 8);
+        assert_eq!(layer.parameters().len(), 6);
+
+        let x = sample(vec![2, 5, 3]);
+        let output = layer.forward(&x).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 5, 8]);
+
+        /* backward direction at the last step only saw the last input */
+        let mut single = LSTM::new(LSTMCell::with_seed(3, 4, 2));
+        let last: Vec<f64> = (0..2).flat_map(|b| x.values()[(b * 5 + 4) * 3..(b * 5 + 5) * 3].to_vec()).collect();
+        let last = NDArray::array(vec![2, 1, 3], last).unwrap();
+        let expected = single.forward(&last).unwrap();
+        for b in 0..2 {
+            let start = (b * 5 + 4) * 8 + 4;
+            for h in 0..4 {
+                assert!((output.values()[start + h] - expected.values()[b * 4 + h]).abs() < 1e-12);
+            }
+        }
+
+        check(|| GRU::bidirectional(GRUCell::with_seed(3, 2, 3), GRUCell::with_seed(3, 2, 4)).unwrap(), sample(vec![2, 3, 3]));
+        check(|| {
+            let mut layer = RNN::bidirectional(RNNCell::with_seed(3, 2, 3), RNNCell::with_seed(3, 2, 4)).unwrap();
+            layer.set_return_sequences(false);
+            layer
+        }, sample(vec![2, 3, 3]));
+    }
+
+    #[test]
+    fn test_masking() {
+
+        let mask = sequence_mask(&[4, 2], 4).unwrap();
+        assert_eq!(mask.values(), &vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0]);
+        assert!(sequence_mask(&[5], 4).is_err());
+
+        /* padded steps do not change the final state of the short sequence */
+        let x = sample(vec![2, 4, 3]);
+        let mut layer = GRU::new(GRUCell::with_seed(3, 4, 5));
+        layer.set_return_sequences(false);
+        layer.set_mask(&mask);
+        let output = layer.forward(&x).unwrap();
+
+        let short = NDArray::array(vec![1, 2, 3], x.values()[12..18].to_vec()).unwrap();
+        let mut unmasked = GRU::new(GRUCell::with_seed(3, 4, 5));
+        unmasked.set_return_sequences(false);
+        let expected = unmasked.forward(&short).unwrap();
+        for h in 0..4 {
+            assert!((output.values()[4 + h] - expected.values()[h]).abs() < 1e-12);
+        }
+
+        /* padded outputs are zero and padded inputs get no gradient */
+        let mut layer = LSTM::new(LSTMCell::with_seed(3, 4, 5));
+        layer.set_mask(&mask);
+        let output = layer.forward(&x).unwrap();
+        assert!(output.values()[(4 + 2) * 4..].iter().all(|v| *v == 0.0));
+
+        let input_grad = layer.backward(NDArray::fill(vec![2, 4, 4], 1.0).unwrap());
+        assert!(input_grad.values()[(4 + 2) * 3..].iter().all(|v| *v == 0.0));
+        assert!(input_grad.values()[..(4 + 2) * 3].iter().any(|v| *v != 0.0));
+
+        check(|| {
+            let mut layer = LSTM::new(LSTMCell::with_seed(3, 4, 5));
+            layer.set_mask(&sequence_mask(&[4, 2], 4).unwrap());
+            layer.set_return_sequences(false);
+            layer
+        }, sample(vec![2, 4, 3]));
+    }
+
+    #[test]
+    fn test_truncated_bptt() {
+
+        let x = sample(vec![2, 6, 3]);
+        let upstream = NDArray::fill(vec![2, 4], 1.0).unwrap();
+
+        let mut full = RNN::new(RNNCell::with_seed(3, 4, 9));
+        full.set_return_sequences(false);
+        full.forward(&x).unwrap();
+        let full_grad = full.backward(upstream.clone());
+
+        /* chunks of two steps only backpropagate last output through steps 4 and 5 */
+        let mut truncated = RNN::new(RNNCell::with_seed(3, 4, 9));
+        truncated.set_return_sequences(false);
+        truncated.set_bptt_steps(Some(2));
+        let output = truncated.forward(&x).unwrap();
+        assert_eq!(output.values(), full.forward(&x).unwrap().values());
+        let grad = truncated.backward(upstream.clone());
+
+        for b in 0..2 {
+            for t in 0..6 {
+                let row = &grad.values()[(b * 6 + t) * 3..(b * 6 + t + 1) * 3];
+                let full_row = &full_grad.values()[(b * 6 + t) * 3..(b * 6 + t + 1) * 3];
+                if t < 4 {
+                    assert!(row.iter().all(|v| *v == 0.0));
+                } else {
+                    assert!(row.iter().zip(full_row.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+                }
+            }
+        }
+
+        /* truncation longer than the sequence matches full backpropagation */
+        truncated.set_bptt_steps(Some(6));
+        truncated.forward(&x).unwrap();
+        assert_eq!(truncated.backward(upstream).values(), full_grad.values());
+    }
+
+    #[test]
+    fn test_sequence_classification() {
+
+        /* label is whether the first step of the sequence is positive */
+        let samples = 24;
+        let mut values: Vec<f64> = Vec::new();
+        let mut labels: Vec<f64> = Vec::new();
+        for i in 0..samples {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            values.push(sign);
+            for t in 1..5 {
+                values.push(((i * 3 + t * 5) % 7) as f64 * 0.2 - 0.6);
+            }
+            labels.push(if sign > 0.0 { 1.0 } else { 0.0 });
+        }
+
+        let x = NDArray::array(vec![samples, 5, 1], values).unwrap();
+        let y = NDArray::array(vec![samples, 1], labels.clone()).unwrap();
+
+        let mut lstm = LSTM::new(LSTMCell::with_seed(1, 8, 21));
+        lstm.set_return_sequences(false);
+
+        let mut model = Sequential::new();
+        model.add(lstm);
+        model.add(Dense::with_seed(8, 1, 22));
+        model.add(Sigmoid::new());
+        model.set_optimizer(Adam::new(0.05));
+
+        let history = model.fit(&x, &y, BinaryCrossEntropy::new, 60, 8, false).unwrap();
+        assert!(history.last().unwrap() < &history[0]);
+
+        let y_pred = model.predict(&x).unwrap();
+        let correct = y_pred.values().iter()
+            .zip(labels.iter())
+            .filter(|(p, l)| (if **p > 0.5 { 1.0 } else { 0.0 }) == **l)
+            .count();
+        assert_eq!(correct, samples);
+    }
+
+}
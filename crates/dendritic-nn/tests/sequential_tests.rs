@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod sequential_tests {
+
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_ndarray::ops::*;
+    use dendritic_datasets::iris::*;
+    use dendritic_preprocessing::encoding::*;
+    use dendritic_preprocessing::standard_scalar::*;
+    use dendritic_autodiff::loss::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_autodiff::scheduler::*;
+    use dendritic_autodiff::state::*;
+    use dendritic_nn::module::*;
+    use dendritic_nn::layers::*;
+    use dendritic_nn::activations::*;
+    use dendritic_nn::sequential::*;
+    use dendritic_nn::normalization::*;
+
+    #[test]
+    fn test_batches() {
+
+        let x = NDArray::array(vec![5, 2], (0..10).map(|v| v as f64).collect()).unwrap();
+        let result = batches(&x, 2).unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].values(), &vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(result[2].shape().values(), vec![1, 2]);
+        assert_eq!(result[2].values(), &vec![8.0, 9.0]);
+
+        let result = batches(&x, 10).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(batches(&x, 0).is_err());
+    }
+
+    #[test]
+    fn test_sequential_parameters() {
+
+        let mut model = Sequential::new();
+        model.add(Dense::with_seed(3, 4, 0));
+        model.add(ReLU::new());
+        model.add(Dense::with_seed(4, 1, 1));
+
+        assert_eq!(model.num_layers(), 3);
+
+        let params = model.parameters();
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[0].val().shape().values(), vec![3, 4]);
+        assert_eq!(params[3].val().shape().values(), vec![1, 1]);
+
+        let x = NDArray::array(vec![2, 3], vec![1.0; 6]).unwrap();
+        let output = model.forward(&x).unwrap();
+        assert_eq!(output.shape().values(), vec![2, 1]);
+
+        let input_grad = model.backward(NDArray::array(vec![2, 1], vec![1.0, 1.0]).unwrap());
+        assert_eq!(input_grad.shape().values(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_sequential_state_dict() {
+
+        let build = |seed: u64| {
+            let mut model = Sequential::new();
+            model.add(Dense::with_seed(3, 4, seed));
+            model.add(LayerNorm::new(4));
+            model.add(ReLU::new());
+            model.add(Dense::with_seed(4, 1, seed + 1));
+            model
+        };
+
+        let mut model = build(0);
+        let names: Vec<String> = model.named_parameters().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["0.weights", "0.bias", "1.gamma", "1.beta", "3.weights", "3.bias"]);
+
+        let dir = std::env::temp_dir().join("dendritic_sequential_state_test");
+        let dir = dir.to_str().unwrap();
+        model.save_state_dict(dir).unwrap();
+
+        let x = NDArray::array(vec![2, 3], vec![1.0, -2.0, 0.5, 3.0, 0.0, -1.0]).unwrap();
+        let mut loaded = build(10);
+        assert_ne!(loaded.forward(&x).unwrap(), model.forward(&x).unwrap());
+        loaded.load_state_dict_from(dir).unwrap();
+        assert_eq!(loaded.forward(&x).unwrap(), model.forward(&x).unwrap());
+
+        /* a different architecture is rejected */
+        let mut wider = Sequential::new();
+        wider.add(Dense::with_seed(3, 5, 0));
+        let err = wider.load_state_dict(&model.state_dict()).unwrap_err();
+        assert_eq!(err, "StateDict: Shape mismatch for 0.weights, expected [3, 5] got [3, 4]");
+    }
+
+    #[test]
+    fn test_sequential_fit_errors() {
+
+        let x = NDArray::array(vec![4, 2], vec![0.0; 8]).unwrap();
+        let y = NDArray::array(vec![3, 1], vec![0.0; 3]).unwrap();
+
+        let mut empty = Sequential::new();
+        assert!(empty.fit(&x, &y, MSE::new, 1, 2, false).is_err());
+
+        let mut model = Sequential::new();
+        model.add(Dense::with_seed(2, 1, 0));
+        assert!(model.fit(&x, &y, MSE::new, 1, 2, false).is_err());
+    }
+
+    #[test]
+    fn test_sequential_fit_single_forward() {
+
+        let x = NDArray::array(vec![4, 2], vec![1.0, -2.0, 3.0, 0.5, -1.0, 4.0, 2.0, 1.0]).unwrap();
+        let y = NDArray::array(vec![4, 2], vec![0.0; 8]).unwrap();
+
+        /* frozen weights so only the running statistics change */
+        let mut model = Sequential::new();
+        model.add(BatchNorm1d::new(2));
+        model.set_optimizer(SGD::new(0.0));
+        model.fit(&x, &y, MSE::new, 1, 4, false).unwrap();
+
+        let mut reference = BatchNorm1d::new(2);
+        reference.train();
+        reference.forward(&x).unwrap();
+        reference.eval();
+
+        model.eval();
+        assert_eq!(model.forward(&x).unwrap(), reference.forward(&x).unwrap());
+    }
+
+    #[test]
+    fn test_mlp_xor() {
+
+        let x = NDArray::array(vec![4, 2], vec![
+            0.0, 0.0,
+            0.0, 1.0,
+            1.0, 0.0,
+            1.0, 1.0
+        ]).unwrap();
+        let y = NDArray::array(vec![4, 1], vec![0.0, 1.0, 1.0, 0.0]).unwrap();
+
+        let mut model = Sequential::new();
+        model.add(Dense::with_seed(2, 8, 3));
+        model.add(Tanh::new());
+        model.add(Dense::with_seed(8, 1, 4));
+        model.add(Sigmoid::new());
+        model.set_optimizer(Adam::new(0.05));
+
+        let history = model.fit(&x, &y, BinaryCrossEntropy::new, 500, 4, false).unwrap();
+        assert_eq!(history.len(), 500);
+        assert!(history[499] < history[0]);
+        assert!(history[499] < 0.1);
+
+        let y_pred = model.predict(&x).unwrap();
+        for (pred, target) in y_pred.values().iter().zip(y.values().iter()) {
+            assert_eq!(pred.round(), *target);
+        }
+    }
+
+    #[test]
+    fn test_mlp_iris() {
+
+        let data_path = "../dendritic-datasets/data/iris.parquet";
+        let (x_train, y_train) = load_iris(data_path).unwrap();
+        let x_train = min_max_scalar(x_train).unwrap();
+
+        let mut encoder = OneHotEncoding::new(y_train.clone()).unwrap();
+        let y_train_encoded = encoder.transform();
+
+        let mut model = Sequential::new();
+        model.add(Dense::with_seed(4, 16, 11));
+        model.add(ReLU::new());
+        model.add(Dense::with_seed(16, 3, 12));
+        model.add(Softmax::new());
+        model.set_optimizer(Adam::new(0.01));
+        model.set_scheduler(StepLR::new(0.01, 50, 0.5));
+
+        let history = model.fit(
+            &x_train,
+            y_train_encoded,
+            CategoricalCrossEntropy::new,
+            150,
+            16,
+            false
+        ).unwrap();
+
+        assert!(history[149] < history[0]);
+
+        let y_pred = model.predict(&x_train).unwrap().argmax(0);
+        let correct = y_pred.values().iter()
+            .zip(y_train.values().iter())
+            .filter(|(pred, target)| pred == target)
+            .count();
+
+        let accuracy = correct as f64 / y_train.size() as f64;
+        assert!(accuracy > 0.9, "accuracy {}", accuracy);
+    }
+
+}
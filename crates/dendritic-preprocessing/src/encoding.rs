@@ -1,4 +1,5 @@
 use dendritic_ndarray::ndarray::NDArray;
+use dendritic::estimator::{Fit, Transform};
 
 
 pub struct OneHotEncoding {
@@ -14,6 +15,22 @@ impl OneHotEncoding {
     /// Create new instance of one hot encoding
     pub fn new(input_column: NDArray<f64>) -> Result<OneHotEncoding, String>  {
 
+        let max_index = OneHotEncoding::class_count(&input_column)?;
+
+        Ok(Self {
+            input_column: input_column.clone(),
+            encoded_values: NDArray::new(vec![
+                input_column.shape().dim(0),
+                max_index as usize
+            ]).unwrap(),
+            max_value: max_index, 
+            num_samples: input_column.shape().dim(0) as f64
+        })
+    }
+
+    /// Validate input column and count classes as the largest label plus one
+    fn class_count(input_column: &NDArray<f64>) -> Result<f64, String> {
+
         if input_column.shape().dim(1) != 1 {
             return Err("Input col must be of size (N, 1)".to_string())
         }
@@ -25,17 +42,7 @@ impl OneHotEncoding {
         let max_value = input_column.values().iter().max_by(
             |a, b| a.total_cmp(b)
         ).unwrap();
-        let max_index = *max_value + 1.0;
-
-        Ok(Self {
-            input_column: input_column.clone(),
-            encoded_values: NDArray::new(vec![
-                input_column.shape().dim(0),
-                max_index as usize
-            ]).unwrap(),
-            max_value: max_index.clone(), 
-            num_samples: input_column.shape().dim(0) as f64
-        })
+        Ok(*max_value + 1.0)
     }
 
     /// Get the maximum bound of one hot encoder
@@ -63,3 +70,43 @@ impl OneHotEncoding {
     }
 
 }
+
+
+impl Fit<NDArray<f64>> for OneHotEncoding {
+
+    /// Replace input column and count classes from its largest label
+    fn fit(&mut self, x: &NDArray<f64>, _y: &()) -> Result<(), String> {
+        *self = OneHotEncoding::new(x.clone())?;
+        Ok(())
+    }
+
+}
+
+
+impl Transform<NDArray<f64>> for OneHotEncoding {
+    type Output = NDArray<f64>;
+
+    /// Encode a label column with the fitted number of classes
+    fn transform(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+        if x.rank() != 2 || x.shape().dim(1) != 1 {
+            return Err("Input col must be of size (N, 1)".to_string())
+        }
+
+        let classes = self.max_value as usize;
+        let mut encoded = NDArray::new(vec![x.shape().dim(0), classes]).unwrap();
+        for (row, label) in x.values().iter().enumerate() {
+            if *label < 0.0 || *label >= self.max_value || label.fract() != 0.0 {
+                let msg = format!(
+                    "One Hot Encoding: Label {} is not a class index below {}",
+                    label, self.max_value
+                );
+                return Err(msg);
+            }
+            encoded.set_idx(row * classes + *label as usize, 1.0)?;
+        }
+
+        Ok(encoded)
+    }
+
+}
@@ -5,7 +5,7 @@
 //! Contains preprocessing for encoding and standard scaling.
 //!
 //! ## Features
-//! - **Standard Scalar**: Standard scalar and min max normlization of data.
+//! - **Standard Scalar**: Standard scalar and min max normlization of data, `StandardScaler` and `MinMaxScaler` keep training statistics for new data
 //! - **Encoding**: One hot encoding for multi class data
 //!
 //! ## Example Usage
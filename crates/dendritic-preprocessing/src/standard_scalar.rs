@@ -1,5 +1,6 @@
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
+use dendritic::estimator::{Fit, Transform};
 
 /// Standard scalar normalization of data
 pub fn standard_scalar(input: NDArray<f64>) -> Result<NDArray<f64>, String>  {
@@ -75,3 +76,139 @@ pub fn min_max_scalar(input: NDArray<f64>) -> Result<NDArray<f64>, String> {
     let result = temp.transpose().unwrap();
     Ok(result)
 }
+
+
+/// Check data is rank 2 with a fixed number of feature columns
+fn check_columns(name: &str, input: &NDArray<f64>, cols: usize) -> Result<(), String> {
+
+    if cols == 0 {
+        return Err(format!("{}: Must be fitted before transforming", name));
+    }
+
+    if input.rank() != 2 || input.shape().dim(1) != cols {
+        let msg = format!(
+            "{}: Expected {} feature columns, got shape {:?}",
+            name, cols, input.shape().values()
+        );
+        return Err(msg);
+    }
+
+    Ok(())
+}
+
+
+/// Subtract offset and divide by scale for every column
+fn scale_columns(
+    input: &NDArray<f64>,
+    offset: &[f64],
+    scale: &[f64]) -> Result<NDArray<f64>, String> {
+
+    let cols = offset.len();
+    let values: Vec<f64> = input.values()
+        .chunks(cols)
+        .flat_map(|row| {
+            row.iter().enumerate().map(|(idx, val)| (val - offset[idx]) / scale[idx])
+        })
+        .collect();
+
+    NDArray::array(input.shape().values(), values)
+}
+
+
+/// Standard scalar normalization keeping the column statistics seen during `Fit`
+#[derive(Debug, Clone, Default)]
+pub struct StandardScaler {
+    pub mean: Vec<f64>,
+    pub stdev: Vec<f64>
+}
+
+
+impl StandardScaler {
+
+    /// Create new unfitted standard scaler
+    pub fn new() -> StandardScaler {
+        StandardScaler::default()
+    }
+
+}
+
+
+impl Fit<NDArray<f64>> for StandardScaler {
+
+    /// Store mean and standard deviation of every column
+    fn fit(&mut self, x: &NDArray<f64>, _y: &()) -> Result<(), String> {
+        if x.rank() != 2 {
+            return Err("Standard Scalar: Must be with rank 2 or higher".to_string());
+        }
+        self.mean = x.mean(1)?;
+        self.stdev = x.stdev(1)?;
+        Ok(())
+    }
+
+}
+
+
+impl Transform<NDArray<f64>> for StandardScaler {
+    type Output = NDArray<f64>;
+
+    /// Normalize columns with the fitted mean and standard deviation
+    fn transform(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        check_columns("Standard Scalar", x, self.mean.len())?;
+        scale_columns(x, &self.mean, &self.stdev)
+    }
+
+}
+
+
+/// Min max normalization keeping the column bounds seen during `Fit`
+#[derive(Debug, Clone, Default)]
+pub struct MinMaxScaler {
+    pub min: Vec<f64>,
+    pub max: Vec<f64>
+}
+
+
+impl MinMaxScaler {
+
+    /// Create new unfitted min max scaler
+    pub fn new() -> MinMaxScaler {
+        MinMaxScaler::default()
+    }
+
+}
+
+
+impl Fit<NDArray<f64>> for MinMaxScaler {
+
+    /// Store minimum and maximum of every column
+    fn fit(&mut self, x: &NDArray<f64>, _y: &()) -> Result<(), String> {
+        if x.rank() != 2 {
+            return Err("MinMax Scalar: Must be with rank 2 or higher".to_string());
+        }
+
+        let cols = x.shape().dim(1);
+        self.min = vec![f64::INFINITY; cols];
+        self.max = vec![f64::NEG_INFINITY; cols];
+        for row in x.values().chunks(cols) {
+            for (idx, val) in row.iter().enumerate() {
+                self.min[idx] = self.min[idx].min(*val);
+                self.max[idx] = self.max[idx].max(*val);
+            }
+        }
+        Ok(())
+    }
+
+}
+
+
+impl Transform<NDArray<f64>> for MinMaxScaler {
+    type Output = NDArray<f64>;
+
+    /// Scale columns with the fitted bounds, values outside the bounds fall outside [0, 1]
+    fn transform(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        check_columns("MinMax Scalar", x, self.min.len())?;
+        let range: Vec<f64> = self.max.iter().zip(self.min.iter()).map(|(max, min)| max - min).collect();
+        scale_columns(x, &self.min, &range)
+    }
+
+}
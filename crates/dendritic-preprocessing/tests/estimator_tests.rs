@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod estimator_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_preprocessing::standard_scalar::*;
+    use dendritic_preprocessing::encoding::*;
+
+    /// Fit transformer on training data and apply it to held out data
+    fn fit_apply<T>(transformer: &mut T, train: &NDArray<f64>, test: &NDArray<f64>) -> (NDArray<f64>, NDArray<f64>)
+    where
+        T: Fit<NDArray<f64>> + Transform<NDArray<f64>, Output = NDArray<f64>>,
+    {
+        let train_out = transformer.fit_transform(train).unwrap();
+        (train_out, transformer.transform(test).unwrap())
+    }
+
+    fn data() -> NDArray<f64> {
+        NDArray::array(vec![4, 3], vec![
+            1.0, 10.0, -2.0,
+            2.0, 20.0, 0.0,
+            3.0, 30.0, 2.0,
+            4.0, 60.0, 4.0
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_standard_scaler() {
+
+        let x = data();
+        let test = NDArray::array(vec![1, 3], vec![2.5, 30.0, 1.0]).unwrap();
+
+        let mut scaler = StandardScaler::new();
+        let err = scaler.transform(&x).unwrap_err();
+        assert_eq!(err, "Standard Scalar: Must be fitted before transforming");
+
+        let (train_out, test_out) = fit_apply(&mut scaler, &x, &test);
+        let expected = standard_scalar(x.clone()).unwrap();
+        for (a, b) in train_out.values().iter().zip(expected.values().iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        /* held out rows use the training statistics */
+        assert_eq!(scaler.mean, vec![2.5, 30.0, 1.0]);
+        assert_eq!(test_out.values()[0], 0.0);
+        assert_eq!(test_out.values()[2], 0.0);
+
+        let wide = NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap();
+        let err = scaler.transform(&wide).unwrap_err();
+        assert_eq!(err, "Standard Scalar: Expected 3 feature columns, got shape [1, 2]");
+    }
+
+    #[test]
+    fn test_min_max_scaler() {
+
+        let x = data();
+        let test = NDArray::array(vec![1, 3], vec![5.0, 35.0, 0.0]).unwrap();
+
+        let mut scaler = MinMaxScaler::new();
+        let (train_out, test_out) = fit_apply(&mut scaler, &x, &test);
+        assert_eq!(train_out, min_max_scalar(x.clone()).unwrap());
+        assert_eq!(scaler.min, vec![1.0, 10.0, -2.0]);
+        assert_eq!(scaler.max, vec![4.0, 60.0, 4.0]);
+        assert_eq!(test_out.values(), &vec![4.0 / 3.0, 0.5, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_one_hot_estimator() {
+
+        let train = NDArray::array(vec![4, 1], vec![0.0, 2.0, 1.0, 2.0]).unwrap();
+        let test = NDArray::array(vec![2, 1], vec![1.0, 0.0]).unwrap();
+
+        let mut encoder = OneHotEncoding::new(test.clone()).unwrap();
+        assert_eq!(encoder.max_value(), 2.0);
+
+        let (train_out, test_out) = fit_apply(&mut encoder, &train, &test);
+        assert_eq!(encoder.max_value(), 3.0);
+        assert_eq!(train_out.shape().values(), vec![4, 3]);
+        assert_eq!(train_out.values(), &vec![
+            1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0,
+            0.0, 1.0, 0.0,
+            0.0, 0.0, 1.0
+        ]);
+        assert_eq!(test_out.values(), &vec![0.0, 1.0, 0.0, 1.0, 0.0, 0.0]);
+
+        let unseen = NDArray::array(vec![1, 1], vec![3.0]).unwrap();
+        let err = encoder.transform(&unseen).unwrap_err();
+        assert_eq!(err, "One Hot Encoding: Label 3 is not a class index below 3");
+    }
+
+}
@@ -0,0 +1,437 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::Value;
+use dendritic::estimator::{Fit, Predict, Score, r2_score};
+use crate::utils::*;
+
+
+/// Order coordinates are visited in during a coordinate descent sweep
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Selection {
+    #[default]
+    Cyclic,
+    Random
+}
+
+
+/// Method used to fit the weights of lasso and elastic net regression
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PenalizedSolver {
+    #[default]
+    GradientDescent,
+    CoordinateDescent
+}
+
+
+/// Coordinate descent on the elastic net objective
+/// `1 / (2n) * ||y - Xw - b||^2 + alpha * l1_ratio * ||w||_1 + alpha * (1 - l1_ratio) / 2 * ||w||^2`.
+/// Stops once the duality gap drops below `tol` times the centered target variance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateDescent {
+    pub l1_ratio: f64,
+    pub max_iter: usize,
+    pub tol: f64,
+    pub selection: Selection,
+    pub seed: u64
+}
+
+
+/// Weights, bias and convergence details of a single coordinate descent fit
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinateFit {
+    pub alpha: f64,
+    pub weights: NDArray<f64>,
+    pub bias: NDArray<f64>,
+    pub dual_gap: f64,
+    pub n_iter: usize
+}
+
+
+/// Soft thresholding operator `sign(x) * max(|x| - threshold, 0)`
+pub fn soft_threshold(x: f64, threshold: f64) -> f64 {
+    if x > threshold {
+        x - threshold
+    } else if x < -threshold {
+        x + threshold
+    } else {
+        0.0
+    }
+}
+
+
+impl CoordinateDescent {
+
+    /// Create cyclic coordinate descent with 1000 sweeps and tolerance `1e-4`
+    pub fn new(l1_ratio: f64) -> Result<CoordinateDescent, String> {
+
+        if !(0.0..=1.0).contains(&l1_ratio) {
+            return Err("Coordinate Descent: L1 ratio must be between 0 and 1".to_string());
+        }
+
+        Ok(Self {
+            l1_ratio,
+            max_iter: 1000,
+            tol: 1e-4,
+            selection: Selection::Cyclic,
+            seed: 0
+        })
+    }
+
+
+    /// Fit weights and unpenalized bias for one alpha, starting from `init` weights when given
+    pub fn fit(
+        &self,
+        x: &NDArray<f64>,
+        y: &NDArray<f64>,
+        alpha: f64,
+        init: Option<&NDArray<f64>>) -> Result<CoordinateFit, String> {
+
+        self.check(x, y, alpha)?;
+
+        let rows = x.shape().dim(0);
+        let cols = x.shape().dim(1);
+        let (xc, means, yc, y_mean) = center(x, y);
+
+        let mut weights = match init {
+            Some(init) if init.size() == cols => init.values().clone(),
+            Some(init) => {
+                let msg = format!(
+                    "Coordinate Descent: Expected {} initial weights, got shape {:?}",
+                    cols, init.shape().values()
+                );
+                return Err(msg);
+            },
+            None => vec![0.0; cols]
+        };
+
+        /* penalties scaled to the objective multiplied by n */
+        let l1 = alpha * self.l1_ratio * rows as f64;
+        let l2 = alpha * (1.0 - self.l1_ratio) * rows as f64;
+        let norms: Vec<f64> = (0..cols)
+            .map(|col| (0..rows).map(|row| xc[row * cols + col].powi(2)).sum())
+            .collect();
+
+        let mut residual = yc.clone();
+        for row in 0..rows {
+            let sample = &xc[row * cols..(row + 1) * cols];
+            residual[row] -= sample.iter().zip(weights.iter()).map(|(a, w)| a * w).sum::<f64>();
+        }
+
+        let tol = self.tol * yc.iter().map(|val| val * val).sum::<f64>();
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut order: Vec<usize> = (0..cols).collect();
+        let mut gap = f64::INFINITY;
+        let mut n_iter = 0;
+
+        while n_iter < self.max_iter {
+
+            n_iter += 1;
+            if self.selection == Selection::Random {
+                order.shuffle(&mut rng);
+            }
+
+            for &col in &order {
+
+                let old = weights[col];
+                if norms[col] == 0.0 {
+                    weights[col] = 0.0;
+                    continue;
+                }
+
+                let rho: f64 = (0..rows)
+                    .map(|row| xc[row * cols + col] * residual[row])
+                    .sum::<f64>() + norms[col] * old;
+
+                let new = soft_threshold(rho, l1) / (norms[col] + l2);
+                if new != old {
+                    for row in 0..rows {
+                        residual[row] -= xc[row * cols + col] * (new - old);
+                    }
+                    weights[col] = new;
+                }
+            }
+
+            gap = duality_gap(&xc, &yc, &residual, &weights, l1, l2);
+            if gap <= tol {
+                break;
+            }
+        }
+
+        let bias = y_mean - weights.iter().zip(means.iter()).map(|(w, m)| w * m).sum::<f64>();
+        Ok(CoordinateFit {
+            alpha,
+            weights: NDArray::array(vec![cols, 1], weights)?,
+            bias: NDArray::array(vec![1, 1], vec![bias])?,
+            dual_gap: gap / rows as f64,
+            n_iter
+        })
+    }
+
+
+    /// Validate data and settings before fitting
+    fn check(&self, x: &NDArray<f64>, y: &NDArray<f64>, alpha: f64) -> Result<(), String> {
+
+        check_data("Coordinate Descent", x, y)?;
+
+        if alpha < 0.0 {
+            return Err("Coordinate Descent: Alpha must be positive".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.l1_ratio) {
+            return Err("Coordinate Descent: L1 ratio must be between 0 and 1".to_string());
+        }
+
+        Ok(())
+    }
+
+}
+
+
+/// Duality gap of the elastic net objective multiplied by n, on centered data
+fn duality_gap(
+    xc: &[f64],
+    yc: &[f64],
+    residual: &[f64],
+    weights: &[f64],
+    l1: f64,
+    l2: f64) -> f64 {
+
+    let rows = yc.len();
+    let cols = weights.len();
+
+    let dual_norm = (0..cols)
+        .map(|col| {
+            let xtr: f64 = (0..rows).map(|row| xc[row * cols + col] * residual[row]).sum();
+            (xtr - l2 * weights[col]).abs()
+        })
+        .fold(0.0, f64::max);
+
+    let r_norm = residual.iter().map(|r| r * r).sum::<f64>();
+    let w_norm = weights.iter().map(|w| w * w).sum::<f64>();
+    let l1_norm = weights.iter().map(|w| w.abs()).sum::<f64>();
+    let ry = residual.iter().zip(yc.iter()).map(|(r, y)| r * y).sum::<f64>();
+
+    /* scale residual into the dual feasible set */
+    let (scale, mut gap) = if dual_norm > l1 {
+        let scale = l1 / dual_norm;
+        (scale, 0.5 * (r_norm + r_norm * scale * scale))
+    } else {
+        (1.0, r_norm)
+    };
+
+    gap += l1 * l1_norm - scale * ry + 0.5 * l2 * (1.0 + scale * scale) * w_norm;
+    gap
+}
+
+
+/// Largest alpha worth fitting, every weight is zero at or above it
+pub fn alpha_max(x: &NDArray<f64>, y: &NDArray<f64>, l1_ratio: f64) -> Result<f64, String> {
+
+    CoordinateDescent::new(l1_ratio)?.check(x, y, 0.0)?;
+    if l1_ratio == 0.0 {
+        return Err("Coordinate Descent: Alpha grid needs a positive L1 ratio".to_string());
+    }
+
+    let rows = x.shape().dim(0);
+    let cols = x.shape().dim(1);
+    let (xc, _, yc, _) = center(x, y);
+    let max = (0..cols)
+        .map(|col| (0..rows).map(|row| xc[row * cols + col] * yc[row]).sum::<f64>().abs())
+        .fold(0.0, f64::max);
+
+    Ok(max / (rows as f64 * l1_ratio))
+}
+
+
+/// Decreasing grid of `n_alphas` log spaced alphas from `alpha_max` down to `eps * alpha_max`
+pub fn alpha_grid(
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    l1_ratio: f64,
+    n_alphas: usize,
+    eps: f64) -> Result<Vec<f64>, String> {
+
+    if n_alphas == 0 || eps <= 0.0 || eps >= 1.0 {
+        let msg = "Coordinate Descent: Alpha grid needs at least one alpha and eps between 0 and 1";
+        return Err(msg.to_string());
+    }
+
+    let max = alpha_max(x, y, l1_ratio)?;
+    if max == 0.0 || n_alphas == 1 {
+        return Ok(vec![max]);
+    }
+
+    let step = eps.ln() / (n_alphas - 1) as f64;
+    Ok((0..n_alphas).map(|idx| max * (step * idx as f64).exp()).collect())
+}
+
+
+/// Elastic net fits along a path of alphas, every fit warm starts from the previous
+/// weights so alphas should be given in decreasing order
+pub fn enet_path(
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    alphas: &[f64],
+    solver: &CoordinateDescent) -> Result<Vec<CoordinateFit>, String> {
+
+    let mut path: Vec<CoordinateFit> = Vec::with_capacity(alphas.len());
+    for alpha in alphas {
+        let init = path.last().map(|fit| &fit.weights);
+        let fit = solver.fit(x, y, *alpha, init)?;
+        path.push(fit);
+    }
+
+    Ok(path)
+}
+
+
+/// Lasso fits along a path of alphas, see `enet_path`
+pub fn lasso_path(
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    alphas: &[f64],
+    solver: &CoordinateDescent) -> Result<Vec<CoordinateFit>, String> {
+
+    let lasso = CoordinateDescent { l1_ratio: 1.0, ..*solver };
+    enet_path(x, y, alphas, &lasso)
+}
+
+
+/// Lasso regression with alpha selected by k fold cross validation over a path
+pub struct LassoCV {
+    pub weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>,
+    pub alpha: f64,
+    pub alphas: Vec<f64>,
+    pub mse_path: Vec<f64>,
+    grid: Option<Vec<f64>>,
+    folds: usize,
+    n_alphas: usize,
+    solver: CoordinateDescent
+}
+
+
+impl LassoCV {
+
+    /// Create lasso cross validation over contiguous folds with a grid of 100 alphas
+    pub fn new(folds: usize) -> Result<LassoCV, String> {
+
+        if folds < 2 {
+            return Err("LassoCV: Need at least 2 folds".to_string());
+        }
+
+        Ok(Self {
+            weights: Value::new(&NDArray::new(vec![0, 1]).unwrap()),
+            bias: Value::new(&NDArray::new(vec![1, 1]).unwrap()),
+            alpha: 0.0,
+            alphas: Vec::new(),
+            mse_path: Vec::new(),
+            grid: None,
+            folds,
+            n_alphas: 100,
+            solver: CoordinateDescent::new(1.0)?
+        })
+    }
+
+
+    /// Use a fixed set of alphas instead of the generated grid, sorted in decreasing order
+    pub fn set_alphas(&mut self, mut alphas: Vec<f64>) {
+        alphas.sort_by(|a, b| b.total_cmp(a));
+        self.grid = Some(alphas);
+    }
+
+
+    /// Set number of alphas in the generated grid
+    pub fn set_n_alphas(&mut self, n_alphas: usize) {
+        self.n_alphas = n_alphas;
+    }
+
+
+    /// Set coordinate descent settings, the L1 ratio is always kept at 1
+    pub fn set_solver(&mut self, solver: CoordinateDescent) {
+        self.solver = CoordinateDescent { l1_ratio: 1.0, ..solver };
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for LassoCV {
+
+    /// Pick the alpha with lowest mean validation error, then refit on all data
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+
+        self.solver.check(x, y, 0.0)?;
+        let rows = x.shape().dim(0);
+        if rows < self.folds {
+            let msg = format!("LassoCV: Need at least {} rows, got {}", self.folds, rows);
+            return Err(msg);
+        }
+
+        let alphas = match &self.grid {
+            Some(grid) if !grid.is_empty() => grid.clone(),
+            _ => alpha_grid(x, y, 1.0, self.n_alphas, 1e-3)?
+        };
+
+        let mut mse_path = vec![0.0; alphas.len()];
+        for fold in 0..self.folds {
+
+            let start = fold * rows / self.folds;
+            let end = (fold + 1) * rows / self.folds;
+            let train: Vec<usize> = (0..rows).filter(|row| *row < start || *row >= end).collect();
+            let test: Vec<usize> = (start..end).collect();
+
+            let x_train = x.axis_indices(0, train.clone())?;
+            let y_train = y.axis_indices(0, train)?;
+            let x_test = x.axis_indices(0, test.clone())?;
+            let y_test = y.axis_indices(0, test)?;
+
+            let path = lasso_path(&x_train, &y_train, &alphas, &self.solver)?;
+            for (idx, fit) in path.iter().enumerate() {
+                let y_pred = linear_output(
+                    "LassoCV", &x_test, &Value::new(&fit.weights), &Value::new(&fit.bias)
+                )?;
+                let mse = y_pred.values().iter()
+                    .zip(y_test.values().iter())
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>() / y_test.size() as f64;
+                mse_path[idx] += mse / self.folds as f64;
+            }
+        }
+
+        /* first minimum keeps the strongest regularization on ties */
+        let best = (0..alphas.len())
+            .fold(0, |best, idx| if mse_path[idx] < mse_path[best] { idx } else { best });
+
+        let fit = self.solver.fit(x, y, alphas[best], None)?;
+        self.weights = Value::new(&fit.weights);
+        self.bias = Value::new(&fit.bias);
+        self.alpha = alphas[best];
+        self.alphas = alphas;
+        self.mse_path = mse_path;
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for LassoCV {
+    type Output = NDArray<f64>;
+
+    /// Predict outcomes with the weights refit at the selected alpha
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        linear_output("LassoCV", x, &self.weights, &self.bias)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for LassoCV {
+
+    /// R2 score of lasso cross validation predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
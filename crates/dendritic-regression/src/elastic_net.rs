@@ -1,10 +1,16 @@
 use dendritic_ndarray::ndarray::NDArray;
-use dendritic_ndarray::ops::*;
-use dendritic_metrics::loss::*;
 use dendritic_autodiff::node::{Node, Value};
-use dendritic_autodiff::regularizers::*; 
 use dendritic_autodiff::ops::*; 
-use std::fs;
+use dendritic_autodiff::loss::{Loss, MSE};
+use dendritic_autodiff::anomaly::{detect_forward_anomaly, detect_backward_anomaly};
+use dendritic_autodiff::optim::Optimizer;
+use dendritic_autodiff::init::Initializer;
+use dendritic_autodiff::penalty::{Penalty, L1, L2};
+use dendritic_autodiff::scheduler::Scheduler;
+use dendritic_autodiff::state::Parameters;
+use dendritic::estimator::{Fit, Predict, Score, r2_score};
+use crate::utils::*;
+use crate::coordinate_descent::{CoordinateDescent, PenalizedSolver};
 
 
 pub struct ElasticNet {
@@ -12,44 +18,57 @@ pub struct ElasticNet {
     pub outputs: Value<NDArray<f64>>,
     pub weights: Value<NDArray<f64>>, 
     pub bias: Value<NDArray<f64>>,
-    lambda: Value<NDArray<f64>>,
-    learning_rate: f64,
-    loss_function: fn(
-        y_true: &NDArray<f64>, 
-        y_pred: &NDArray<f64>) -> Result<f64, String>
+    lambda: f64,
+    optimizer: Box<dyn Optimizer>,
+    scheduler: Option<Box<dyn Scheduler>>,
+    epochs: usize,
+    solver: PenalizedSolver,
+    coordinate: CoordinateDescent,
 }
 
 
 impl ElasticNet {
 
-    /// Create new instance of elastic net regression
-    pub fn new(
+    /// Create new instance of elastic net regression trained with `optimizer`.
+    /// Every solver minimizes 1 / 2 * ||y - Xw - b||^2 + lambda * (r * ||w||_1 + (1 - r) / 2 * ||w||^2)
+    /// with L1 ratio r of the coordinate descent settings, 0.5 by default
+    pub fn new<O: Optimizer + 'static>(
         features: &NDArray<f64>, 
         y: &NDArray<f64>,
         lambda: f64, 
-        learning_rate: f64) -> Result<ElasticNet, String> {
+        optimizer: O) -> Result<ElasticNet, String> {
 
-        if learning_rate < 0.0 || learning_rate > 1.0 {
-            return Err("Learning rate must be between 1 and 0".to_string());
-        }
+        ElasticNet::with_initializer(
+            features, y, lambda, optimizer, &mut Initializer::zeros()
+        )
+    }
+
+    /// Create new instance of elastic net regression with weights drawn from an initializer,
+    /// bias starts at zero
+    pub fn with_initializer<O: Optimizer + 'static>(
+        features: &NDArray<f64>, 
+        y: &NDArray<f64>,
+        lambda: f64, 
+        optimizer: O,
+        initializer: &mut Initializer) -> Result<ElasticNet, String> {
 
-        let weights = NDArray::new(vec![features.shape().dim(1), 1]).unwrap();
+        check_learning_rate("ElasticNet", &optimizer)?;
+        let weights = initializer.sample(vec![features.shape().dim(1), 1])?;
         let bias = NDArray::new(vec![1, 1]).unwrap();
         let inputs = Value::new(features); 
         let outputs = Value::new(y);
 
-        let lambda_value: NDArray<f64> = NDArray::array(
-            vec![1, 1], vec![lambda]
-        ).unwrap();
-
         Ok(Self {
             features: inputs.clone(),
             outputs: outputs.clone(),
             weights: Value::new(&weights),
             bias: Value::new(&bias),
-            lambda: Value::new(&lambda_value),
-            learning_rate: learning_rate,
-            loss_function: mse,
+            lambda,
+            optimizer: Box::new(optimizer),
+            scheduler: None,
+            epochs: DEFAULT_EPOCHS,
+            solver: PenalizedSolver::default(),
+            coordinate: CoordinateDescent::new(0.5)?
         })
     }
 
@@ -59,8 +78,8 @@ impl ElasticNet {
 
         self.features = Value::new(&inputs); 
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
+        let mut linear = Add::new(
+            MatMul::new(self.features.clone(), self.weights.clone()),
             self.bias.clone()
         );
 
@@ -68,106 +87,114 @@ impl ElasticNet {
         linear.value() 
     }
 
-    /// Save parameters for elastic net regression
-    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+    /// Set optimizer used to update parameters during training
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+
+    /// Set scheduler that updates the optimizer learning rate after every epoch
+    pub fn set_scheduler<S: Scheduler + 'static>(&mut self, scheduler: S) {
+        self.optimizer.set_learning_rate(scheduler.learning_rate());
+        self.scheduler = Some(Box::new(scheduler));
+    }
+
+
+    /// Set number of epochs trained when fitting through the `Fit` trait
+    pub fn set_epochs(&mut self, epochs: usize) {
+        self.epochs = epochs;
+    }
+
+
+    /// Set solver used when fitting through the `Fit` trait
+    pub fn set_solver(&mut self, solver: PenalizedSolver) {
+        self.solver = solver;
+    }
+
+
+    /// Set coordinate descent settings, including the L1 ratio mixing both penalties
+    pub fn set_coordinate_descent(&mut self, coordinate: CoordinateDescent) {
+        self.coordinate = coordinate;
+    }
 
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-        fs::create_dir_all(filepath)?;
 
-        self.weights.val().save(&weights_file).unwrap();
-        self.bias.val().save(&bias_path).unwrap();
+    /// Fit weights and bias with coordinate descent using `lambda / n` as alpha and
+    /// the configured L1 ratio, warm starting from the current weights
+    pub fn solve(&mut self) -> Result<(), String> {
 
+        if self.solver != PenalizedSolver::CoordinateDescent {
+            let msg = format!("ElasticNet: Solver {:?} is not supported, use coordinate descent", self.solver);
+            return Err(msg);
+        }
+
+        let fit = self.coordinate.fit(
+            &self.features.val(),
+            &self.outputs.val(),
+            self.lambda / self.features.val().shape().dim(0) as f64,
+            Some(&self.weights.val())
+        )?;
+        self.weights.set_val(&fit.weights);
+        self.bias.set_val(&fit.bias);
         Ok(())
     }
 
+    /// Save parameters for elastic net regression
+    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+        self.save_state_dict(filepath)
+    }
+
 
     /// Load parameters for elastic net regression
-    pub fn load(
+    pub fn load<O: Optimizer + 'static>(
         filepath: &str, 
         features: &NDArray<f64>, 
         y: &NDArray<f64>, 
-        learning_rate: f64,
-        lambda: f64) -> std::io::Result<ElasticNet> {
-
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-
-        let inputs = Value::new(features); 
-        let outputs = Value::new(y);
-        let load_weights = NDArray::load(&weights_file).unwrap();
-        let load_bias = NDArray::load(&bias_path).unwrap();
-
-        let lambda_value: NDArray<f64> = NDArray::array(
-            vec![1, 1], vec![lambda]
-        ).unwrap();
+        lambda: f64,
+        optimizer: O) -> std::io::Result<ElasticNet> {
 
-        Ok(ElasticNet {
-            features: inputs.clone(),
-            outputs: outputs.clone(),
-            weights: Value::new(&load_weights),
-            bias: Value::new(&load_bias),
-            lambda: Value::new(&lambda_value),
-            learning_rate: learning_rate,
-            loss_function: mse
-        })
+        let mut model = match ElasticNet::new(features, y, lambda, optimizer) {
+            Ok(model) => model,
+            Err(err) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err));
+            }
+        };
 
+        model.load_state_dict_from(filepath)?;
+        Ok(model)
     }
 
 
     /// Train elastic net regression model
     pub fn train(&mut self, epochs: usize, log_output: bool) {
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = MSE::new(
+            Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            ),
+            self.outputs.clone()
         );
 
-        let mut l1_reg = L1Regularization::new(
-            self.weights.clone(),
-            self.lambda.clone(),
-            self.learning_rate
-        );
-
-        let mut l2_reg = L2Regularization::new(
-            self.weights.clone(),
-            self.lambda.clone(),
-            self.learning_rate
-        );
 
         for epoch in 0..epochs {
 
-            linear.forward();
-            l1_reg.forward();
-            l2_reg.forward();
-
-            let y_pred = linear.value();
-            let loss = (self.loss_function)(&self.outputs.val(), &y_pred).unwrap();
-            let error = y_pred.subtract(self.outputs.val()).unwrap();
-            let l1_error = error.scale_add(l1_reg.value()).unwrap();
-            let l2_error = error.scale_add(l2_reg.value()).unwrap();
-
-            linear.backward(error.clone()); 
-            l1_reg.backward(l1_error.clone());
-            l2_reg.backward(l2_error);
-
-            let w_grad = self.features.grad().scalar_mult(
-                self.learning_rate/y_pred.size() as f64
-            ).unwrap();
-
-            let w_update = self.weights.val().subtract(w_grad).unwrap();
-            let l1_reg_w = l1_reg.grad(); 
-            let l2_reg_w = l2_reg.grad(); 
-            let reg_w = l1_reg_w.add(l2_reg_w).unwrap(); 
-            let dw = w_update.subtract(reg_w).unwrap();
-            self.weights.set_val(&dw);
-
-            /* update biases */
-            let b_collapse = self.bias.grad().sum_axis(1).unwrap();
-            let db = b_collapse.scalar_mult(
-                self.learning_rate/y_pred.size() as f64
-            ).unwrap();
-            self.bias.set_val(&db);
+            graph.forward();
+            detect_forward_anomaly(&graph);
+            let loss = graph.loss();
+
+            graph.zero_grad();
+            graph.backprop();
+            detect_backward_anomaly(&graph);
+
+            /* add penalty gradient, scaled by 2 / n like the mean squared error gradient */
+            let lambda = 2.0 * self.lambda / self.outputs.val().size() as f64;
+            let ratio = self.coordinate.l1_ratio;
+            L1::new(lambda * ratio).and(L2::new(lambda * (1.0 - ratio) / 2.0)).apply(&mut self.weights);
+            self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
+
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
 
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
@@ -187,22 +214,14 @@ impl ElasticNet {
         let x_train = x_train_binding.batch(batch_size).unwrap();
         let y_train = y_train_binding.batch(batch_size).unwrap();
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = MSE::new(
+            Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            ),
+            self.outputs.clone()
         );
 
-        let mut l1_reg = L1Regularization::new(
-            self.weights.clone(),
-            self.lambda.clone(),
-            self.learning_rate
-        );
-
-        let mut l2_reg = L2Regularization::new(
-            self.weights.clone(),
-            self.lambda.clone(),
-            self.learning_rate
-        );
 
         for epoch in 0..epochs {
 
@@ -212,42 +231,28 @@ impl ElasticNet {
                 self.features.set_val(&batch);
                 self.outputs.set_val(&y_train[batch_index]);
 
-                linear.forward();
-                l1_reg.forward();
-                l2_reg.forward();
-
-                let y_pred = linear.value();
-                loss = (self.loss_function)(&self.outputs.val(), &y_pred).unwrap();
-                let error = y_pred.subtract(self.outputs.val()).unwrap();
-                let l1_error = error.scale_add(l1_reg.value()).unwrap();
-                let l2_error = error.scale_add(l2_reg.value()).unwrap();
-
-                linear.backward(error.clone()); 
-                l1_reg.backward(l1_error);
-                l2_reg.backward(l2_error);
-
-                let w_grad = self.features.grad().scalar_mult(
-                    self.learning_rate/y_pred.size() as f64
-                ).unwrap();
-
-                let w_update = self.weights.val().subtract(w_grad).unwrap();
-                let l1_reg_w = l1_reg.grad(); 
-                let l2_reg_w = l2_reg.grad(); 
-                let reg_w = l1_reg_w.add(l2_reg_w).unwrap(); 
-                let dw = w_update.subtract(reg_w).unwrap();
-                self.weights.set_val(&dw);
-
-                /* update biases */
-                let b_collapse = self.bias.grad().sum_axis(1).unwrap();
-                let db = b_collapse.scalar_mult(
-                    self.learning_rate/y_pred.size() as f64
-                ).unwrap();
-                self.bias.set_val(&db);
+                graph.forward();
+                detect_forward_anomaly(&graph);
+                loss = graph.loss();
+
+                graph.zero_grad();
+                graph.backprop();
+                detect_backward_anomaly(&graph);
+
+                /* add penalty gradient, scaled by 2 / n like the mean squared error gradient */
+                let lambda = 2.0 * self.lambda / self.outputs.val().size() as f64;
+                let ratio = self.coordinate.l1_ratio;
+                L1::new(lambda * ratio).and(L2::new(lambda * (1.0 - ratio) / 2.0)).apply(&mut self.weights);
+                self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
 
                 batch_index += 1; 
 
             }
 
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
+
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
             }
@@ -257,3 +262,56 @@ impl ElasticNet {
     }
 
 }
+
+
+impl Parameters for ElasticNet {
+
+    /// Weights and bias of elastic net regression
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        vec![
+            ("weights".to_string(), self.weights.clone()),
+            ("bias".to_string(), self.bias.clone())
+        ]
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for ElasticNet {
+
+    /// Fit elastic net regression on new data with the configured solver, gradient
+    /// descent trains for the configured number of epochs from the current weights
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_fit("ElasticNet", x, y, &self.weights)?;
+        self.features = Value::new(x);
+        self.outputs = Value::new(y);
+        match self.solver {
+            PenalizedSolver::GradientDescent => self.train(self.epochs, false),
+            PenalizedSolver::CoordinateDescent => self.solve()?
+        }
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for ElasticNet {
+    type Output = NDArray<f64>;
+
+    /// Predict outcomes for elastic net regression without replacing the training features
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        linear_output("ElasticNet", x, &self.weights, &self.bias)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for ElasticNet {
+
+    /// R2 score of elastic net regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
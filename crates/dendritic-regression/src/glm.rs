@@ -0,0 +1,499 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::Value;
+use dendritic_autodiff::optim::{Optimizer, SGD};
+use dendritic::estimator::{Fit, Predict, Score};
+use crate::utils::*;
+use crate::solver::{Minimized, weighted_ridge};
+
+
+/// Distribution of the target in a generalized linear model,
+/// `Tweedie` takes the power of its variance function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Family {
+    Gaussian,
+    Poisson,
+    Gamma,
+    Tweedie(f64),
+    Binomial
+}
+
+
+/// Link between the mean of the target and the linear predictor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Link {
+    Identity,
+    Log,
+    Logit
+}
+
+
+/// Method used to fit generalized linear model weights
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GLMSolver {
+    #[default]
+    IRLS,
+    GradientDescent
+}
+
+
+impl Family {
+
+    /// Link commonly paired with the family, log for the positive families
+    pub fn default_link(&self) -> Link {
+        match self {
+            Family::Gaussian => Link::Identity,
+            Family::Binomial => Link::Logit,
+            _ => Link::Log
+        }
+    }
+
+    /// Variance of the target as a function of its mean
+    pub fn variance(&self, mu: f64) -> f64 {
+        match self {
+            Family::Gaussian => 1.0,
+            Family::Poisson => mu,
+            Family::Gamma => mu * mu,
+            Family::Tweedie(power) => mu.powf(*power),
+            Family::Binomial => mu * (1.0 - mu)
+        }
+    }
+
+    /// Deviance of a single target and predicted mean
+    pub fn unit_deviance(&self, y: f64, mu: f64) -> f64 {
+        match self {
+            Family::Gaussian => (y - mu).powi(2),
+            Family::Poisson => 2.0 * (xlogy(y, y / mu) - y + mu),
+            Family::Gamma => 2.0 * ((y - mu) / mu - (y / mu).ln()),
+            Family::Binomial => 2.0 * (xlogy(y, y / mu) + xlogy(1.0 - y, (1.0 - y) / (1.0 - mu))),
+            Family::Tweedie(power) => match *power {
+                0.0 => Family::Gaussian.unit_deviance(y, mu),
+                1.0 => Family::Poisson.unit_deviance(y, mu),
+                2.0 => Family::Gamma.unit_deviance(y, mu),
+                p => 2.0 * (
+                    y.max(0.0).powf(2.0 - p) / ((1.0 - p) * (2.0 - p))
+                    - y * mu.powf(1.0 - p) / (1.0 - p)
+                    + mu.powf(2.0 - p) / (2.0 - p)
+                )
+            }
+        }
+    }
+
+    /// Total deviance of targets and predicted means
+    pub fn deviance(&self, y: &[f64], mu: &[f64]) -> f64 {
+        y.iter().zip(mu.iter()).map(|(y, mu)| self.unit_deviance(*y, *mu)).sum()
+    }
+
+    /// Check targets lie in the support of the family
+    pub fn check_target(&self, y: &[f64]) -> Result<(), String> {
+
+        let (valid, support) = match self {
+            Family::Gaussian => (true, ""),
+            Family::Poisson => (y.iter().all(|y| *y >= 0.0), "non negative"),
+            Family::Gamma => (y.iter().all(|y| *y > 0.0), "positive"),
+            Family::Binomial => (y.iter().all(|y| (0.0..=1.0).contains(y)), "between 0 and 1"),
+            Family::Tweedie(power) => match *power {
+                p if p > 0.0 && p < 1.0 => {
+                    return Err("GLM: Tweedie power between 0 and 1 is not supported".to_string());
+                },
+                p if p <= 0.0 => (true, ""),
+                p if p < 2.0 => (y.iter().all(|y| *y >= 0.0), "non negative"),
+                _ => (y.iter().all(|y| *y > 0.0), "positive")
+            }
+        };
+
+        if !valid {
+            return Err(format!("GLM: Targets must be {} for the {:?} family", support, self));
+        }
+
+        Ok(())
+    }
+
+}
+
+
+impl Link {
+
+    /// Linear predictor for a mean
+    pub fn link(&self, mu: f64) -> f64 {
+        match self {
+            Link::Identity => mu,
+            Link::Log => mu.ln(),
+            Link::Logit => (mu / (1.0 - mu)).ln()
+        }
+    }
+
+    /// Mean for a linear predictor
+    pub fn inverse(&self, eta: f64) -> f64 {
+        match self {
+            Link::Identity => eta,
+            Link::Log => eta.exp(),
+            Link::Logit => 1.0 / (1.0 + (-eta).exp())
+        }
+    }
+
+    /// Derivative of the link with respect to the mean
+    pub fn derivative(&self, mu: f64) -> f64 {
+        match self {
+            Link::Identity => 1.0,
+            Link::Log => 1.0 / mu,
+            Link::Logit => 1.0 / (mu * (1.0 - mu))
+        }
+    }
+
+}
+
+
+/// `x * ln(y)` with the convention that it is zero when `x` is zero
+fn xlogy(x: f64, y: f64) -> f64 {
+    if x == 0.0 { 0.0 } else { x * y.ln() }
+}
+
+
+/// Generalized linear model minimizing `deviance / (2n) + alpha / 2 * ||w||^2`
+pub struct GLM {
+    pub weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>,
+    family: Family,
+    link: Link,
+    solver: GLMSolver,
+    optimizer: Box<dyn Optimizer>,
+    alpha: f64,
+    max_iter: usize,
+    tol: f64,
+    n_iter: usize
+}
+
+
+impl GLM {
+
+    /// Create new unfitted generalized linear model
+    pub fn new(family: Family, link: Link) -> Result<GLM, String> {
+
+        family.check_target(&[])?;
+        if link == Link::Logit && family != Family::Binomial {
+            return Err("GLM: Logit link requires the binomial family".to_string());
+        }
+
+        Ok(Self {
+            weights: Value::new(&NDArray::new(vec![0, 1]).unwrap()),
+            bias: Value::new(&NDArray::new(vec![1, 1]).unwrap()),
+            family,
+            link,
+            solver: GLMSolver::default(),
+            optimizer: Box::new(SGD::new(0.1)),
+            alpha: 0.0,
+            max_iter: 100,
+            tol: 1e-8,
+            n_iter: 0
+        })
+    }
+
+    /// Create generalized linear model with the default link of the family
+    pub fn with_family(family: Family) -> Result<GLM, String> {
+        GLM::new(family, family.default_link())
+    }
+
+    /// Create logistic regression as a binomial model with logit link,
+    /// `Logistic` fits with the objective and IRLS of this model
+    pub fn logistic() -> Result<GLM, String> {
+        GLM::new(Family::Binomial, Link::Logit)
+    }
+
+    /// Set solver used when fitting
+    pub fn set_solver(&mut self, solver: GLMSolver) {
+        self.solver = solver;
+    }
+
+    /// Set optimizer used by the gradient descent solver
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+    /// Set strength of the L2 penalty on the weights
+    pub fn set_alpha(&mut self, alpha: f64) -> Result<(), String> {
+        if alpha < 0.0 {
+            return Err("GLM: Alpha must be positive".to_string());
+        }
+        self.alpha = alpha;
+        Ok(())
+    }
+
+    /// Set maximum number of IRLS iterations or gradient descent epochs
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+    /// Set tolerance on the relative objective change for IRLS
+    /// and on the gradient norm for gradient descent
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+    /// Family of the model
+    pub fn family(&self) -> Family {
+        self.family
+    }
+
+    /// Link of the model
+    pub fn link(&self) -> Link {
+        self.link
+    }
+
+    /// Number of iterations run by the last fit
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+    /// Total deviance of the predicted means against targets
+    pub fn deviance(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let mu = Predict::predict(self, x)?;
+        check_rows("GLM", y, mu.size())?;
+        Ok(self.family.deviance(y.values(), mu.values()))
+    }
+
+    /// Clip a mean inside the support of the family and the domain of the link
+    fn clip(&self, mu: f64) -> f64 {
+        let mu = match self.family {
+            Family::Gaussian => mu,
+            Family::Binomial => mu.clamp(1e-12, 1.0 - 1e-12),
+            Family::Tweedie(power) if power <= 0.0 => mu,
+            _ => mu.max(1e-12)
+        };
+        match self.link {
+            Link::Log => mu.max(1e-12),
+            _ => mu
+        }
+    }
+
+    /// Clipped means for linear predictors
+    fn means(&self, eta: &[f64]) -> Vec<f64> {
+        eta.iter().map(|eta| self.clip(self.link.inverse(eta.clamp(-700.0, 700.0)))).collect()
+    }
+
+    /// Linear predictor `x * w + b` for row major features
+    fn eta(x: &[f64], cols: usize, weights: &[f64], bias: f64) -> Vec<f64> {
+        x.chunks(cols)
+            .map(|row| row.iter().zip(weights.iter()).map(|(x, w)| x * w).sum::<f64>() + bias)
+            .collect()
+    }
+
+    /// Penalized objective `deviance / (2n) + alpha / 2 * ||w||^2`
+    fn objective(&self, y: &[f64], mu: &[f64], weights: &[f64]) -> f64 {
+        let penalty = weights.iter().map(|w| w * w).sum::<f64>();
+        self.family.deviance(y, mu) / (2.0 * y.len() as f64) + 0.5 * self.alpha * penalty
+    }
+
+    /// Gradient of the penalized objective over the weights and the bias at means `mu`
+    fn gradient(&self, x: &[f64], cols: usize, y: &[f64], mu: &[f64], weights: &[f64]) -> (Vec<f64>, f64) {
+
+        /* derivative of the half mean deviance with respect to the linear predictor */
+        let rows = y.len() as f64;
+        let grad_eta: Vec<f64> = y.iter().zip(mu.iter()).map(|(y, mu)| {
+            let denom = self.family.variance(*mu) * self.link.derivative(*mu);
+            -(y - mu) / (denom * rows)
+        }).collect();
+
+        let mut grad_w: Vec<f64> = weights.iter().map(|w| self.alpha * w).collect();
+        for (sample, grad) in x.chunks(cols).zip(grad_eta.iter()) {
+            for col in 0..cols {
+                grad_w[col] += sample[col] * grad;
+            }
+        }
+
+        (grad_w, grad_eta.iter().sum())
+    }
+
+    /// Penalized objective and its gradient over the weights followed by the bias
+    pub(crate) fn objective_gradient(&self, x: &NDArray<f64>, y: &[f64], params: &[f64]) -> (f64, Vec<f64>) {
+        let cols = x.shape().dim(1);
+        let weights = &params[..cols];
+        let mu = self.means(&Self::eta(x.values(), cols, weights, params[cols]));
+        let (mut grad, grad_b) = self.gradient(x.values(), cols, y, &mu, weights);
+        grad.push(grad_b);
+        (self.objective(y, &mu, weights), grad)
+    }
+
+    /// Iteratively reweighted least squares with step halving, starting from `init` weights
+    /// followed by the bias. Stops after `max_iter` iterations, once the relative objective
+    /// change drops below `change_tol` or once the gradient norm drops to `grad_tol`
+    pub(crate) fn irls(
+        &self,
+        x: &NDArray<f64>,
+        y: &[f64],
+        init: Vec<f64>,
+        change_tol: f64,
+        grad_tol: f64) -> Result<Minimized, String> {
+
+        let rows = y.len();
+        let cols = x.shape().dim(1);
+        let vals = x.values();
+
+        let mut weights = init[..cols].to_vec();
+        let mut bias = init[cols];
+        let mut mu = self.means(&Self::eta(vals, cols, &weights, bias));
+        let mut objective = self.objective(y, &mu, &weights);
+        let grad_norm = |weights: &[f64], mu: &[f64]| {
+            let (grad_w, grad_b) = self.gradient(vals, cols, y, mu, weights);
+            (grad_w.iter().map(|g| g * g).sum::<f64>() + grad_b * grad_b).sqrt()
+        };
+
+        let mut n_iter = 0;
+        while n_iter < self.max_iter && grad_norm(&weights, &mu) > grad_tol {
+
+            n_iter += 1;
+            let eta = Self::eta(vals, cols, &weights, bias);
+
+            /* working response and weights of the local weighted least squares */
+            let mut z = vec![0.0; rows];
+            let mut w = vec![0.0; rows];
+            for row in 0..rows {
+                let deriv = self.link.derivative(mu[row]);
+                z[row] = eta[row] + (y[row] - mu[row]) * deriv;
+                w[row] = 1.0 / (self.family.variance(mu[row]) * deriv * deriv);
+            }
+
+            let (new_weights, new_bias) = weighted_ridge(vals, cols, &z, &w, self.alpha * rows as f64)?;
+
+            /* halve the step until the objective does not increase */
+            let mut step = 1.0;
+            let (mut cand_weights, mut cand_bias, mut cand_mu, mut cand_obj);
+            loop {
+                cand_weights = weights.iter().zip(new_weights.iter())
+                    .map(|(old, new)| old + step * (new - old))
+                    .collect::<Vec<f64>>();
+                cand_bias = bias + step * (new_bias - bias);
+                cand_mu = self.means(&Self::eta(vals, cols, &cand_weights, cand_bias));
+                cand_obj = self.objective(y, &cand_mu, &cand_weights);
+                if cand_obj <= objective || step < 1e-10 {
+                    break;
+                }
+                step *= 0.5;
+            }
+
+            let change = (objective - cand_obj).abs() / (cand_obj.abs() + 0.1);
+            weights = cand_weights;
+            bias = cand_bias;
+            mu = cand_mu;
+            objective = cand_obj;
+            if change < change_tol {
+                break;
+            }
+        }
+
+        let grad_norm = grad_norm(&weights, &mu);
+        weights.push(bias);
+        Ok(Minimized { params: weights, n_iter, grad_norm })
+    }
+
+    /// Fit with IRLS from zero weights and the bias of the mean target
+    fn fit_irls(&mut self, x: &NDArray<f64>, y: &[f64]) -> Result<(), String> {
+
+        let cols = x.shape().dim(1);
+        let y_mean = y.iter().sum::<f64>() / y.len() as f64;
+        let mut init = vec![0.0; cols];
+        init.push(self.link.link(self.clip(y_mean)));
+
+        let result = self.irls(x, y, init, self.tol, 0.0)?;
+        self.weights = Value::new(&NDArray::array(vec![cols, 1], result.params[..cols].to_vec())?);
+        self.bias = Value::new(&NDArray::array(vec![1, 1], vec![result.params[cols]])?);
+        self.n_iter = result.n_iter;
+        Ok(())
+    }
+
+    /// Gradient descent on the penalized objective with the configured optimizer
+    fn gradient_descent(&mut self, x: &NDArray<f64>, y: &[f64]) -> Result<(), String> {
+
+        let cols = x.shape().dim(1);
+        let vals = x.values();
+
+        if self.weights.val().size() != cols {
+            self.weights = Value::new(&NDArray::new(vec![cols, 1])?);
+            self.bias = Value::new(&NDArray::new(vec![1, 1])?);
+        }
+
+        self.n_iter = 0;
+        while self.n_iter < self.max_iter {
+
+            self.n_iter += 1;
+            let weights = self.weights.val().values().clone();
+            let bias = self.bias.val().values()[0];
+            let mu = self.means(&Self::eta(vals, cols, &weights, bias));
+            let (grad_w, grad_b) = self.gradient(vals, cols, y, &mu, &weights);
+
+            let norm = (grad_w.iter().map(|g| g * g).sum::<f64>() + grad_b * grad_b).sqrt();
+            if norm < self.tol {
+                break;
+            }
+
+            self.weights.set_grad(&NDArray::array(vec![cols, 1], grad_w)?);
+            self.bias.set_grad(&NDArray::array(vec![1, 1], vec![grad_b])?);
+            self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
+        }
+
+        Ok(())
+    }
+
+}
+
+
+/// Check number of target rows matches predictions
+fn check_rows(name: &str, y: &NDArray<f64>, rows: usize) -> Result<(), String> {
+    if y.rank() != 2 || y.shape().values() != vec![rows, 1] {
+        let msg = format!(
+            "{}: Target shape {:?} does not match {} feature rows",
+            name, y.shape().values(), rows
+        );
+        return Err(msg);
+    }
+    Ok(())
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for GLM {
+
+    /// Fit the model with the configured solver, gradient descent warm starts
+    /// from the current weights when the feature count matches
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+
+        check_data("GLM", x, y)?;
+        self.family.check_target(y.values())?;
+
+        match self.solver {
+            GLMSolver::IRLS => self.fit_irls(x, y.values()),
+            GLMSolver::GradientDescent => self.gradient_descent(x, y.values())
+        }
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for GLM {
+    type Output = NDArray<f64>;
+
+    /// Predicted means of the target
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        let eta = linear_output("GLM", x, &self.weights, &self.bias)?;
+        NDArray::array(eta.shape().values(), self.means(eta.values()))
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for GLM {
+
+    /// Fraction of deviance explained `D^2`, relative to predicting the target mean
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+
+        let deviance = self.deviance(x, y)?;
+        let y_mean = y.values().iter().sum::<f64>() / y.size() as f64;
+        let null: Vec<f64> = vec![y_mean; y.size()];
+        let null_deviance = self.family.deviance(y.values(), &null);
+
+        if null_deviance == 0.0 {
+            return Ok(if deviance == 0.0 { 1.0 } else { 0.0 });
+        }
+
+        Ok(1.0 - deviance / null_deviance)
+    }
+
+}
@@ -1,10 +1,16 @@
 use dendritic_ndarray::ndarray::NDArray;
-use dendritic_ndarray::ops::*;
-use dendritic_metrics::loss::*;
 use dendritic_autodiff::node::{Node, Value};
-use dendritic_autodiff::regularizers::*; 
 use dendritic_autodiff::ops::*; 
-use std::fs;
+use dendritic_autodiff::loss::{Loss, MSE};
+use dendritic_autodiff::anomaly::{detect_forward_anomaly, detect_backward_anomaly};
+use dendritic_autodiff::optim::Optimizer;
+use dendritic_autodiff::init::Initializer;
+use dendritic_autodiff::penalty::{Penalty, L1};
+use dendritic_autodiff::scheduler::Scheduler;
+use dendritic_autodiff::state::Parameters;
+use dendritic::estimator::{Fit, Predict, Score, r2_score};
+use crate::utils::*;
+use crate::coordinate_descent::{CoordinateDescent, PenalizedSolver};
 
 
 pub struct Lasso {
@@ -12,44 +18,56 @@ pub struct Lasso {
     pub outputs: Value<NDArray<f64>>,
     pub weights: Value<NDArray<f64>>, 
     pub bias: Value<NDArray<f64>>,
-    lambda: Value<NDArray<f64>>,
-    learning_rate: f64,
-    loss_function: fn(
-        y_true: &NDArray<f64>, 
-        y_pred: &NDArray<f64>) -> Result<f64, String>
+    lambda: f64,
+    optimizer: Box<dyn Optimizer>,
+    scheduler: Option<Box<dyn Scheduler>>,
+    epochs: usize,
+    solver: PenalizedSolver,
+    coordinate: CoordinateDescent,
 }
 
 
 impl Lasso {
 
-    /// Create new instance of lasso regression
-    pub fn new(
+    /// Create new instance of lasso regression trained with `optimizer`.
+    /// Every solver minimizes 1 / 2 * ||y - Xw - b||^2 + lambda * ||w||_1
+    pub fn new<O: Optimizer + 'static>(
         features: &NDArray<f64>, 
         y: &NDArray<f64>,
         lambda: f64, 
-        learning_rate: f64) -> Result<Lasso, String> {
+        optimizer: O) -> Result<Lasso, String> {
 
-        if learning_rate < 0.0 || learning_rate > 1.0 {
-            return Err("Learning rate must be between 1 and 0".to_string());
-        }
+        Lasso::with_initializer(
+            features, y, lambda, optimizer, &mut Initializer::zeros()
+        )
+    }
+
+    /// Create new instance of lasso regression with weights drawn from an initializer,
+    /// bias starts at zero
+    pub fn with_initializer<O: Optimizer + 'static>(
+        features: &NDArray<f64>, 
+        y: &NDArray<f64>,
+        lambda: f64, 
+        optimizer: O,
+        initializer: &mut Initializer) -> Result<Lasso, String> {
 
-        let weights = NDArray::new(vec![features.shape().dim(1), 1]).unwrap();
+        check_learning_rate("Lasso", &optimizer)?;
+        let weights = initializer.sample(vec![features.shape().dim(1), 1])?;
         let bias = NDArray::new(vec![1, 1]).unwrap();
         let inputs = Value::new(features); 
         let outputs = Value::new(y);
 
-        let lambda_value: NDArray<f64> = NDArray::array(
-            vec![1, 1], vec![lambda]
-        ).unwrap();
-
         Ok(Self {
             features: inputs.clone(),
             outputs: outputs.clone(),
             weights: Value::new(&weights),
             bias: Value::new(&bias),
-            lambda: Value::new(&lambda_value),
-            learning_rate: learning_rate,
-            loss_function: mse,
+            lambda,
+            optimizer: Box::new(optimizer),
+            scheduler: None,
+            epochs: DEFAULT_EPOCHS,
+            solver: PenalizedSolver::default(),
+            coordinate: CoordinateDescent::new(1.0)?
         })
     }
 
@@ -58,8 +76,8 @@ impl Lasso {
 
         self.features = Value::new(&inputs); 
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
+        let mut linear = Add::new(
+            MatMul::new(self.features.clone(), self.weights.clone()),
             self.bias.clone()
         );
 
@@ -67,94 +85,111 @@ impl Lasso {
         linear.value() 
     }
 
-    /// Save model parameters for lasso regression
-    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+    /// Set optimizer used to update parameters during training
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
 
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-        fs::create_dir_all(filepath)?;
 
-        self.weights.val().save(&weights_file).unwrap();
-        self.bias.val().save(&bias_path).unwrap();
+    /// Set scheduler that updates the optimizer learning rate after every epoch
+    pub fn set_scheduler<S: Scheduler + 'static>(&mut self, scheduler: S) {
+        self.optimizer.set_learning_rate(scheduler.learning_rate());
+        self.scheduler = Some(Box::new(scheduler));
+    }
+
+
+    /// Set number of epochs trained when fitting through the `Fit` trait
+    pub fn set_epochs(&mut self, epochs: usize) {
+        self.epochs = epochs;
+    }
+
+
+    /// Set solver used when fitting through the `Fit` trait
+    pub fn set_solver(&mut self, solver: PenalizedSolver) {
+        self.solver = solver;
+    }
+
+
+    /// Set coordinate descent settings, the L1 ratio is always kept at 1
+    pub fn set_coordinate_descent(&mut self, coordinate: CoordinateDescent) {
+        self.coordinate = CoordinateDescent { l1_ratio: 1.0, ..coordinate };
+    }
 
+
+    /// Fit weights and bias with coordinate descent using `lambda / n` as alpha,
+    /// warm starting from the current weights
+    pub fn solve(&mut self) -> Result<(), String> {
+
+        if self.solver != PenalizedSolver::CoordinateDescent {
+            let msg = format!("Lasso: Solver {:?} is not supported, use coordinate descent", self.solver);
+            return Err(msg);
+        }
+
+        let fit = self.coordinate.fit(
+            &self.features.val(),
+            &self.outputs.val(),
+            self.lambda / self.features.val().shape().dim(0) as f64,
+            Some(&self.weights.val())
+        )?;
+        self.weights.set_val(&fit.weights);
+        self.bias.set_val(&fit.bias);
         Ok(())
     }
 
+    /// Save model parameters for lasso regression
+    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+        self.save_state_dict(filepath)
+    }
+
 
     /// Load model parameters for lasso regression
-    pub fn load(
+    pub fn load<O: Optimizer + 'static>(
         filepath: &str, 
         features: &NDArray<f64>, 
         y: &NDArray<f64>, 
-        learning_rate: f64,
-        lambda: f64) -> std::io::Result<Lasso> {
+        lambda: f64,
+        optimizer: O) -> std::io::Result<Lasso> {
 
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-
-        let inputs = Value::new(features); 
-        let outputs = Value::new(y);
-        let load_weights = NDArray::load(&weights_file).unwrap();
-        let load_bias = NDArray::load(&bias_path).unwrap();
-
-        let lambda_value: NDArray<f64> = NDArray::array(
-            vec![1, 1], vec![lambda]
-        ).unwrap();
-
-        Ok(Lasso {
-            features: inputs.clone(),
-            outputs: outputs.clone(),
-            weights: Value::new(&load_weights),
-            bias: Value::new(&load_bias),
-            lambda: Value::new(&lambda_value),
-            learning_rate: learning_rate,
-            loss_function: mse
-        })
+        let mut model = match Lasso::new(features, y, lambda, optimizer) {
+            Ok(model) => model,
+            Err(err) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err));
+            }
+        };
 
+        model.load_state_dict_from(filepath)?;
+        Ok(model)
     }
 
     /// Train model parameters for lasso regression
     pub fn train(&mut self, epochs: usize, log_output: bool) {
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
-        );
-
-        let mut reg = L1Regularization::new(
-            self.weights.clone(),
-            self.lambda.clone(),
-            self.learning_rate
+        let mut graph = MSE::new(
+            Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            ),
+            self.outputs.clone()
         );
 
         for epoch in 0..epochs {
 
-            linear.forward();
-            reg.forward();
-
-            let y_pred = linear.value();
-            let loss = (self.loss_function)(&self.outputs.val(), &y_pred).unwrap();
-            let error = y_pred.subtract(self.outputs.val()).unwrap();
-            let final_output = error.scale_add(reg.value()).unwrap();
-
-            linear.backward(error.clone()); 
-            reg.backward(final_output);
+            graph.forward();
+            detect_forward_anomaly(&graph);
+            let loss = graph.loss();
 
-            let w_grad = self.features.grad().scalar_mult(
-                self.learning_rate/y_pred.size() as f64
-            ).unwrap();
+            graph.zero_grad();
+            graph.backprop();
+            detect_backward_anomaly(&graph);
 
-            let w_update = self.weights.val().subtract(w_grad).unwrap();
-            let reg_w = reg.grad();
-            let dw = w_update.subtract(reg_w).unwrap();
-            self.weights.set_val(&dw);
+            /* add penalty gradient, scaled by 2 / n like the mean squared error gradient */
+            let lambda = 2.0 * self.lambda / self.outputs.val().size() as f64;
+            L1::new(lambda).apply(&mut self.weights);
+            self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
 
-            /* update biases */
-            let b_collapse = self.bias.grad().sum_axis(1).unwrap();
-            let db = b_collapse.scalar_mult(
-                self.learning_rate/y_pred.size() as f64
-            ).unwrap();
-            self.bias.set_val(&db);
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
 
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
@@ -173,15 +208,12 @@ impl Lasso {
         let x_train = x_train_binding.batch(batch_size).unwrap();
         let y_train = y_train_binding.batch(batch_size).unwrap();
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
-        );
-
-        let mut reg = L1Regularization::new(
-            self.weights.clone(),
-            self.lambda.clone(),
-            self.learning_rate
+        let mut graph = MSE::new(
+            Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            ),
+            self.outputs.clone()
         );
 
         for epoch in 0..epochs {
@@ -192,39 +224,27 @@ impl Lasso {
                 self.features.set_val(&batch);
                 self.outputs.set_val(&y_train[batch_index]);
 
-                linear.forward();
-                reg.forward();
-
-                let y_pred = linear.value();
-                loss = (self.loss_function)(
-                    &self.outputs.val(), &y_pred
-                ).unwrap();
-
-                let error = y_pred.subtract(self.outputs.val()).unwrap();
-
-                linear.backward(error.clone());
-                reg.backward(error);
+                graph.forward();
+                detect_forward_anomaly(&graph);
+                loss = graph.loss();
 
-                let w_grad = self.features.grad().scalar_mult(
-                    self.learning_rate/y_pred.size() as f64
-                ).unwrap();
+                graph.zero_grad();
+                graph.backprop();
+                detect_backward_anomaly(&graph);
 
-                let w_update = self.weights.val().subtract(w_grad).unwrap();
-                let reg_w = reg.grad();
-                let dw = w_update.subtract(reg_w).unwrap();
-                self.weights.set_val(&dw);
-
-                /* update biases */
-                let b_collapse = self.bias.grad().sum_axis(1).unwrap();
-                let db = b_collapse.scalar_mult(
-                    self.learning_rate/y_pred.size() as f64
-                ).unwrap();
-                self.bias.set_val(&db);
+                /* add penalty gradient, scaled by 2 / n like the mean squared error gradient */
+                let lambda = 2.0 * self.lambda / self.outputs.val().size() as f64;
+                L1::new(lambda).apply(&mut self.weights);
+                self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
                 
                 batch_index += 1; 
 
             }
 
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
+
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
             }
@@ -233,3 +253,56 @@ impl Lasso {
     }
 
 }
+
+
+impl Parameters for Lasso {
+
+    /// Weights and bias of lasso regression
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        vec![
+            ("weights".to_string(), self.weights.clone()),
+            ("bias".to_string(), self.bias.clone())
+        ]
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for Lasso {
+
+    /// Fit lasso regression on new data with the configured solver, gradient
+    /// descent trains for the configured number of epochs from the current weights
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_fit("Lasso", x, y, &self.weights)?;
+        self.features = Value::new(x);
+        self.outputs = Value::new(y);
+        match self.solver {
+            PenalizedSolver::GradientDescent => self.train(self.epochs, false),
+            PenalizedSolver::CoordinateDescent => self.solve()?
+        }
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for Lasso {
+    type Output = NDArray<f64>;
+
+    /// Predict outcomes for lasso regression without replacing the training features
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        linear_output("Lasso", x, &self.weights, &self.bias)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for Lasso {
+
+    /// R2 score of lasso regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
@@ -11,6 +11,11 @@
 //! - **Ridge**: One hot encoding for multi class data
 //! - **Elastic Net**: One hot encoding for multi class data
 //! - **Logistic**: One hot encoding for multi class data
+//! - **Solver**: Cholesky and QR closed form solvers for linear and ridge regression
+//! - **Coordinate Descent**: Exact sparse lasso and elastic net fits, regularization paths and `LassoCV`
+//! - **GLM**: Gaussian, Poisson, Gamma, Tweedie and Binomial regression fit with IRLS or gradient descent
+//! - **Robust**: Huber and quantile regression and a RANSAC wrapper for outlier heavy data
+//! - **Summary**: Standard errors, tests, confidence intervals and fit statistics for `Linear` and `Logistic`
 //!
 //! ## Example Linear Model Usage
 //! This is an example of using the linear models available in the regression crate for dendritic. 
@@ -24,6 +29,7 @@
 //! use dendritic_regression::ridge::*;
 //! use dendritic_regression::lasso::*;
 //! use dendritic_datasets::airfoil_noise::*;
+//! use dendritic_autodiff::optim::SGD;
 //! 
 //! fn main() {
 //!
@@ -38,28 +44,28 @@
 //!    let mut linear = Linear::new(
 //!        &x_train, 
 //!        &y_train, 
-//!        0.01
+//!        SGD::new(0.01)
 //!    ).unwrap();
 //!
 //!    // ridge
 //!    let mut ridge = Ridge::new(
 //!        &x_train, 
 //!        &y_train,
-//!        lambda, learning_rate
+//!        lambda, SGD::new(learning_rate)
 //!    ).unwrap();
 //!
 //!    // lasso
 //!    let mut lasso = Lasso::new(
 //!        &x_train, 
 //!        &y_train,
-//!        lambda, learning_rate
+//!        lambda, SGD::new(learning_rate)
 //!    ).unwrap();
 //!
 //!    // elastic net
 //!    let mut model = ElasticNet::new(
 //!        &x_train, 
 //!        &y_train,
-//!        lambda, learning_rate
+//!        lambda, SGD::new(learning_rate)
 //!    ).unwrap();
 //!
 //!    // Example of training the linear model
@@ -80,7 +86,7 @@
 //! use dendritic_metrics::loss::*;
 //! use dendritic_regression::logistic::*;
 //! use dendritic_datasets::breast_cancer::*;
-//! use dendritic_metrics::activations::*;
+//! use dendritic_autodiff::optim::SGD;
 //! 
 //! fn main() {
 //!
@@ -92,8 +98,7 @@
 //!    let mut log_model = Logistic::new(
 //!        &x_train,
 //!        &y_train,
-//!        sigmoid_vec,
-//!        0.001
+//!        SGD::new(0.001)
 //!    ).unwrap();
 //!
 //!    log_model.sgd(1000, true, 5);
@@ -118,3 +123,9 @@ pub mod linear;
 pub mod ridge;
 pub mod lasso;
 pub mod elastic_net;
+pub mod utils;
+pub mod solver;
+pub mod coordinate_descent;
+pub mod glm;
+pub mod robust;
+pub mod summary;
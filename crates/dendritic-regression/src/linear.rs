@@ -1,35 +1,50 @@
 use dendritic_ndarray::ndarray::NDArray;
-use dendritic_ndarray::ops::*;
-use dendritic_metrics::loss::*;
 use dendritic_autodiff::node::{Node, Value};
 use dendritic_autodiff::ops::*; 
-use std::fs;
+use dendritic_autodiff::loss::{Loss, MSE};
+use dendritic_autodiff::anomaly::{detect_forward_anomaly, detect_backward_anomaly};
+use dendritic_autodiff::optim::Optimizer;
+use dendritic_autodiff::init::Initializer;
+use dendritic_autodiff::scheduler::Scheduler;
+use dendritic_autodiff::state::Parameters;
+use dendritic::estimator::{Fit, Predict, Score, r2_score};
+use crate::utils::*;
+use crate::solver::{Solver, solve_linear};
+use crate::summary::Summary;
 
 pub struct Linear {
     pub features: Value<NDArray<f64>>,
     pub outputs: Value<NDArray<f64>>,
     pub weights: Value<NDArray<f64>>, 
     pub bias: Value<NDArray<f64>>,
-    learning_rate: f64,
-    loss_function: fn(
-        y_true: &NDArray<f64>, 
-        y_pred: &NDArray<f64>) -> Result<f64, String>
+    optimizer: Box<dyn Optimizer>,
+    scheduler: Option<Box<dyn Scheduler>>,
+    epochs: usize,
+    solver: Solver,
 }
 
 
 impl Linear {
 
-    /// Create new instance of linear regression
-    pub fn new(
+    /// Create new instance of linear regression trained with `optimizer`, weights start at zero
+    pub fn new<O: Optimizer + 'static>(
         features: &NDArray<f64>, 
         y: &NDArray<f64>, 
-        learning_rate: f64) -> Result<Linear, String> {
+        optimizer: O) -> Result<Linear, String> {
 
-        if learning_rate < 0.0 || learning_rate > 1.0 {
-            return Err("Learning rate must be between 1 and 0".to_string());
-        }
+        Linear::with_initializer(features, y, optimizer, &mut Initializer::zeros())
+    }
+
+    /// Create new instance of linear regression with weights drawn from an initializer,
+    /// bias starts at zero
+    pub fn with_initializer<O: Optimizer + 'static>(
+        features: &NDArray<f64>, 
+        y: &NDArray<f64>, 
+        optimizer: O,
+        initializer: &mut Initializer) -> Result<Linear, String> {
 
-        let weights = NDArray::new(vec![features.shape().dim(1), 1]).unwrap();
+        check_learning_rate("Linear", &optimizer)?;
+        let weights = initializer.sample(vec![features.shape().dim(1), 1])?;
         let bias = NDArray::new(vec![1, 1]).unwrap();
         let inputs = Value::new(features); 
         let outputs = Value::new(y);
@@ -39,8 +54,10 @@ impl Linear {
             outputs: outputs.clone(),
             weights: Value::new(&weights),
             bias: Value::new(&bias),
-            learning_rate: learning_rate,
-            loss_function: mse,
+            optimizer: Box::new(optimizer),
+            scheduler: None,
+            epochs: DEFAULT_EPOCHS,
+            solver: Solver::default()
         })
     }
 
@@ -49,8 +66,8 @@ impl Linear {
 
         self.features = Value::new(&inputs); 
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
+        let mut linear = Add::new(
+            MatMul::new(self.features.clone(), self.weights.clone()),
             self.bias.clone()
         );
 
@@ -59,84 +76,107 @@ impl Linear {
         
     }
 
-    /// Save model parameters for linear regression
-    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+    /// Set optimizer used to update parameters during training
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+
+    /// Set scheduler that updates the optimizer learning rate after every epoch
+    pub fn set_scheduler<S: Scheduler + 'static>(&mut self, scheduler: S) {
+        self.optimizer.set_learning_rate(scheduler.learning_rate());
+        self.scheduler = Some(Box::new(scheduler));
+    }
 
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-        fs::create_dir_all(filepath)?;
 
-        self.weights.val().save(&weights_file).unwrap();
-        self.bias.val().save(&bias_path).unwrap();
+    /// Set number of epochs trained when fitting through the `Fit` trait
+    pub fn set_epochs(&mut self, epochs: usize) {
+        self.epochs = epochs;
+    }
 
+
+    /// Set solver used when fitting through the `Fit` trait
+    pub fn set_solver(&mut self, solver: Solver) {
+        self.solver = solver;
+    }
+
+
+    /// Set weights and bias to the exact solution on the stored features
+    /// with the configured `Cholesky` or `QR` solver
+    pub fn solve(&mut self) -> Result<(), String> {
+        let (weights, bias) = solve_linear(
+            self.solver,
+            &self.features.val(),
+            &self.outputs.val(),
+            0.0
+        )?;
+        self.weights.set_val(&weights);
+        self.bias.set_val(&bias);
         Ok(())
     }
 
+
+    /// Ordinary least squares summary on the stored features at the exact least squares
+    /// solution, with standard errors, t tests, 95% confidence intervals and goodness of fit.
+    /// The model weights are left untouched
+    pub fn summary(&self) -> Result<Summary, String> {
+        let features = self.features.val();
+        let outputs = self.outputs.val();
+        let (weights, bias) = solve_linear(Solver::QR, &features, &outputs, 0.0)?;
+        Summary::ols(&features, &outputs, &weights, bias.values()[0], 0.05)
+    }
+
+    /// Save model parameters for linear regression
+    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+        self.save_state_dict(filepath)
+    }
+
     /// Load model parameters for linear regression
-    pub fn load(
+    pub fn load<O: Optimizer + 'static>(
         filepath: &str, 
         features: &NDArray<f64>, 
         y: &NDArray<f64>, 
-        learning_rate: f64) -> std::io::Result<Linear> {
-
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-
-        let inputs = Value::new(features); 
-        let outputs = Value::new(y);
-        let load_weights = NDArray::load(&weights_file).unwrap();
-        let load_bias = NDArray::load(&bias_path).unwrap();
+        optimizer: O) -> std::io::Result<Linear> {
 
-        Ok(Linear {
-            features: inputs.clone(),
-            outputs: outputs.clone(),
-            weights: Value::new(&load_weights),
-            bias: Value::new(&load_bias),
-            learning_rate: learning_rate,
-            loss_function: mse,
-        })
+        let mut model = match Linear::new(features, y, optimizer) {
+            Ok(model) => model,
+            Err(err) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err));
+            }
+        };
 
+        model.load_state_dict_from(filepath)?;
+        Ok(model)
     }
 
     /// Train model parameters for linear regression
     pub fn train(&mut self, epochs: usize, log_output: bool) {
         
         /* create node graph */     
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = MSE::new(
+            Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            ),
+            self.outputs.clone()
         );
 
         for epoch in 0..epochs {
 
-            linear.forward();
-
-            let y_pred = linear.value();
-            let loss = (self.loss_function)(&self.outputs.val(), &y_pred);
-            let error = y_pred.subtract(self.outputs.val()).unwrap();
-
-            linear.backward(error);
-
-            /* update weights */
-            let learning_rate_factor = self.learning_rate/y_pred.size() 
-                as f64;
-
-            let w_grad = self.features
-                .grad()
-                .scalar_mult(learning_rate_factor)
-                .unwrap();
+            graph.forward();
+            detect_forward_anomaly(&graph);
+            let loss = graph.loss();
 
-            let dw = self.weights.val().subtract(w_grad).unwrap();
-            self.weights.set_val(&dw); 
+            graph.zero_grad();
+            graph.backprop();
+            detect_backward_anomaly(&graph);
 
-            /* update biases */
-            let b_collapse = self.bias
-                .grad()
-                .sum_axis(1)
-                .unwrap();
+            /* update weights and biases */
+            self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
 
-            let db = b_collapse.scalar_mult(learning_rate_factor).unwrap();
-            self.bias.set_val(&db);
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
 
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
@@ -156,9 +196,12 @@ impl Linear {
         let x_train = x_train_binding.batch(batch_size).unwrap();
         let y_train = y_train_binding.batch(batch_size).unwrap();
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = MSE::new(
+            Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            ),
+            self.outputs.clone()
         );
 
         for epoch in 0..epochs {
@@ -169,38 +212,24 @@ impl Linear {
                 self.features.set_val(&batch);
                 self.outputs.set_val(&y_train[batch_index]);
 
-                linear.forward();
-
-                let y_pred = linear.value();
-                loss = (self.loss_function)(&self.outputs.val(), &y_pred).unwrap();
-                let error = y_pred.subtract(self.outputs.val()).unwrap();
-
-                linear.backward(error);
-
-                /* update weights */
-                let learning_rate_factor = self.learning_rate/y_pred.size() 
-                    as f64;
+                graph.forward();
+                detect_forward_anomaly(&graph);
+                loss = graph.loss();
 
-                let w_grad = self.features
-                    .grad()
-                    .scalar_mult(learning_rate_factor)
-                    .unwrap();
+                graph.zero_grad();
+                graph.backprop();
+                detect_backward_anomaly(&graph);
 
-                let dw = self.weights.val().subtract(w_grad).unwrap();
-                self.weights.set_val(&dw); 
-
-                /* update biases */
-                let b_collapse = self.bias
-                    .grad()
-                    .sum_axis(1)
-                    .unwrap();
-
-                let db = b_collapse.scalar_mult(learning_rate_factor).unwrap();
-                self.bias.set_val(&db);
+                /* update weights and biases */
+                self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
 
                 batch_index += 1; 
             }
 
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
+
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
             }
@@ -213,4 +242,57 @@ impl Linear {
 
 
 
+}
+
+
+impl Parameters for Linear {
+
+    /// Weights and bias of linear regression
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        vec![
+            ("weights".to_string(), self.weights.clone()),
+            ("bias".to_string(), self.bias.clone())
+        ]
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for Linear {
+
+    /// Fit linear regression on new data with the configured solver, gradient
+    /// descent trains for the configured number of epochs from the current weights
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_fit("Linear", x, y, &self.weights)?;
+        self.features = Value::new(x);
+        self.outputs = Value::new(y);
+        match self.solver {
+            Solver::GradientDescent => self.train(self.epochs, false),
+            _ => self.solve()?
+        }
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for Linear {
+    type Output = NDArray<f64>;
+
+    /// Predict outcomes for linear regression without replacing the training features
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        linear_output("Linear", x, &self.weights, &self.bias)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for Linear {
+
+    /// R2 score of linear regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
 }
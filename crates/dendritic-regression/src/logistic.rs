@@ -1,38 +1,114 @@
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
-use dendritic_metrics::loss::*;
-use dendritic_metrics::utils::*; 
+use dendritic_metrics::activations::sigmoid_vec;
 use dendritic_autodiff::node::{Node, Value};
 use dendritic_autodiff::ops::*; 
-use std::fs;
+use dendritic_autodiff::loss::{Loss, BinaryCrossEntropy, CategoricalCrossEntropy};
+use dendritic_autodiff::activations::{Sigmoid, Softmax, softmax_rows};
+use dendritic_autodiff::anomaly::{detect_forward_anomaly, detect_backward_anomaly};
+use dendritic_autodiff::optim::Optimizer;
+use dendritic_autodiff::init::Initializer;
+use dendritic_autodiff::scheduler::Scheduler;
+use dendritic_autodiff::state::Parameters;
+use dendritic::estimator::{Fit, Predict, PredictProba, Score, accuracy_score};
+use crate::utils::*;
+use crate::solver::{Minimized, newton, lbfgs};
+use crate::summary::Summary;
+use crate::glm::GLM;
+
+
+/// Method used to fit logistic regression weights. `SGD` trains with the optimizer
+/// for the configured epochs, `Newton` and `LBFGS` run until the gradient norm
+/// of the mean cross entropy reaches the tolerance.
+/// `Logistic` minimizes the objective of `GLM::logistic` and its `Newton` is the GLM IRLS,
+/// `MultiClassLogistic` always uses the softmax so its closed form Hessian holds
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LogisticSolver {
+    #[default]
+    SGD,
+    Newton,
+    LBFGS
+}
+
+
+/// Mean categorical cross entropy with its gradient over the row major weights
+/// followed by the bias of every class, and the predicted class probabilities
+fn categorical_objective(
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    params: &[f64]) -> (f64, Vec<f64>, Vec<f64>) {
+
+    let cols = x.shape().dim(1);
+    let rows = x.shape().dim(0);
+    let classes = y.shape().dim(1);
+    let bias = &params[cols * classes..];
+
+    let mut scores = vec![0.0; rows * classes];
+    for (idx, row) in x.values().chunks(cols).enumerate() {
+        for class in 0..classes {
+            scores[idx * classes + class] = bias[class]
+                + (0..cols).map(|col| row[col] * params[col * classes + class]).sum::<f64>();
+        }
+    }
+    let probs = softmax_rows(&NDArray::array(vec![rows, classes], scores).unwrap()).values().clone();
+
+    let mut loss = 0.0;
+    let mut grad = vec![0.0; (cols + 1) * classes];
+    for (idx, row) in x.values().chunks(cols).enumerate() {
+        for class in 0..classes {
+            let p = probs[idx * classes + class];
+            let target = y.values()[idx * classes + class];
+            loss -= target * p.max(1e-15).ln();
+            for col in 0..cols {
+                grad[col * classes + class] += row[col] * (p - target) / rows as f64;
+            }
+            grad[cols * classes + class] += (p - target) / rows as f64;
+        }
+    }
+
+    (loss / rows as f64, grad, probs)
+}
 
 pub struct Logistic {
     pub features: Value<NDArray<f64>>,
     pub outputs: Value<NDArray<f64>>,
     pub weights: Value<NDArray<f64>>, 
     pub bias: Value<NDArray<f64>>,
-    learning_rate: f64,
-    activation_function: fn(values: NDArray<f64>) -> NDArray<f64>,
-    loss_function: fn(
-        y_true: &NDArray<f64>, 
-        y_pred: &NDArray<f64>) -> Result<f64, String>
+    optimizer: Box<dyn Optimizer>,
+    scheduler: Option<Box<dyn Scheduler>>,
+    epochs: usize,
+    solver: LogisticSolver,
+    max_iter: usize,
+    tol: f64,
+    n_iter: usize,
+    grad_norm: f64,
 }
 
 
 impl Logistic {
 
-    /// Create new instance of logistic regression
-    pub fn new(
+    /// Create new instance of logistic regression with a sigmoid output trained with `optimizer`,
+    /// weights start at zero
+    pub fn new<O: Optimizer + 'static>(
         features: &NDArray<f64>, 
         y: &NDArray<f64>,
-        activation_function: fn(values: NDArray<f64>) -> NDArray<f64>,
-        learning_rate: f64) -> Result<Logistic, String> {
+        optimizer: O) -> Result<Logistic, String> {
 
-        if learning_rate < 0.0 || learning_rate > 1.0 {
-            return Err("Learning rate must be between 1 and 0".to_string());
-        }
-    
-        let weights = NDArray::new(vec![features.shape().dim(1), 1]).unwrap();
+        Logistic::with_initializer(
+            features, y, optimizer, &mut Initializer::zeros()
+        )
+    }
+
+    /// Create new instance of logistic regression with weights drawn from an initializer,
+    /// bias starts at zero
+    pub fn with_initializer<O: Optimizer + 'static>(
+        features: &NDArray<f64>, 
+        y: &NDArray<f64>,
+        optimizer: O,
+        initializer: &mut Initializer) -> Result<Logistic, String> {
+
+        check_learning_rate("Logistic", &optimizer)?;
+        let weights = initializer.sample(vec![features.shape().dim(1), 1])?;
         let bias = NDArray::new(vec![1, 1]).unwrap();
         let inputs = Value::new(features); 
         let outputs = Value::new(y);
@@ -42,9 +118,14 @@ impl Logistic {
             outputs: outputs,
             weights: Value::new(&weights),
             bias: Value::new(&bias),
-            learning_rate: learning_rate,
-            activation_function: activation_function, 
-            loss_function: binary_cross_entropy // default loss function
+            optimizer: Box::new(optimizer),
+            scheduler: None,
+            epochs: DEFAULT_EPOCHS,
+            solver: LogisticSolver::default(),
+            max_iter: 100,
+            tol: 1e-6,
+            n_iter: 0,
+            grad_norm: f64::NAN
         })
     }
 
@@ -53,56 +134,144 @@ impl Logistic {
 
         self.features = Value::new(&inputs); 
 
-        let mut linear = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
+        let mut linear = Add::new(
+            MatMul::new(self.features.clone(), self.weights.clone()),
             self.bias.clone()
         );
 
         linear.forward();
-        (self.activation_function)(linear.value())
+        sigmoid_vec(linear.value())
     }
 
 
-    /// Save model parameters for logistic regression
-    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+    /// Set optimizer used to update parameters during training
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+
+    /// Set scheduler that updates the optimizer learning rate after every epoch
+    pub fn set_scheduler<S: Scheduler + 'static>(&mut self, scheduler: S) {
+        self.optimizer.set_learning_rate(scheduler.learning_rate());
+        self.scheduler = Some(Box::new(scheduler));
+    }
+
+
+    /// Set number of epochs trained when fitting through the `Fit` trait
+    pub fn set_epochs(&mut self, epochs: usize) {
+        self.epochs = epochs;
+    }
+
+
+    /// Set solver used when fitting through the `Fit` trait
+    pub fn set_solver(&mut self, solver: LogisticSolver) {
+        self.solver = solver;
+    }
+
+
+    /// Set maximum number of `Newton` or `LBFGS` iterations
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+
+    /// Set gradient norm at which `Newton` and `LBFGS` stop
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+
+    /// Iterations or epochs run by the last fit
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+
+
+    /// Gradient norm of the mean cross entropy after the last fit
+    pub fn grad_norm(&self) -> f64 {
+        self.grad_norm
+    }
+
+
+    /// Fit weights and bias on the stored features with the `Newton` or `LBFGS` solver,
+    /// starting from the current parameters
+    pub fn solve(&mut self) -> Result<(), String> {
+
+        let x = self.features.val();
+        let y = self.outputs.val();
+        let mut glm = GLM::logistic()?;
+        glm.set_max_iter(self.max_iter);
+
+        let mut init = self.weights.val().values().clone();
+        init.push(self.bias.val().values()[0]);
+
+        let result = match self.solver {
+            LogisticSolver::Newton => glm.irls(&x, y.values(), init, 0.0, self.tol)?,
+            LogisticSolver::LBFGS => lbfgs(
+                |params: &[f64]| glm.objective_gradient(&x, y.values(), params),
+                init, 10, self.max_iter, self.tol
+            ),
+            LogisticSolver::SGD => {
+                return Err("Logistic: SGD has no solve, use train or sgd".to_string());
+            }
+        };
+
+        self.set_minimized(result)
+    }
 
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-        fs::create_dir_all(filepath)?;
 
-        self.weights.val().save(&weights_file).unwrap();
-        self.bias.val().save(&bias_path).unwrap();
+    /// Inference summary on the stored features at the maximum likelihood, with standard
+    /// errors from the Fisher information, z tests and 95% confidence intervals.
+    /// IRLS of `GLM::logistic` is run to convergence from the current weights,
+    /// which are left untouched
+    pub fn summary(&self) -> Result<Summary, String> {
+        let features = self.features.val();
+        let outputs = self.outputs.val();
+        check_fit("Logistic", &features, &outputs, &self.weights)?;
 
+        let mut init = self.weights.val().values().clone();
+        init.push(self.bias.val().values()[0]);
+        let params = GLM::logistic()?.irls(&features, outputs.values(), init, f64::EPSILON, 0.0)?.params;
+
+        let cols = params.len() - 1;
+        let weights = NDArray::array(vec![cols, 1], params[..cols].to_vec())?;
+        Summary::logit(&features, &outputs, &weights, params[cols], 0.05)
+    }
+
+
+    /// Store parameters and convergence details from a minimizer
+    fn set_minimized(&mut self, result: Minimized) -> Result<(), String> {
+        let cols = result.params.len() - 1;
+        self.weights.set_val(&NDArray::array(vec![cols, 1], result.params[..cols].to_vec())?);
+        self.bias.set_val(&NDArray::array(vec![1, 1], vec![result.params[cols]])?);
+        self.n_iter = result.n_iter;
+        self.grad_norm = result.grad_norm;
         Ok(())
     }
 
 
+    /// Save model parameters for logistic regression
+    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+        self.save_state_dict(filepath)
+    }
+
+
     /// Load model parameters for logistic regression
-    pub fn load(
+    pub fn load<O: Optimizer + 'static>(
         filepath: &str, 
         features: &NDArray<f64>, 
         y: &NDArray<f64>,
-        activation_function: fn(values: NDArray<f64>) -> NDArray<f64>,
-        learning_rate: f64) -> std::io::Result<Logistic> {
-
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-
-        let inputs = Value::new(features); 
-        let outputs = Value::new(y);
-        let load_weights = NDArray::load(&weights_file).unwrap();
-        let load_bias = NDArray::load(&bias_path).unwrap();
+        optimizer: O) -> std::io::Result<Logistic> {
 
-        Ok(Logistic {
-            features: inputs,
-            outputs: outputs,
-            weights: Value::new(&load_weights),
-            bias: Value::new(&load_bias),
-            learning_rate: learning_rate,
-            activation_function: activation_function,
-            loss_function: mse,
-        })
+        let mut model = match Logistic::new(features, y, optimizer) {
+            Ok(model) => model,
+            Err(err) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err));
+            }
+        };
 
+        model.load_state_dict_from(filepath)?;
+        Ok(model)
     }
 
 
@@ -110,40 +279,31 @@ impl Logistic {
     pub fn train(&mut self, epochs: usize, log_output: bool) {
 
         /* create node graph */     
-        let mut logistic = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = BinaryCrossEntropy::new(
+            Sigmoid::new(Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            )),
+            self.outputs.clone()
         );
 
 
         for epoch in 0..epochs {
 
-            logistic.forward();
-
-            let y_pred = (self.activation_function)(logistic.value());
-            let loss = (self.loss_function)(&y_pred, &self.outputs.val()).unwrap();
-            let error = y_pred.subtract(self.outputs.val()).unwrap();
-            logistic.backward(error);
+            graph.forward();
+            detect_forward_anomaly(&graph);
+            let loss = graph.loss();
 
-            /* update weights */
-            let learning_rate_factor = (1.0/y_pred.size() as f64) * self.learning_rate;
+            graph.zero_grad();
+            graph.backprop();
+            detect_backward_anomaly(&graph);
 
-            let w_grad = self.features
-                .grad()
-                .scalar_mult(learning_rate_factor)
-                .unwrap();
+            /* update weights and biases */
+            self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
 
-            let dw = self.weights.val().subtract(w_grad).unwrap();
-            self.weights.set_val(&dw); 
-
-            /* update biases */
-            let b_collapse = self.bias
-                .grad()
-                .sum_axis(1)
-                .unwrap();
-
-            let db = b_collapse.scalar_mult(learning_rate_factor).unwrap();
-            self.bias.set_val(&db);
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
 
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
@@ -163,9 +323,12 @@ impl Logistic {
         let x_train = x_train_binding.batch(batch_size).unwrap();
         let y_train = y_train_binding.batch(batch_size).unwrap();
 
-        let mut logistic = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = BinaryCrossEntropy::new(
+            Sigmoid::new(Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            )),
+            self.outputs.clone()
         ); 
 
         for epoch in 0..epochs {
@@ -176,35 +339,23 @@ impl Logistic {
                 self.features.set_val(&batch);
                 self.outputs.set_val(&y_train[batch_index]);
 
-                logistic.forward();
-
-                let y_pred = (self.activation_function)(logistic.value());
-                loss = (self.loss_function)(&y_pred, &self.outputs.val()).unwrap();
-                let error = y_pred.subtract(self.outputs.val()).unwrap();
-
-                logistic.backward(error);
+                graph.forward();
+                detect_forward_anomaly(&graph);
+                loss = graph.loss();
 
-                /* update weights */
-                let learning_rate_factor = self.learning_rate/y_pred.size() as f64;
+                graph.zero_grad();
+                graph.backprop();
+                detect_backward_anomaly(&graph);
 
-                let w_grad = self.features
-                    .grad()
-                    .scalar_mult(learning_rate_factor)
-                    .unwrap();
-
-                let dw = self.weights.val().subtract(w_grad).unwrap();
-                self.weights.set_val(&dw); 
-
-                let b_collapse = self.bias
-                    .grad()
-                    .sum_axis(1)
-                    .unwrap();
-
-                let db = b_collapse.scalar_mult(learning_rate_factor).unwrap();
-                self.bias.set_val(&db); 
+                /* update weights and biases */
+                self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
                 batch_index += 1; 
             }
 
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
+
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
             }
@@ -221,27 +372,40 @@ pub struct MultiClassLogistic {
     pub outputs: Value<NDArray<f64>>,
     pub weights: Value<NDArray<f64>>, 
     pub bias: Value<NDArray<f64>>,
-    learning_rate: f64,
-    activation_function: fn(values: NDArray<f64>) -> NDArray<f64>,
-    loss_function: fn(
-        y_true: &NDArray<f64>, 
-        y_pred: &NDArray<f64>) -> Result<f64, String>
+    optimizer: Box<dyn Optimizer>,
+    scheduler: Option<Box<dyn Scheduler>>,
+    epochs: usize,
+    solver: LogisticSolver,
+    max_iter: usize,
+    tol: f64,
+    n_iter: usize,
+    grad_norm: f64,
 }
 
 
 impl MultiClassLogistic {
 
-    /// Create instance of multi class logistic regression
-    pub fn new(
+    /// Create instance of multi class logistic regression with a softmax output,
+    /// weights start at zero
+    pub fn new<O: Optimizer + 'static>(
         features: &NDArray<f64>, 
         y: &NDArray<f64>,
-        activation_function: fn(values: NDArray<f64>) -> NDArray<f64>,
-        learning_rate: f64) -> Result<MultiClassLogistic, String> {
+        optimizer: O) -> Result<MultiClassLogistic, String> {
 
-        if learning_rate < 0.0 || learning_rate > 1.0 {
-            return Err("Learning rate must be between 1 and 0".to_string());
-        }
+        MultiClassLogistic::with_initializer(
+            features, y, optimizer, &mut Initializer::zeros()
+        )
+    }
 
+    /// Create instance of multi class logistic regression with weights drawn from an initializer,
+    /// the bias of every class starts at zero
+    pub fn with_initializer<O: Optimizer + 'static>(
+        features: &NDArray<f64>, 
+        y: &NDArray<f64>,
+        optimizer: O,
+        initializer: &mut Initializer) -> Result<MultiClassLogistic, String> {
+
+        check_learning_rate("MultiClassLogistic", &optimizer)?;
         if y.shape().dim(1) <= 1 {
             return Err("Outputs must be one hot encoded".to_string());
         }
@@ -252,8 +416,8 @@ impl MultiClassLogistic {
             y.shape().dim(1)
         ];
 
-        let weights = NDArray::new(weights_shape).unwrap();
-        let bias = NDArray::new(vec![1, 1]).unwrap();
+        let weights = initializer.sample(weights_shape)?;
+        let bias = NDArray::new(vec![1, y.shape().dim(1)]).unwrap();
         let inputs = Value::new(features); 
         let outputs = Value::new(y);
 
@@ -262,9 +426,14 @@ impl MultiClassLogistic {
             outputs: outputs,
             weights: Value::new(&weights),
             bias: Value::new(&bias),
-            learning_rate: learning_rate,
-            activation_function: activation_function, 
-            loss_function: categorical_cross_entropy
+            optimizer: Box::new(optimizer),
+            scheduler: None,
+            epochs: DEFAULT_EPOCHS,
+            solver: LogisticSolver::default(),
+            max_iter: 100,
+            tol: 1e-6,
+            n_iter: 0,
+            grad_norm: f64::NAN
         })
     }
 
@@ -274,62 +443,145 @@ impl MultiClassLogistic {
 
         self.features = Value::new(&inputs); 
 
-        let mut logistic = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
+        let mut logistic = Add::new(
+            MatMul::new(self.features.clone(), self.weights.clone()),
             self.bias.clone()
         );
 
         logistic.forward();
 
-        let y_pred = apply(
-            logistic.value(), 0, 
-            self.activation_function
-        );
-
+        let y_pred = softmax_rows(&logistic.value());
         y_pred.argmax(0)
     }
 
 
-    /// Save model parameters for multi class logistic regression
-    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+    /// Set optimizer used to update parameters during training
+    pub fn set_optimizer<O: Optimizer + 'static>(&mut self, optimizer: O) {
+        self.optimizer = Box::new(optimizer);
+    }
+
+
+    /// Set scheduler that updates the optimizer learning rate after every epoch
+    pub fn set_scheduler<S: Scheduler + 'static>(&mut self, scheduler: S) {
+        self.optimizer.set_learning_rate(scheduler.learning_rate());
+        self.scheduler = Some(Box::new(scheduler));
+    }
+
+
+    /// Set number of epochs trained when fitting through the `Fit` trait
+    pub fn set_epochs(&mut self, epochs: usize) {
+        self.epochs = epochs;
+    }
+
+
+    /// Set solver used when fitting through the `Fit` trait
+    pub fn set_solver(&mut self, solver: LogisticSolver) {
+        self.solver = solver;
+    }
 
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-        fs::create_dir_all(filepath)?;
 
-        self.weights.val().save(&weights_file).unwrap();
-        self.bias.val().save(&bias_path).unwrap();
+    /// Set maximum number of `Newton` or `LBFGS` iterations
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+
+    /// Set gradient norm at which `Newton` and `LBFGS` stop
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+
+    /// Iterations or epochs run by the last fit
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
 
+
+    /// Gradient norm of the mean cross entropy after the last fit
+    pub fn grad_norm(&self) -> f64 {
+        self.grad_norm
+    }
+
+
+    /// Fit weights and the bias of every class on the stored features with the `Newton`
+    /// or `LBFGS` solver, starting from the current parameters
+    pub fn solve(&mut self) -> Result<(), String> {
+
+        let x = self.features.val();
+        let y = self.outputs.val();
+        let objective = |params: &[f64]| {
+            let (loss, grad, _) = categorical_objective(&x, &y, params);
+            (loss, grad)
+        };
+
+        let mut init = self.weights.val().values().clone();
+        init.extend(self.bias.val().values().iter());
+        let result = match self.solver {
+            LogisticSolver::Newton => newton(objective, |params: &[f64]| {
+                let (_, _, probs) = categorical_objective(&x, &y, params);
+                let cols = x.shape().dim(1);
+                let classes = y.shape().dim(1);
+                let rows = x.shape().dim(0) as f64;
+                let n = (cols + 1) * classes;
+
+                /* d2 loss / dw_ia dw_jc = sum x_i x_j p_a (delta_ac - p_c) / rows, the bias is a feature of ones */
+                let mut hess = vec![0.0; n * n];
+                for (idx, row) in x.values().chunks(cols).enumerate() {
+                    let p = &probs[idx * classes..(idx + 1) * classes];
+                    let sample: Vec<f64> = row.iter().copied().chain([1.0]).collect();
+                    for i in 0..=cols {
+                        for j in 0..=cols {
+                            let xx = sample[i] * sample[j] / rows;
+                            for a in 0..classes {
+                                for c in 0..classes {
+                                    let delta = if a == c { 1.0 } else { 0.0 };
+                                    hess[(i * classes + a) * n + j * classes + c] += xx * p[a] * (delta - p[c]);
+                                }
+                            }
+                        }
+                    }
+                }
+                hess
+            }, init, self.max_iter, self.tol)?,
+            LogisticSolver::LBFGS => lbfgs(objective, init, 10, self.max_iter, self.tol),
+            LogisticSolver::SGD => {
+                return Err("MultiClassLogistic: SGD has no solve, use train or sgd".to_string());
+            }
+        };
+
+        let shape = self.weights.val().shape().values();
+        let size = shape[0] * shape[1];
+        self.weights.set_val(&NDArray::array(shape.clone(), result.params[..size].to_vec())?);
+        self.bias.set_val(&NDArray::array(vec![1, shape[1]], result.params[size..].to_vec())?);
+        self.n_iter = result.n_iter;
+        self.grad_norm = result.grad_norm;
         Ok(())
     }
 
 
+    /// Save model parameters for multi class logistic regression
+    pub fn save(&self, filepath: &str) -> std::io::Result<()> {
+        self.save_state_dict(filepath)
+    }
+
+
     /// Load model parameters for multi class logistic regression
-    pub fn load(
+    pub fn load<O: Optimizer + 'static>(
         filepath: &str, 
         features: NDArray<f64>, 
         y: NDArray<f64>,
-        activation_function: fn(values: NDArray<f64>) -> NDArray<f64>,
-        learning_rate: f64) -> std::io::Result<MultiClassLogistic> {
-
-        let weights_file = format!("{}/weights", filepath);
-        let bias_path = format!("{}/bias", filepath); 
-
-        let inputs = Value::new(&features); 
-        let outputs = Value::new(&y);
-        let load_weights = NDArray::load(&weights_file).unwrap();
-        let load_bias = NDArray::load(&bias_path).unwrap();
-
-        Ok(MultiClassLogistic {
-            features: inputs.clone(),
-            outputs: outputs.clone(),
-            weights: Value::new(&load_weights),
-            bias: Value::new(&load_bias),
-            learning_rate: learning_rate,
-            activation_function: activation_function,
-            loss_function: mse,
-        })
+        optimizer: O) -> std::io::Result<MultiClassLogistic> {
+
+        let mut model = match MultiClassLogistic::new(&features, &y, optimizer) {
+            Ok(model) => model,
+            Err(err) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err));
+            }
+        };
 
+        model.load_state_dict_from(filepath)?;
+        Ok(model)
     }
 
 
@@ -337,44 +589,30 @@ impl MultiClassLogistic {
     pub fn train(&mut self, epochs: usize, log_output: bool) {
 
         /* create node graph */     
-        let mut logistic = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = CategoricalCrossEntropy::new(
+            Softmax::new(Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            )),
+            self.outputs.clone()
         );
 
         for epoch in 0..epochs {
 
-            logistic.forward();
-
-            let y_pred = apply(
-                logistic.value(), 0, 
-                self.activation_function
-            );
-
-            let loss = (self.loss_function)(&y_pred, &self.outputs.val()).unwrap();
-            let error = y_pred.subtract(self.outputs.val()).unwrap();
-            logistic.backward(error);
+            graph.forward();
+            detect_forward_anomaly(&graph);
+            let loss = graph.loss();
 
-            /* update weights */
-            let n = y_pred.shape().dim(0);
-            let learning_rate_factor = (1.0/n as f64) * self.learning_rate;
+            graph.zero_grad();
+            graph.backprop();
+            detect_backward_anomaly(&graph);
 
-            let w_grad = self.features
-                .grad()
-                .scalar_mult(learning_rate_factor)
-                .unwrap();
+            /* update weights and biases */
+            self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
 
-            let dw = self.weights.val().subtract(w_grad).unwrap();
-            self.weights.set_val(&dw); 
-
-            /* update biases */
-            let b_collapse = self.bias
-                .grad()
-                .sum_axis(1)
-                .unwrap();
-
-            let db = b_collapse.scalar_mult(learning_rate_factor).unwrap();
-            self.bias.set_val(&db);
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
 
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
@@ -394,9 +632,12 @@ impl MultiClassLogistic {
         let x_train = x_train_binding.batch(batch_size).unwrap();
         let y_train = y_train_binding.batch(batch_size).unwrap();
 
-        let mut logistic = ScaleAdd::new(
-            Dot::new(self.features.clone(), self.weights.clone()),
-            self.bias.clone()
+        let mut graph = CategoricalCrossEntropy::new(
+            Softmax::new(Add::new(
+                MatMul::new(self.features.clone(), self.weights.clone()),
+                self.bias.clone()
+            )),
+            self.outputs.clone()
         ); 
 
         for epoch in 0..epochs {
@@ -407,38 +648,23 @@ impl MultiClassLogistic {
                 self.features.set_val(&batch);
                 self.outputs.set_val(&y_train[batch_index]);
 
-                logistic.forward();
-
-                let y_pred = apply(
-                    logistic.value(), 0, 
-                    self.activation_function
-                );
-                loss = (self.loss_function)(&y_pred, &self.outputs.val()).unwrap();
-                let error = y_pred.subtract(self.outputs.val()).unwrap();
-
-                logistic.backward(error);
-
-                /* update weights */
-                let learning_rate_factor = self.learning_rate/y_pred.size() as f64;
-
-                let w_grad = self.features
-                    .grad()
-                    .scalar_mult(learning_rate_factor)
-                    .unwrap();
-
-                let dw = self.weights.val().subtract(w_grad).unwrap();
-                self.weights.set_val(&dw); 
+                graph.forward();
+                detect_forward_anomaly(&graph);
+                loss = graph.loss();
 
-                let b_collapse = self.bias
-                    .grad()
-                    .sum_axis(1)
-                    .unwrap();
+                graph.zero_grad();
+                graph.backprop();
+                detect_backward_anomaly(&graph);
 
-                let db = b_collapse.scalar_mult(learning_rate_factor).unwrap();
-                self.bias.set_val(&db); 
+                /* update weights and biases */
+                self.optimizer.step(&mut [self.weights.clone(), self.bias.clone()]);
                 batch_index += 1; 
             }
 
+            if let Some(scheduler) = self.scheduler.as_mut() {
+                scheduler.step_optimizer(self.optimizer.as_mut(), loss);
+            }
+
             if log_output {
                 println!("Epoch [{:?}/{:?}]: {:?}", epoch, epochs, loss);
             }
@@ -449,3 +675,170 @@ impl MultiClassLogistic {
 
 
 }
+
+
+impl Parameters for Logistic {
+
+    /// Weights and bias of logistic regression
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        vec![
+            ("weights".to_string(), self.weights.clone()),
+            ("bias".to_string(), self.bias.clone())
+        ]
+    }
+
+}
+
+
+impl Parameters for MultiClassLogistic {
+
+    /// Weights and bias of multi class logistic regression
+    fn named_parameters(&self) -> Vec<(String, Value<NDArray<f64>>)> {
+        vec![
+            ("weights".to_string(), self.weights.clone()),
+            ("bias".to_string(), self.bias.clone())
+        ]
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for Logistic {
+
+    /// Fit logistic regression on new data with the configured solver,
+    /// starting from the current weights
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_fit("Logistic", x, y, &self.weights)?;
+        self.features = Value::new(x);
+        self.outputs = Value::new(y);
+        match self.solver {
+            LogisticSolver::SGD => {
+                self.train(self.epochs, false);
+                let mut params = self.weights.val().values().clone();
+                params.push(self.bias.val().values()[0]);
+                let (_, grad) = GLM::logistic()?.objective_gradient(x, y.values(), &params);
+                self.n_iter = self.epochs;
+                self.grad_norm = grad.iter().map(|g| g * g).sum::<f64>().sqrt();
+            },
+            _ => self.solve()?
+        }
+        Ok(())
+    }
+
+}
+
+
+impl PredictProba<NDArray<f64>> for Logistic {
+    type Output = NDArray<f64>;
+
+    /// Probability of class 0 and class 1 for every sample
+    fn predict_proba(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        let linear = linear_output("Logistic", x, &self.weights, &self.bias)?;
+        let positive = sigmoid_vec(linear);
+        let probabilities: Vec<f64> = positive.values().iter()
+            .flat_map(|p| [1.0 - p, *p])
+            .collect();
+        NDArray::array(vec![positive.size(), 2], probabilities)
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for Logistic {
+    type Output = NDArray<f64>;
+
+    /// Predict class 1 for samples with a probability of at least 0.5
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        let linear = linear_output("Logistic", x, &self.weights, &self.bias)?;
+        let positive = sigmoid_vec(linear);
+        let labels: Vec<f64> = positive.values().iter()
+            .map(|p| if *p >= 0.5 { 1.0 } else { 0.0 })
+            .collect();
+        NDArray::array(vec![labels.len(), 1], labels)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for Logistic {
+
+    /// Accuracy of logistic regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(y.values(), y_pred.values())
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for MultiClassLogistic {
+
+    /// Fit multi class logistic regression on new data with the configured solver,
+    /// targets are either one hot encoded or a column of class indices
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        check_fit("MultiClassLogistic", x, y, &self.weights)?;
+
+        let classes = self.weights.val().shape().dim(1);
+        let y = match y.shape().dim(1) {
+            1 => one_hot("MultiClassLogistic", y, classes)?,
+            cols if cols == classes => y.clone(),
+            cols => {
+                let msg = format!(
+                    "MultiClassLogistic: Expected {} target columns, got {}",
+                    classes, cols
+                );
+                return Err(msg);
+            }
+        };
+
+        self.features = Value::new(x);
+        self.outputs = Value::new(&y);
+        match self.solver {
+            LogisticSolver::SGD => {
+                self.train(self.epochs, false);
+                let mut params = self.weights.val().values().clone();
+                params.extend(self.bias.val().values().iter());
+                let (_, grad, _) = categorical_objective(x, &y, &params);
+                self.n_iter = self.epochs;
+                self.grad_norm = grad.iter().map(|g| g * g).sum::<f64>().sqrt();
+            },
+            _ => self.solve()?
+        }
+        Ok(())
+    }
+
+}
+
+
+impl PredictProba<NDArray<f64>> for MultiClassLogistic {
+    type Output = NDArray<f64>;
+
+    /// Softmax output with one column per class
+    fn predict_proba(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        let linear = linear_output("MultiClassLogistic", x, &self.weights, &self.bias)?;
+        Ok(softmax_rows(&linear))
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for MultiClassLogistic {
+    type Output = NDArray<f64>;
+
+    /// Predict class index with the highest probability
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        Ok(self.predict_proba(x)?.argmax(0))
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for MultiClassLogistic {
+
+    /// Accuracy of predictions against one hot encoded or class index targets
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(class_labels(y).values(), y_pred.values())
+    }
+
+}
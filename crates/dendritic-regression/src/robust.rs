@@ -0,0 +1,482 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand::seq::index::sample;
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_autodiff::node::Value;
+use dendritic::estimator::{Fit, Predict, Score, r2_score};
+use crate::utils::*;
+use crate::solver::weighted_ridge;
+
+
+/// Mean pinball loss of predictions for a quantile between 0 and 1
+pub fn pinball_loss(y_true: &[f64], y_pred: &[f64], quantile: f64) -> f64 {
+    let total: f64 = y_true.iter().zip(y_pred.iter()).map(|(y, pred)| {
+        let residual = y - pred;
+        if residual >= 0.0 { quantile * residual } else { (quantile - 1.0) * residual }
+    }).sum();
+    total / y_true.len() as f64
+}
+
+
+/// Median of values, zero when empty
+fn median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mid = sorted.len() / 2;
+    match sorted.len() % 2 {
+        0 => (sorted[mid - 1] + sorted[mid]) / 2.0,
+        _ => sorted[mid]
+    }
+}
+
+
+/// Scale of normal residuals from their median absolute value, kept above zero
+fn mad_scale(abs_residuals: &[f64]) -> f64 {
+    (median(abs_residuals) / 0.6745).max(1e-10)
+}
+
+
+/// Residuals `y - x * w - b` for row major features
+fn residuals(x: &[f64], cols: usize, y: &[f64], weights: &[f64], bias: f64) -> Vec<f64> {
+    x.chunks(cols)
+        .zip(y.iter())
+        .map(|(row, y)| y - row.iter().zip(weights.iter()).map(|(x, w)| x * w).sum::<f64>() - bias)
+        .collect()
+}
+
+
+/// Iteratively reweighted least squares, `sample_weights` maps residuals to the weights
+/// of the next weighted ridge solve. Stops once no parameter moves more than `tol`
+fn irls<F>(
+    x: &NDArray<f64>,
+    y: &[f64],
+    penalty: f64,
+    max_iter: usize,
+    tol: f64,
+    mut sample_weights: F) -> Result<(Vec<f64>, f64, usize), String>
+where
+    F: FnMut(&[f64]) -> Vec<f64>,
+{
+    let cols = x.shape().dim(1);
+    let ones = vec![1.0; y.len()];
+    let (mut weights, mut bias) = weighted_ridge(x.values(), cols, y, &ones, penalty)?;
+
+    let mut n_iter = 0;
+    while n_iter < max_iter {
+
+        n_iter += 1;
+        let residual = residuals(x.values(), cols, y, &weights, bias);
+        let (new_weights, new_bias) = weighted_ridge(x.values(), cols, y, &sample_weights(&residual), penalty)?;
+
+        let change = new_weights.iter()
+            .zip(weights.iter())
+            .map(|(a, b)| (a - b).abs())
+            .fold((new_bias - bias).abs(), f64::max);
+
+        weights = new_weights;
+        bias = new_bias;
+        if change < tol {
+            break;
+        }
+    }
+
+    Ok((weights, bias, n_iter))
+}
+
+
+/// Linear regression with the Huber loss, squared for residuals within `epsilon`
+/// scales and linear beyond, so `epsilon` does not depend on the units of the target.
+/// The scale is not optimized jointly with the coefficients as in the concomitant
+/// Huber objective, it is re-estimated from the median absolute residual before every
+/// reweighting. This approximates the joint fit and matches it when the scaled median
+/// absolute deviation is the scale of the inliers.
+/// `scale` and `outliers` are computed from the residuals of the final coefficients
+pub struct HuberRegressor {
+    pub weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>,
+    pub scale: f64,
+    pub outliers: Vec<bool>,
+    epsilon: f64,
+    alpha: f64,
+    max_iter: usize,
+    tol: f64
+}
+
+
+impl HuberRegressor {
+
+    /// Create new unfitted huber regression, `epsilon` must be at least 1
+    pub fn new(epsilon: f64) -> Result<HuberRegressor, String> {
+
+        if epsilon < 1.0 {
+            return Err("HuberRegressor: Epsilon must be at least 1".to_string());
+        }
+
+        Ok(Self {
+            weights: Value::new(&NDArray::new(vec![0, 1]).unwrap()),
+            bias: Value::new(&NDArray::new(vec![1, 1]).unwrap()),
+            scale: 1.0,
+            outliers: Vec::new(),
+            epsilon,
+            alpha: 1e-4,
+            max_iter: 100,
+            tol: 1e-8
+        })
+    }
+
+    /// Set strength of the L2 penalty on the weights
+    pub fn set_alpha(&mut self, alpha: f64) -> Result<(), String> {
+        if alpha < 0.0 {
+            return Err("HuberRegressor: Alpha must be positive".to_string());
+        }
+        self.alpha = alpha;
+        Ok(())
+    }
+
+    /// Set maximum number of reweighting iterations
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+    /// Set tolerance on the largest parameter change between iterations
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for HuberRegressor {
+
+    /// Fit weights, bias and scale, marking samples beyond `epsilon` scales as outliers
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+
+        check_data("HuberRegressor", x, y)?;
+        let epsilon = self.epsilon;
+
+        let (weights, bias, _) = irls(x, y.values(), self.alpha, self.max_iter, self.tol, |residual| {
+            let abs: Vec<f64> = residual.iter().map(|r| r.abs()).collect();
+            let scale = mad_scale(&abs);
+            abs.iter().map(|r| if r / scale <= epsilon { 1.0 } else { epsilon * scale / r }).collect()
+        })?;
+
+        let cols = x.shape().dim(1);
+        let abs: Vec<f64> = residuals(x.values(), cols, y.values(), &weights, bias)
+            .iter()
+            .map(|r| r.abs())
+            .collect();
+        let scale = mad_scale(&abs);
+        self.outliers = abs.iter().map(|r| *r > epsilon * scale).collect();
+        self.scale = scale;
+        self.weights = Value::new(&NDArray::array(vec![cols, 1], weights)?);
+        self.bias = Value::new(&NDArray::array(vec![1, 1], vec![bias])?);
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for HuberRegressor {
+    type Output = NDArray<f64>;
+
+    /// Predict outcomes for huber regression
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        linear_output("HuberRegressor", x, &self.weights, &self.bias)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for HuberRegressor {
+
+    /// R2 score of huber regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
+
+
+/// Linear regression of a conditional quantile minimizing the pinball loss,
+/// fit by iteratively reweighted least squares
+pub struct QuantileRegressor {
+    pub weights: Value<NDArray<f64>>,
+    pub bias: Value<NDArray<f64>>,
+    quantile: f64,
+    alpha: f64,
+    max_iter: usize,
+    tol: f64
+}
+
+
+impl QuantileRegressor {
+
+    /// Create new unfitted quantile regression, `0.5` fits the median
+    pub fn new(quantile: f64) -> Result<QuantileRegressor, String> {
+
+        if quantile <= 0.0 || quantile >= 1.0 {
+            return Err("QuantileRegressor: Quantile must be between 0 and 1".to_string());
+        }
+
+        Ok(Self {
+            weights: Value::new(&NDArray::new(vec![0, 1]).unwrap()),
+            bias: Value::new(&NDArray::new(vec![1, 1]).unwrap()),
+            quantile,
+            alpha: 0.0,
+            max_iter: 1000,
+            tol: 1e-8
+        })
+    }
+
+    /// Quantile fit by the model
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// Set strength of the L2 penalty on the weights
+    pub fn set_alpha(&mut self, alpha: f64) -> Result<(), String> {
+        if alpha < 0.0 {
+            return Err("QuantileRegressor: Alpha must be positive".to_string());
+        }
+        self.alpha = alpha;
+        Ok(())
+    }
+
+    /// Set maximum number of reweighting iterations
+    pub fn set_max_iter(&mut self, max_iter: usize) {
+        self.max_iter = max_iter;
+    }
+
+    /// Set tolerance on the largest parameter change between iterations
+    pub fn set_tol(&mut self, tol: f64) {
+        self.tol = tol;
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for QuantileRegressor {
+
+    /// Fit weights and bias minimizing the pinball loss
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+
+        check_data("QuantileRegressor", x, y)?;
+        let quantile = self.quantile;
+
+        /* pinball loss as a weighted absolute residual, floored to keep weights finite */
+        let (weights, bias, _) = irls(x, y.values(), self.alpha, self.max_iter, self.tol, |residual| {
+            residual.iter().map(|r| {
+                let side = if *r >= 0.0 { quantile } else { 1.0 - quantile };
+                side / r.abs().max(1e-8)
+            }).collect()
+        })?;
+
+        let cols = x.shape().dim(1);
+        self.weights = Value::new(&NDArray::array(vec![cols, 1], weights)?);
+        self.bias = Value::new(&NDArray::array(vec![1, 1], vec![bias])?);
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for QuantileRegressor {
+    type Output = NDArray<f64>;
+
+    /// Predict the conditional quantile
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        linear_output("QuantileRegressor", x, &self.weights, &self.bias)
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for QuantileRegressor {
+
+    /// R2 score of quantile regression predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
+
+
+/// Random sample consensus around any regression model. Fits the model on random
+/// subsets and keeps the one agreeing with the most samples, then refits on those inliers
+pub struct RANSAC<M> {
+    pub estimator: M,
+    pub inlier_mask: Vec<bool>,
+    min_samples: usize,
+    residual_threshold: Option<f64>,
+    max_trials: usize,
+    seed: u64
+}
+
+
+impl<M> RANSAC<M>
+where
+    M: Fit<NDArray<f64>, NDArray<f64>> + Predict<NDArray<f64>, Output = NDArray<f64>>,
+{
+
+    /// Create new instance of RANSAC fitting `estimator` on subsets of `min_samples` rows
+    pub fn new(estimator: M, min_samples: usize) -> Result<RANSAC<M>, String> {
+
+        if min_samples == 0 {
+            return Err("RANSAC: Minimum samples must be positive".to_string());
+        }
+
+        Ok(Self {
+            estimator,
+            inlier_mask: Vec::new(),
+            min_samples,
+            residual_threshold: None,
+            max_trials: 100,
+            seed: 0
+        })
+    }
+
+    /// Set absolute residual below which a sample is an inlier, defaults to the
+    /// median absolute deviation of the targets
+    pub fn set_residual_threshold(&mut self, threshold: f64) -> Result<(), String> {
+        if threshold < 0.0 {
+            return Err("RANSAC: Residual threshold must be positive".to_string());
+        }
+        self.residual_threshold = Some(threshold);
+        Ok(())
+    }
+
+    /// Set number of random subsets tried
+    pub fn set_max_trials(&mut self, max_trials: usize) {
+        self.max_trials = max_trials;
+    }
+
+    /// Set seed of the subset sampling
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Number of inliers found by the last fit
+    pub fn n_inliers(&self) -> usize {
+        self.inlier_mask.iter().filter(|inlier| **inlier).count()
+    }
+
+    /// Inlier mask and sum of absolute inlier residuals of the current estimator
+    fn consensus(
+        &self,
+        x: &NDArray<f64>,
+        y: &NDArray<f64>,
+        threshold: f64) -> Result<(Vec<bool>, f64), String> {
+
+        let y_pred = self.estimator.predict(x)?;
+        let residual: Vec<f64> = y.values().iter()
+            .zip(y_pred.values().iter())
+            .map(|(y, pred)| (y - pred).abs())
+            .collect();
+
+        let mask: Vec<bool> = residual.iter().map(|r| *r <= threshold).collect();
+        let error = residual.iter().zip(mask.iter()).filter(|(_, inlier)| **inlier).map(|(r, _)| r).sum();
+        Ok((mask, error))
+    }
+
+}
+
+
+impl<M> Fit<NDArray<f64>, NDArray<f64>> for RANSAC<M>
+where
+    M: Fit<NDArray<f64>, NDArray<f64>> + Predict<NDArray<f64>, Output = NDArray<f64>>,
+{
+
+    /// Pick the subset fit with the most inliers, ties broken by lower inlier residual,
+    /// and refit the estimator on its inliers
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+
+        check_data("RANSAC", x, y)?;
+        let rows = x.shape().dim(0);
+        if self.min_samples > rows {
+            let msg = format!("RANSAC: Minimum samples {} exceeds {} rows", self.min_samples, rows);
+            return Err(msg);
+        }
+
+        let threshold = match self.residual_threshold {
+            Some(threshold) => threshold,
+            None => {
+                let center = median(y.values());
+                let deviation: Vec<f64> = y.values().iter().map(|y| (y - center).abs()).collect();
+                median(&deviation)
+            }
+        };
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut best: Option<(usize, f64, Vec<bool>)> = None;
+        for _ in 0..self.max_trials {
+
+            let subset = sample(&mut rng, rows, self.min_samples).into_vec();
+            let x_subset = x.axis_indices(0, subset.clone())?;
+            let y_subset = y.axis_indices(0, subset)?;
+
+            /* degenerate subsets that can not be fit are skipped */
+            if self.estimator.fit(&x_subset, &y_subset).is_err() {
+                continue;
+            }
+
+            let (mask, error) = self.consensus(x, y, threshold)?;
+            let count = mask.iter().filter(|inlier| **inlier).count();
+            let better = match &best {
+                Some((best_count, best_error, _)) => {
+                    count > *best_count || (count == *best_count && error < *best_error)
+                },
+                None => count > 0
+            };
+
+            if better {
+                best = Some((count, error, mask));
+            }
+        }
+
+        let mask = match best {
+            Some((_, _, mask)) => mask,
+            None => return Err("RANSAC: No valid consensus set found".to_string())
+        };
+
+        let inliers: Vec<usize> = (0..rows).filter(|row| mask[*row]).collect();
+        let x_inliers = x.axis_indices(0, inliers.clone())?;
+        let y_inliers = y.axis_indices(0, inliers)?;
+        self.estimator.fit(&x_inliers, &y_inliers)?;
+        self.inlier_mask = mask;
+        Ok(())
+    }
+
+}
+
+
+impl<M> Predict<NDArray<f64>> for RANSAC<M>
+where
+    M: Predict<NDArray<f64>, Output = NDArray<f64>>,
+{
+    type Output = NDArray<f64>;
+
+    /// Predict with the estimator refit on the inliers
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        self.estimator.predict(x)
+    }
+
+}
+
+
+impl<M> Score<NDArray<f64>, NDArray<f64>> for RANSAC<M>
+where
+    M: Predict<NDArray<f64>, Output = NDArray<f64>>,
+{
+
+    /// R2 score of the estimator refit on the inliers
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = self.estimator.predict(x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
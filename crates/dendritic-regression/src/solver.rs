@@ -0,0 +1,464 @@
+use dendritic_ndarray::ndarray::NDArray;
+use crate::utils::center;
+
+
+/// Method used to fit the weights of linear and ridge regression
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Solver {
+    #[default]
+    GradientDescent,
+    Cholesky,
+    QR
+}
+
+
+/// Cholesky factor `L` of a symmetric positive definite matrix with `a = L * L^T`
+pub fn cholesky(a: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+    let n = a.shape().dim(0);
+    if a.rank() != 2 || a.shape().dim(1) != n {
+        return Err("Cholesky: Matrix must be square".to_string());
+    }
+
+    let vals = a.values();
+    let scale = (0..n).map(|idx| vals[idx * n + idx].abs()).fold(0.0, f64::max);
+    let mut lower = vec![0.0; n * n];
+    for row in 0..n {
+        for col in 0..=row {
+            let dot: f64 = (0..col).map(|k| lower[row * n + k] * lower[col * n + k]).sum();
+            if row == col {
+                let diag = vals[row * n + row] - dot;
+                if diag <= 1e-12 * scale.max(1.0) || !diag.is_finite() {
+                    let msg = "Cholesky: Matrix is not positive definite, features may be collinear";
+                    return Err(msg.to_string());
+                }
+                lower[row * n + col] = diag.sqrt();
+            } else {
+                lower[row * n + col] = (vals[row * n + col] - dot) / lower[col * n + col];
+            }
+        }
+    }
+
+    NDArray::array(vec![n, n], lower)
+}
+
+
+/// Solve `a * x = b` for a symmetric positive definite `a` with a Cholesky factorization
+pub fn cholesky_solve(a: &NDArray<f64>, b: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+    let lower = cholesky(a)?;
+    let n = lower.shape().dim(0);
+    if b.rank() != 2 || b.shape().dim(0) != n {
+        let msg = format!("Cholesky: Right hand side must have {} rows", n);
+        return Err(msg);
+    }
+
+    let l = lower.values();
+    let m = b.shape().dim(1);
+    let mut x = b.values().clone();
+    for col in 0..m {
+
+        /* forward substitution with L */
+        for row in 0..n {
+            let dot: f64 = (0..row).map(|k| l[row * n + k] * x[k * m + col]).sum();
+            x[row * m + col] = (x[row * m + col] - dot) / l[row * n + row];
+        }
+
+        /* back substitution with L^T */
+        for row in (0..n).rev() {
+            let dot: f64 = (row + 1..n).map(|k| l[k * n + row] * x[k * m + col]).sum();
+            x[row * m + col] = (x[row * m + col] - dot) / l[row * n + row];
+        }
+    }
+
+    NDArray::array(vec![n, m], x)
+}
+
+
+/// Thin QR factorization with Householder reflections, returns `q` with shape
+/// `[rows, cols]` and upper triangular `r` with shape `[cols, cols]`
+pub fn qr(a: &NDArray<f64>) -> Result<(NDArray<f64>, NDArray<f64>), String> {
+
+    if a.rank() != 2 {
+        return Err("QR: Matrix must be rank 2".to_string());
+    }
+
+    let rows = a.shape().dim(0);
+    let cols = a.shape().dim(1);
+    if rows < cols {
+        return Err("QR: Matrix needs at least as many rows as columns".to_string());
+    }
+
+    let mut r = a.values().clone();
+    let mut q = vec![0.0; rows * rows];
+    for idx in 0..rows {
+        q[idx * rows + idx] = 1.0;
+    }
+
+    for col in 0..cols {
+
+        let norm = (col..rows).map(|row| r[row * cols + col].powi(2)).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+
+        /* reflect column onto -sign(a_kk) * norm * e_k */
+        let alpha = if r[col * cols + col] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = (col..rows).map(|row| r[row * cols + col]).collect();
+        v[0] -= alpha;
+        let v_norm = v.iter().map(|x| x * x).sum::<f64>();
+        if v_norm == 0.0 {
+            continue;
+        }
+
+        for other in col..cols {
+            let dot: f64 = (col..rows).map(|row| v[row - col] * r[row * cols + other]).sum();
+            let scale = 2.0 * dot / v_norm;
+            for row in col..rows {
+                r[row * cols + other] -= scale * v[row - col];
+            }
+        }
+
+        /* accumulate q = q * H */
+        for row in 0..rows {
+            let dot: f64 = (col..rows).map(|k| q[row * rows + k] * v[k - col]).sum();
+            let scale = 2.0 * dot / v_norm;
+            for k in col..rows {
+                q[row * rows + k] -= scale * v[k - col];
+            }
+        }
+    }
+
+    let thin_q: Vec<f64> = q.chunks(rows).flat_map(|row| row[..cols].to_vec()).collect();
+    let mut upper = vec![0.0; cols * cols];
+    for row in 0..cols {
+        for col in row..cols {
+            upper[row * cols + col] = r[row * cols + col];
+        }
+    }
+
+    Ok((
+        NDArray::array(vec![rows, cols], thin_q)?,
+        NDArray::array(vec![cols, cols], upper)?
+    ))
+}
+
+
+/// Least squares solution of `a * x = b` through a QR factorization of `a`
+pub fn lstsq_qr(a: &NDArray<f64>, b: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+    let (q, r) = qr(a)?;
+    let rows = q.shape().dim(0);
+    let cols = q.shape().dim(1);
+    if b.rank() != 2 || b.shape().dim(0) != rows {
+        let msg = format!("QR: Right hand side must have {} rows", rows);
+        return Err(msg);
+    }
+
+    let r_vals = r.values();
+    let max_diag = (0..cols).map(|idx| r_vals[idx * cols + idx].abs()).fold(0.0, f64::max);
+    if (0..cols).any(|idx| r_vals[idx * cols + idx].abs() <= 1e-12 * max_diag.max(1.0)) {
+        return Err("QR: Matrix is rank deficient, features may be collinear".to_string());
+    }
+
+    let q_vals = q.values();
+    let m = b.shape().dim(1);
+    let mut x = vec![0.0; cols * m];
+    for target in 0..m {
+
+        /* q^T * b */
+        let qtb: Vec<f64> = (0..cols)
+            .map(|col| (0..rows).map(|row| q_vals[row * cols + col] * b.values()[row * m + target]).sum())
+            .collect();
+
+        /* back substitution with r */
+        for row in (0..cols).rev() {
+            let dot: f64 = (row + 1..cols).map(|k| r_vals[row * cols + k] * x[k * m + target]).sum();
+            x[row * m + target] = (qtb[row] - dot) / r_vals[row * cols + row];
+        }
+    }
+
+    NDArray::array(vec![cols, m], x)
+}
+
+
+/// Exact weights and bias of a linear model minimizing the squared error plus
+/// `lambda * ||w||^2`. Features and target are centered so the bias is not penalized,
+/// the weights are then `(X^T X + lambda * I)^-1 X^T y` on the centered data
+pub fn solve_linear(
+    solver: Solver,
+    features: &NDArray<f64>,
+    y: &NDArray<f64>,
+    lambda: f64) -> Result<(NDArray<f64>, NDArray<f64>), String> {
+
+    if features.rank() != 2 || y.shape().values() != vec![features.shape().dim(0), 1] {
+        let msg = format!(
+            "Solver: Target shape {:?} must be a single column for {} feature rows",
+            y.shape().values(), features.shape().dim(0)
+        );
+        return Err(msg);
+    }
+
+    if lambda < 0.0 {
+        return Err("Solver: Lambda must be positive".to_string());
+    }
+
+    let rows = features.shape().dim(0);
+    let cols = features.shape().dim(1);
+    let (centered, means, y_centered, y_mean) = center(features, y);
+
+    let weights = match solver {
+        Solver::GradientDescent => {
+            return Err("Solver: Gradient descent has no closed form, use train".to_string());
+        },
+        Solver::Cholesky => {
+            let mut gram = vec![0.0; cols * cols];
+            let mut xty = vec![0.0; cols];
+            for row in 0..rows {
+                let sample = &centered[row * cols..(row + 1) * cols];
+                for i in 0..cols {
+                    xty[i] += sample[i] * y_centered[row];
+                    for j in 0..cols {
+                        gram[i * cols + j] += sample[i] * sample[j];
+                    }
+                }
+            }
+            for i in 0..cols {
+                gram[i * cols + i] += lambda;
+            }
+            cholesky_solve(
+                &NDArray::array(vec![cols, cols], gram)?,
+                &NDArray::array(vec![cols, 1], xty)?
+            )?
+        },
+        Solver::QR => {
+            /* ridge as least squares on data stacked with sqrt(lambda) * I */
+            let mut a = centered;
+            let mut b = y_centered;
+            if lambda > 0.0 {
+                for i in 0..cols {
+                    let mut row = vec![0.0; cols];
+                    row[i] = lambda.sqrt();
+                    a.extend(row);
+                    b.push(0.0);
+                }
+            }
+            let a_rows = b.len();
+            lstsq_qr(
+                &NDArray::array(vec![a_rows, cols], a)?,
+                &NDArray::array(vec![a_rows, 1], b)?
+            )?
+        }
+    };
+
+    let bias = y_mean - weights.values().iter().zip(means.iter()).map(|(w, m)| w * m).sum::<f64>();
+    Ok((weights, NDArray::array(vec![1, 1], vec![bias])?))
+}
+
+
+/// Solve `min sum w_i (z_i - x_i * beta - b)^2 + penalty * ||beta||^2` with an unpenalized bias
+pub fn weighted_ridge(
+    x: &[f64],
+    cols: usize,
+    z: &[f64],
+    w: &[f64],
+    penalty: f64) -> Result<(Vec<f64>, f64), String> {
+
+    let total: f64 = w.iter().sum();
+    let means: Vec<f64> = (0..cols)
+        .map(|col| x.chunks(cols).zip(w.iter()).map(|(row, w)| w * row[col]).sum::<f64>() / total)
+        .collect();
+    let z_mean = z.iter().zip(w.iter()).map(|(z, w)| z * w).sum::<f64>() / total;
+
+    let mut gram = vec![0.0; cols * cols];
+    let mut xtz = vec![0.0; cols];
+    for (idx, row) in x.chunks(cols).enumerate() {
+        let centered: Vec<f64> = row.iter().zip(means.iter()).map(|(x, m)| x - m).collect();
+        for i in 0..cols {
+            xtz[i] += w[idx] * centered[i] * (z[idx] - z_mean);
+            for j in 0..cols {
+                gram[i * cols + j] += w[idx] * centered[i] * centered[j];
+            }
+        }
+    }
+    for i in 0..cols {
+        gram[i * cols + i] += penalty;
+    }
+
+    let beta = cholesky_solve(
+        &NDArray::array(vec![cols, cols], gram)?,
+        &NDArray::array(vec![cols, 1], xtz)?
+    )?.values().clone();
+
+    let bias = z_mean - beta.iter().zip(means.iter()).map(|(b, m)| b * m).sum::<f64>();
+    Ok((beta, bias))
+}
+
+
+/// Parameters found by an iterative minimizer with its convergence details
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minimized {
+    pub params: Vec<f64>,
+    pub n_iter: usize,
+    pub grad_norm: f64
+}
+
+
+/// Euclidean norm of a gradient
+fn norm(values: &[f64]) -> f64 {
+    values.iter().map(|val| val * val).sum::<f64>().sqrt()
+}
+
+
+/// Backtracking line search along `direction` until the Armijo condition holds,
+/// returns the accepted parameters with their loss and gradient
+fn line_search<F>(
+    objective: &mut F,
+    params: &[f64],
+    loss: f64,
+    grad: &[f64],
+    direction: &[f64]) -> Option<(Vec<f64>, f64, Vec<f64>)>
+where
+    F: FnMut(&[f64]) -> (f64, Vec<f64>),
+{
+    let slope: f64 = grad.iter().zip(direction.iter()).map(|(g, d)| g * d).sum();
+    let mut step = 1.0;
+    while step > 1e-12 {
+        let candidate: Vec<f64> = params.iter()
+            .zip(direction.iter())
+            .map(|(p, d)| p + step * d)
+            .collect();
+        let (cand_loss, cand_grad) = objective(&candidate);
+        if cand_loss.is_finite() && cand_loss <= loss + 1e-4 * step * slope {
+            return Some((candidate, cand_loss, cand_grad));
+        }
+        step *= 0.5;
+    }
+    None
+}
+
+
+/// Newton's method with a backtracking line search. `objective` returns the loss and
+/// gradient and `hessian` the row major Hessian, which is damped when singular.
+/// Stops once the gradient norm drops to `tol`
+pub fn newton<F, H>(
+    mut objective: F,
+    mut hessian: H,
+    init: Vec<f64>,
+    max_iter: usize,
+    tol: f64) -> Result<Minimized, String>
+where
+    F: FnMut(&[f64]) -> (f64, Vec<f64>),
+    H: FnMut(&[f64]) -> Vec<f64>,
+{
+    let n = init.len();
+    let mut params = init;
+    let (mut loss, mut grad) = objective(&params);
+    let mut n_iter = 0;
+
+    while n_iter < max_iter && norm(&grad) > tol {
+
+        n_iter += 1;
+        let hess = hessian(&params);
+        let rhs = NDArray::array(vec![n, 1], grad.clone())?;
+
+        /* increase damping until the hessian is positive definite */
+        let mut damping = 0.0;
+        let step = loop {
+            let mut damped = hess.clone();
+            for idx in 0..n {
+                damped[idx * n + idx] += damping;
+            }
+            match cholesky_solve(&NDArray::array(vec![n, n], damped)?, &rhs) {
+                Ok(step) => break step,
+                Err(err) if damping > 1e6 => return Err(format!("Newton: {}", err)),
+                Err(_) => damping = if damping == 0.0 { 1e-8 } else { damping * 10.0 }
+            }
+        };
+
+        let direction: Vec<f64> = step.values().iter().map(|val| -val).collect();
+        match line_search(&mut objective, &params, loss, &grad, &direction) {
+            Some((next, next_loss, next_grad)) => {
+                params = next;
+                loss = next_loss;
+                grad = next_grad;
+            },
+            None => break
+        }
+    }
+
+    Ok(Minimized { grad_norm: norm(&grad), params, n_iter })
+}
+
+
+/// Limited memory BFGS keeping `memory` correction pairs, with a backtracking
+/// line search. `objective` returns the loss and gradient at the parameters.
+/// Stops once the gradient norm drops to `tol`
+pub fn lbfgs<F>(
+    mut objective: F,
+    init: Vec<f64>,
+    memory: usize,
+    max_iter: usize,
+    tol: f64) -> Minimized
+where
+    F: FnMut(&[f64]) -> (f64, Vec<f64>),
+{
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b.iter()).map(|(a, b)| a * b).sum::<f64>();
+
+    let mut params = init;
+    let (mut loss, mut grad) = objective(&params);
+    let mut pairs: Vec<(Vec<f64>, Vec<f64>)> = Vec::new();
+    let mut n_iter = 0;
+
+    while n_iter < max_iter && norm(&grad) > tol {
+
+        n_iter += 1;
+
+        /* two loop recursion for the inverse hessian times the gradient */
+        let mut q = grad.clone();
+        let mut alphas = Vec::with_capacity(pairs.len());
+        for (s, y) in pairs.iter().rev() {
+            let alpha = dot(s, &q) / dot(y, s);
+            q.iter_mut().zip(y.iter()).for_each(|(q, y)| *q -= alpha * y);
+            alphas.push(alpha);
+        }
+
+        let gamma = match pairs.last() {
+            Some((s, y)) => dot(s, y) / dot(y, y),
+            None => 1.0 / norm(&grad).max(1.0)
+        };
+        q.iter_mut().for_each(|q| *q *= gamma);
+
+        for ((s, y), alpha) in pairs.iter().zip(alphas.iter().rev()) {
+            let beta = dot(y, &q) / dot(y, s);
+            q.iter_mut().zip(s.iter()).for_each(|(q, s)| *q += (alpha - beta) * s);
+        }
+
+        /* fall back to steepest descent when the direction does not descend */
+        let mut direction: Vec<f64> = q.iter().map(|val| -val).collect();
+        if dot(&direction, &grad) >= 0.0 {
+            pairs.clear();
+            direction = grad.iter().map(|g| -g / norm(&grad).max(1.0)).collect();
+        }
+
+        let (next, next_loss, next_grad) = match line_search(&mut objective, &params, loss, &grad, &direction) {
+            Some(accepted) => accepted,
+            None => break
+        };
+
+        let s: Vec<f64> = next.iter().zip(params.iter()).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = next_grad.iter().zip(grad.iter()).map(|(a, b)| a - b).collect();
+        if dot(&s, &y) > 1e-10 {
+            pairs.push((s, y));
+            if pairs.len() > memory {
+                pairs.remove(0);
+            }
+        }
+
+        params = next;
+        loss = next_loss;
+        grad = next_grad;
+    }
+
+    Minimized { grad_norm: norm(&grad), params, n_iter }
+}
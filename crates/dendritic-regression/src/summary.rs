@@ -0,0 +1,588 @@
+use std::fmt;
+use dendritic_ndarray::ndarray::NDArray;
+use crate::solver::cholesky_solve;
+use crate::utils::check_data;
+
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7
+];
+
+const EPS: f64 = 1e-15;
+const MAX_ITER: usize = 500;
+
+/// Largest score norm, relative to `1 + ||[1, X]^T y||`, accepted as the optimum for a summary
+pub const SCORE_TOL: f64 = 1e-6;
+
+
+/// Natural log of the gamma function for positive `x` with the Lanczos approximation
+pub fn ln_gamma(x: f64) -> f64 {
+
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |acc, (idx, c)| acc + c / (x + idx as f64 + 1.0));
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+
+/// Regularized lower incomplete gamma function `P(a, x)`
+pub fn gamma_inc(a: f64, x: f64) -> f64 {
+
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    let log_prefix = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+
+        /* power series converges quickly below the mean */
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..MAX_ITER {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        return (sum.ln() + log_prefix).exp().min(1.0);
+    }
+
+    /* continued fraction for the upper tail with the modified Lentz method */
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for n in 1..MAX_ITER {
+        let an = -(n as f64) * (n as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        d = if d.abs() < tiny { tiny } else { d };
+        c = b + an / c;
+        c = if c.abs() < tiny { tiny } else { c };
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    1.0 - (log_prefix + h.ln()).exp()
+}
+
+
+/// Continued fraction used by `beta_inc`
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+
+    let tiny = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    d = if d.abs() < tiny { tiny } else { d };
+    d = 1.0 / d;
+    let mut h = d;
+
+    for m in 1..MAX_ITER {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        let even = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + even * d;
+        d = if d.abs() < tiny { tiny } else { d };
+        c = 1.0 + even / c;
+        c = if c.abs() < tiny { tiny } else { c };
+        d = 1.0 / d;
+        h *= d * c;
+
+        let odd = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + odd * d;
+        d = if d.abs() < tiny { tiny } else { d };
+        c = 1.0 + odd / c;
+        c = if c.abs() < tiny { tiny } else { c };
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    h
+}
+
+
+/// Regularized incomplete beta function `I_x(a, b)`
+pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let log_prefix = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b)
+        + a * x.ln() + b * (1.0 - x).ln();
+
+    /* the fraction converges fastest on the side of the mean */
+    if x < (a + 1.0) / (a + b + 2.0) {
+        (log_prefix.exp() * beta_fraction(a, b, x) / a).min(1.0)
+    } else {
+        1.0 - (log_prefix.exp() * beta_fraction(b, a, 1.0 - x) / b).max(0.0)
+    }
+}
+
+
+/// Standard normal cumulative distribution function
+pub fn normal_cdf(z: f64) -> f64 {
+    let erf = gamma_inc(0.5, z * z / 2.0);
+    if z >= 0.0 { 0.5 + 0.5 * erf } else { 0.5 - 0.5 * erf }
+}
+
+
+/// Cumulative distribution function of Student's t with `df` degrees of freedom
+pub fn student_t_cdf(t: f64, df: f64) -> f64 {
+    if t.is_infinite() {
+        return if t > 0.0 { 1.0 } else { 0.0 };
+    }
+    let tail = 0.5 * beta_inc(df / 2.0, 0.5, df / (df + t * t));
+    if t >= 0.0 { 1.0 - tail } else { tail }
+}
+
+
+/// Upper tail probability of the F distribution with `d1` and `d2` degrees of freedom
+pub fn f_sf(f: f64, d1: f64, d2: f64) -> f64 {
+    if f <= 0.0 {
+        return 1.0;
+    }
+    if f.is_infinite() {
+        return 0.0;
+    }
+    beta_inc(d2 / 2.0, d1 / 2.0, d2 / (d2 + d1 * f))
+}
+
+
+/// Upper tail probability of the chi squared distribution with `k` degrees of freedom
+pub fn chi2_sf(x: f64, k: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    1.0 - gamma_inc(k / 2.0, x / 2.0)
+}
+
+
+/// Invert a monotone cumulative distribution function by bisection
+fn bisect<F: Fn(f64) -> f64>(cdf: F, p: f64) -> f64 {
+
+    let (mut low, mut high) = (-1.0, 1.0);
+    while cdf(low) > p {
+        low *= 2.0;
+    }
+    while cdf(high) < p {
+        high *= 2.0;
+    }
+
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if cdf(mid) < p { low = mid } else { high = mid }
+        if high - low < 1e-12 * (1.0 + mid.abs()) {
+            break;
+        }
+    }
+    0.5 * (low + high)
+}
+
+
+/// Quantile of the standard normal distribution
+pub fn normal_quantile(p: f64) -> f64 {
+    bisect(normal_cdf, p)
+}
+
+
+/// Quantile of Student's t distribution with `df` degrees of freedom
+pub fn student_t_quantile(p: f64, df: f64) -> f64 {
+    bisect(|t| student_t_cdf(t, df), p)
+}
+
+
+/// Reference distribution of the coefficient test statistics
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    StudentT(f64),
+    Normal
+}
+
+impl Distribution {
+
+    /// Two sided p value of a test statistic
+    pub fn p_value(&self, statistic: f64) -> f64 {
+        let tail = match self {
+            Distribution::StudentT(df) => student_t_cdf(-statistic.abs(), *df),
+            Distribution::Normal => normal_cdf(-statistic.abs())
+        };
+        (2.0 * tail).min(1.0)
+    }
+
+    /// Quantile at probability `p`
+    pub fn quantile(&self, p: f64) -> f64 {
+        match self {
+            Distribution::StudentT(df) => student_t_quantile(p, *df),
+            Distribution::Normal => normal_quantile(p)
+        }
+    }
+}
+
+
+/// Estimate and test of a single coefficient
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coefficient {
+    pub name: String,
+    pub estimate: f64,
+    pub std_error: f64,
+    pub statistic: f64,
+    pub p_value: f64,
+    pub lower: f64,
+    pub upper: f64
+}
+
+
+/// Statistical inference summary of a fitted linear or logistic model.
+/// The intercept is reported first as `const`, followed by features `x1` to `xk`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub model: String,
+    pub distribution: Distribution,
+    pub alpha: f64,
+    pub coefficients: Vec<Coefficient>,
+    pub n_obs: usize,
+    pub df_model: usize,
+    pub df_resid: usize,
+    pub log_likelihood: f64,
+    pub aic: f64,
+    pub bic: f64,
+    pub r_squared: Option<f64>,
+    pub adj_r_squared: Option<f64>,
+    pub f_statistic: Option<f64>,
+    pub f_p_value: Option<f64>,
+    pub pseudo_r_squared: Option<f64>,
+    pub llr_p_value: Option<f64>
+}
+
+impl Summary {
+
+    /// Ordinary least squares summary for weights and bias fit on `x` and `y`,
+    /// with `1 - alpha` confidence intervals. Errors unless the weights and bias
+    /// solve the normal equations within `SCORE_TOL`
+    pub fn ols(
+        x: &NDArray<f64>,
+        y: &NDArray<f64>,
+        weights: &NDArray<f64>,
+        bias: f64,
+        alpha: f64) -> Result<Summary, String> {
+
+        let (rows, cols) = check_summary("Linear", x, y, weights, alpha)?;
+        let params = params(weights, bias);
+        let df_resid = rows - cols - 1;
+
+        let fitted = fitted(x, &params);
+        check_score("Linear", x, y, &fitted)?;
+        let sse: f64 = y.values().iter().zip(fitted.iter()).map(|(y, f)| (y - f).powi(2)).sum();
+        let y_mean = y.values().iter().sum::<f64>() / rows as f64;
+        let sst: f64 = y.values().iter().map(|y| (y - y_mean).powi(2)).sum();
+
+        let sigma2 = sse / df_resid as f64;
+        let cov = inverse(&information(x, |_| 1.0))?
+            .iter()
+            .map(|c| c * sigma2)
+            .collect::<Vec<f64>>();
+
+        let n = rows as f64;
+        let k = (cols + 1) as f64;
+        let log_likelihood = -n / 2.0 * ((2.0 * std::f64::consts::PI * sse / n).ln() + 1.0);
+        let r_squared = 1.0 - sse / sst;
+        let adj_r_squared = 1.0 - (1.0 - r_squared) * (n - 1.0) / df_resid as f64;
+        let f_statistic = ((sst - sse) / cols as f64) / sigma2;
+
+        let distribution = Distribution::StudentT(df_resid as f64);
+        Ok(Summary {
+            model: "Linear".to_string(),
+            distribution,
+            alpha,
+            coefficients: coefficients(&params, &cov, distribution, alpha),
+            n_obs: rows,
+            df_model: cols,
+            df_resid,
+            log_likelihood,
+            aic: 2.0 * k - 2.0 * log_likelihood,
+            bic: k * n.ln() - 2.0 * log_likelihood,
+            r_squared: Some(r_squared),
+            adj_r_squared: Some(adj_r_squared),
+            f_statistic: Some(f_statistic),
+            f_p_value: Some(f_sf(f_statistic, cols as f64, df_resid as f64)),
+            pseudo_r_squared: None,
+            llr_p_value: None
+        })
+    }
+
+
+    /// Binary logistic regression summary for weights and bias fit on `x` and labels `y`,
+    /// with `1 - alpha` confidence intervals from the inverse Fisher information.
+    /// Probabilities are the sigmoid of the linear output, as for `Logistic`, and the
+    /// summary errors unless the weights and bias maximize the likelihood within `SCORE_TOL`
+    pub fn logit(
+        x: &NDArray<f64>,
+        y: &NDArray<f64>,
+        weights: &NDArray<f64>,
+        bias: f64,
+        alpha: f64) -> Result<Summary, String> {
+
+        let (rows, cols) = check_summary("Logistic", x, y, weights, alpha)?;
+        if y.values().iter().any(|y| *y != 0.0 && *y != 1.0) {
+            return Err("Logistic: Summary labels must be 0 or 1".to_string());
+        }
+
+        let params = params(weights, bias);
+        let probs: Vec<f64> = fitted(x, &params)
+            .iter()
+            .map(|z| (1.0 / (1.0 + (-z).exp())).clamp(EPS, 1.0 - EPS))
+            .collect();
+        check_score("Logistic", x, y, &probs)?;
+
+        let cov = inverse(&information(x, |row| probs[row] * (1.0 - probs[row])))?;
+
+        let bernoulli = |y: f64, p: f64| y * p.ln() + (1.0 - y) * (1.0 - p).ln();
+        let log_likelihood: f64 = y.values().iter().zip(probs.iter()).map(|(y, p)| bernoulli(*y, *p)).sum();
+        let y_mean = (y.values().iter().sum::<f64>() / rows as f64).clamp(EPS, 1.0 - EPS);
+        let null_likelihood: f64 = y.values().iter().map(|y| bernoulli(*y, y_mean)).sum();
+        let llr = 2.0 * (log_likelihood - null_likelihood);
+
+        let n = rows as f64;
+        let k = (cols + 1) as f64;
+        Ok(Summary {
+            model: "Logistic".to_string(),
+            distribution: Distribution::Normal,
+            alpha,
+            coefficients: coefficients(&params, &cov, Distribution::Normal, alpha),
+            n_obs: rows,
+            df_model: cols,
+            df_resid: rows - cols - 1,
+            log_likelihood,
+            aic: 2.0 * k - 2.0 * log_likelihood,
+            bic: k * n.ln() - 2.0 * log_likelihood,
+            r_squared: None,
+            adj_r_squared: None,
+            f_statistic: None,
+            f_p_value: None,
+            pseudo_r_squared: Some(1.0 - log_likelihood / null_likelihood),
+            llr_p_value: Some(chi2_sf(llr, cols as f64))
+        })
+    }
+
+
+    /// Look up a coefficient by name
+    pub fn coefficient(&self, name: &str) -> Option<&Coefficient> {
+        self.coefficients.iter().find(|coef| coef.name == name)
+    }
+}
+
+
+/// Validate shapes for a summary, returns rows and feature columns
+fn check_summary(
+    name: &str,
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    weights: &NDArray<f64>,
+    alpha: f64) -> Result<(usize, usize), String> {
+
+    check_data(name, x, y)?;
+    let (rows, cols) = (x.shape().dim(0), x.shape().dim(1));
+    if y.shape().dim(1) != 1 || weights.size() != cols {
+        let msg = format!("{}: Summary needs a single output and {} weights", name, cols);
+        return Err(msg);
+    }
+    if rows <= cols + 1 {
+        let msg = format!("{}: Need more rows than parameters for a summary", name);
+        return Err(msg);
+    }
+    if alpha <= 0.0 || alpha >= 1.0 {
+        let msg = format!("{}: Alpha must be in (0, 1), got {}", name, alpha);
+        return Err(msg);
+    }
+    Ok((rows, cols))
+}
+
+
+/// Check the score `[1, X]^T (y - mean)` vanishes, so the parameters are the optimum
+/// the standard errors and tests are derived at
+fn check_score(name: &str, x: &NDArray<f64>, y: &NDArray<f64>, means: &[f64]) -> Result<(), String> {
+
+    let cols = x.shape().dim(1);
+    let mut score = vec![0.0; cols + 1];
+    let mut scale = vec![0.0; cols + 1];
+    for ((row, y), mean) in x.values().chunks(cols).zip(y.values().iter()).zip(means.iter()) {
+        score[0] += y - mean;
+        scale[0] += y;
+        for col in 0..cols {
+            score[col + 1] += row[col] * (y - mean);
+            scale[col + 1] += row[col] * y;
+        }
+    }
+
+    let norm = |v: &[f64]| v.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let score_norm = norm(&score);
+    if score_norm > SCORE_TOL * (1.0 + norm(&scale)) {
+        let msg = format!(
+            "{}: Summary needs converged weights, score norm is {:e}",
+            name, score_norm
+        );
+        return Err(msg);
+    }
+    Ok(())
+}
+
+
+/// Intercept followed by weights
+fn params(weights: &NDArray<f64>, bias: f64) -> Vec<f64> {
+    std::iter::once(bias).chain(weights.values().iter().copied()).collect()
+}
+
+
+/// Linear predictor for each row
+fn fitted(x: &NDArray<f64>, params: &[f64]) -> Vec<f64> {
+    let cols = x.shape().dim(1);
+    x.values()
+        .chunks(cols)
+        .map(|row| params[0] + row.iter().zip(params[1..].iter()).map(|(a, b)| a * b).sum::<f64>())
+        .collect()
+}
+
+
+/// Weighted cross product of the design matrix with a leading column of ones
+fn information<F: Fn(usize) -> f64>(x: &NDArray<f64>, weight: F) -> NDArray<f64> {
+
+    let cols = x.shape().dim(1);
+    let n = cols + 1;
+    let mut info = vec![0.0; n * n];
+    for (idx, row) in x.values().chunks(cols).enumerate() {
+        let w = weight(idx);
+        let sample: Vec<f64> = std::iter::once(1.0).chain(row.iter().copied()).collect();
+        for i in 0..n {
+            for j in 0..n {
+                info[i * n + j] += w * sample[i] * sample[j];
+            }
+        }
+    }
+    NDArray::array(vec![n, n], info).unwrap()
+}
+
+
+/// Inverse of a symmetric positive definite matrix
+fn inverse(a: &NDArray<f64>) -> Result<Vec<f64>, String> {
+    let n = a.shape().dim(0);
+    let mut identity = vec![0.0; n * n];
+    for i in 0..n {
+        identity[i * n + i] = 1.0;
+    }
+    let inv = cholesky_solve(a, &NDArray::array(vec![n, n], identity)?)?;
+    Ok(inv.values().clone())
+}
+
+
+/// Standard errors, tests and confidence intervals from a covariance matrix
+fn coefficients(
+    params: &[f64],
+    cov: &[f64],
+    distribution: Distribution,
+    alpha: f64) -> Vec<Coefficient> {
+
+    let n = params.len();
+    let critical = distribution.quantile(1.0 - alpha / 2.0);
+    params.iter().enumerate().map(|(idx, estimate)| {
+        let std_error = cov[idx * n + idx].max(0.0).sqrt();
+        let statistic = estimate / std_error;
+        let name = if idx == 0 { "const".to_string() } else { format!("x{}", idx) };
+        Coefficient {
+            name,
+            estimate: *estimate,
+            std_error,
+            statistic,
+            p_value: distribution.p_value(statistic),
+            lower: estimate - critical * std_error,
+            upper: estimate + critical * std_error
+        }
+    }).collect()
+}
+
+
+impl fmt::Display for Summary {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        let rule = "=".repeat(78);
+        let thin = "-".repeat(78);
+        let stat = match self.distribution {
+            Distribution::StudentT(_) => "t",
+            Distribution::Normal => "z"
+        };
+        let optional = |value: Option<f64>| value.map_or("".to_string(), |v| format!("{:.4}", v));
+
+        writeln!(f, "{:^78}", format!("{} Regression Results", self.model))?;
+        writeln!(f, "{}", rule)?;
+
+        let mut left = vec![
+            ("No. Observations:", self.n_obs.to_string()),
+            ("Df Residuals:", self.df_resid.to_string()),
+            ("Df Model:", self.df_model.to_string()),
+            ("Log-Likelihood:", format!("{:.4}", self.log_likelihood)),
+        ];
+        let mut right = vec![
+            ("AIC:", format!("{:.4}", self.aic)),
+            ("BIC:", format!("{:.4}", self.bic)),
+        ];
+        if self.r_squared.is_some() {
+            right.insert(0, ("R-squared:", optional(self.r_squared)));
+            right.insert(1, ("Adj. R-squared:", optional(self.adj_r_squared)));
+            left.push(("F-statistic:", optional(self.f_statistic)));
+            left.push(("Prob (F-statistic):", optional(self.f_p_value)));
+        } else {
+            right.insert(0, ("Pseudo R-squ.:", optional(self.pseudo_r_squared)));
+            right.insert(1, ("LLR p-value:", optional(self.llr_p_value)));
+        }
+        for idx in 0..left.len().max(right.len()) {
+            let (l_name, l_val) = left.get(idx).cloned().unwrap_or(("", String::new()));
+            let (r_name, r_val) = right.get(idx).cloned().unwrap_or(("", String::new()));
+            writeln!(f, "{:<20}{:>18}  {:<20}{:>18}", l_name, l_val, r_name, r_val)?;
+        }
+
+        writeln!(f, "{}", thin)?;
+        writeln!(
+            f, "{:<10}{:>11}{:>11}{:>11}{:>11}{:>12}{:>12}",
+            "", "coef", "std err", stat, format!("P>|{}|", stat),
+            format!("[{}", self.alpha / 2.0), format!("{}]", 1.0 - self.alpha / 2.0)
+        )?;
+        writeln!(f, "{}", thin)?;
+        for coef in &self.coefficients {
+            writeln!(
+                f, "{:<10}{:>11.4}{:>11.4}{:>11.3}{:>11.3}{:>12.4}{:>12.4}",
+                coef.name, coef.estimate, coef.std_error, coef.statistic,
+                coef.p_value, coef.lower, coef.upper
+            )?;
+        }
+        write!(f, "{}", rule)
+    }
+}
@@ -0,0 +1,150 @@
+use dendritic_ndarray::ndarray::NDArray;
+use dendritic_ndarray::ops::*;
+use dendritic_autodiff::node::{Node, Value};
+use dendritic_autodiff::ops::*;
+use dendritic_autodiff::optim::Optimizer;
+
+
+/// Default number of epochs used when fitting through the `Fit` trait
+pub const DEFAULT_EPOCHS: usize = 1000;
+
+
+/// Check features match the number of weight rows
+pub fn check_features(
+    name: &str,
+    x: &NDArray<f64>,
+    weights: &Value<NDArray<f64>>) -> Result<(), String> {
+
+    if x.rank() != 2 {
+        let msg = format!("{}: Features must be rank 2, got rank {}", name, x.rank());
+        return Err(msg);
+    }
+
+    let expected = weights.val().shape().dim(0);
+    if x.shape().dim(1) != expected {
+        let msg = format!(
+            "{}: Expected {} features, got {}",
+            name, expected, x.shape().dim(1)
+        );
+        return Err(msg);
+    }
+
+    Ok(())
+}
+
+
+/// Check the optimizer learning rate is between 0 and 1
+pub fn check_learning_rate(name: &str, optimizer: &dyn Optimizer) -> Result<(), String> {
+    let learning_rate = optimizer.learning_rate();
+    if !(0.0..=1.0).contains(&learning_rate) {
+        let msg = format!(
+            "{}: Learning rate must be between 0 and 1, got {}",
+            name, learning_rate
+        );
+        return Err(msg);
+    }
+    Ok(())
+}
+
+
+/// Check features and targets line up before fitting
+pub fn check_fit(
+    name: &str,
+    x: &NDArray<f64>,
+    y: &NDArray<f64>,
+    weights: &Value<NDArray<f64>>) -> Result<(), String> {
+
+    check_features(name, x, weights)?;
+
+    if y.rank() != 2 || y.shape().dim(0) != x.shape().dim(0) {
+        let msg = format!(
+            "{}: Target shape {:?} does not match {} feature rows",
+            name, y.shape().values(), x.shape().dim(0)
+        );
+        return Err(msg);
+    }
+
+    Ok(())
+}
+
+
+/// Check features are non empty rank 2 and targets are a single matching column
+pub fn check_data(name: &str, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+
+    if x.rank() != 2 || x.shape().dim(0) == 0 {
+        let msg = format!(
+            "{}: Features must be non empty rank 2, got shape {:?}",
+            name, x.shape().values()
+        );
+        return Err(msg);
+    }
+
+    if y.shape().values() != vec![x.shape().dim(0), 1] {
+        let msg = format!(
+            "{}: Target shape {:?} does not match {} feature rows",
+            name, y.shape().values(), x.shape().dim(0)
+        );
+        return Err(msg);
+    }
+
+    Ok(())
+}
+
+
+/// Linear output `x * weights + bias` without touching the model features
+pub fn linear_output(
+    name: &str,
+    x: &NDArray<f64>,
+    weights: &Value<NDArray<f64>>,
+    bias: &Value<NDArray<f64>>) -> Result<NDArray<f64>, String> {
+
+    check_features(name, x, weights)?;
+    let linear = Add::new(
+        MatMul::new(Value::new(x), weights.clone()),
+        bias.clone()
+    );
+    Ok(linear.value())
+}
+
+
+/// Column centered features and target in row major order, along with
+/// the feature column means and the target mean
+pub fn center(x: &NDArray<f64>, y: &NDArray<f64>) -> (Vec<f64>, Vec<f64>, Vec<f64>, f64) {
+
+    let rows = x.shape().dim(0);
+    let cols = x.shape().dim(1);
+    let vals = x.values();
+
+    let means: Vec<f64> = (0..cols)
+        .map(|col| (0..rows).map(|row| vals[row * cols + col]).sum::<f64>() / rows as f64)
+        .collect();
+    let y_mean = y.values().iter().sum::<f64>() / rows as f64;
+    let centered = vals.iter().enumerate().map(|(idx, val)| val - means[idx % cols]).collect();
+    let y_centered = y.values().iter().map(|val| val - y_mean).collect();
+    (centered, means, y_centered, y_mean)
+}
+
+
+/// Class labels from one hot encoded rows, label columns are returned as is
+pub fn class_labels(y: &NDArray<f64>) -> NDArray<f64> {
+    match y.shape().dim(1) {
+        1 => y.clone(),
+        _ => y.argmax(0)
+    }
+}
+
+
+/// One hot encode a label column with a fixed number of classes
+pub fn one_hot(name: &str, y: &NDArray<f64>, classes: usize) -> Result<NDArray<f64>, String> {
+
+    let mut encoded = NDArray::new(vec![y.shape().dim(0), classes]).unwrap();
+    for (row, label) in y.values().iter().enumerate() {
+        if *label < 0.0 || label.fract() != 0.0 || *label as usize >= classes {
+            let msg = format!("{}: Label {} is not a class index below {}", name, label, classes);
+            return Err(msg);
+        }
+        encoded.set_idx(row * classes + *label as usize, 1.0)?;
+    }
+
+    Ok(encoded)
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod coordinate_descent_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_regression::coordinate_descent::*;
+    use dendritic_regression::solver::*;
+    use dendritic_regression::lasso::*;
+    use dendritic_regression::elastic_net::*;
+    use dendritic_autodiff::optim::SGD;
+
+    /// Target only depends on the first two of four features, y = 3 * x1 - 2 * x2 + 1
+    fn data() -> (NDArray<f64>, NDArray<f64>) {
+        let x_vals: Vec<f64> = (0..40)
+            .flat_map(|row| {
+                let t = row as f64;
+                vec![(t * 0.7).sin(), (t * 1.3).cos(), (t * 0.37).sin() * 0.5, ((t * 2.1).cos() - 0.2) * 0.5]
+            })
+            .collect();
+        let y_vals: Vec<f64> = x_vals
+            .chunks(4)
+            .enumerate()
+            .map(|(idx, row)| 3.0 * row[0] - 2.0 * row[1] + 1.0 + 0.01 * (idx as f64 * 5.3).sin())
+            .collect();
+        (
+            NDArray::array(vec![40, 4], x_vals).unwrap(),
+            NDArray::array(vec![40, 1], y_vals).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_soft_threshold() {
+        assert_eq!(soft_threshold(3.0, 1.0), 2.0);
+        assert_eq!(soft_threshold(-3.0, 1.0), -2.0);
+        assert_eq!(soft_threshold(0.5, 1.0), 0.0);
+        assert_eq!(soft_threshold(-1.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_coordinate_descent() {
+
+        let (x, y) = data();
+        let solver = CoordinateDescent::new(1.0).unwrap();
+
+        /* without penalty coordinate descent matches least squares */
+        let exact = CoordinateDescent { tol: 1e-14, max_iter: 10000, ..solver };
+        let fit = exact.fit(&x, &y, 0.0, None).unwrap();
+        let (weights, bias) = solve_linear(Solver::QR, &x, &y, 0.0).unwrap();
+        for (a, b) in fit.weights.values().iter().zip(weights.values().iter()) {
+            assert!((a - b).abs() < 1e-6, "{:?}", fit.weights.values());
+        }
+        assert!((fit.bias.values()[0] - bias.values()[0]).abs() < 1e-6);
+
+        /* lasso zeroes the irrelevant features exactly */
+        let fit = solver.fit(&x, &y, 0.05, None).unwrap();
+        let w = fit.weights.values();
+        assert!(w[0] > 2.5 && w[1] < -1.5, "{:?}", w);
+        assert_eq!(w[2], 0.0);
+        assert_eq!(w[3], 0.0);
+        assert!(fit.dual_gap >= -1e-12 && fit.n_iter < solver.max_iter);
+
+        /* random order converges to the same solution */
+        let random = CoordinateDescent { selection: Selection::Random, seed: 7, tol: 1e-10, ..solver };
+        let cyclic = CoordinateDescent { tol: 1e-10, ..solver };
+        let a = random.fit(&x, &y, 0.05, None).unwrap();
+        let b = cyclic.fit(&x, &y, 0.05, None).unwrap();
+        for (a, b) in a.weights.values().iter().zip(b.weights.values().iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        assert!(CoordinateDescent::new(1.5).is_err());
+        let short = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let err = solver.fit(&x, &short, 0.1, None).unwrap_err();
+        assert_eq!(err, "Coordinate Descent: Target shape [2, 1] does not match 40 feature rows");
+    }
+
+    #[test]
+    fn test_paths() {
+
+        let (x, y) = data();
+        let solver = CoordinateDescent::new(1.0).unwrap();
+
+        let max = alpha_max(&x, &y, 1.0).unwrap();
+        let fit = solver.fit(&x, &y, max, None).unwrap();
+        assert!(fit.weights.values().iter().all(|w| *w == 0.0));
+
+        let alphas = alpha_grid(&x, &y, 1.0, 10, 1e-3).unwrap();
+        assert_eq!(alphas.len(), 10);
+        assert!((alphas[0] - max).abs() < 1e-12);
+        assert!((alphas[9] - max * 1e-3).abs() < 1e-12);
+        assert!(alphas.windows(2).all(|pair| pair[0] > pair[1]));
+
+        /* number of active features grows as alpha decreases */
+        let path = lasso_path(&x, &y, &alphas, &solver).unwrap();
+        let active: Vec<usize> = path.iter()
+            .map(|fit| fit.weights.values().iter().filter(|w| **w != 0.0).count())
+            .collect();
+        assert_eq!(active[0], 0);
+        assert!(active.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", active);
+
+        let enet = CoordinateDescent::new(0.5).unwrap();
+        let path = enet_path(&x, &y, &alphas, &enet).unwrap();
+        assert_eq!(path.len(), 10);
+        assert_eq!(path[3].alpha, alphas[3]);
+    }
+
+    #[test]
+    fn test_lasso_cv() {
+
+        let (x, y) = data();
+        let mut model = LassoCV::new(5).unwrap();
+        model.set_n_alphas(20);
+        model.fit(&x, &y).unwrap();
+
+        assert_eq!(model.alphas.len(), 20);
+        assert_eq!(model.mse_path.len(), 20);
+        assert!(model.alphas.contains(&model.alpha));
+        assert!(model.score(&x, &y).unwrap() > 0.99);
+
+        model.set_alphas(vec![0.001, 1.0, 0.1]);
+        model.fit(&x, &y).unwrap();
+        assert_eq!(model.alphas, vec![1.0, 0.1, 0.001]);
+        assert_ne!(model.alpha, 1.0);
+
+        assert!(LassoCV::new(1).is_err());
+    }
+
+    #[test]
+    fn test_model_coordinate_descent() {
+
+        let (x, y) = data();
+
+        /* model lambda is alpha times the number of rows */
+        let mut lasso = Lasso::new(&x, &y, 2.0, SGD::new(0.01)).unwrap();
+        assert!(lasso.solve().is_err());
+        lasso.set_solver(PenalizedSolver::CoordinateDescent);
+        lasso.fit(&x, &y).unwrap();
+        let expected = CoordinateDescent::new(1.0).unwrap().fit(&x, &y, 0.05, None).unwrap();
+        for (a, b) in lasso.weights.val().values().iter().zip(expected.weights.values().iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+        assert_eq!(lasso.weights.val().values()[2], 0.0);
+
+        let mut model = ElasticNet::new(&x, &y, 2.0, SGD::new(0.01)).unwrap();
+        model.set_solver(PenalizedSolver::CoordinateDescent);
+        model.set_coordinate_descent(CoordinateDescent::new(0.9).unwrap());
+        model.fit(&x, &y).unwrap();
+        assert!(model.score(&x, &y).unwrap() > 0.95);
+
+        model.set_solver(PenalizedSolver::GradientDescent);
+        let err = model.solve().unwrap_err();
+        assert_eq!(err, "ElasticNet: Solver GradientDescent is not supported, use coordinate descent");
+    }
+
+    #[test]
+    fn test_gradient_descent_matches_coordinate_descent() {
+
+        /* no weight is zero at the optimum, so the L1 subgradient is smooth near it */
+        let x_vals: Vec<f64> = (0..20)
+            .flat_map(|row| vec![(row as f64 * 0.9).sin(), (row as f64 * 0.4).cos()])
+            .collect();
+        let y_vals: Vec<f64> = x_vals.chunks(2).map(|v| 2.0 * v[0] - 1.5 * v[1] + 0.5).collect();
+        let x = NDArray::array(vec![20, 2], x_vals).unwrap();
+        let y = NDArray::array(vec![20, 1], y_vals).unwrap();
+        let exact = CoordinateDescent { tol: 1e-14, max_iter: 10000, ..CoordinateDescent::new(1.0).unwrap() };
+        let assert_close = |a: &NDArray<f64>, b: &NDArray<f64>| {
+            for (a, b) in a.values().iter().zip(b.values().iter()) {
+                assert!((a - b).abs() < 1e-8, "{} != {}", a, b);
+            }
+        };
+
+        let mut descent = Lasso::new(&x, &y, 2.0, SGD::new(0.5)).unwrap();
+        descent.set_epochs(3000);
+        descent.fit(&x, &y).unwrap();
+
+        let mut coordinate = Lasso::new(&x, &y, 2.0, SGD::new(0.5)).unwrap();
+        coordinate.set_solver(PenalizedSolver::CoordinateDescent);
+        coordinate.set_coordinate_descent(exact);
+        coordinate.fit(&x, &y).unwrap();
+        assert_close(&descent.weights.val(), &coordinate.weights.val());
+        assert_close(&descent.bias.val(), &coordinate.bias.val());
+
+        let mut descent = ElasticNet::new(&x, &y, 2.0, SGD::new(0.5)).unwrap();
+        descent.set_coordinate_descent(CoordinateDescent { l1_ratio: 0.7, ..exact });
+        descent.set_epochs(3000);
+        descent.fit(&x, &y).unwrap();
+
+        let mut coordinate = ElasticNet::new(&x, &y, 2.0, SGD::new(0.5)).unwrap();
+        coordinate.set_solver(PenalizedSolver::CoordinateDescent);
+        coordinate.set_coordinate_descent(CoordinateDescent { l1_ratio: 0.7, ..exact });
+        coordinate.fit(&x, &y).unwrap();
+        assert_close(&descent.weights.val(), &coordinate.weights.val());
+        assert_close(&descent.bias.val(), &coordinate.bias.val());
+    }
+
+}
@@ -6,6 +6,7 @@ mod elastic {
     use dendritic_ndarray::ops::*;
     use dendritic_metrics::loss::*;
     use dendritic_regression::elastic_net::*;
+    use dendritic_autodiff::optim::SGD;
 
     #[test]
     fn test_elastic_net_model_train() {
@@ -24,7 +25,7 @@ mod elastic {
 
         let mut model = ElasticNet::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.train(1000, false);
@@ -53,7 +54,7 @@ mod elastic {
 
         let mut model = ElasticNet::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         let x_train = x.batch(batch_size).unwrap();
@@ -73,7 +74,8 @@ mod elastic {
     #[test]
     fn test_elastic_load_save() {
 
-        let model_path = "data/models/lasso";
+        let model_dir = std::env::temp_dir().join("dendritic_elastic_net_model_test");
+        let model_path = model_dir.to_str().unwrap();
         let x_path = "data/linear_modeling_data/inputs";
         let y_path = "data/linear_modeling_data/outputs"; 
 
@@ -88,7 +90,7 @@ mod elastic {
 
         let mut model = ElasticNet::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.train(1000, false);
@@ -97,7 +99,7 @@ mod elastic {
         let mut loaded_model = ElasticNet::load(
             model_path,
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         let results = loaded_model.predict(x.clone());
@@ -0,0 +1,149 @@
+
+#[cfg(test)]
+mod estimator_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_regression::linear::Linear;
+    use dendritic_regression::ridge::Ridge;
+    use dendritic_regression::lasso::Lasso;
+    use dendritic_regression::elastic_net::ElasticNet;
+    use dendritic_regression::logistic::{Logistic, MultiClassLogistic};
+    use dendritic_regression::utils::one_hot;
+    use dendritic_autodiff::optim::SGD;
+    use dendritic_autodiff::init::{Init, Initializer};
+
+    /// Generic evaluation only relying on the shared traits
+    fn fit_score<M>(model: &mut M, x: &NDArray<f64>, y: &NDArray<f64>) -> f64
+    where
+        M: Fit<NDArray<f64>, NDArray<f64>> + Score<NDArray<f64>, NDArray<f64>>,
+    {
+        model.fit(x, y).unwrap();
+        model.score(x, y).unwrap()
+    }
+
+    /// Rows of y = 2 * x1 - x2 + 1
+    fn linear_data() -> (NDArray<f64>, NDArray<f64>) {
+        let x_vals = vec![
+            0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0,
+            0.5, 0.2, 0.2, 0.8, 0.9, 0.4, 0.3, 0.3
+        ];
+        let y_vals: Vec<f64> = x_vals.chunks(2).map(|r| 2.0 * r[0] - r[1] + 1.0).collect();
+        (
+            NDArray::array(vec![8, 2], x_vals).unwrap(),
+            NDArray::array(vec![8, 1], y_vals).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_linear_estimators() {
+
+        let (x, y) = linear_data();
+
+        let mut linear = Linear::new(&x, &y, SGD::new(0.5)).unwrap();
+        linear.set_epochs(2000);
+        assert!(fit_score(&mut linear, &x, &y) > 0.99);
+
+        let mut ridge = Ridge::new(&x, &y, 0.0001, SGD::new(0.5)).unwrap();
+        ridge.set_epochs(2000);
+        assert!(fit_score(&mut ridge, &x, &y) > 0.95);
+
+        let mut lasso = Lasso::new(&x, &y, 0.0001, SGD::new(0.5)).unwrap();
+        lasso.set_epochs(2000);
+        assert!(fit_score(&mut lasso, &x, &y) > 0.95);
+
+        let mut elastic = ElasticNet::new(&x, &y, 0.0001, SGD::new(0.5)).unwrap();
+        elastic.set_epochs(2000);
+        assert!(fit_score(&mut elastic, &x, &y) > 0.95);
+
+        /* trait prediction leaves the training features in place */
+        let x_new = NDArray::array(vec![1, 2], vec![2.0, 1.0]).unwrap();
+        let y_pred = Predict::predict(&linear, &x_new).unwrap();
+        assert!((y_pred.values()[0] - 4.0).abs() < 0.1);
+        assert_eq!(linear.features.val().shape().values(), vec![8, 2]);
+    }
+
+    #[test]
+    fn test_penalized_initializer() {
+
+        let (x, y) = linear_data();
+        let mut initializer = Initializer::new(Init::Constant(0.5), 0);
+
+        let mut ridge = Ridge::with_initializer(&x, &y, 0.0001, SGD::new(0.5), &mut initializer).unwrap();
+        let mut lasso = Lasso::with_initializer(&x, &y, 0.0001, SGD::new(0.5), &mut initializer).unwrap();
+        let mut elastic = ElasticNet::with_initializer(&x, &y, 0.0001, SGD::new(0.5), &mut initializer).unwrap();
+        for weights in [&ridge.weights, &lasso.weights, &elastic.weights] {
+            assert!(weights.val().values().iter().all(|w| *w == 0.5));
+        }
+        assert_eq!(ridge.bias.val().values(), &vec![0.0]);
+
+        ridge.set_epochs(2000);
+        lasso.set_epochs(2000);
+        elastic.set_epochs(2000);
+        assert!(fit_score(&mut ridge, &x, &y) > 0.95);
+        assert!(fit_score(&mut lasso, &x, &y) > 0.95);
+        assert!(fit_score(&mut elastic, &x, &y) > 0.95);
+
+        let mut invalid = Initializer::new(Init::Uniform(1.0, 0.0), 0);
+        assert!(Ridge::with_initializer(&x, &y, 0.1, SGD::new(0.1), &mut invalid).is_err());
+    }
+
+    #[test]
+    fn test_linear_estimator_errors() {
+
+        let (x, y) = linear_data();
+        let mut linear = Linear::new(&x, &y, SGD::new(0.1)).unwrap();
+
+        let wide = NDArray::array(vec![1, 3], vec![1.0, 2.0, 3.0]).unwrap();
+        let err = Predict::predict(&linear, &wide).unwrap_err();
+        assert_eq!(err, "Linear: Expected 2 features, got 3");
+
+        let short = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let err = linear.fit(&x, &short).unwrap_err();
+        assert_eq!(err, "Linear: Target shape [2, 1] does not match 8 feature rows");
+    }
+
+    #[test]
+    fn test_logistic_estimator() {
+
+        let x = NDArray::array(vec![6, 1], vec![-3.0, -2.0, -1.0, 1.0, 2.0, 3.0]).unwrap();
+        let y = NDArray::array(vec![6, 1], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+
+        let mut model = Logistic::new(&x, &y, SGD::new(0.5)).unwrap();
+        model.set_epochs(200);
+        assert_eq!(fit_score(&mut model, &x, &y), 1.0);
+
+        let proba = model.predict_proba(&x).unwrap();
+        assert_eq!(proba.shape().values(), vec![6, 2]);
+        for row in proba.values().chunks(2) {
+            assert!((row[0] + row[1] - 1.0).abs() < 1e-12);
+        }
+        assert!(proba.values()[1] < 0.5 && proba.values()[11] > 0.5);
+    }
+
+    #[test]
+    fn test_multi_class_estimator() {
+
+        let x = NDArray::array(vec![9, 2], vec![
+            -3.0, -3.0, -2.8, -3.1, -3.1, -2.7,
+            3.0, 0.0, 3.2, 0.2, 2.9, 0.1,
+            0.0, 3.0, 0.1, 3.1, 0.3, 2.8
+        ]).unwrap();
+        let labels = NDArray::array(vec![9, 1], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0]).unwrap();
+        let y = one_hot("Test", &labels, 3).unwrap();
+
+        /* targets may be one hot encoded or class indices */
+        let mut model = MultiClassLogistic::new(&x, &y, SGD::new(0.1)).unwrap();
+        model.set_epochs(500);
+        assert_eq!(fit_score(&mut model, &x, &labels), 1.0);
+        assert_eq!(model.score(&x, &y).unwrap(), 1.0);
+        assert_eq!(Predict::predict(&model, &x).unwrap(), labels);
+
+        let proba = model.predict_proba(&x).unwrap();
+        assert_eq!(proba.shape().values(), vec![9, 3]);
+
+        let bad = NDArray::array(vec![9, 1], vec![3.0; 9]).unwrap();
+        assert!(model.fit(&x, &bad).is_err());
+    }
+
+}
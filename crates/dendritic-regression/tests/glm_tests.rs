@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod glm_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_ndarray::ops::*;
+    use dendritic_autodiff::optim::Adam;
+    use dendritic_regression::glm::*;
+    use dendritic_regression::solver::*;
+
+    fn features() -> NDArray<f64> {
+        let vals: Vec<f64> = (0..30)
+            .flat_map(|row| {
+                let t = row as f64;
+                vec![(t * 0.45).sin(), (t * 0.8).cos() * 0.5]
+            })
+            .collect();
+        NDArray::array(vec![30, 2], vals).unwrap()
+    }
+
+    /// Targets equal to the mean `exp(0.8 * x1 - 0.5 * x2 + 1)` of a log link model
+    fn log_targets(x: &NDArray<f64>) -> NDArray<f64> {
+        let vals: Vec<f64> = x.values()
+            .chunks(2)
+            .map(|row| (0.8 * row[0] - 0.5 * row[1] + 1.0).exp())
+            .collect();
+        NDArray::array(vec![30, 1], vals).unwrap()
+    }
+
+    #[test]
+    fn test_family_and_link() {
+
+        assert_eq!(Family::Poisson.default_link(), Link::Log);
+        assert_eq!(Family::Binomial.default_link(), Link::Logit);
+        assert_eq!(Family::Gaussian.default_link(), Link::Identity);
+
+        assert_eq!(Family::Gaussian.unit_deviance(3.0, 1.0), 4.0);
+        assert_eq!(Family::Poisson.unit_deviance(0.0, 2.0), 4.0);
+        assert_eq!(Family::Gamma.unit_deviance(2.0, 2.0), 0.0);
+        assert!((Family::Binomial.unit_deviance(1.0, 0.5) - 2.0 * 2.0_f64.ln()).abs() < 1e-12);
+
+        /* tweedie power 1 matches poisson and the general deviance is continuous in the power */
+        assert_eq!(Family::Tweedie(1.0).unit_deviance(3.0, 2.0), Family::Poisson.unit_deviance(3.0, 2.0));
+        let near = Family::Tweedie(1.5 - 1e-9).unit_deviance(3.0, 2.0);
+        assert!((near - Family::Tweedie(1.5).unit_deviance(3.0, 2.0)).abs() < 1e-6);
+        assert_eq!(Family::Tweedie(1.5).variance(4.0), 8.0);
+
+        for link in [Link::Identity, Link::Log, Link::Logit] {
+            let mu = 0.3;
+            assert!((link.inverse(link.link(mu)) - mu).abs() < 1e-12);
+            let h = 1e-6;
+            let numeric = (link.link(mu + h) - link.link(mu - h)) / (2.0 * h);
+            assert!((link.derivative(mu) - numeric).abs() < 1e-6);
+        }
+
+        let err = Family::Poisson.check_target(&[1.0, -1.0]).unwrap_err();
+        assert_eq!(err, "GLM: Targets must be non negative for the Poisson family");
+        assert!(Family::Tweedie(0.5).check_target(&[1.0]).is_err());
+        assert!(GLM::new(Family::Poisson, Link::Logit).is_err());
+    }
+
+    #[test]
+    fn test_gaussian_matches_least_squares() {
+
+        let x = features();
+        let y_vals: Vec<f64> = x.values().chunks(2).map(|row| 2.0 * row[0] - row[1] + 0.5).collect();
+        let y = NDArray::array(vec![30, 1], y_vals).unwrap();
+
+        let mut model = GLM::with_family(Family::Gaussian).unwrap();
+        model.fit(&x, &y).unwrap();
+        let (weights, bias) = solve_linear(Solver::Cholesky, &x, &y, 0.0).unwrap();
+        for (a, b) in model.weights.val().values().iter().zip(weights.values().iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!((model.bias.val().values()[0] - bias.values()[0]).abs() < 1e-9);
+        assert!(model.n_iter() <= 3);
+        assert!((model.score(&x, &y).unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_log_link_families() {
+
+        let x = features();
+        let y = log_targets(&x);
+
+        for family in [Family::Poisson, Family::Gamma, Family::Tweedie(1.5)] {
+            let mut model = GLM::with_family(family).unwrap();
+            model.fit(&x, &y).unwrap();
+            let w = model.weights.val();
+            assert!((w.values()[0] - 0.8).abs() < 1e-6, "{:?} {:?}", family, w.values());
+            assert!((w.values()[1] + 0.5).abs() < 1e-6);
+            assert!((model.bias.val().values()[0] - 1.0).abs() < 1e-6);
+            assert!(model.deviance(&x, &y).unwrap() < 1e-9);
+        }
+
+        /* the penalty shrinks weights towards zero */
+        let mut model = GLM::with_family(Family::Poisson).unwrap();
+        model.set_alpha(1.0).unwrap();
+        model.fit(&x, &y).unwrap();
+        assert!(model.weights.val().values()[0].abs() < 0.8);
+        assert!(model.set_alpha(-1.0).is_err());
+
+        let err = model.fit(&x, &y.scalar_mult(-1.0).unwrap()).unwrap_err();
+        assert_eq!(err, "GLM: Targets must be non negative for the Poisson family");
+    }
+
+    #[test]
+    fn test_gradient_descent() {
+
+        let x = features();
+        let y = log_targets(&x);
+
+        let mut irls = GLM::with_family(Family::Poisson).unwrap();
+        irls.fit(&x, &y).unwrap();
+
+        let mut model = GLM::with_family(Family::Poisson).unwrap();
+        model.set_solver(GLMSolver::GradientDescent);
+        model.set_optimizer(Adam::new(0.05));
+        model.set_max_iter(3000);
+        model.set_tol(1e-6);
+        model.fit(&x, &y).unwrap();
+
+        for (a, b) in model.weights.val().values().iter().zip(irls.weights.val().values().iter()) {
+            assert!((a - b).abs() < 1e-3);
+        }
+        assert!(model.score(&x, &y).unwrap() > 0.999);
+    }
+
+    #[test]
+    fn test_logistic_glm() {
+
+        let x = features();
+        let y_vals: Vec<f64> = x.values()
+            .chunks(2)
+            .enumerate()
+            .map(|(idx, row)| if row[0] + 0.3 * (idx as f64 * 1.7).sin() > 0.0 { 1.0 } else { 0.0 })
+            .collect();
+        let y = NDArray::array(vec![30, 1], y_vals).unwrap();
+
+        let mut model = GLM::logistic().unwrap();
+        model.set_alpha(0.01).unwrap();
+        model.fit(&x, &y).unwrap();
+
+        let proba = model.predict(&x).unwrap();
+        assert!(proba.values().iter().all(|p| *p > 0.0 && *p < 1.0));
+        let correct = proba.values().iter()
+            .zip(y.values().iter())
+            .filter(|(p, y)| (**p >= 0.5) == (**y == 1.0))
+            .count();
+        assert!(correct >= 27, "{}", correct);
+        assert!(model.score(&x, &y).unwrap() > 0.5);
+    }
+
+}
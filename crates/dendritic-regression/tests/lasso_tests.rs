@@ -6,6 +6,7 @@ mod lasso {
     use dendritic_ndarray::ops::*;
     use dendritic_metrics::loss::*;
     use dendritic_regression::lasso::*;
+    use dendritic_autodiff::optim::SGD;
 
     #[test]
     fn test_lasso_model_train() {
@@ -21,7 +22,7 @@ mod lasso {
 
         let mut model = Lasso::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.train(1000, false);
@@ -48,7 +49,7 @@ mod lasso {
 
         let mut model = Lasso::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.sgd(500, false, 2);
@@ -65,7 +66,8 @@ mod lasso {
     #[test]
     fn test_lasso_save_load() -> std::io::Result<()> {
 
-        let model_path = "data/models/lasso";
+        let model_dir = std::env::temp_dir().join("dendritic_lasso_model_test");
+        let model_path = model_dir.to_str().unwrap();
         let x_path = "data/linear_modeling_data/inputs";
         let y_path = "data/linear_modeling_data/outputs"; 
 
@@ -79,7 +81,7 @@ mod lasso {
 
         let mut model = Lasso::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.sgd(500, false, 2);
@@ -91,7 +93,7 @@ mod lasso {
         let mut loaded_model = Lasso::load(
             model_path,
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         let results = loaded_model.predict(x_train[1].clone());
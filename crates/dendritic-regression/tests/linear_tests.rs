@@ -6,6 +6,12 @@ mod linear {
     use dendritic_ndarray::ops::*;
     use dendritic_metrics::loss::*;
     use dendritic_regression::linear::*;
+    use dendritic_autodiff::optim::*;
+    use dendritic_autodiff::scheduler::*;
+    use dendritic_autodiff::clip::*;
+    use dendritic_autodiff::anomaly::*;
+    use dendritic_autodiff::init::*;
+    use dendritic_autodiff::state::*;
 
     #[test]
     fn test_linear_model() {
@@ -16,7 +22,7 @@ mod linear {
         let x: NDArray<f64> = NDArray::load(x_path).unwrap();
         let y: NDArray<f64> = NDArray::load(y_path).unwrap();
         let model = Linear::new(
-            &x, &y, 0.01
+            &x, &y, SGD::new(0.01)
         ).unwrap();
 
         assert_eq!(model.features.val().shape().values(), x.shape().values()); 
@@ -58,7 +64,7 @@ mod linear {
 
         let x: NDArray<f64> = NDArray::load(x_path).unwrap();
         let y: NDArray<f64> = NDArray::load(y_path).unwrap();
-        let mut model = Linear::new(&x, &y, 0.01).unwrap();
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
 
         let weights_binding = model.weights.val(); 
         let bias_binding = model.bias.val();
@@ -81,6 +87,61 @@ mod linear {
         assert_eq!(loss_condition, true); 
     }
 
+    #[test]
+    fn test_linear_train_optimizer() {
+
+        let x_path = "data/linear_modeling_data/inputs";
+        let y_path = "data/linear_modeling_data/outputs"; 
+
+        let x: NDArray<f64> = NDArray::load(x_path).unwrap();
+        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_optimizer(Adam::new(0.1));
+        model.train(1000, false);
+
+        let results = model.predict(x.clone());
+        let loss = mse(&results, &y).unwrap(); 
+        assert!(loss < 0.1, "loss {}", loss);
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_optimizer(SGD::with_momentum(0.01, 0.9, true));
+        model.train(500, false);
+
+        let results = model.predict(x);
+        let loss = mse(&results, &y).unwrap(); 
+        assert!(loss < 0.1, "loss {}", loss);
+    }
+
+    #[test]
+    fn test_linear_train_scheduler() {
+
+        let x_path = "data/linear_modeling_data/inputs";
+        let y_path = "data/linear_modeling_data/outputs"; 
+
+        let x: NDArray<f64> = NDArray::load(x_path).unwrap();
+        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_scheduler(LinearWarmup::with_scheduler(
+            50, CosineAnnealing::with_restarts(0.02, 0.005, 200, 2)
+        ));
+        model.train(1000, false);
+
+        let results = model.predict(x.clone());
+        let loss = mse(&results, &y).unwrap(); 
+        assert!(loss < 0.1, "loss {}", loss);
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_optimizer(Adam::new(0.1));
+        model.set_scheduler(ReduceLROnPlateau::new(0.1, 0.5, 50));
+        model.train(1000, false);
+
+        let results = model.predict(x);
+        let loss = mse(&results, &y).unwrap(); 
+        assert!(loss < 0.1, "loss {}", loss);
+    }
+
     #[test]
     fn test_linear_sgd() {
 
@@ -91,7 +152,7 @@ mod linear {
         let x: NDArray<f64> = NDArray::load(x_path).unwrap();
         let y: NDArray<f64> = NDArray::load(y_path).unwrap();
         let mut model = Linear::new(
-            &x, &y, 0.01
+            &x, &y, SGD::new(0.01)
         ).unwrap();
 
         let weights_binding = model.weights.val(); 
@@ -122,7 +183,8 @@ mod linear {
     #[test]
     fn test_linear_save_load() -> std::io::Result<()> {
 
-        let model_path = "data/models/linear";
+        let model_dir = std::env::temp_dir().join("dendritic_linear_model_test");
+        let model_path = model_dir.to_str().unwrap();
         let x_path = "data/linear_modeling_data/inputs";
         let y_path = "data/linear_modeling_data/outputs"; 
 
@@ -131,7 +193,7 @@ mod linear {
         let y: NDArray<f64> = NDArray::load(y_path).unwrap();
 
         let mut model = Linear::new(
-            &x, &y, 0.01
+            &x, &y, SGD::new(0.01)
         ).unwrap();
 
         model.sgd(500, false, 2);
@@ -142,7 +204,7 @@ mod linear {
         let y_train = y.batch(batch_size).unwrap();
 
         let mut loaded_model = Linear::load(
-            model_path, &x, &y, 0.01
+            model_path, &x, &y, SGD::new(0.01)
         ).unwrap();
 
         let results = loaded_model.predict(x_train[1].clone());
@@ -152,7 +214,112 @@ mod linear {
 
         Ok(())
 
-    } 
+    }
+
+    #[test]
+    fn test_linear_load_legacy() {
+
+        /* fixture saved one array file per parameter before state dicts */
+        let model_path = "data/models/linear";
+        let x: NDArray<f64> = NDArray::load("data/linear_modeling_data/inputs").unwrap();
+        let y: NDArray<f64> = NDArray::load("data/linear_modeling_data/outputs").unwrap();
 
+        let weights: NDArray<f64> = NDArray::load("data/models/linear/weights").unwrap();
+        let bias: NDArray<f64> = NDArray::load("data/models/linear/bias").unwrap();
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.load_state_dict_from(model_path).unwrap();
+        assert_eq!(model.weights.val().values(), weights.values());
+        assert_eq!(model.bias.val().values(), bias.values());
+
+        let loaded = Linear::load(model_path, &x, &y, SGD::new(0.01)).unwrap();
+        assert_eq!(loaded.weights.val().values(), weights.values());
+        assert_eq!(loaded.bias.val().values(), bias.values());
+    }
+
+    fn unscaled_data() -> (NDArray<f64>, NDArray<f64>) {
+        let x: Vec<f64> = (0..10).map(|i| 100.0 + 25.0 * i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|v| 3.0 * v + 2.0).collect();
+        (
+            NDArray::array(vec![10, 1], x).unwrap(),
+            NDArray::array(vec![10, 1], y).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_linear_train_clipping() {
+
+        /* unclipped updates on unscaled inputs blow up to non-finite weights */
+        let (x, y) = unscaled_data();
+        let mut model = Linear::new(&x, &y, SGD::new(0.1)).unwrap();
+        model.train(200, false);
+        assert!(!model.weights.val().values()[0].is_finite());
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.1)).unwrap();
+        model.set_optimizer(Clipped::by_norm(SGD::new(0.02), 1.0));
+        model.train(500, false);
+        let weight = model.weights.val().values()[0];
+        assert!(weight.is_finite());
+        assert!((weight - 3.0).abs() < 0.1, "weight {}", weight);
+    }
+
+    #[test]
+    #[should_panic(expected = "Anomaly: Non-finite")]
+    fn test_linear_train_anomaly() {
+
+        let (x, y) = unscaled_data();
+        let mut model = Linear::new(&x, &y, SGD::new(0.1)).unwrap();
+        set_anomaly_detection(true);
+        model.train(200, false);
+    }
+
+    #[test]
+    fn test_linear_initializer() {
+
+        let x_path = "data/linear_modeling_data/inputs";
+        let y_path = "data/linear_modeling_data/outputs"; 
+
+        let x: NDArray<f64> = NDArray::load(x_path).unwrap();
+        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
+
+        let mut initializer = Initializer::new(Init::Constant(0.5), 0);
+        let mut model = Linear::with_initializer(&x, &y, SGD::new(0.01), &mut initializer).unwrap();
+        assert!(model.weights.val().values().iter().all(|w| *w == 0.5));
+        assert_eq!(model.bias.val().values(), &vec![0.0]);
+
+        model.train(1000, false);
+        let results = model.predict(x);
+        let loss = mse(&results, &y).unwrap(); 
+        assert!(loss < 0.1, "loss {}", loss);
+
+        let mut invalid = Initializer::new(Init::Uniform(1.0, 0.0), 0);
+        assert!(Linear::with_initializer(&y, &y, SGD::new(0.01), &mut invalid).is_err());
+    }
+
+    #[test]
+    fn test_linear_state_dict() {
+
+        let x_path = "data/linear_modeling_data/inputs";
+        let y_path = "data/linear_modeling_data/outputs"; 
+
+        let x: NDArray<f64> = NDArray::load(x_path).unwrap();
+        let y: NDArray<f64> = NDArray::load(y_path).unwrap();
+
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.train(200, false);
+
+        let state = model.state_dict();
+        assert_eq!(state.names(), vec!["weights".to_string(), "bias".to_string()]);
+
+        let mut loaded = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        loaded.load_state_dict(&state).unwrap();
+        assert_eq!(loaded.predict(x.clone()), model.predict(x.clone()));
+
+        /* weights trained on three features do not fit a model with one */
+        let narrow = NDArray::array(vec![x.shape().dim(0), 1], vec![1.0; x.shape().dim(0)]).unwrap();
+        let mut other = Linear::new(&narrow, &y, SGD::new(0.01)).unwrap();
+        let err = other.load_state_dict(&state).unwrap_err();
+        assert_eq!(err, "StateDict: Shape mismatch for weights, expected [1, 1] got [3, 1]");
+    }
 
 }
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod logistic_solver_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_regression::logistic::*;
+    use dendritic_regression::glm::GLM;
+    use dendritic_autodiff::optim::SGD;
+
+    /// Overlapping classes, label 1 is more likely as `x1 - 0.05 * x2` grows
+    fn binary_data() -> (NDArray<f64>, NDArray<f64>) {
+        let x_vals: Vec<f64> = (0..60)
+            .flat_map(|row| {
+                let t = row as f64;
+                vec![(t * 0.61).sin() * 3.0, (t * 1.7).cos() * 40.0]
+            })
+            .collect();
+        let y_vals: Vec<f64> = x_vals
+            .chunks(2)
+            .enumerate()
+            .map(|(idx, row)| {
+                let noise = 1.5 * (idx as f64 * 2.9).sin();
+                if row[0] - 0.05 * row[1] + noise > 0.0 { 1.0 } else { 0.0 }
+            })
+            .collect();
+        (
+            NDArray::array(vec![60, 2], x_vals).unwrap(),
+            NDArray::array(vec![60, 1], y_vals).unwrap()
+        )
+    }
+
+    /// Three overlapping classes around directions from the origin, labelled by class index
+    fn multi_data() -> (NDArray<f64>, NDArray<f64>) {
+        let x_vals: Vec<f64> = (0..60)
+            .flat_map(|row| {
+                let angle = (row % 3) as f64 * 2.0 * std::f64::consts::PI / 3.0;
+                let t = row as f64;
+                vec![2.0 * angle.cos() + (t * 1.3).sin() * 1.2, 2.0 * angle.sin() + (t * 0.7).cos() * 1.2]
+            })
+            .collect();
+        let y_vals: Vec<f64> = (0..60).map(|row| (row % 3) as f64).collect();
+        (
+            NDArray::array(vec![60, 2], x_vals).unwrap(),
+            NDArray::array(vec![60, 1], y_vals).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_logistic_newton_and_lbfgs() {
+
+        let (x, y) = binary_data();
+
+        let mut newton = Logistic::new(&x, &y, SGD::new(0.01)).unwrap();
+        newton.set_solver(LogisticSolver::Newton);
+        newton.set_tol(1e-10);
+        newton.fit(&x, &y).unwrap();
+        assert!(newton.n_iter() < 20, "{}", newton.n_iter());
+        assert!(newton.grad_norm() <= 1e-10);
+
+        let mut lbfgs = Logistic::new(&x, &y, SGD::new(0.01)).unwrap();
+        lbfgs.set_solver(LogisticSolver::LBFGS);
+        lbfgs.set_tol(1e-8);
+        lbfgs.set_max_iter(500);
+        lbfgs.fit(&x, &y).unwrap();
+        assert!(lbfgs.grad_norm() <= 1e-8);
+        assert!(lbfgs.n_iter() < 500);
+
+        for (a, b) in newton.weights.val().values().iter().zip(lbfgs.weights.val().values().iter()) {
+            assert!((a - b).abs() < 1e-4, "{} {}", a, b);
+        }
+        assert!((newton.bias.val().values()[0] - lbfgs.bias.val().values()[0]).abs() < 1e-4);
+        assert!(newton.score(&x, &y).unwrap() > 0.8);
+
+        /* a short gradient descent run is far from converged on unscaled features */
+        let mut sgd = Logistic::new(&x, &y, SGD::new(0.001)).unwrap();
+        sgd.set_epochs(50);
+        sgd.fit(&x, &y).unwrap();
+        assert_eq!(sgd.n_iter(), 50);
+        assert!(sgd.grad_norm() > newton.grad_norm());
+
+        let err = sgd.solve().unwrap_err();
+        assert_eq!(err, "Logistic: SGD has no solve, use train or sgd");
+    }
+
+    #[test]
+    fn test_logistic_max_iter() {
+
+        let (x, y) = binary_data();
+        let mut model = Logistic::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_solver(LogisticSolver::LBFGS);
+        model.set_max_iter(2);
+        model.set_tol(0.0);
+        model.solve().unwrap();
+        assert_eq!(model.n_iter(), 2);
+        assert!(model.grad_norm() > 0.0);
+    }
+
+    #[test]
+    fn test_logistic_matches_binomial_glm() {
+
+        let (x, y) = binary_data();
+        let mut model = Logistic::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_solver(LogisticSolver::Newton);
+        model.set_tol(1e-10);
+        model.fit(&x, &y).unwrap();
+
+        let mut glm = GLM::logistic().unwrap();
+        glm.set_tol(1e-14);
+        glm.fit(&x, &y).unwrap();
+
+        for (a, b) in model.weights.val().values().iter().zip(glm.weights.val().values().iter()) {
+            assert!((a - b).abs() < 1e-6, "{} {}", a, b);
+        }
+        assert!((model.bias.val().values()[0] - glm.bias.val().values()[0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_multi_class_solvers() {
+
+        let (x, y) = multi_data();
+        let y_encoded = {
+            let mut vals = vec![0.0; 180];
+            for (row, label) in y.values().iter().enumerate() {
+                vals[row * 3 + *label as usize] = 1.0;
+            }
+            NDArray::array(vec![60, 3], vals).unwrap()
+        };
+
+        let mut newton = MultiClassLogistic::new(&x, &y_encoded, SGD::new(0.1)).unwrap();
+        newton.set_solver(LogisticSolver::Newton);
+        newton.set_tol(1e-8);
+        newton.fit(&x, &y).unwrap();
+        assert!(newton.grad_norm() <= 1e-8);
+        assert!(newton.n_iter() < 30, "{}", newton.n_iter());
+        assert!(newton.score(&x, &y).unwrap() > 0.7);
+
+        let mut lbfgs = MultiClassLogistic::new(&x, &y_encoded, SGD::new(0.1)).unwrap();
+        lbfgs.set_solver(LogisticSolver::LBFGS);
+        lbfgs.set_tol(1e-8);
+        lbfgs.set_max_iter(1000);
+        lbfgs.fit(&x, &y_encoded).unwrap();
+        assert!(lbfgs.grad_norm() <= 1e-8);
+
+        /* softmax weights are only identified up to a shift per feature, compare probabilities */
+        let p_newton = newton.predict_proba(&x).unwrap();
+        let p_lbfgs = lbfgs.predict_proba(&x).unwrap();
+        for (a, b) in p_newton.values().iter().zip(p_lbfgs.values().iter()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_multi_class_bias_per_class() {
+
+        /* one feature that is zero everywhere, so classes are only told apart by their bias */
+        let x = NDArray::new(vec![6, 1]).unwrap();
+        let y = NDArray::array(vec![6, 1], vec![0.0, 1.0, 1.0, 2.0, 2.0, 2.0]).unwrap();
+
+        for solver in [LogisticSolver::Newton, LogisticSolver::LBFGS] {
+            let y_encoded = NDArray::array(vec![6, 3], vec![
+                1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+                0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0
+            ]).unwrap();
+            let mut model = MultiClassLogistic::new(&x, &y_encoded, SGD::new(0.1)).unwrap();
+            model.set_solver(solver);
+            model.set_tol(1e-10);
+            model.set_max_iter(500);
+            model.fit(&x, &y).unwrap();
+            assert_eq!(model.bias.val().shape().values(), vec![1, 3]);
+
+            /* class frequencies are 1/6, 2/6 and 3/6 */
+            let proba = model.predict_proba(&x).unwrap();
+            for (p, expected) in proba.values()[..3].iter().zip([1.0 / 6.0, 2.0 / 6.0, 0.5]) {
+                assert!((p - expected).abs() < 1e-6, "{:?} {}", solver, p);
+            }
+        }
+    }
+
+}
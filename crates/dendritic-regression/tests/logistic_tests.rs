@@ -7,7 +7,8 @@ mod logistic_tests {
     use dendritic_ndarray::ndarray::NDArray;
     use dendritic_ndarray::ops::*;
     use dendritic_metrics::loss::*;
-    use dendritic_metrics::activations::*;
+    use dendritic_autodiff::init::*;
+    use dendritic_autodiff::optim::SGD;
 
     #[test]
     fn test_logistic_model() {
@@ -21,8 +22,7 @@ mod logistic_tests {
         let model = Logistic::new(
             &x, 
             &y, 
-            sigmoid_vec,
-            0.01
+            SGD::new(0.01)
         ).unwrap();
 
         assert_eq!(model.features.val().shape().values(), x.shape().values()); 
@@ -69,8 +69,7 @@ mod logistic_tests {
         let mut model = Logistic::new(
             &x, 
             &y, 
-            sigmoid_vec,
-            0.01
+            SGD::new(0.01)
         ).unwrap();
 
         let weights_binding = model.weights.val(); 
@@ -109,8 +108,7 @@ mod logistic_tests {
         let mut model = Logistic::new(
             &x, 
             &y, 
-            sigmoid_vec,
-            0.001
+            SGD::new(0.001)
         ).unwrap();
 
         let weights_binding = model.weights.val(); 
@@ -141,7 +139,8 @@ mod logistic_tests {
     #[test]
     fn test_logistic_save_load() -> std::io::Result<()> {
 
-        let model_path = "data/models/logistic";
+        let model_dir = std::env::temp_dir().join("dendritic_logistic_model_test");
+        let model_path = model_dir.to_str().unwrap();
         let x_path = "data/logistic_modeling_data/inputs";
         let y_path = "data/logistic_modeling_data/outputs"; 
 
@@ -152,8 +151,7 @@ mod logistic_tests {
         let mut model = Logistic::new(
             &x,
             &y, 
-            sigmoid_vec,
-            0.001
+            SGD::new(0.001)
         ).unwrap();
 
         model.sgd(1000, false, 2);
@@ -166,8 +164,7 @@ mod logistic_tests {
             model_path, 
             &x, 
             &y, 
-            sigmoid_vec,
-            0.01
+            SGD::new(0.01)
         ).unwrap();
 
         let results = loaded_model.predict(x_train[1].clone());
@@ -199,8 +196,7 @@ mod logistic_tests {
         let mut model = MultiClassLogistic::new(
             &x_train,
             &y_train_encoded,
-            softmax,
-            0.1
+            SGD::new(0.1)
         ).unwrap();
 
         model.train(1000, false);
@@ -235,8 +231,7 @@ mod logistic_tests {
         let mut model = MultiClassLogistic::new(
             &x_train,
             &y_train_encoded,
-            softmax,
-            0.1
+            SGD::new(0.1)
         ).unwrap();
 
         model.sgd(1000, false, batch_size);
@@ -250,4 +245,51 @@ mod logistic_tests {
         assert_eq!(results.values(), &expected_predictions); 
     }
 
+
+    #[test]
+    fn test_multi_class_logistic_initializer() {
+
+        let x_path = "data/logistic_modeling_data/multi_class_input";
+        let y_path = "data/logistic_modeling_data/multi_class_output_2";
+
+        let x_train = NDArray::load(x_path).unwrap();
+        let y_train = NDArray::load(y_path).unwrap();
+
+        let mut encoder = OneHotEncoding::new(y_train.clone()).unwrap();
+        let y_train_encoded = encoder.transform();
+
+        let mut initializer = Initializer::new(Init::XavierNormal, 42);
+        let mut model = MultiClassLogistic::with_initializer(
+            &x_train,
+            y_train_encoded,
+            SGD::new(0.1),
+            &mut initializer
+        ).unwrap();
+
+        /* weights start random and distinct, bias still starts at zero */
+        let weights = model.weights.val();
+        assert_eq!(weights.shape().values(), vec![3, 3]);
+        assert_eq!(weights, xavier_normal(vec![3, 3], 42).unwrap());
+        assert!(weights.values().iter().all(|w| *w != 0.0));
+        assert_eq!(model.bias.val().values(), &vec![0.0; 3]);
+
+        model.train(1000, false);
+        let results = model.predict(x_train);
+        let expected_predictions = vec![
+            0.0, 1.0, 2.0, 0.0, 1.0, 
+            2.0, 0.0, 1.0, 2.0, 0.0
+        ];
+        assert_eq!(results.values(), &expected_predictions); 
+
+        let mut orthogonal = Initializer::new(Init::Orthogonal(1.0), 1);
+        let model = Logistic::with_initializer(
+            &NDArray::new(vec![4, 3]).unwrap(),
+            &NDArray::new(vec![4, 1]).unwrap(),
+            SGD::new(0.1),
+            &mut orthogonal
+        ).unwrap();
+        let norm: f64 = model.weights.val().values().iter().map(|w| w * w).sum();
+        assert!((norm - 1.0).abs() < 1e-10);
+    }
+
 }
@@ -7,6 +7,7 @@ mod ridge_test {
     use dendritic_autodiff::node::*; 
     use dendritic_metrics::loss::*;
     use dendritic_regression::ridge::*;
+    use dendritic_autodiff::optim::SGD;
 
     #[test]
     fn test_ridge_model_train() {
@@ -22,7 +23,7 @@ mod ridge_test {
 
         let mut model = Ridge::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.train(1100, false);
@@ -50,7 +51,7 @@ mod ridge_test {
 
         let mut model = Ridge::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.sgd(1100, false, 2);
@@ -82,7 +83,7 @@ mod ridge_test {
 
             let mut model = Ridge::new(
                 &x, &y,
-                *lambda, learning_rate
+                *lambda, SGD::new(learning_rate)
             ).unwrap();
 
             model.train(1100, false);
@@ -105,7 +106,8 @@ mod ridge_test {
     #[test]
     fn test_save_load_ridge_model() {
 
-        let model_path = "data/models/ridge";
+        let model_dir = std::env::temp_dir().join("dendritic_ridge_model_test");
+        let model_path = model_dir.to_str().unwrap();
         let x_path = "data/linear_modeling_data/inputs"; 
         let y_path = "data/linear_modeling_data/outputs";
 
@@ -120,7 +122,7 @@ mod ridge_test {
 
         let mut model = Ridge::new(
             &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         model.sgd(1100, false, 2);
@@ -133,7 +135,7 @@ mod ridge_test {
 
         let mut loaded_model = Ridge::load(
             model_path, &x, &y,
-            lambda, learning_rate
+            lambda, SGD::new(learning_rate)
         ).unwrap();
 
         let loaded_output = loaded_model.predict(x);
@@ -142,4 +144,17 @@ mod ridge_test {
 
     }  
 
+    #[test]
+    fn test_ridge_learning_rate() {
+
+        let x = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let y = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+
+        let err = Ridge::new(&x, &y, 0.1, SGD::new(1.5)).err().unwrap();
+        assert_eq!(err, "Ridge: Learning rate must be between 0 and 1, got 1.5");
+
+        let err = Ridge::new(&x, &y, 0.1, SGD::new(-0.1)).err().unwrap();
+        assert_eq!(err, "Ridge: Learning rate must be between 0 and 1, got -0.1");
+    }
+
 }
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod robust_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_regression::robust::*;
+    use dendritic_regression::linear::*;
+    use dendritic_regression::solver::*;
+    use dendritic_autodiff::optim::SGD;
+
+    /// Line y = 2 * x + 1 with small noise and gross outliers on every fifth row
+    fn data() -> (NDArray<f64>, NDArray<f64>, Vec<bool>) {
+        let x_vals: Vec<f64> = (0..40).map(|row| row as f64 * 0.25).collect();
+        let outliers: Vec<bool> = (0..40).map(|row| row % 5 == 2).collect();
+        let y_vals: Vec<f64> = x_vals.iter()
+            .enumerate()
+            .map(|(row, x)| {
+                let noise = 0.05 * (row as f64 * 2.3).sin();
+                let outlier = if outliers[row] { 30.0 } else { 0.0 };
+                2.0 * x + 1.0 + noise + outlier
+            })
+            .collect();
+        (
+            NDArray::array(vec![40, 1], x_vals).unwrap(),
+            NDArray::array(vec![40, 1], y_vals).unwrap(),
+            outliers
+        )
+    }
+
+    #[test]
+    fn test_pinball_loss() {
+        let y = [1.0, 2.0, 3.0];
+        let pred = [2.0, 2.0, 2.0];
+        assert_eq!(pinball_loss(&y, &pred, 0.5), 1.0 / 3.0);
+        assert!((pinball_loss(&y, &pred, 0.9) - (0.1 + 0.9) / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_huber() {
+
+        let (x, y, outliers) = data();
+
+        let (_, ols_bias) = solve_linear(Solver::QR, &x, &y, 0.0).unwrap();
+        let mut model = HuberRegressor::new(1.35).unwrap();
+        model.fit(&x, &y).unwrap();
+
+        let slope = model.weights.val().values()[0];
+        assert!((slope - 2.0).abs() < 0.05, "{}", slope);
+
+        /* evenly spread outliers only shift the least squares intercept */
+        let bias = model.bias.val().values()[0];
+        assert!((bias - 1.0).abs() < 0.5, "{}", bias);
+        assert!((ols_bias.values()[0] - 1.0).abs() > 5.0);
+        assert!(model.scale > 0.0 && model.scale < 1.0, "{}", model.scale);
+
+        /* every gross outlier lies beyond epsilon scales, along with some noisy samples */
+        assert_eq!(model.outliers.len(), 40);
+        assert!(outliers.iter().zip(model.outliers.iter()).all(|(truth, flagged)| !truth || *flagged));
+
+        /* scale and outliers come from the residuals of the final coefficients */
+        let pred = model.predict(&x).unwrap();
+        let mut abs: Vec<f64> = y.values().iter().zip(pred.values().iter()).map(|(y, p)| (y - p).abs()).collect();
+        let flagged: Vec<bool> = abs.iter().map(|r| *r > 1.35 * model.scale).collect();
+        abs.sort_by(|a, b| a.total_cmp(b));
+        let mad = (abs[19] + abs[20]) / 2.0 / 0.6745;
+        assert!((model.scale - mad).abs() < 1e-12, "{} {}", model.scale, mad);
+        assert_eq!(flagged, model.outliers);
+
+        assert!(HuberRegressor::new(0.5).is_err());
+        let short = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let err = model.fit(&x, &short).unwrap_err();
+        assert_eq!(err, "HuberRegressor: Target shape [2, 1] does not match 40 feature rows");
+    }
+
+    #[test]
+    fn test_quantile() {
+
+        let (x, y, _) = data();
+
+        let mut median = QuantileRegressor::new(0.5).unwrap();
+        median.fit(&x, &y).unwrap();
+        assert!((median.weights.val().values()[0] - 2.0).abs() < 0.05);
+        assert!((median.bias.val().values()[0] - 1.0).abs() < 0.2);
+
+        /* a higher quantile sits above more of the targets */
+        let mut upper = QuantileRegressor::new(0.9).unwrap();
+        upper.fit(&x, &y).unwrap();
+        let below = |model: &QuantileRegressor| {
+            let pred = model.predict(&x).unwrap();
+            y.values().iter().zip(pred.values().iter()).filter(|(y, p)| *y <= *p).count()
+        };
+        assert!(below(&upper) > below(&median));
+        assert!(below(&upper) >= 34, "{}", below(&upper));
+
+        let loss = |model: &QuantileRegressor, q: f64| {
+            pinball_loss(y.values(), model.predict(&x).unwrap().values(), q)
+        };
+        assert!(loss(&upper, 0.9) < loss(&median, 0.9));
+        assert!(loss(&median, 0.5) < loss(&upper, 0.5));
+
+        assert!(QuantileRegressor::new(1.0).is_err());
+    }
+
+    #[test]
+    fn test_ransac() {
+
+        let (x, y, outliers) = data();
+
+        let mut linear = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        linear.set_solver(Solver::QR);
+
+        let mut model = RANSAC::new(linear, 2).unwrap();
+        model.set_residual_threshold(1.0).unwrap();
+        model.set_seed(3);
+        model.fit(&x, &y).unwrap();
+
+        let expected: Vec<bool> = outliers.iter().map(|outlier| !outlier).collect();
+        assert_eq!(model.inlier_mask, expected);
+        assert_eq!(model.n_inliers(), 32);
+
+        let weights = model.estimator.weights.val();
+        assert!((weights.values()[0] - 2.0).abs() < 0.01);
+
+        let x_test = NDArray::array(vec![1, 1], vec![20.0]).unwrap();
+        let y_pred = model.predict(&x_test).unwrap();
+        assert!((y_pred.values()[0] - 41.0).abs() < 0.2);
+
+        /* default threshold from the target spread also rejects the outliers */
+        let mut linear = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        linear.set_solver(Solver::Cholesky);
+        let mut model = RANSAC::new(linear, 2).unwrap();
+        model.fit(&x, &y).unwrap();
+        assert!(model.inlier_mask.iter().zip(outliers.iter()).all(|(inlier, outlier)| !(*inlier && *outlier)));
+
+        let mut model = RANSAC::new(HuberRegressor::new(1.35).unwrap(), 50).unwrap();
+        let err = model.fit(&x, &y).unwrap_err();
+        assert_eq!(err, "RANSAC: Minimum samples 50 exceeds 40 rows");
+    }
+
+}
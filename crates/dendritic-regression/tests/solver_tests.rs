@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod solver_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_regression::solver::*;
+    use dendritic_regression::linear::*;
+    use dendritic_regression::ridge::*;
+    use dendritic_autodiff::optim::SGD;
+
+    /// Unscaled features with y = 0.5 * x1 - 0.003 * x2 + 4
+    fn data() -> (NDArray<f64>, NDArray<f64>) {
+        let x = NDArray::array(vec![6, 2], vec![
+            1.0, 1200.0,
+            2.0, 800.0,
+            3.0, 2500.0,
+            4.0, 300.0,
+            5.0, 1900.0,
+            6.0, 700.0
+        ]).unwrap();
+        let y_vals: Vec<f64> = x.values()
+            .chunks(2)
+            .map(|row| 0.5 * row[0] - 0.003 * row[1] + 4.0)
+            .collect();
+        let y = NDArray::array(vec![6, 1], y_vals).unwrap();
+        (x, y)
+    }
+
+    fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < tol, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_cholesky() {
+
+        let a = NDArray::array(vec![2, 2], vec![4.0, 2.0, 2.0, 3.0]).unwrap();
+        let lower = cholesky(&a).unwrap();
+        assert_close(lower.values(), &[2.0, 0.0, 1.0, 2.0_f64.sqrt()], 1e-12);
+
+        let b = NDArray::array(vec![2, 1], vec![6.0, 5.0]).unwrap();
+        let x = cholesky_solve(&a, &b).unwrap();
+        assert_close(x.values(), &[1.0, 1.0], 1e-12);
+
+        let singular = NDArray::array(vec![2, 2], vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        let err = cholesky(&singular).unwrap_err();
+        assert_eq!(err, "Cholesky: Matrix is not positive definite, features may be collinear");
+    }
+
+    #[test]
+    fn test_qr() {
+
+        let a = NDArray::array(vec![3, 2], vec![1.0, 1.0, 1.0, 2.0, 1.0, 3.0]).unwrap();
+        let (q, r) = qr(&a).unwrap();
+        assert_eq!(q.shape().values(), vec![3, 2]);
+        assert_eq!(r.values()[2], 0.0);
+
+        /* q * r reconstructs a */
+        let q_vals = q.values();
+        let r_vals = r.values();
+        for row in 0..3 {
+            for col in 0..2 {
+                let val: f64 = (0..2).map(|k| q_vals[row * 2 + k] * r_vals[k * 2 + col]).sum();
+                assert!((val - a.values()[row * 2 + col]).abs() < 1e-12);
+            }
+        }
+
+        /* least squares line through (1, 1), (2, 2), (3, 2) */
+        let b = NDArray::array(vec![3, 1], vec![1.0, 2.0, 2.0]).unwrap();
+        let x = lstsq_qr(&a, &b).unwrap();
+        assert_close(x.values(), &[2.0 / 3.0, 0.5], 1e-12);
+
+        let wide = NDArray::array(vec![1, 2], vec![1.0, 2.0]).unwrap();
+        assert!(qr(&wide).is_err());
+    }
+
+    #[test]
+    fn test_solve_linear() {
+
+        let (x, y) = data();
+        for solver in [Solver::Cholesky, Solver::QR] {
+            let (weights, bias) = solve_linear(solver, &x, &y, 0.0).unwrap();
+            assert_close(weights.values(), &[0.5, -0.003], 1e-9);
+            assert_close(bias.values(), &[4.0], 1e-9);
+        }
+
+        /* both solvers agree on the ridge closed form and shrink the weights */
+        let (chol, chol_bias) = solve_linear(Solver::Cholesky, &x, &y, 5.0).unwrap();
+        let (qr, qr_bias) = solve_linear(Solver::QR, &x, &y, 5.0).unwrap();
+        assert_close(chol.values(), qr.values(), 1e-9);
+        assert_close(chol_bias.values(), qr_bias.values(), 1e-9);
+        assert!(chol.values()[0].abs() < 0.5);
+
+        let collinear = NDArray::array(vec![3, 2], vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]).unwrap();
+        let target = NDArray::array(vec![3, 1], vec![1.0, 2.0, 3.0]).unwrap();
+        assert!(solve_linear(Solver::Cholesky, &collinear, &target, 0.0).is_err());
+        let err = solve_linear(Solver::QR, &collinear, &target, 0.0).unwrap_err();
+        assert_eq!(err, "QR: Matrix is rank deficient, features may be collinear");
+        assert!(solve_linear(Solver::Cholesky, &collinear, &target, 1.0).is_ok());
+
+        let err = solve_linear(Solver::GradientDescent, &x, &y, 0.0).unwrap_err();
+        assert_eq!(err, "Solver: Gradient descent has no closed form, use train");
+    }
+
+    #[test]
+    fn test_model_solvers() {
+
+        let (x, y) = data();
+
+        let mut linear = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        assert!(linear.solve().is_err());
+        linear.set_solver(Solver::QR);
+        Fit::fit(&mut linear, &x, &y).unwrap();
+        assert_close(linear.weights.val().values(), &[0.5, -0.003], 1e-9);
+        assert!((Score::score(&linear, &x, &y).unwrap() - 1.0).abs() < 1e-12);
+
+        let mut ridge = Ridge::new(&x, &y, 5.0, SGD::new(0.01)).unwrap();
+        ridge.set_solver(Solver::Cholesky);
+        Fit::fit(&mut ridge, &x, &y).unwrap();
+        let (expected, bias) = solve_linear(Solver::QR, &x, &y, 5.0).unwrap();
+        assert_close(ridge.weights.val().values(), expected.values(), 1e-9);
+        assert_close(ridge.bias.val().values(), bias.values(), 1e-9);
+    }
+
+    #[test]
+    fn test_ridge_gradient_descent() {
+
+        let x = NDArray::array(vec![8, 2], vec![
+            0.2, -1.0, 1.1, 0.4, -0.7, 0.9, 1.5, -0.3,
+            -1.2, -0.6, 0.4, 1.3, -0.1, -1.4, 0.8, 0.2
+        ]).unwrap();
+        let y_vals: Vec<f64> = x.values()
+            .chunks(2)
+            .enumerate()
+            .map(|(row, v)| 2.0 * v[0] - 1.5 * v[1] + 0.5 + 0.1 * (row as f64).sin())
+            .collect();
+        let y = NDArray::array(vec![8, 1], y_vals).unwrap();
+
+        /* gradient descent converges to the closed form of the same objective */
+        let mut ridge = Ridge::new(&x, &y, 3.0, SGD::new(0.1)).unwrap();
+        ridge.set_epochs(5000);
+        Fit::fit(&mut ridge, &x, &y).unwrap();
+
+        let (expected, bias) = solve_linear(Solver::Cholesky, &x, &y, 3.0).unwrap();
+        assert_close(ridge.weights.val().values(), expected.values(), 1e-8);
+        assert_close(ridge.bias.val().values(), bias.values(), 1e-8);
+    }
+
+}
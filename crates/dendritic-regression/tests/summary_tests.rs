@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod summary_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_regression::linear::*;
+    use dendritic_regression::logistic::*;
+    use dendritic_regression::solver::*;
+    use dendritic_regression::summary::*;
+    use dendritic_autodiff::optim::SGD;
+
+    /// Line y = 1.5 * x - 2 with deterministic noise
+    fn line_data() -> (NDArray<f64>, NDArray<f64>) {
+        let x_vals: Vec<f64> = (0..25).map(|row| row as f64 * 0.4).collect();
+        let y_vals: Vec<f64> = x_vals.iter()
+            .enumerate()
+            .map(|(row, x)| 1.5 * x - 2.0 + 0.8 * (row as f64 * 2.1).sin())
+            .collect();
+        (
+            NDArray::array(vec![25, 1], x_vals).unwrap(),
+            NDArray::array(vec![25, 1], y_vals).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_distributions() {
+
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-12);
+        assert!((gamma_inc(1.0, 2.0) - (1.0 - (-2.0_f64).exp())).abs() < 1e-12);
+        assert!((beta_inc(2.0, 1.0, 0.3) - 0.09).abs() < 1e-12);
+
+        assert!((normal_cdf(1.96) - 0.9750021048517795).abs() < 1e-12);
+        assert!((normal_cdf(-1.0) + normal_cdf(1.0) - 1.0).abs() < 1e-14);
+        assert!((normal_quantile(0.975) - 1.959963984540054).abs() < 1e-9);
+
+        /* closed forms for one and two degrees of freedom */
+        for t in [-3.0, -0.4, 0.0, 1.2, 7.5] {
+            let cauchy = 0.5 + f64::atan(t) / std::f64::consts::PI;
+            assert!((student_t_cdf(t, 1.0) - cauchy).abs() < 1e-12, "{}", t);
+            let two = 0.5 + t / (2.0 * (2.0 + t * t).sqrt());
+            assert!((student_t_cdf(t, 2.0) - two).abs() < 1e-12, "{}", t);
+        }
+        assert!((student_t_quantile(0.975, 10.0) - 2.2281388519649385).abs() < 1e-9);
+
+        assert!((f_sf(3.0, 2.0, 20.0) - (20.0_f64 / 26.0).powi(10)).abs() < 1e-12);
+        assert!((chi2_sf(3.0, 2.0) - (-1.5_f64).exp()).abs() < 1e-12);
+        assert!((chi2_sf(3.841458820694124, 1.0) - 0.05).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_linear_summary() {
+
+        let (x, y) = line_data();
+        let mut model = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_solver(Solver::QR);
+        model.fit(&x, &y).unwrap();
+        let summary = model.summary().unwrap();
+
+        /* closed form standard errors of simple regression */
+        let n = 25.0;
+        let x_mean = x.values().iter().sum::<f64>() / n;
+        let sxx: f64 = x.values().iter().map(|x| (x - x_mean).powi(2)).sum();
+        let pred = Predict::predict(&model, &x).unwrap();
+        let sse: f64 = y.values().iter().zip(pred.values().iter()).map(|(y, p)| (y - p).powi(2)).sum();
+        let sigma2 = sse / (n - 2.0);
+
+        let slope = summary.coefficient("x1").unwrap();
+        let intercept = summary.coefficient("const").unwrap();
+        assert_eq!(summary.coefficients[0].name, "const");
+        assert!((slope.std_error - (sigma2 / sxx).sqrt()).abs() < 1e-10);
+        assert!((intercept.std_error - (sigma2 * (1.0 / n + x_mean * x_mean / sxx)).sqrt()).abs() < 1e-10);
+        assert!((slope.statistic - slope.estimate / slope.std_error).abs() < 1e-12);
+
+        let critical = student_t_quantile(0.975, 23.0);
+        assert!((slope.upper - slope.estimate - critical * slope.std_error).abs() < 1e-9);
+        assert!(slope.lower < 1.5 && slope.upper > 1.5);
+        assert!(slope.p_value < 1e-10);
+
+        /* with a single feature the F test equals the squared t test */
+        assert_eq!(summary.df_model, 1);
+        assert_eq!(summary.df_resid, 23);
+        assert!((summary.f_statistic.unwrap() - slope.statistic.powi(2)).abs() < 1e-6);
+        assert!((summary.f_p_value.unwrap() - slope.p_value).abs() < 1e-12);
+        assert!((summary.r_squared.unwrap() - model.score(&x, &y).unwrap()).abs() < 1e-12);
+        assert!(summary.adj_r_squared.unwrap() < summary.r_squared.unwrap());
+
+        let ll = -n / 2.0 * ((2.0 * std::f64::consts::PI * sse / n).ln() + 1.0);
+        assert!((summary.log_likelihood - ll).abs() < 1e-9);
+        assert!((summary.aic - (4.0 - 2.0 * ll)).abs() < 1e-9);
+        assert!((summary.bic - (2.0 * n.ln() - 2.0 * ll)).abs() < 1e-9);
+
+        let table = summary.to_string();
+        assert!(table.contains("Linear Regression Results"));
+        assert!(table.contains("P>|t|"));
+        assert!(table.contains("const"));
+
+        let short = NDArray::array(vec![2, 1], vec![1.0, 2.0]).unwrap();
+        let model = Linear::new(&short, &short, SGD::new(0.01)).unwrap();
+        let err = model.summary().unwrap_err();
+        assert_eq!(err, "Linear: Need more rows than parameters for a summary");
+    }
+
+    #[test]
+    fn test_logistic_summary() {
+
+        let x_vals: Vec<f64> = (0..40).map(|row| (row as f64 * 0.83).sin() * 2.0).collect();
+        let y_vals: Vec<f64> = x_vals.iter()
+            .enumerate()
+            .map(|(idx, x)| if x + 0.9 * (idx as f64 * 2.9).cos() > 0.2 { 1.0 } else { 0.0 })
+            .collect();
+        let x = NDArray::array(vec![40, 1], x_vals).unwrap();
+        let y = NDArray::array(vec![40, 1], y_vals).unwrap();
+
+        let mut model = Logistic::new(&x, &y, SGD::new(0.01)).unwrap();
+        model.set_solver(LogisticSolver::Newton);
+        model.set_tol(1e-10);
+        model.fit(&x, &y).unwrap();
+        let summary = model.summary().unwrap();
+        assert_eq!(summary.distribution, Distribution::Normal);
+
+        /* invert the two by two fisher information by hand */
+        let w = model.weights.val().values()[0];
+        let b = model.bias.val().values()[0];
+        let (mut i00, mut i01, mut i11, mut ll) = (0.0, 0.0, 0.0, 0.0);
+        for (x, y) in x.values().iter().zip(y.values().iter()) {
+            let p = 1.0 / (1.0 + (-(w * x + b)).exp());
+            let v = p * (1.0 - p);
+            i00 += v;
+            i01 += v * x;
+            i11 += v * x * x;
+            ll += y * p.ln() + (1.0 - y) * (1.0 - p).ln();
+        }
+        let det = i00 * i11 - i01 * i01;
+        let slope = summary.coefficient("x1").unwrap();
+        assert!((slope.std_error - (i00 / det).sqrt()).abs() < 1e-9);
+        assert!((summary.coefficients[0].std_error - (i11 / det).sqrt()).abs() < 1e-9);
+        assert!((slope.p_value - 2.0 * normal_cdf(-slope.statistic.abs())).abs() < 1e-12);
+        assert!((slope.upper - slope.estimate - 1.959963984540054 * slope.std_error).abs() < 1e-8);
+
+        assert!((summary.log_likelihood - ll).abs() < 1e-9);
+        assert!((summary.aic - (4.0 - 2.0 * ll)).abs() < 1e-9);
+        let pseudo = summary.pseudo_r_squared.unwrap();
+        assert!(pseudo > 0.0 && pseudo < 1.0, "{}", pseudo);
+        assert!(summary.llr_p_value.unwrap() < 0.01);
+        assert!(summary.r_squared.is_none() && summary.f_statistic.is_none());
+
+        let table = summary.to_string();
+        assert!(table.contains("Logistic Regression Results"));
+        assert!(table.contains("Pseudo R-squ."));
+
+        let labels = NDArray::array(vec![40, 1], vec![2.0; 40]).unwrap();
+        let weights = NDArray::array(vec![1, 1], vec![0.0]).unwrap();
+        let err = Summary::logit(&x, &labels, &weights, 0.0, 0.05).unwrap_err();
+        assert_eq!(err, "Logistic: Summary labels must be 0 or 1");
+        assert!(Summary::ols(&x, &y, &weights, 0.0, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_summary_at_optimum() {
+
+        /* a short gradient descent run leaves the weights far from the optimum */
+        let (x, y) = line_data();
+        let mut exact = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        exact.set_solver(Solver::QR);
+        exact.fit(&x, &y).unwrap();
+        let mut short = Linear::new(&x, &y, SGD::new(0.01)).unwrap();
+        short.set_epochs(5);
+        short.fit(&x, &y).unwrap();
+
+        let expected = exact.summary().unwrap();
+        let summary = short.summary().unwrap();
+        assert!((summary.coefficients[1].estimate - expected.coefficients[1].estimate).abs() < 1e-10);
+        assert!((summary.coefficients[1].std_error - expected.coefficients[1].std_error).abs() < 1e-10);
+
+        let err = Summary::ols(&x, &y, &short.weights.val(), short.bias.val().values()[0], 0.05).unwrap_err();
+        assert!(err.starts_with("Linear: Summary needs converged weights"), "{}", err);
+
+        let labels = NDArray::array(vec![25, 1], (0..25).map(|row| (row % 3 == 0) as u8 as f64).collect()).unwrap();
+        let mut newton = Logistic::new(&x, &labels, SGD::new(0.01)).unwrap();
+        newton.set_solver(LogisticSolver::Newton);
+        newton.set_tol(1e-10);
+        newton.fit(&x, &labels).unwrap();
+        let mut sgd = Logistic::new(&x, &labels, SGD::new(0.01)).unwrap();
+        sgd.set_epochs(5);
+        sgd.fit(&x, &labels).unwrap();
+
+        let expected = newton.summary().unwrap();
+        let summary = sgd.summary().unwrap();
+        for (a, b) in summary.coefficients.iter().zip(expected.coefficients.iter()) {
+            assert!((a.estimate - b.estimate).abs() < 1e-8);
+            assert!((a.std_error - b.std_error).abs() < 1e-8);
+        }
+
+        let err = Summary::logit(&x, &labels, &sgd.weights.val(), sgd.bias.val().values()[0], 0.05).unwrap_err();
+        assert!(err.starts_with("Logistic: Summary needs converged weights"), "{}", err);
+    }
+
+}
@@ -2,6 +2,7 @@ use std::fs;
 use std::fs::{File}; 
 use std::io::{BufWriter, Write};
 
+use dendritic::estimator::{Fit, Predict, Score, accuracy_score};
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
 use crate::node::*;
@@ -255,5 +256,39 @@ None => -1.0
 }
 
 
+impl Fit<NDArray<f64>, NDArray<f64>> for DecisionTreeClassifier {
+
+    /// Fit decision tree classifier to features and a separate target column
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        let dataset = with_target("DecisionTreeClassifier", x, y)?;
+        self.root = self.build_tree(&dataset, 0);
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for DecisionTreeClassifier {
+    type Output = NDArray<f64>;
+
+    /// Generate prediction for all row samples of decision tree classifier
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if x.rank() != 2 {
+            let msg = format!("DecisionTreeClassifier: Features must be rank 2, got rank {}", x.rank());
+            return Err(msg);
+        }
+        Ok(DecisionTreeClassifier::predict(self, x.clone()))
+    }
+
+}
 
 
+impl Score<NDArray<f64>, NDArray<f64>> for DecisionTreeClassifier {
+
+    /// Accuracy of decision tree classifier predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(y.values(), y_pred.values())
+    }
+
+}
@@ -2,6 +2,7 @@ use std::fs;
 use std::fs::{File}; 
 use std::io::{BufWriter, Write};
 
+use dendritic::estimator::{Fit, Predict, Score, r2_score};
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
 use crate::node::*;
@@ -250,4 +251,42 @@ None => -1.0
 
 
 
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for DecisionTreeRegressor {
+
+    /// Fit decision tree regressor to features and a separate target column
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        let dataset = with_target("DecisionTreeRegressor", x, y)?;
+        self.root = self.build_tree(&dataset, 0);
+        Ok(())
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for DecisionTreeRegressor {
+    type Output = NDArray<f64>;
+
+    /// Generate prediction for all row samples of decision tree regressor
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if x.rank() != 2 {
+            let msg = format!("DecisionTreeRegressor: Features must be rank 2, got rank {}", x.rank());
+            return Err(msg);
+        }
+        Ok(DecisionTreeRegressor::predict(self, x.clone()))
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for DecisionTreeRegressor {
+
+    /// R2 score of decision tree regressor predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
 }
@@ -1,11 +1,13 @@
 use std::fs; 
 use std::collections::BTreeMap;
 
+use dendritic::estimator::{Fit, Predict, Score, accuracy_score, r2_score};
 use dendritic_ndarray::ndarray::NDArray;
 use dendritic_ndarray::ops::*;
 use crate::bootstrap::*;
 use crate::decision_tree::*; 
 use crate::decision_tree_regressor::*; 
+use crate::utils::with_target;
 
 pub struct RandomForestClassifier {
     max_depth: usize, 
@@ -425,3 +427,78 @@ impl RandomForestRegressor {
 
 }
 
+
+impl Fit<NDArray<f64>, NDArray<f64>> for RandomForestClassifier {
+
+    /// Fit random forest classifier to features and a separate target column
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        let dataset = with_target("RandomForestClassifier", x, y)?;
+        self.trees.clear();
+        self.bootstrap_trees(&dataset, y)
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for RandomForestClassifier {
+    type Output = NDArray<f64>;
+
+    /// Generate prediction for all row samples of random forest classifier
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if x.rank() != 2 {
+            let msg = format!("RandomForestClassifier: Features must be rank 2, got rank {}", x.rank());
+            return Err(msg);
+        }
+        Ok(RandomForestClassifier::predict(self, x.clone()))
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for RandomForestClassifier {
+
+    /// Accuracy of random forest classifier predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        accuracy_score(y.values(), y_pred.values())
+    }
+
+}
+
+
+impl Fit<NDArray<f64>, NDArray<f64>> for RandomForestRegressor {
+
+    /// Fit random forest regressor to features and a separate target column
+    fn fit(&mut self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<(), String> {
+        let dataset = with_target("RandomForestRegressor", x, y)?;
+        self.trees.clear();
+        self.bootstrap_trees(&dataset, y)
+    }
+
+}
+
+
+impl Predict<NDArray<f64>> for RandomForestRegressor {
+    type Output = NDArray<f64>;
+
+    /// Generate prediction for all row samples of random forest regressor
+    fn predict(&self, x: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+        if x.rank() != 2 {
+            let msg = format!("RandomForestRegressor: Features must be rank 2, got rank {}", x.rank());
+            return Err(msg);
+        }
+        Ok(RandomForestRegressor::predict(self, x.clone()))
+    }
+
+}
+
+
+impl Score<NDArray<f64>, NDArray<f64>> for RandomForestRegressor {
+
+    /// R2 score of random forest regressor predictions
+    fn score(&self, x: &NDArray<f64>, y: &NDArray<f64>) -> Result<f64, String> {
+        let y_pred = Predict::predict(self, x)?;
+        r2_score(y.values(), y_pred.values())
+    }
+
+}
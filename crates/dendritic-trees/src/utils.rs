@@ -145,3 +145,29 @@ pub fn print_tree(node: Node, level: usize) {
 
 }
 
+
+
+/// Append target column to features, tree models expect the target as the last column
+pub fn with_target(
+    name: &str,
+    features: &NDArray<f64>,
+    target: &NDArray<f64>) -> Result<NDArray<f64>, String> {
+
+    let rows = features.shape().dim(0);
+    if features.rank() != 2 || target.shape().values() != vec![rows, 1] {
+        let msg = format!(
+            "{}: Target shape {:?} does not match {} feature rows",
+            name, target.shape().values(), rows
+        );
+        return Err(msg);
+    }
+
+    let cols = features.shape().dim(1);
+    let mut values: Vec<f64> = Vec::new();
+    for (row, target_val) in features.values().chunks(cols).zip(target.values()) {
+        values.extend_from_slice(row);
+        values.push(*target_val);
+    }
+
+    NDArray::array(vec![rows, cols + 1], values)
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod estimator_tests {
+
+    use dendritic::estimator::*;
+    use dendritic_ndarray::ndarray::NDArray;
+    use dendritic_metrics::loss::*;
+    use dendritic_metrics::utils::*;
+    use dendritic_trees::decision_tree::*;
+    use dendritic_trees::decision_tree_regressor::*;
+    use dendritic_trees::random_forest::*;
+    use dendritic_trees::utils::*;
+
+    /// Generic evaluation only relying on the shared traits
+    fn fit_score<M>(model: &mut M, x: &NDArray<f64>, y: &NDArray<f64>) -> f64
+    where
+        M: Fit<NDArray<f64>, NDArray<f64>> + Score<NDArray<f64>, NDArray<f64>>,
+    {
+        model.fit(x, y).unwrap();
+        model.score(x, y).unwrap()
+    }
+
+    fn features() -> NDArray<f64> {
+        NDArray::array(vec![9, 2], vec![
+            1.0, 5.0, 1.5, 4.0, 2.0, 6.0,
+            5.0, 1.0, 5.5, 2.0, 6.0, 1.5,
+            9.0, 5.0, 9.5, 4.5, 10.0, 6.0
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_with_target() {
+
+        let x = NDArray::array(vec![2, 2], vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let y = NDArray::array(vec![2, 1], vec![5.0, 6.0]).unwrap();
+        let dataset = with_target("Test", &x, &y).unwrap();
+        assert_eq!(dataset.shape().values(), vec![2, 3]);
+        assert_eq!(dataset.values(), &vec![1.0, 2.0, 5.0, 3.0, 4.0, 6.0]);
+
+        let short = NDArray::array(vec![1, 1], vec![5.0]).unwrap();
+        let err = with_target("Test", &x, &short).unwrap_err();
+        assert_eq!(err, "Test: Target shape [1, 1] does not match 2 feature rows");
+    }
+
+    #[test]
+    fn test_tree_estimators() {
+
+        let x = features();
+        let labels = NDArray::array(vec![9, 1], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0]).unwrap();
+        let mut classifier = DecisionTreeClassifier::new(3, 2, entropy);
+        assert_eq!(fit_score(&mut classifier, &x, &labels), 1.0);
+        assert_eq!(Predict::predict(&classifier, &x).unwrap(), labels);
+
+        let y = NDArray::array(vec![9, 1], vec![1.0, 1.2, 0.8, 4.0, 4.3, 3.8, 9.0, 9.1, 8.7]).unwrap();
+        let mut regressor = DecisionTreeRegressor::new(3, 2, mse);
+        assert!(fit_score(&mut regressor, &x, &y) > 0.9);
+
+        let short = NDArray::array(vec![3, 1], vec![0.0; 3]).unwrap();
+        assert!(Fit::fit(&mut classifier, &x, &short).is_err());
+    }
+
+    #[test]
+    fn test_forest_estimators() {
+
+        let x = features();
+        let labels = NDArray::array(vec![9, 1], vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0]).unwrap();
+        let mut classifier = RandomForestClassifier::new(3, 2, 10, 2, entropy);
+        assert!(fit_score(&mut classifier, &x, &labels) > 0.6);
+
+        /* refitting replaces the previous trees */
+        Fit::fit(&mut classifier, &x, &labels).unwrap();
+        assert_eq!(classifier.trees().len(), 10);
+
+        let y = NDArray::array(vec![9, 1], vec![1.0, 1.2, 0.8, 4.0, 4.3, 3.8, 9.0, 9.1, 8.7]).unwrap();
+        let mut regressor = RandomForestRegressor::new(3, 2, 10, 2, mse);
+        assert!(fit_score(&mut regressor, &x, &y) > 0.5);
+
+        let mut wide = RandomForestRegressor::new(3, 2, 10, 5, mse);
+        assert!(Fit::fit(&mut wide, &x, &y).is_err());
+    }
+
+}
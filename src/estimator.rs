@@ -0,0 +1,107 @@
+//! Shared interfaces implemented by every model crate.
+//!
+//! Models are fitted with `Fit`, produce outputs with `Predict`,
+//! `PredictProba` or `Transform` and are evaluated with `Score`.
+//! Unsupervised models and transformers take `()` as the target type.
+//! Generic evaluation and tuning code only needs these bounds:
+//!
+//! ```rust
+//! use dendritic::estimator::*;
+//!
+//! fn fit_and_score<M, X, Y>(model: &mut M, x: &X, y: &Y) -> Result<f64, String>
+//! where
+//!     M: Fit<X, Y> + Score<X, Y>,
+//! {
+//!     model.fit(x, y)?;
+//!     model.score(x, y)
+//! }
+//! ```
+
+
+/// Learn model state from features and targets
+pub trait Fit<X, Y = ()> {
+    fn fit(&mut self, x: &X, y: &Y) -> Result<(), String>;
+}
+
+
+/// Predict targets for features with a fitted model
+pub trait Predict<X> {
+    type Output;
+    fn predict(&self, x: &X) -> Result<Self::Output, String>;
+}
+
+
+/// Predict class probabilities with one column per class in sorted class order
+pub trait PredictProba<X> {
+    type Output;
+    fn predict_proba(&self, x: &X) -> Result<Self::Output, String>;
+}
+
+
+/// Map features to a new representation with a fitted transformer
+pub trait Transform<X> {
+    type Output;
+    fn transform(&self, x: &X) -> Result<Self::Output, String>;
+
+    /// Fit transformer on features and transform them
+    fn fit_transform(&mut self, x: &X) -> Result<Self::Output, String>
+    where
+        Self: Fit<X>,
+    {
+        self.fit(x, &())?;
+        self.transform(x)
+    }
+}
+
+
+/// Evaluate model on features and targets, higher is better.
+/// Classifiers report accuracy and regressors report the R2 score
+pub trait Score<X, Y> {
+    fn score(&self, x: &X, y: &Y) -> Result<f64, String>;
+}
+
+
+/// Check that prediction and target slices can be compared
+fn check_lengths(name: &str, y_true: &[f64], y_pred: &[f64]) -> Result<(), String> {
+
+    if y_true.len() != y_pred.len() {
+        let msg = format!(
+            "{}: Target length {} does not match prediction length {}",
+            name, y_true.len(), y_pred.len()
+        );
+        return Err(msg);
+    }
+
+    if y_true.is_empty() {
+        return Err(format!("{}: Targets must not be empty", name));
+    }
+
+    Ok(())
+}
+
+
+/// Fraction of predictions equal to their target
+pub fn accuracy_score(y_true: &[f64], y_pred: &[f64]) -> Result<f64, String> {
+    check_lengths("Accuracy", y_true, y_pred)?;
+    let correct = y_true.iter()
+        .zip(y_pred.iter())
+        .filter(|(target, pred)| target == pred)
+        .count();
+    Ok(correct as f64 / y_true.len() as f64)
+}
+
+
+/// Coefficient of determination, 1.0 for a perfect fit.
+/// Constant targets score 1.0 when matched exactly and 0.0 otherwise
+pub fn r2_score(y_true: &[f64], y_pred: &[f64]) -> Result<f64, String> {
+    check_lengths("R2", y_true, y_pred)?;
+    let mean = y_true.iter().sum::<f64>() / y_true.len() as f64;
+    let ss_res: f64 = y_true.iter().zip(y_pred.iter()).map(|(t, p)| (t - p).powi(2)).sum();
+    let ss_tot: f64 = y_true.iter().map(|t| (t - mean).powi(2)).sum();
+
+    if ss_tot == 0.0 {
+        return Ok(if ss_res == 0.0 { 1.0 } else { 0.0 });
+    }
+
+    Ok(1.0 - ss_res / ss_tot)
+}
@@ -22,6 +22,7 @@
 //! | [dendritic_bayes](https://crates.io/crates/dendritic-bayes)                 | Bayesian statistics package                                                            |
 //! | [dendritic_clustering](https://crates.io/crates/dendritic-clustering)       | Clustering package utilizing various distance metrics                                  |
 //! | [dendritic_knn](https://crates.io/crates/dendritic-knn)                     | K Nearest Neighbors for regression and classification                                  |
+//! | [dendritic_nn](https://crates.io/crates/dendritic-nn)                       | Neural network layers, sequential container and training loop                          |
 //! | [dendritic_models](https://crates.io/crates/dendritic-models)                  | Pre-trained models for testing `dendritic` functionality                               |
 //! | [dendritic_regression](https://crates.io/crates/dendritic-regression)       | Regression package for linear modeling & multi class classification                    |
 //! | [dendritic_trees](https://crates.io/crates/dendritic-trees)                 | Tree based models using decision trees and random forests                              |
@@ -44,7 +45,7 @@
 //! use dendritic_datasets::iris::*;
 //! use dendritic_regression::logistic::*;
 //! use dendritic_metrics::loss::*;
-//! use dendritic_metrics::activations::*;
+//! use dendritic_autodiff::optim::SGD;
 //! use dendritic_preprocessing::encoding::*;
 
 
@@ -62,8 +63,7 @@
 //!     let mut log_model = MultiClassLogistic::new(
 //!         &x_train,
 //!         &y_train_encoded,
-//!         softmax,
-//!         0.1
+//!         SGD::new(0.1)
 //!     ).unwrap();
 
 //!     log_model.sgd(500, true, 5);
@@ -79,4 +79,11 @@
 //!     let loss = mse(&y_test[sample_index], &y_pred).unwrap(); 
 //!     println!("LOSS: {:?}", loss);  
 //! }
-//! ```
\ No newline at end of file
+//! ```
+
+//! ## Estimator Interface
+//! The `estimator` module defines the `Fit`, `Predict`, `PredictProba`, `Transform` and `Score`
+//! traits implemented by the regression, tree, knn, bayes, clustering and preprocessing models.
+//! Code written against these traits works with any model, for example a generic cross validation loop.
+
+pub mod estimator;